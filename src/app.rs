@@ -3,21 +3,34 @@ use std::collections::{HashMap, HashSet};
 
 use anyhow::Result;
 use clipboard::{ClipboardContext, ClipboardProvider};
+use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 
-use crate::command::CommandBuffer;
+use crate::command::{CommandBuffer, CommandProcessor};
 use crate::config::Config;
-use crate::database::core::ForeignKeyTarget;
+use crate::database::core::{
+    placeholder_count, ColumnAlignment, ColumnFormat, ColumnKind, ForeignKeyTarget, NullsOrder,
+    OrderByTerm, RelationKind,
+};
 use crate::database::{
-    ConnectionConfig, ConnectionManager, ConnectionStatus, DatabaseType, PrefetchedStructure,
-    QueryParams, QueryResult,
+    clamp_page_size, with_timeout, ConnectionConfig, ConnectionFailure, ConnectionManager,
+    ConnectionStatus, DatabaseConnection, DatabaseType, PrefetchedDatabase, PrefetchedSchema,
+    PrefetchedStructure, QueryParams, QueryResult, SSHConfig, DEFAULT_PAGE_SIZE,
 };
+use crate::deletion::{BulkDeletion, DeletePredicate};
+use crate::export::ExportFormat;
 use crate::input::{NavigationAction, TreeAction};
 use crate::logging;
 use crate::navigation::types::Pane;
 use crate::navigation::NavigationManager;
+use crate::query_history::QueryHistory;
+use crate::query_log::{QueryLog, QueryLogEntry};
+use crate::saved_queries::{self, SavedQuery};
+use crate::search::ResultSearch;
+use crate::session::{self, Session, SessionTab};
 use crate::ui::layout::QueryField;
 use crate::ui::modal_manager::ModalManager;
+use crate::ui::modals::QueryParamsModal;
 use crate::ui::panes::query_input::QueryInputPane;
 use crate::ui::panes::results::ResultsPane;
 use crate::ui::panes::sidebar::SidebarPane;
@@ -26,7 +39,32 @@ use crate::ui::types::Direction;
 #[derive(Debug)]
 pub enum PrefetchResult {
     Success(String, PrefetchedStructure),
-    Failed(String, String), // connection_name, error_message
+    Failed(String, ConnectionFailure),
+}
+
+/// What a cell is edited to for it to be set to NULL (as in `COPY`), since an emptied cell
+/// is an empty string
+const NULL_EDIT_MARKER: &str = "\\N";
+
+/// Longest wait between two retries of a failed connection
+const MAX_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Automatic retries of a connection whose background prefetch failed
+/// (see `check_connection_retries`)
+#[derive(Debug, Clone, Copy)]
+pub struct ConnectionRetry {
+    /// Retries started so far
+    pub attempt: u32,
+    pub max_attempts: u32,
+    /// When the next retry starts; `None` while one is running
+    pub next_at: Option<std::time::Instant>,
+}
+
+/// Row count of a table just opened, counted in the background (see `spawn_row_count`)
+#[derive(Debug)]
+pub struct RowCount {
+    pub tab_name: String,
+    pub count: Result<u64, String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -44,7 +82,7 @@ pub enum ActiveBlock {
 }
 
 /// Represents the form data for creating or editing a database connection.
-#[derive(Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct ConnectionForm {
     pub name: String,
     pub db_type: DatabaseType,
@@ -59,11 +97,31 @@ pub struct ConnectionForm {
     pub ssh_username: String,
     pub ssh_password: String,
     pub ssh_key_path: String,
+    /// Passphrase of the encrypted `ssh_key_path`
+    pub ssh_passphrase: String,
+    pub ssh_use_agent: bool,
     pub ssh_tunnel_name: Option<String>,
+    pub read_only: bool,
     pub current_field: usize,
     pub editing_index: Option<usize>,
 }
 
+impl ConnectionForm {
+    /// The connection's own SSH tunnel, if `ssh_enabled`; empty secrets are left unset
+    fn ssh_config(&self) -> Option<SSHConfig> {
+        let secret = |value: &str| Some(value.to_string()).filter(|value| !value.is_empty());
+        self.ssh_enabled.then(|| SSHConfig {
+            host: self.ssh_host.clone(),
+            port: self.ssh_port.parse().unwrap_or(22),
+            username: self.ssh_username.clone(),
+            private_key_path: secret(&self.ssh_key_path),
+            password: secret(&self.ssh_password),
+            passphrase: secret(&self.ssh_passphrase),
+            use_agent: self.ssh_use_agent,
+        })
+    }
+}
+
 /// Represents the query state for a single tab/table
 #[derive(Clone, Default)]
 pub struct QueryState {
@@ -73,10 +131,84 @@ pub struct QueryState {
     pub current_page: u32,
     pub total_pages: Option<u32>,
     pub total_records: Option<u64>,
-    pub sort_column: Option<String>,
-    pub sort_order: Option<bool>,
+    /// Sort columns in priority order, each with whether it sorts ascending
+    pub sort_columns: Vec<(String, bool)>,
+    /// NULL placement applied to every sort column
+    pub nulls_order: Option<NullsOrder>,
     pub rows_marked_for_deletion: HashSet<usize>,
+    /// Marks removed by the last `clear_deletion_marks`, restored by `undo_deletion`
+    pub cleared_deletion_marks: HashSet<usize>,
+    /// Rows deleted and committed from this tab, which undo can only warn about
+    pub committed_deletions: u64,
     pub primary_key_columns: Vec<String>,
+    /// SELECT the tab reads from instead of a table (see `open_base_query`)
+    pub base_query: Option<String>,
+    /// Columns past `max_visible_columns` the user chose to show
+    pub revealed_columns: Vec<String>,
+    /// Columns hidden with `hide_cursor_column`, by index; they are left out of drawing,
+    /// cursor movement and clicks, but still exported
+    pub hidden_columns: HashSet<usize>,
+    /// Connection, schema and table the tab reads from, restored when switching to it
+    pub table_info: Option<(String, String, String)>,
+    /// Values bound to the `$n` placeholders of `where_clause`
+    pub where_params: Vec<String>,
+    /// Set on tabs restored from the last session, whose rows are fetched once it is shown
+    pub needs_fetch: bool,
+    /// Position in the visible columns of the first one drawn, once the results pane has
+    /// scrolled right (see `ResultsPane::scrolled_columns`)
+    pub col_scroll_offset: usize,
+    /// Interval the tab is re-fetched at while it is shown (see `set_auto_refresh`)
+    pub auto_refresh: Option<std::time::Duration>,
+    /// When the last auto-refresh of the tab was queued
+    pub last_auto_refresh: Option<std::time::Instant>,
+    /// Whether the tab shows the cursor's row as stacked column/value lines instead of a grid
+    pub record_view: bool,
+    /// Display options set with `format`, by column name
+    pub column_formats: HashMap<String, ColumnFormat>,
+    /// Corner (column, row) of the visual selection started with `v`; the cursor is the other
+    pub selection_anchor: Option<(usize, usize)>,
+    /// Whether the cursor's row wraps long values onto extra lines instead of cutting them
+    pub wrap_cells: bool,
+    /// Connection and statement of an ad-hoc tab with rows left to fetch (see
+    /// `load_more_rows`)
+    pub capped_query: Option<(String, String)>,
+}
+
+impl QueryState {
+    /// Indices of the columns to render: the first `max_visible`, plus any primary key or
+    /// revealed column after them, less the hidden ones. `max_visible == 0` renders every
+    /// column that isn't hidden.
+    pub fn visible_column_indices(&self, columns: &[String], max_visible: usize) -> Vec<usize> {
+        columns
+            .iter()
+            .enumerate()
+            .filter(|(index, name)| {
+                (max_visible == 0
+                    || *index < max_visible
+                    || self.primary_key_columns.contains(name)
+                    || self.revealed_columns.contains(name))
+                    && !self.hidden_columns.contains(index)
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Whether cell (column, row) lies in the rectangle between the visual selection's anchor
+    /// and `cursor`
+    pub fn in_selection(&self, cursor: (usize, usize), cell: (usize, usize)) -> bool {
+        self.selection_anchor.is_some_and(|(column, row)| {
+            (column.min(cursor.0)..=column.max(cursor.0)).contains(&cell.0)
+                && (row.min(cursor.1)..=row.max(cursor.1)).contains(&cell.1)
+        })
+    }
+}
+
+/// Result tab kept for comparison, drawn beside the selected one in a split results pane
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PinnedTab {
+    pub index: usize,
+    /// Cursor of the tab while the other half of the split has focus
+    pub cursor: (usize, usize),
 }
 
 /// Represents an item in the connection tree.
@@ -92,6 +224,74 @@ pub enum TreeItem {
     Table(usize, usize, usize, usize),
 }
 
+/// Whether the characters of `pattern` (lowercase) appear in order in `text`, ignoring case
+fn fuzzy_match(pattern: &str, text: &str) -> bool {
+    let mut text = text.chars().flat_map(char::to_lowercase);
+    pattern.chars().all(|p| text.any(|c| c == p))
+}
+
+/// `item` followed by its children's rows when it or one of its descendants matches `filter`.
+/// Below a match (or without a filter) the children, from `children`, are only shown when
+/// `expanded`; otherwise they are shown if they lead to a match.
+fn tree_rows(
+    item: TreeItem,
+    name: &str,
+    expanded: bool,
+    filter: Option<&str>,
+    children: &dyn Fn(Option<&str>) -> Vec<TreeItem>,
+) -> Vec<TreeItem> {
+    let matched = filter.is_none_or(|filter| fuzzy_match(filter, name));
+    if matched && !expanded {
+        return vec![item];
+    }
+    let child_rows = children(if matched { None } else { filter });
+    if !matched && child_rows.is_empty() {
+        return Vec::new();
+    }
+    std::iter::once(item).chain(child_rows).collect()
+}
+
+/// Rows of the tables of a schema matching `filter`
+fn table_rows(
+    conn_idx: usize,
+    db_idx: usize,
+    schema_idx: usize,
+    schema: &SchemaTreeItem,
+    filter: Option<&str>,
+) -> Vec<TreeItem> {
+    schema
+        .tables
+        .iter()
+        .enumerate()
+        .filter(|(_, table)| filter.is_none_or(|filter| fuzzy_match(filter, table)))
+        .map(|(table_idx, _)| TreeItem::Table(conn_idx, db_idx, schema_idx, table_idx))
+        .collect()
+}
+
+/// `name` as written into a WHERE or ORDER BY clause for `db_type`: quoted unless the database
+/// reads it back unchanged. Mock and MongoDB fields are never quoted.
+fn completion_identifier(db_type: Option<&DatabaseType>, name: &str) -> String {
+    let plain = |folds_case: bool| {
+        name.chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+            && name.chars().all(|c| {
+                (c.is_alphanumeric() || c == '_' || c == '$') && !(folds_case && c.is_uppercase())
+            })
+    };
+    match db_type {
+        // Unquoted names fold to lowercase
+        Some(DatabaseType::Postgres) if !plain(true) => {
+            format!("\"{}\"", name.replace('"', "\"\""))
+        }
+        Some(DatabaseType::SQLite) if !plain(false) => {
+            format!("\"{}\"", name.replace('"', "\"\""))
+        }
+        Some(DatabaseType::MySQL) if !plain(false) => format!("`{}`", name.replace('`', "``")),
+        _ => name.to_string(),
+    }
+}
+
 /// Represents a connection item in the connection tree.
 #[derive(Clone)]
 pub struct ConnectionTreeItem {
@@ -105,6 +305,34 @@ pub struct ConnectionTreeItem {
     pub is_expanded: bool,
 }
 
+impl ConnectionTreeItem {
+    /// Index of the database a schema-scoped connection reads from: the default database
+    /// if one is configured, otherwise the first database containing the scoped schema.
+    fn scope_database_index(&self) -> Option<usize> {
+        let scope = self.connection_config.scope_schema.as_deref()?;
+        match self.connection_config.get_default_database() {
+            Some(default_db) => self.databases.iter().position(|db| &db.name == default_db),
+            None => self
+                .databases
+                .iter()
+                .position(|db| db.schemas.iter().any(|schema| schema.name == scope))
+                .or(if self.databases.is_empty() { None } else { Some(0) }),
+        }
+    }
+
+    /// Database and schema indices whose tables are shown flattened under the connection,
+    /// or `None` when the connection isn't scoped or the scoped schema isn't loaded.
+    pub fn scoped_schema(&self) -> Option<(usize, usize)> {
+        let scope = self.connection_config.scope_schema.as_deref()?;
+        let db_idx = self.scope_database_index()?;
+        let schema_idx = self.databases[db_idx]
+            .schemas
+            .iter()
+            .position(|schema| schema.name == scope)?;
+        Some((db_idx, schema_idx))
+    }
+}
+
 /// Represents a database item in the connection tree.
 #[derive(Clone)]
 pub struct DatabaseTreeItem {
@@ -123,6 +351,8 @@ pub struct SchemaTreeItem {
     pub name: String,
     /// List of tables within this schema.
     pub tables: Vec<String>,
+    /// Kind of each entry in `tables` (table, view, materialized view, ...)
+    pub relation_kinds: HashMap<String, RelationKind>,
     /// Whether the schema is expanded in the tree.
     pub is_expanded: bool,
 }
@@ -147,9 +377,28 @@ pub struct App {
     pub connection_tree: Vec<ConnectionTreeItem>,
     pub last_table_info: Option<(String, String, String)>,
     pub selected_result_tab_index: Option<usize>,
+    /// Tab drawn beside the selected one while `split_results` is on (see `pin_current_tab`)
+    pub pinned_tab: Option<PinnedTab>,
+    /// Whether the results pane shows the selected and the pinned tab side by side
+    pub split_results: bool,
+    /// Whether the selected tab is drawn in the right half of the split
+    pub split_focus_right: bool,
     pub connection_manager: ConnectionManager,
     pub prefetched_structures: HashMap<String, PrefetchedStructure>,
     pub prefetch_receiver: Option<mpsc::UnboundedReceiver<PrefetchResult>>,
+    /// Connections whose background prefetch has been spawned but not yet applied
+    pub prefetch_in_flight: HashSet<String>,
+    /// Sender of `prefetch_receiver` and the limit on concurrent prefetches, kept for retries
+    pub prefetch_spawner: Option<(
+        mpsc::UnboundedSender<PrefetchResult>,
+        std::sync::Arc<tokio::sync::Semaphore>,
+    )>,
+    /// Retries of connections whose background prefetch failed, by connection name
+    pub connection_retries: HashMap<String, ConnectionRetry>,
+    /// Why each failed connection last failed, by connection name
+    pub connection_failures: HashMap<String, ConnectionFailure>,
+    pub row_count_sender: mpsc::UnboundedSender<RowCount>,
+    pub row_count_receiver: mpsc::UnboundedReceiver<RowCount>,
     pub command_buffer: CommandBuffer,
     pub clipboard: String,
     pub last_key_was_d: bool,
@@ -168,6 +417,34 @@ pub struct App {
     pub last_key_was_y: bool,
     pub editing_cell_position: Option<(usize, usize)>,
     pub editing_cell_original: String,
+    /// Client-side search over the loaded result rows
+    pub result_search: ResultSearch,
+    /// Whether keys in the results pane are typed into the search
+    pub result_search_editing: bool,
+    pub query_log: QueryLog,
+    /// Statements run with `:sql`, persisted to `history.log`
+    pub query_history: QueryHistory,
+    /// Batched deletion of marked rows currently running, if any
+    pub bulk_deletion: Option<BulkDeletion>,
+    /// Connections with a transaction opened by `begin`, until `commit` or `rollback`
+    pub open_transactions: HashSet<String>,
+    /// Whether quitting was refused once because of open transactions
+    pub quit_warned: bool,
+    /// Result rows that fit on screen, updated before each key press; PageUp/PageDown move
+    /// by this many rows
+    pub results_visible_rows: usize,
+    /// Set when the current tab should be reloaded on the next tick
+    pub reload_requested: bool,
+    /// Text the connection tree is filtered by (see `visible_tree_items`)
+    pub tree_filter: Option<String>,
+    /// Whether keys in the connections pane are typed into `tree_filter`
+    pub tree_filter_editing: bool,
+    /// Number of ad-hoc query tabs opened so far, used to name the next one
+    pub adhoc_query_count: usize,
+    /// Named base queries registered with `openquery`, keyed by name
+    pub base_queries: HashMap<String, String>,
+    /// Database chosen with `use` for a connection, connected to instead of its default
+    pub active_databases: HashMap<String, String>,
     pub cell_text_input: crate::ui::components::text_input::TextInput,
 }
 
@@ -176,6 +453,7 @@ impl App {
     pub fn new() -> Self {
         let config = Config::new();
         let navigation_config = config.navigation.clone();
+        let (row_count_sender, row_count_receiver) = mpsc::unbounded_channel();
         let mut app = Self {
             should_quit: false,
             active_block: ActiveBlock::Connections,
@@ -199,9 +477,18 @@ impl App {
             connection_tree: Vec::new(),
             last_table_info: None,
             selected_result_tab_index: None,
+            pinned_tab: None,
+            split_results: false,
+            split_focus_right: false,
             connection_manager: ConnectionManager::new(),
             prefetched_structures: HashMap::new(),
             prefetch_receiver: None,
+            prefetch_in_flight: HashSet::new(),
+            prefetch_spawner: None,
+            connection_retries: HashMap::new(),
+            connection_failures: HashMap::new(),
+            row_count_sender,
+            row_count_receiver,
             command_buffer: CommandBuffer::new(),
             clipboard: String::new(),
             last_key_was_d: false,
@@ -218,6 +505,20 @@ impl App {
             last_key_was_y: false,
             editing_cell_position: None,
             editing_cell_original: String::new(),
+            result_search: ResultSearch::default(),
+            query_log: QueryLog::default(),
+            bulk_deletion: None,
+            open_transactions: HashSet::new(),
+            quit_warned: false,
+            reload_requested: false,
+            tree_filter: None,
+            tree_filter_editing: false,
+            result_search_editing: false,
+            results_visible_rows: 0,
+            query_history: QueryHistory::load_or_default(),
+            adhoc_query_count: 0,
+            base_queries: HashMap::new(),
+            active_databases: HashMap::new(),
             cell_text_input: crate::ui::components::text_input::TextInput::new(),
         };
 
@@ -242,6 +543,7 @@ impl App {
     pub async fn new_with_async_connections() -> Result<Self> {
         let config = Config::new();
         let navigation_config = config.navigation.clone();
+        let (row_count_sender, row_count_receiver) = mpsc::unbounded_channel();
         let mut app = Self {
             should_quit: false,
             active_block: ActiveBlock::Connections,
@@ -266,9 +568,18 @@ impl App {
             connection_tree: Vec::new(),
             last_table_info: None,
             selected_result_tab_index: None,
+            pinned_tab: None,
+            split_results: false,
+            split_focus_right: false,
             connection_manager: ConnectionManager::new(),
             prefetched_structures: HashMap::new(),
             prefetch_receiver: None,
+            prefetch_in_flight: HashSet::new(),
+            prefetch_spawner: None,
+            connection_retries: HashMap::new(),
+            connection_failures: HashMap::new(),
+            row_count_sender,
+            row_count_receiver,
             command_buffer: CommandBuffer::new(),
             clipboard: String::new(),
             last_key_was_d: false,
@@ -284,6 +595,20 @@ impl App {
             last_key_was_y: false,
             editing_cell_position: None,
             editing_cell_original: String::new(),
+            result_search: ResultSearch::default(),
+            query_log: QueryLog::default(),
+            bulk_deletion: None,
+            open_transactions: HashSet::new(),
+            quit_warned: false,
+            reload_requested: false,
+            tree_filter: None,
+            tree_filter_editing: false,
+            result_search_editing: false,
+            results_visible_rows: 0,
+            query_history: QueryHistory::load_or_default(),
+            adhoc_query_count: 0,
+            base_queries: HashMap::new(),
+            active_databases: HashMap::new(),
             cell_text_input: crate::ui::components::text_input::TextInput::new(),
         };
 
@@ -322,6 +647,8 @@ impl App {
             app.start_background_prefetching();
         }
 
+        app.restore_session();
+
         Ok(app)
     }
 
@@ -329,6 +656,9 @@ impl App {
     pub fn start_background_prefetching(&mut self) {
         let (tx, rx) = mpsc::unbounded_channel();
         self.prefetch_receiver = Some(rx);
+        self.prefetch_in_flight.clear();
+        self.connection_retries.clear();
+        self.connection_failures.clear();
 
         // Initialize connections as not connected - databases will be loaded after validation
         for connection in &mut self.connection_tree {
@@ -337,126 +667,357 @@ impl App {
             connection.databases = Vec::new(); // Will be populated after validation
         }
 
+        // Bound how many connections are attempted at once; the rest queue on the semaphore
+        let concurrency = self.config.database.prefetch_concurrency.max(1);
+        let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(concurrency));
+        self.prefetch_spawner = Some((tx, semaphore));
+
         // Start background connection validation
-        for mut config in self.saved_connections.clone() {
-            // Migrate from legacy format
-            config.migrate_from_legacy();
-
-            let config_clone = config.clone();
-            let connection_name = config.name.clone();
-            let tx_clone = tx.clone();
-
-            // Spawn a background task to validate connection and fetch all databases
-            tokio::spawn(async move {
-                let result =
-                    ConnectionManager::fast_prefetch_databases_only(config_clone.clone()).await;
-
-                // Send the result back to the main app
-                match result {
-                    Ok(mut prefetched_structure) => {
-                        // Filter databases based on configuration
-                        if !config_clone.databases.is_empty() {
-                            prefetched_structure
-                                .databases
-                                .retain(|db| config_clone.should_show_database(&db.name));
-                        }
+        for config in self.saved_connections.clone() {
+            self.spawn_prefetch(config);
+        }
+    }
 
-                        let _ = tx_clone.send(PrefetchResult::Success(
-                            connection_name.clone(),
-                            prefetched_structure,
-                        ));
-                        logging::info(&format!(
-                            "Successfully loaded databases for: {}",
-                            connection_name
-                        ));
-                    }
-                    Err(e) => {
-                        let _ = tx_clone.send(PrefetchResult::Failed(
-                            connection_name.clone(),
-                            e.to_string(),
-                        ));
-                        logging::error(&format!(
-                            "Failed to load databases for {}: {}",
-                            connection_name, e
-                        ));
+    /// Spawns a background task validating `config` and fetching its databases, reported
+    /// through `prefetch_receiver`
+    fn spawn_prefetch(&mut self, mut config: ConnectionConfig) {
+        let Some((tx, semaphore)) = self.prefetch_spawner.clone() else {
+            return;
+        };
+        // Migrate from legacy format
+        config.migrate_from_legacy();
+
+        let config_clone = config.clone();
+        let connection_name = config.name.clone();
+        self.prefetch_in_flight.insert(connection_name.clone());
+
+        // Spawn a background task to validate connection and fetch all databases
+        tokio::spawn(async move {
+            // The semaphore is never closed, so acquiring cannot fail
+            let Ok(_permit) = semaphore.acquire_owned().await else {
+                return;
+            };
+            let result =
+                ConnectionManager::fast_prefetch_databases_only(config_clone.clone()).await;
+
+            // Send the result back to the main app
+            match result {
+                Ok(mut prefetched_structure) => {
+                    // Filter databases based on configuration
+                    if !config_clone.databases.is_empty() {
+                        prefetched_structure
+                            .databases
+                            .retain(|db| config_clone.should_show_database(&db.name));
                     }
+
+                    let _ = tx.send(PrefetchResult::Success(
+                        connection_name.clone(),
+                        prefetched_structure,
+                    ));
+                    logging::info(&format!(
+                        "Successfully loaded databases for: {}",
+                        connection_name
+                    ));
+                }
+                Err(e) => {
+                    let _ = tx.send(PrefetchResult::Failed(
+                        connection_name.clone(),
+                        ConnectionFailure::from_error(&e),
+                    ));
+                    logging::error(&format!(
+                        "Failed to load databases for {}: {}",
+                        connection_name, e
+                    ));
                 }
+            }
+        });
+    }
+
+    /// Schedules the next retry of `connection_name` after a failed background prefetch,
+    /// waiting `connect_retry_delay` doubled for every retry already made, or gives up once
+    /// its `connect_retries` are used
+    fn schedule_connection_retry(&mut self, connection_name: &str) {
+        let Some(config) = self
+            .saved_connections
+            .iter()
+            .find(|saved| saved.name == connection_name)
+        else {
+            return;
+        };
+        let max_attempts = config
+            .connect_retries
+            .unwrap_or(self.config.database.connect_retries);
+        let retry = self
+            .connection_retries
+            .entry(connection_name.to_string())
+            .or_insert(ConnectionRetry {
+                attempt: 0,
+                max_attempts,
+                next_at: None,
             });
+        if retry.attempt >= retry.max_attempts {
+            if retry.max_attempts > 0 {
+                logging::warn(&format!(
+                    "Giving up on {} after {} retries",
+                    connection_name, retry.max_attempts
+                ));
+            }
+            self.connection_retries.remove(connection_name);
+            return;
+        }
+
+        let base = std::time::Duration::from_secs(self.config.database.connect_retry_delay);
+        let delay = base
+            .saturating_mul(2u32.saturating_pow(retry.attempt))
+            .min(MAX_RETRY_DELAY);
+        retry.next_at = Some(std::time::Instant::now() + delay);
+        logging::info(&format!(
+            "Retrying {} in {}s ({}/{})",
+            connection_name,
+            delay.as_secs(),
+            retry.attempt + 1,
+            retry.max_attempts
+        ));
+    }
+
+    /// Starts the retries that are due, at most one running per connection. Connections
+    /// connected meanwhile, e.g. by expanding them, or since removed are no longer retried.
+    pub fn check_connection_retries(&mut self) {
+        let now = std::time::Instant::now();
+        let due: Vec<String> = self
+            .connection_retries
+            .iter()
+            .filter(|(_, retry)| retry.next_at.is_some_and(|next_at| next_at <= now))
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        for name in due {
+            let connected = self.prefetched_structures.contains_key(&name)
+                || self.connection_statuses.get(&name) == Some(&ConnectionStatus::Connected);
+            let index = self
+                .saved_connections
+                .iter()
+                .position(|saved| saved.name == name);
+            let Some(index) = index.filter(|_| !connected) else {
+                self.connection_retries.remove(&name);
+                continue;
+            };
+            if self.prefetch_in_flight.contains(&name) {
+                continue;
+            }
+
+            if let Some(retry) = self.connection_retries.get_mut(&name) {
+                retry.attempt += 1;
+                retry.next_at = None;
+            }
+            self.set_tree_connection_status(index, &name, ConnectionStatus::Connecting);
+            self.spawn_prefetch(self.saved_connections[index].clone());
         }
     }
 
     /// Check for completed background prefetching results and update the UI
     pub fn check_background_prefetching(&mut self) -> Result<()> {
-        if let Some(ref mut receiver) = self.prefetch_receiver {
-            while let Ok(result) = receiver.try_recv() {
-                match result {
-                    PrefetchResult::Success(connection_name, prefetched_structure) => {
-                        // Store the prefetched structure
-                        self.prefetched_structures
-                            .insert(connection_name.clone(), prefetched_structure);
-
-                        // Update connection status
-                        self.connection_statuses
-                            .insert(connection_name.clone(), ConnectionStatus::Connected);
-
-                        // Update connection tree with all available databases
-                        if let Some(tree_item) = self
-                            .connection_tree
-                            .iter_mut()
-                            .find(|item| item.connection_config.name == connection_name)
-                        {
-                            tree_item.status = ConnectionStatus::Connected;
-                            // Don't expand by default - user needs to click to expand
-                            tree_item.is_expanded = false;
+        while let Some(result) = self
+            .prefetch_receiver
+            .as_mut()
+            .and_then(|receiver| receiver.try_recv().ok())
+        {
+            self.apply_prefetch_result(result);
+        }
+        Ok(())
+    }
 
-                            // Populate databases from prefetched data
-                            if let Some(prefetched) =
-                                self.prefetched_structures.get(&connection_name)
-                            {
-                                tree_item.databases = prefetched
-                                    .databases
-                                    .iter()
-                                    .map(|db| DatabaseTreeItem {
-                                        name: db.name.clone(),
-                                        schemas: Vec::new(), // Will be loaded on-demand
-                                        is_expanded: false,
-                                    })
-                                    .collect();
-                            }
-                        }
+    /// Applies the row counts finished in the background (see `spawn_row_count`)
+    pub fn check_background_row_counts(&mut self) {
+        while let Ok(RowCount { tab_name, count }) = self.row_count_receiver.try_recv() {
+            self.apply_row_count(&tab_name, count);
+        }
+    }
 
-                        logging::info(&format!(
-                            "Successfully loaded {} databases for: {}",
-                            self.prefetched_structures
-                                .get(&connection_name)
-                                .map(|p| p.databases.len())
-                                .unwrap_or(0),
-                            connection_name
-                        ));
-                    }
-                    PrefetchResult::Failed(connection_name, error_message) => {
-                        // Update connection status to failed
-                        self.connection_statuses
-                            .insert(connection_name.clone(), ConnectionStatus::Failed);
-
-                        // Update connection tree status
-                        if let Some(tree_item) = self
-                            .connection_tree
-                            .iter_mut()
-                            .find(|item| item.connection_config.name == connection_name)
+    /// Sets the totals of `tab_name` from the count of its rows, warning when the table is
+    /// large. A failed count leaves the totals unknown.
+    fn apply_row_count(&mut self, tab_name: &str, count: Result<u64, String>) {
+        let Some((_, _, state)) = self
+            .result_tabs
+            .iter_mut()
+            .find(|(name, _, _)| name == tab_name)
+        else {
+            return;
+        };
+        // A filter or reload since the table was opened has counted the rows again
+        if state.total_records.is_some()
+            || !state.where_clause.is_empty()
+            || state.base_query.is_some()
+        {
+            return;
+        }
+
+        let total_records = match count {
+            Ok(count) => count,
+            Err(err) => {
+                logging::warn(&format!("Could not count rows of {}: {}", tab_name, err));
+                return;
+            }
+        };
+        state.total_records = Some(total_records);
+        state.total_pages = Some(
+            total_records
+                .div_ceil(u64::from(state.page_size.max(1)))
+                .max(1) as u32,
+        );
+
+        let threshold = self.config.database.large_table_rows;
+        if threshold > 0 && total_records >= threshold {
+            self.set_status_message(format!(
+                "{} has {} rows; add a WHERE filter before sorting or paging through it",
+                tab_name, total_records
+            ));
+        }
+    }
+
+    /// Runs `count`, the rows of the table just opened in `tab_name`, in the background,
+    /// sending the result to `check_background_row_counts`
+    fn spawn_row_count(
+        &self,
+        tab_name: String,
+        timeout: Option<std::time::Duration>,
+        count: crate::database::core::RowCountFuture,
+    ) {
+        let sender = self.row_count_sender.clone();
+        tokio::spawn(async move {
+            let count = with_timeout(timeout, count).await;
+            let _ = sender.send(RowCount {
+                tab_name,
+                count: count.map_err(|err| err.to_string()),
+            });
+        });
+    }
+
+    /// Waits for an in-flight background prefetch of `connection_name` to finish,
+    /// applying any results that arrive in the meantime.
+    async fn await_in_flight_prefetch(&mut self, connection_name: &str) {
+        if !self.prefetch_in_flight.contains(connection_name) {
+            return;
+        }
+
+        logging::debug(&format!(
+            "Waiting for background prefetch of {} before expanding",
+            connection_name
+        ));
+
+        // Slightly longer than the fast prefetch timeout so its result can still arrive
+        let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(5);
+        while self.prefetch_in_flight.contains(connection_name) {
+            let Some(receiver) = self.prefetch_receiver.as_mut() else {
+                break;
+            };
+            match tokio::time::timeout_at(deadline, receiver.recv()).await {
+                Ok(Some(result)) => self.apply_prefetch_result(result),
+                Ok(None) => {
+                    // All prefetch tasks are gone; nothing left to wait for
+                    self.prefetch_in_flight.clear();
+                    break;
+                }
+                Err(_) => {
+                    logging::warn(&format!(
+                        "Timed out waiting for background prefetch of {}",
+                        connection_name
+                    ));
+                    break;
+                }
+            }
+        }
+    }
+
+    fn apply_prefetch_result(&mut self, result: PrefetchResult) {
+        match result {
+            PrefetchResult::Success(connection_name, prefetched_structure) => {
+                self.prefetch_in_flight.remove(&connection_name);
+
+                // On-demand expansion may already have stored a fuller structure
+                if self.prefetched_structures.contains_key(&connection_name) {
+                    logging::debug(&format!(
+                        "Ignoring background prefetch for {}: already loaded on demand",
+                        connection_name
+                    ));
+                    return;
+                }
+
+                self.connection_retries.remove(&connection_name);
+                self.connection_failures.remove(&connection_name);
+
+                // Store the prefetched structure
+                self.prefetched_structures
+                    .insert(connection_name.clone(), prefetched_structure);
+
+                // Update connection status
+                self.connection_statuses
+                    .insert(connection_name.clone(), ConnectionStatus::Connected);
+
+                // Update connection tree with all available databases
+                if let Some(tree_item) = self
+                    .connection_tree
+                    .iter_mut()
+                    .find(|item| item.connection_config.name == connection_name)
+                {
+                    tree_item.status = ConnectionStatus::Connected;
+
+                    // Never clobber a tree the user has already expanded
+                    if !tree_item.is_expanded {
+                        if let Some(prefetched) = self.prefetched_structures.get(&connection_name)
                         {
-                            tree_item.status = ConnectionStatus::Failed;
+                            tree_item.databases = prefetched
+                                .databases
+                                .iter()
+                                .map(|db| DatabaseTreeItem {
+                                    name: db.name.clone(),
+                                    schemas: Vec::new(), // Will be loaded on-demand
+                                    is_expanded: false,
+                                })
+                                .collect();
                         }
-
-                        logging::error(&format!(
-                            "Background prefetching failed for {}: {}",
-                            connection_name, error_message
-                        ));
                     }
                 }
+
+                logging::info(&format!(
+                    "Successfully loaded {} databases for: {}",
+                    self.prefetched_structures
+                        .get(&connection_name)
+                        .map(|p| p.databases.len())
+                        .unwrap_or(0),
+                    connection_name
+                ));
+            }
+            PrefetchResult::Failed(connection_name, failure) => {
+                self.prefetch_in_flight.remove(&connection_name);
+
+                // A failed background attempt must not override a successful on-demand connect
+                if self.prefetched_structures.contains_key(&connection_name) {
+                    return;
+                }
+
+                // Update connection status to failed
+                self.connection_statuses
+                    .insert(connection_name.clone(), ConnectionStatus::Failed);
+
+                // Update connection tree status
+                if let Some(tree_item) = self
+                    .connection_tree
+                    .iter_mut()
+                    .find(|item| item.connection_config.name == connection_name)
+                {
+                    tree_item.status = ConnectionStatus::Failed;
+                }
+
+                logging::error(&format!(
+                    "Background prefetching failed for {}: {}",
+                    connection_name, failure.message
+                ));
+                self.connection_failures
+                    .insert(connection_name.clone(), failure);
+                self.schedule_connection_retry(&connection_name);
             }
         }
-        Ok(())
     }
 
     pub async fn follow_foreign_key(&mut self) -> Result<()> {
@@ -475,7 +1036,8 @@ impl App {
                         .rows
                         .get(row_idx)
                         .and_then(|r| r.get(col_idx))
-                        .cloned();
+                        .cloned()
+                        .flatten();
                     (col_name, cell_val)
                 } else {
                     (None, None)
@@ -484,11 +1046,14 @@ impl App {
                 (None, None)
             };
             match (col, val) {
-                (Some(c), Some(v)) if v.to_ascii_uppercase() != "NULL" => (c, v),
+                (Some(c), Some(v)) => (c, v),
                 _ => return Ok(()),
             }
         };
 
+        self.ensure_alive(&conn_name).await?;
+        let page_size = self.page_size_for(&conn_name);
+        let timeout = self.statement_timeout_for(&conn_name);
         let db = match self.connection_manager.get_connection(&conn_name) {
             Some(db) => db,
             None => return Ok(()),
@@ -510,11 +1075,14 @@ impl App {
             let params = QueryParams {
                 where_clause: Some(where_clause),
                 order_by: None,
-                limit: Some(50),
+                limit: Some(page_size),
                 offset: None,
+                base_query: None,
+                where_params: Vec::new(),
             };
 
-            let result = db.fetch_table_data(&schema, &table, &params).await?;
+            let result =
+                with_timeout(timeout, db.fetch_table_data(&schema, &table, &params)).await?;
 
             let tab_name = format!("{}:{}.{}", conn_name, schema, table);
             let tab_index = self
@@ -523,24 +1091,36 @@ impl App {
                 .position(|(name, _, _)| name == &tab_name);
 
             let mut query_state = QueryState {
-                page_size: 50,
+                page_size,
                 current_page: 1,
                 total_pages: Some(1),
                 total_records: Some(0),
-                sort_column: None,
-                sort_order: None,
+                sort_columns: Vec::new(),
+                nulls_order: None,
                 rows_marked_for_deletion: HashSet::new(),
+                cleared_deletion_marks: HashSet::new(),
+                committed_deletions: 0,
                 where_clause: params.where_clause.clone().unwrap_or_default(),
                 order_by_clause: String::new(),
                 primary_key_columns: Vec::new(),
+                base_query: None,
+                revealed_columns: Vec::new(),
+                hidden_columns: HashSet::new(),
+                table_info: None,
+                where_params: Vec::new(),
+                needs_fetch: false,
+                col_scroll_offset: 0,
+                auto_refresh: None,
+                last_auto_refresh: None,
+                record_view: false,
+                column_formats: HashMap::new(),
+                selection_anchor: None,
+                wrap_cells: false,
+                capped_query: None,
             };
 
-            let pk_columns = match db.get_columns(&schema, &table).await {
-                Ok(cols) => cols
-                    .iter()
-                    .filter(|c| c.is_primary_key)
-                    .map(|c| c.name.clone())
-                    .collect::<Vec<String>>(),
+            let pk_columns = match db.get_primary_keys(&schema, &table).await {
+                Ok(keys) => keys,
                 Err(e) => {
                     crate::logging::warn(&format!("Could not fetch PKs: {}", e));
                     Vec::new()
@@ -562,6 +1142,8 @@ impl App {
                     self.result_tabs.get_mut(index)
                 {
                     *result_slot = result;
+                    state.rows_marked_for_deletion.clear();
+                    state.cleared_deletion_marks.clear();
                     state.total_records = Some(total_records);
                     state.total_pages = Some(total_pages);
                     state.current_page = 1;
@@ -575,18 +1157,116 @@ impl App {
                 self.cursor_position = (0, 0);
             }
 
-            self.last_table_info = Some((conn_name, schema, table));
+            self.set_current_table_info((conn_name, schema, table));
             self.active_pane = Pane::Results;
         }
 
         Ok(())
     }
 
-    /// Sets the `should_quit` flag to true, signaling the application to terminate.
+    /// Sets the `should_quit` flag to true, signaling the application to terminate. With a
+    /// transaction open, the first call only warns; quitting rolls it back.
     pub fn quit(&mut self) {
+        if !self.open_transactions.is_empty() && !self.quit_warned {
+            self.quit_warned = true;
+            self.set_status_message(format!(
+                "Transaction open on {}; quit again to roll it back, or :commit first",
+                self.open_transaction_names()
+            ));
+            return;
+        }
         self.should_quit = true;
     }
 
+    /// Names of the connections with an open transaction, sorted and comma-separated
+    pub fn open_transaction_names(&self) -> String {
+        let mut names: Vec<&str> = self.open_transactions.iter().map(String::as_str).collect();
+        names.sort_unstable();
+        names.join(", ")
+    }
+
+    /// Connection `begin`, `commit` and `rollback` act on: the current one, if its database
+    /// has transactions
+    fn transaction_connection(&self) -> Result<String> {
+        let name = self
+            .active_connection_name()
+            .ok_or_else(|| anyhow::anyhow!("No connection selected"))?;
+        let db_type = self
+            .connection_tree
+            .iter()
+            .find(|item| item.connection_config.name == name)
+            .map(|item| item.connection_config.db_type.clone());
+        if matches!(db_type, Some(DatabaseType::MongoDB | DatabaseType::Mock)) {
+            return Err(anyhow::anyhow!("{} has no transactions", name));
+        }
+        Ok(name)
+    }
+
+    /// `begin`: opens a transaction on the current connection. Cell edits and deletions on it
+    /// then wait for `commit` or `rollback`.
+    pub async fn begin_transaction(&mut self) -> Result<()> {
+        let name = self.transaction_connection()?;
+        if self.open_transactions.contains(&name) {
+            self.set_status_message(format!("A transaction is already open on {}", name));
+            return Ok(());
+        }
+        let connection = self
+            .connection_manager
+            .get_connection(&name)
+            .ok_or_else(|| anyhow::anyhow!("Connection '{}' is not connected", name))?;
+        connection.execute_statement("BEGIN").await?;
+
+        logging::info(&format!("Began transaction on {}", name));
+        self.open_transactions.insert(name.clone());
+        self.set_status_message(format!(
+            "Transaction open on {}; :commit or :rollback to end it",
+            name
+        ));
+        Ok(())
+    }
+
+    /// `commit`/`rollback`: ends the transaction opened with `begin` on the current connection
+    pub async fn end_transaction(&mut self, commit: bool) -> Result<()> {
+        let name = self.transaction_connection()?;
+        if !self.open_transactions.contains(&name) {
+            self.set_status_message(format!("No transaction open on {}", name));
+            return Ok(());
+        }
+        let connection = self
+            .connection_manager
+            .get_connection(&name)
+            .ok_or_else(|| anyhow::anyhow!("Connection '{}' is not connected", name))?;
+        let (statement, done) = if commit {
+            ("COMMIT", "Committed")
+        } else {
+            ("ROLLBACK", "Rolled back")
+        };
+        connection.execute_statement(statement).await?;
+
+        logging::info(&format!("{} transaction on {}", done, name));
+        self.open_transactions.remove(&name);
+        self.quit_warned = false;
+        // A rollback undoes edits the current tab shows
+        if !commit && self.last_table_info.as_ref().is_some_and(|(c, _, _)| *c == name) {
+            self.reload_requested = true;
+        }
+        self.set_status_message(format!("{} transaction on {}", done, name));
+        Ok(())
+    }
+
+    /// Rolls back every open transaction, before quitting
+    pub async fn rollback_open_transactions(&mut self) {
+        for name in std::mem::take(&mut self.open_transactions) {
+            let Some(connection) = self.connection_manager.get_connection(&name) else {
+                continue;
+            };
+            match connection.execute_statement("ROLLBACK").await {
+                Ok(_) => logging::warn(&format!("Rolled back the open transaction on {}", name)),
+                Err(e) => logging::error(&format!("Error rolling back on {}: {}", name, e)),
+            }
+        }
+    }
+
     /// Toggles the visibility of the themes modal.
     pub fn toggle_themes_modal(&mut self) {
         use crate::ui::modals::ThemesModal;
@@ -604,17 +1284,13 @@ impl App {
 
     /// Toggles the visibility of the connection modal.
     pub fn toggle_connection_modal(&mut self) {
-        use crate::ui::modals::ConnectionModal;
-
         // If connection modal is already open, bring it to the front
         if self.modal_manager.focus_modal_with_title("New Connection") {
             return;
         }
 
         // Otherwise, open it (allowing modal stacking)
-        let connection_modal = Box::new(ConnectionModal::new());
-        self.modal_manager.push(connection_modal);
-        self.active_block = ActiveBlock::ConnectionModal;
+        self.show_connection_modal();
     }
 
     /// Close the active modal
@@ -631,27 +1307,59 @@ impl App {
         self.modal_manager.push(themes_modal);
     }
 
-    /// Show connection modal
+    /// Show connection modal for a new connection
     pub fn show_connection_modal(&mut self) {
+        self.connection_form = ConnectionForm::default();
+        self.show_connection_form();
+    }
+
+    /// Show connection modal filled in from `connection_form`, e.g. to edit a connection
+    pub fn show_connection_form(&mut self) {
         use crate::ui::modals::ConnectionModal;
-        let connection_modal = Box::new(ConnectionModal::new());
+        let connection_modal = Box::new(ConnectionModal::new(&self.connection_form));
         self.modal_manager.push(connection_modal);
         self.active_block = ActiveBlock::ConnectionModal;
     }
 
+    /// Saves the connection submitted by the connection modal: edits the one it was opened
+    /// for, or else adds a new one
+    pub fn submit_connection_form(&mut self, form: ConnectionForm) {
+        let editing = form.editing_index.is_some();
+        self.connection_form = form;
+        if editing {
+            self.edit_connection();
+        } else {
+            self.save_connection();
+        }
+    }
+
     /// Saves a new connection based on the data in `connection_form`.
     pub fn save_connection(&mut self) {
+        let port = match self.connection_form.db_type.parse_port(&self.connection_form.port) {
+            Ok(port) => port,
+            Err(e) => {
+                self.set_status_message(e);
+                return;
+            }
+        };
         let new_connection = ConnectionConfig {
             name: self.connection_form.name.clone(),
             db_type: self.connection_form.db_type.clone(),
             host: self.connection_form.host.clone(),
-            port: self.connection_form.port.parse().unwrap_or(5432),
+            port,
             username: self.connection_form.username.clone(),
             password: Some(self.connection_form.password.clone()),
             default_database: Some(self.connection_form.database.clone()),
             databases: std::collections::HashMap::new(),
-            ssh_tunnel: None,
+            ssh_tunnel: self.connection_form.ssh_config(),
             ssh_tunnel_name: self.connection_form.ssh_tunnel_name.clone(),
+            on_connect: Vec::new(),
+            scope_schema: None,
+            relation_kinds: Vec::new(),
+            read_only: self.connection_form.read_only,
+            default_page_size: None,
+            statement_timeout: ConnectionConfig::default().statement_timeout,
+            connect_retries: None,
             database: Some(self.connection_form.database.clone()),
         };
 
@@ -660,6 +1368,8 @@ impl App {
             crate::logging::handle_non_critical_error(&err);
         }
 
+        self.set_status_message(format!("Added connection '{}'", new_connection.name));
+
         // Add to connection tree
         self.connection_tree.push(ConnectionTreeItem {
             connection_config: new_connection,
@@ -671,6 +1381,93 @@ impl App {
         self.connection_form = ConnectionForm::default();
     }
 
+    /// Writes the saved connections to `path` for sharing; passwords are left out unless
+    /// `include_passwords` is set
+    pub fn export_connections(&mut self, path: &str, include_passwords: bool) -> Result<()> {
+        let connections: Vec<ConnectionConfig> = self
+            .saved_connections
+            .iter()
+            .cloned()
+            .map(|mut connection| {
+                if !include_passwords {
+                    connection.password = None;
+                    if let Some(ssh) = connection.ssh_tunnel.as_mut() {
+                        ssh.password = None;
+                        ssh.passphrase = None;
+                    }
+                }
+                connection
+            })
+            .collect();
+
+        let path = crate::export::resolve_path(path);
+        crate::config::Config::export_connections(&path, &connections)?;
+        let suffix = if include_passwords { " (with passwords)" } else { "" };
+        self.set_status_message(format!(
+            "Exported {} connections to {}{}",
+            connections.len(),
+            path.display(),
+            suffix
+        ));
+        Ok(())
+    }
+
+    /// Adds the connections of a file written by `export_connections`. Ones named like an
+    /// existing connection are skipped and reported, or replace it with `replace`.
+    pub fn import_connections(&mut self, path: &str, replace: bool) -> Result<()> {
+        let path = crate::export::resolve_path(path);
+        let imported = crate::config::Config::import_connections(&path)?;
+
+        let mut added = 0;
+        let mut replaced = 0;
+        let mut skipped = Vec::new();
+        for mut connection in imported {
+            connection.migrate_from_legacy();
+            match self
+                .saved_connections
+                .iter()
+                .position(|saved| saved.name == connection.name)
+            {
+                Some(index) if replace => {
+                    self.saved_connections[index] = connection.clone();
+                    if let Some(tree_item) = self.connection_tree.get_mut(index) {
+                        tree_item.connection_config = connection;
+                    }
+                    replaced += 1;
+                }
+                Some(_) => skipped.push(connection.name),
+                None => {
+                    self.saved_connections.push(connection.clone());
+                    self.connection_statuses
+                        .insert(connection.name.clone(), ConnectionStatus::NotConnected);
+                    self.connection_tree.push(ConnectionTreeItem {
+                        connection_config: connection,
+                        status: ConnectionStatus::NotConnected,
+                        databases: Vec::new(),
+                        is_expanded: false,
+                    });
+                    added += 1;
+                }
+            }
+        }
+
+        if added + replaced > 0 {
+            self.config.save_connections(&self.saved_connections)?;
+        }
+        let mut message = format!("Imported {} connections from {}", added, path.display());
+        if replaced > 0 {
+            message.push_str(&format!(", replaced {}", replaced));
+        }
+        if !skipped.is_empty() {
+            message.push_str(&format!(
+                "; skipped existing {} (use --replace to overwrite)",
+                skipped.join(", ")
+            ));
+        }
+        self.set_status_message(message);
+        Ok(())
+    }
+
     /// Loads connections from the configuration file.
     pub fn load_connections(&mut self) {
         self.saved_connections = match self.config.load_connections() {
@@ -692,15 +1489,23 @@ impl App {
     pub fn move_cursor_in_results(&mut self, direction: Direction) {
         if let Some(selected_tab_index) = self.selected_result_tab_index {
             if let Some((_, result, _)) = self.result_tabs.get(selected_tab_index) {
+                // The cursor may point past a page that has since shrunk
+                let last_row = result.rows.len().saturating_sub(1);
+                let last_column = result.columns.len().saturating_sub(1);
+                self.cursor_position.0 = self.cursor_position.0.min(last_column);
+                self.cursor_position.1 = self.cursor_position.1.min(last_row);
+
+                let visible = self.visible_column_indices();
+                let column = self.cursor_position.0;
                 match direction {
                     Direction::Left => {
-                        if self.cursor_position.0 > 0 {
-                            self.cursor_position.0 -= 1;
+                        if let Some(&previous) = visible.iter().rev().find(|&&i| i < column) {
+                            self.cursor_position.0 = previous;
                         }
                     }
                     Direction::Right => {
-                        if self.cursor_position.0 < result.columns.len().saturating_sub(1) {
-                            self.cursor_position.0 += 1;
+                        if let Some(&next) = visible.iter().find(|&&i| i > column) {
+                            self.cursor_position.0 = next;
                         }
                     }
                     Direction::Up => {
@@ -709,7 +1514,7 @@ impl App {
                         }
                     }
                     Direction::Down => {
-                        if self.cursor_position.1 < result.rows.len().saturating_sub(1) {
+                        if self.cursor_position.1 < last_row {
                             self.cursor_position.1 += 1;
                         }
                     }
@@ -718,20 +1523,212 @@ impl App {
         }
     }
 
-    pub fn get_current_field_length(&self) -> usize {
-        if let Some(state) = self.current_query_state() {
-            match self.cursor_position.0 {
-                0 => state.where_clause.chars().count(),
-                1 => state.order_by_clause.chars().count(),
-                _ => 0,
-            }
-        } else {
-            0
-        }
+    /// Indices of the current tab's columns that are rendered (see `max_visible_columns`)
+    pub fn visible_column_indices(&self) -> Vec<usize> {
+        self.selected_result_tab_index
+            .map(|index| self.tab_visible_column_indices(index))
+            .unwrap_or_default()
     }
 
-    /// Handles navigation actions based on the current active pane.
-    pub fn handle_navigation(&mut self, action: NavigationAction) {
+    /// Indices of the columns of the tab at `index` that are rendered
+    pub fn tab_visible_column_indices(&self, index: usize) -> Vec<usize> {
+        self.result_tabs
+            .get(index)
+            .map(|(_, result, state)| {
+                state.visible_column_indices(
+                    &result.columns,
+                    self.config.layout.max_visible_columns,
+                )
+            })
+            .unwrap_or_default()
+    }
+
+    /// Shows a column hidden by `max_visible_columns` or `hide_cursor_column`; `all` reveals
+    /// every column
+    pub fn reveal_column(&mut self, column: &str) {
+        let max_visible = self.config.layout.max_visible_columns;
+        let Some((_, result, state)) = self
+            .selected_result_tab_index
+            .and_then(|index| self.result_tabs.get_mut(index))
+        else {
+            self.set_status_message("No result tab selected".to_string());
+            return;
+        };
+
+        let visible = state.visible_column_indices(&result.columns, max_visible);
+        let hidden: Vec<String> = result
+            .columns
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !visible.contains(index))
+            .map(|(_, name)| name.clone())
+            .collect();
+
+        if column.is_empty() {
+            let message = if hidden.is_empty() {
+                "No hidden columns".to_string()
+            } else {
+                format!("Hidden columns: {}", hidden.join(", "))
+            };
+            self.set_status_message(message);
+            return;
+        }
+
+        if column.eq_ignore_ascii_case("all") {
+            let count = hidden.len();
+            state.hidden_columns.clear();
+            state.revealed_columns.extend(hidden);
+            self.set_status_message(format!("Revealed {} hidden columns", count));
+            return;
+        }
+
+        match hidden.iter().find(|name| name.eq_ignore_ascii_case(column)) {
+            Some(name) => {
+                let message = format!("Revealed column {}", name);
+                if let Some(index) = result.columns.iter().position(|existing| existing == name) {
+                    state.hidden_columns.remove(&index);
+                }
+                state.revealed_columns.push(name.clone());
+                self.set_status_message(message);
+            }
+            None if result.columns.iter().any(|name| name.eq_ignore_ascii_case(column)) => {
+                self.set_status_message(format!("Column {} is already visible", column));
+            }
+            None => self.set_status_message(format!("Unknown column: {}", column)),
+        }
+    }
+
+    /// Hides the cursor's column of the selected tab and moves the cursor onto the next shown
+    /// column, or the previous one at the right edge. The last shown column stays.
+    pub fn hide_cursor_column(&mut self) {
+        let visible = self.visible_column_indices();
+        let column = self.cursor_position.0;
+        let Some((_, result, state)) = self
+            .selected_result_tab_index
+            .and_then(|index| self.result_tabs.get_mut(index))
+        else {
+            self.set_status_message("No result tab selected".to_string());
+            return;
+        };
+        let Some(name) = result.columns.get(column).cloned() else {
+            self.set_status_message("No column to hide".to_string());
+            return;
+        };
+        if visible.len() <= 1 {
+            self.set_status_message("The last shown column can't be hidden".to_string());
+            return;
+        }
+
+        state.hidden_columns.insert(column);
+        state.revealed_columns.retain(|revealed| *revealed != name);
+        state.col_scroll_offset = state.col_scroll_offset.min(visible.len() - 2);
+        if let Some(&next) = visible
+            .iter()
+            .find(|&&index| index > column)
+            .or_else(|| visible.iter().rev().find(|&&index| index < column))
+        {
+            self.cursor_position.0 = next;
+        }
+        let count = state.hidden_columns.len();
+        self.set_status_message(format!(
+            "Hid column {} ({} hidden, Alt+Shift+h or :showcol all shows them)",
+            name, count
+        ));
+    }
+
+    /// Applies a `format` option to `column` of the selected tab: `thousands`,
+    /// `decimals <n>`, `left`, `center`, `right`, or `clear` to drop them all
+    pub fn format_column(&mut self, column: &str, option: &str) {
+        let Some((_, result, state)) = self
+            .selected_result_tab_index
+            .and_then(|index| self.result_tabs.get_mut(index))
+        else {
+            self.set_status_message("No result tab selected".to_string());
+            return;
+        };
+        let Some(index) = result
+            .columns
+            .iter()
+            .position(|name| name == column)
+            .or_else(|| {
+                result
+                    .columns
+                    .iter()
+                    .position(|name| name.eq_ignore_ascii_case(column))
+            })
+        else {
+            self.set_status_message(format!("Unknown column: {}", column));
+            return;
+        };
+        let name = result.columns[index].clone();
+
+        let mut format = state.column_formats.get(&name).copied().unwrap_or_default();
+        let lowered = option.to_ascii_lowercase();
+        let words: Vec<&str> = lowered.split_whitespace().collect();
+        match words.as_slice() {
+            ["thousands"] => format.thousands = true,
+            ["decimals", digits] => match digits.parse::<usize>() {
+                Ok(digits) if digits <= 20 => format.decimals = Some(digits),
+                _ => {
+                    self.set_status_message(format!("Decimals must be 0 to 20, not {}", digits));
+                    return;
+                }
+            },
+            ["left"] => format.alignment = Some(ColumnAlignment::Left),
+            ["center"] => format.alignment = Some(ColumnAlignment::Center),
+            ["right"] => format.alignment = Some(ColumnAlignment::Right),
+            ["clear"] => format = ColumnFormat::default(),
+            _ => {
+                self.set_status_message(format!(
+                    "Unknown format '{}' (use thousands, decimals <n>, left, center, right or \
+                     clear)",
+                    option
+                ));
+                return;
+            }
+        }
+
+        // Columns of unknown type qualify when every loaded value is a number
+        let untyped = result.column_types.get(index).is_none_or(|t| t.is_empty());
+        let numeric = result.column_kind(index) == ColumnKind::Numeric
+            || untyped
+                && result
+                    .rows
+                    .iter()
+                    .filter_map(|row| row.get(index).and_then(|cell| cell.as_deref()))
+                    .all(|cell| ColumnFormat::default().format_number(cell).is_some());
+        if format.formats_numbers() && !numeric {
+            self.set_status_message(format!(
+                "Column {} isn't numeric, so it can't take number formats",
+                name
+            ));
+            return;
+        }
+
+        let message = if format == ColumnFormat::default() {
+            state.column_formats.remove(&name);
+            format!("Cleared the format of {}", name)
+        } else {
+            state.column_formats.insert(name.clone(), format);
+            format!("Formatted {}", name)
+        };
+        self.set_status_message(message);
+    }
+
+    pub fn get_current_field_length(&self) -> usize {
+        if let Some(state) = self.current_query_state() {
+            match self.cursor_position.0 {
+                0 => state.where_clause.chars().count(),
+                1 => state.order_by_clause.chars().count(),
+                _ => 0,
+            }
+        } else {
+            0
+        }
+    }
+
+    /// Handles navigation actions based on the current active pane.
+    pub fn handle_navigation(&mut self, action: NavigationAction) {
         match action {
             NavigationAction::Direction(direction) => {
                 match self.active_pane {
@@ -800,7 +1797,7 @@ impl App {
             match action {
                 TreeAction::Expand => {
                     logging::debug(&format!("Expanding connection at visual index {}", idx));
-                    self.toggle_tree_item(idx).await?;
+                    self.toggle_tree_item(idx, true).await?;
                 }
                 TreeAction::Collapse => {
                     // Just collapse without making any async calls
@@ -831,11 +1828,82 @@ impl App {
                         }
                     }
                 }
+                TreeAction::Activate => {
+                    self.activate_tree_item(idx).await?;
+                }
             }
         }
         Ok(())
     }
 
+    /// Activates the tree item at `visual_index`. Unlike `toggle_tree_item` this never
+    /// collapses: connections (connecting first if needed), databases and schemas are
+    /// expanded, and tables are opened in a results tab.
+    async fn activate_tree_item(&mut self, visual_index: usize) -> Result<()> {
+        let Some(tree_item) = self.get_tree_item_at_visual_index(visual_index) else {
+            return Ok(());
+        };
+
+        let is_expanded = match tree_item {
+            TreeItem::Connection(conn_idx) => self
+                .connection_tree
+                .get(conn_idx)
+                .map(|connection| connection.is_expanded),
+            TreeItem::Database(conn_idx, db_idx) => self
+                .connection_tree
+                .get(conn_idx)
+                .and_then(|connection| connection.databases.get(db_idx))
+                .map(|database| database.is_expanded),
+            TreeItem::Schema(conn_idx, db_idx, schema_idx) => self
+                .connection_tree
+                .get(conn_idx)
+                .and_then(|connection| connection.databases.get(db_idx))
+                .and_then(|database| database.schemas.get(schema_idx))
+                .map(|schema| schema.is_expanded),
+            TreeItem::Table(_, _, _, _) => Some(false),
+        };
+
+        if is_expanded == Some(false) {
+            self.toggle_tree_item(visual_index, true).await?;
+        }
+        Ok(())
+    }
+
+    /// Opens the table selected in the tree in a results tab, or reloads its tab, leaving the
+    /// selected tab and the focused pane as they are
+    pub async fn open_table_in_background(&mut self) -> Result<()> {
+        let Some(visual_index) = self.selected_connection_idx else {
+            return Ok(());
+        };
+        let Some(TreeItem::Table(conn_idx, db_idx, schema_idx, table_idx)) =
+            self.get_tree_item_at_visual_index(visual_index)
+        else {
+            self.set_status_message("Only tables open in a background tab".to_string());
+            return Ok(());
+        };
+        let Some(tab_name) = self.connection_tree.get(conn_idx).and_then(|connection| {
+            let database = connection.databases.get(db_idx)?;
+            let schema = database.schemas.get(schema_idx)?;
+            let table = schema.tables.get(table_idx)?;
+            Some(format!(
+                "{}:{}:{}.{}",
+                connection.connection_config.name, database.name, schema.name, table
+            ))
+        }) else {
+            return Ok(());
+        };
+
+        let tab_count = self.result_tabs.len();
+        self.toggle_tree_item(visual_index, false).await?;
+        let message = if self.result_tabs.len() > tab_count {
+            format!("Opened {} in a background tab", tab_name)
+        } else {
+            format!("Reloaded the tab of {}", tab_name)
+        };
+        self.set_status_message(message);
+        Ok(())
+    }
+
     /// Expands a connection in the tree to show databases.
     pub async fn expand_connection(&mut self, index: usize) -> Result<()> {
         logging::debug(&format!(
@@ -843,6 +1911,16 @@ impl App {
             index
         ));
 
+        // Let an in-flight background prefetch finish instead of racing it with a second connect
+        if let Some(name) = self
+            .connection_tree
+            .get(index)
+            .filter(|connection| !connection.is_expanded)
+            .map(|connection| connection.connection_config.name.clone())
+        {
+            self.await_in_flight_prefetch(&name).await;
+        }
+
         if let Some(connection) = self.connection_tree.get_mut(index) {
             if !connection.is_expanded {
                 // Check if we already have prefetched data
@@ -865,6 +1943,7 @@ impl App {
                                 .map(|schema| SchemaTreeItem {
                                     name: schema.name.clone(),
                                     tables: schema.tables.clone(),
+                                    relation_kinds: schema.relation_kinds.clone(),
                                     is_expanded: false,
                                 })
                                 .collect(),
@@ -934,6 +2013,7 @@ impl App {
                                         .map(|schema| SchemaTreeItem {
                                             name: schema.name.clone(),
                                             tables: schema.tables.clone(),
+                                            relation_kinds: schema.relation_kinds.clone(),
                                             is_expanded: false,
                                         })
                                         .collect(),
@@ -978,6 +2058,8 @@ impl App {
 
                                             connection.status = ConnectionStatus::Connected;
                                             connection.is_expanded = true;
+                                            self.connection_failures
+                                                .remove(&connection.connection_config.name);
 
                                             logging::info(&format!(
                                                 "Successfully expanded connection {}",
@@ -986,6 +2068,10 @@ impl App {
                                         }
                                         Err(e) => {
                                             connection.status = ConnectionStatus::Failed;
+                                            self.connection_failures.insert(
+                                                connection.connection_config.name.clone(),
+                                                ConnectionFailure::from_error(&e),
+                                            );
                                             let error_msg =
                                                 format!("Failed to list databases: {}", e);
                                             logging::error(&error_msg);
@@ -1003,6 +2089,10 @@ impl App {
                             }
                             Err(e) => {
                                 connection.status = ConnectionStatus::Failed;
+                                self.connection_failures.insert(
+                                    connection.connection_config.name.clone(),
+                                    ConnectionFailure::from_error(&e),
+                                );
                                 let error_msg = format!("Failed to connect: {}", e);
                                 logging::error(&error_msg);
                                 return Err(anyhow::anyhow!(error_msg));
@@ -1051,6 +2141,7 @@ impl App {
                                     .map(|schema| SchemaTreeItem {
                                         name: schema.name.clone(),
                                         tables: schema.tables.clone(),
+                                        relation_kinds: schema.relation_kinds.clone(),
                                         is_expanded: false,
                                     })
                                     .collect();
@@ -1144,6 +2235,7 @@ impl App {
                                 .map(|schema| SchemaTreeItem {
                                     name: schema.name.clone(),
                                     tables: schema.tables.clone(),
+                                    relation_kinds: schema.relation_kinds.clone(),
                                     is_expanded: false,
                                 })
                                 .collect();
@@ -1200,6 +2292,8 @@ impl App {
                                     if !prefetched_schema.tables.is_empty() {
                                         // Use existing prefetched tables
                                         schema.tables = prefetched_schema.tables.clone();
+                                        schema.relation_kinds =
+                                            prefetched_schema.relation_kinds.clone();
                                         schema.is_expanded = true;
                                         logging::info(&format!(
                                             "Successfully expanded schema {} using prefetched data",
@@ -1218,11 +2312,16 @@ impl App {
                             .connection_manager
                             .prefetch_tables_for_schema(
                                 &connection.connection_config.name,
+                                &database.name,
                                 &schema.name,
                             )
                             .await
                         {
-                            Ok(tables) => {
+                            Ok(relations) => {
+                                let fetched = PrefetchedSchema::with_relations(
+                                    schema.name.clone(),
+                                    relations,
+                                );
                                 // Update the prefetched structure
                                 if let Some(prefetched) = self
                                     .prefetched_structures
@@ -1238,13 +2337,16 @@ impl App {
                                             .iter_mut()
                                             .find(|s| s.name == schema.name)
                                         {
-                                            prefetched_schema.tables = tables.clone();
+                                            prefetched_schema.tables = fetched.tables.clone();
+                                            prefetched_schema.relation_kinds =
+                                                fetched.relation_kinds.clone();
                                         }
                                     }
                                 }
 
                                 // Update the UI
-                                schema.tables = tables;
+                                schema.tables = fetched.tables;
+                                schema.relation_kinds = fetched.relation_kinds;
                                 schema.is_expanded = true;
                                 logging::info(&format!(
                                     "Successfully prefetched tables for schema: {}",
@@ -1266,28 +2368,93 @@ impl App {
         Ok(())
     }
 
+    /// Loads the tables of a schema-scoped connection's schema so the tree can show them
+    /// directly under the connection.
+    async fn load_scoped_schema(&mut self, conn_idx: usize) -> Result<()> {
+        let Some(connection) = self.connection_tree.get(conn_idx) else {
+            return Ok(());
+        };
+        if !connection.is_expanded {
+            return Ok(());
+        }
+        let Some(scope) = connection.connection_config.scope_schema.clone() else {
+            return Ok(());
+        };
+        let Some(db_idx) = connection.scope_database_index() else {
+            self.set_status_message(format!("No database found for schema scope '{}'", scope));
+            return Ok(());
+        };
+
+        if !connection.databases[db_idx].is_expanded {
+            self.expand_database(conn_idx, db_idx).await?;
+        }
+
+        let schema_idx = self.connection_tree[conn_idx].databases[db_idx]
+            .schemas
+            .iter()
+            .position(|schema| schema.name == scope);
+        match schema_idx {
+            Some(schema_idx) => {
+                if !self.connection_tree[conn_idx].databases[db_idx].schemas[schema_idx]
+                    .is_expanded
+                {
+                    self.expand_schema(conn_idx, db_idx, schema_idx).await?;
+                }
+            }
+            None => {
+                let message = format!("Scoped schema '{}' not found, showing all schemas", scope);
+                logging::warn(&message);
+                self.set_status_message(message);
+            }
+        }
+        Ok(())
+    }
+
     /// Edits an existing connection based on the data in `connection_form`.
     pub fn edit_connection(&mut self) {
         if let Some(index) = self.connection_form.editing_index {
+            let port = match self.connection_form.db_type.parse_port(&self.connection_form.port) {
+                Ok(port) => port,
+                Err(e) => {
+                    self.set_status_message(e);
+                    return;
+                }
+            };
             let updated_connection = ConnectionConfig {
                 name: self.connection_form.name.clone(),
                 db_type: self.connection_form.db_type.clone(),
                 host: self.connection_form.host.clone(),
-                port: self.connection_form.port.parse().unwrap_or(5432),
+                port,
                 username: self.connection_form.username.clone(),
                 password: Some(self.connection_form.password.clone()),
                 default_database: Some(self.connection_form.database.clone()),
                 databases: std::collections::HashMap::new(),
-                ssh_tunnel: None,
+                ssh_tunnel: self.connection_form.ssh_config(),
                 ssh_tunnel_name: self.connection_form.ssh_tunnel_name.clone(),
+                // The form has no fields for the init statements; keep the configured ones
+                on_connect: self.saved_connections[index].on_connect.clone(),
+                scope_schema: self.saved_connections[index].scope_schema.clone(),
+                relation_kinds: self.saved_connections[index].relation_kinds.clone(),
+                read_only: self.connection_form.read_only,
+                default_page_size: self.saved_connections[index].default_page_size,
+                statement_timeout: self.saved_connections[index].statement_timeout,
+                connect_retries: self.saved_connections[index].connect_retries,
                 database: Some(self.connection_form.database.clone()),
             };
 
+            let previous_name = self.saved_connections[index].name.clone();
             self.saved_connections[index] = updated_connection.clone();
             if let Err(err) = self.config.save_connections(&self.saved_connections) {
                 crate::logging::handle_non_critical_error(&err);
+            } else if previous_name != updated_connection.name && self.config.database.use_keyring {
+                // The secrets were stored again under the new name
+                if let Err(err) = crate::secrets::forget_connection(&previous_name) {
+                    crate::logging::handle_non_critical_error(&err);
+                }
             }
 
+            self.set_status_message(format!("Updated connection '{}'", updated_connection.name));
+
             // Update the connection tree
             if let Some(tree_item) = self.connection_tree.get_mut(index) {
                 tree_item.connection_config = updated_connection;
@@ -1299,9 +2466,13 @@ impl App {
     pub fn delete_connection(&mut self) {
         if let Some(index) = self.selected_connection_idx {
             // Remove the connection from saved_connections
-            self.saved_connections.remove(index);
+            let removed = self.saved_connections.remove(index);
             if let Err(err) = self.config.save_connections(&self.saved_connections) {
                 crate::logging::handle_non_critical_error(&err);
+            } else if self.config.database.use_keyring {
+                if let Err(err) = crate::secrets::forget_connection(&removed.name) {
+                    crate::logging::handle_non_critical_error(&err);
+                }
             }
 
             // Remove from connection tree
@@ -1317,92 +2488,112 @@ impl App {
         }
     }
 
-    /// Calculates the total number of visible items in the connection tree.
-    pub fn get_total_visible_items(&self) -> usize {
-        let mut total = 0;
-        for connection in &self.connection_tree {
-            total += 1; // Count the connection itself
-            if connection.is_expanded {
-                for database in &connection.databases {
-                    total += 1; // Count the database
-                    if database.is_expanded {
-                        for schema in &database.schemas {
-                            total += 1; // Count the schema
-                            if schema.is_expanded {
-                                total += schema.tables.len(); // Count all tables
-                            }
+    /// Rows of the connection tree in display order. Without a filter these are the items
+    /// whose ancestors are all expanded; with one, only items leading to a match are shown,
+    /// their ancestors expanded, and below a match its children follow the expansion state.
+    pub fn visible_tree_items(&self) -> Vec<TreeItem> {
+        let filter = self
+            .tree_filter
+            .as_deref()
+            .filter(|filter| !filter.is_empty())
+            .map(str::to_lowercase);
+        let filter = filter.as_deref();
+
+        let mut items = Vec::new();
+        for (conn_idx, connection) in self.connection_tree.iter().enumerate() {
+            let databases = |filter: Option<&str>| -> Vec<TreeItem> {
+                // Schema-scoped connections list their tables directly under the connection
+                if let Some((db_idx, schema_idx)) = connection.scoped_schema() {
+                    let schema = &connection.databases[db_idx].schemas[schema_idx];
+                    return table_rows(conn_idx, db_idx, schema_idx, schema, filter);
+                }
+                let mut rows = Vec::new();
+                for (db_idx, database) in connection.databases.iter().enumerate() {
+                    let schemas = |filter: Option<&str>| -> Vec<TreeItem> {
+                        let mut rows = Vec::new();
+                        for (schema_idx, schema) in database.schemas.iter().enumerate() {
+                            rows.extend(tree_rows(
+                                TreeItem::Schema(conn_idx, db_idx, schema_idx),
+                                &schema.name,
+                                schema.is_expanded,
+                                filter,
+                                &|filter| table_rows(conn_idx, db_idx, schema_idx, schema, filter),
+                            ));
                         }
-                    }
+                        rows
+                    };
+                    rows.extend(tree_rows(
+                        TreeItem::Database(conn_idx, db_idx),
+                        &database.name,
+                        database.is_expanded,
+                        filter,
+                        &schemas,
+                    ));
                 }
-            }
+                rows
+            };
+            items.extend(tree_rows(
+                TreeItem::Connection(conn_idx),
+                &connection.connection_config.name,
+                connection.is_expanded,
+                filter,
+                &databases,
+            ));
         }
-        total
+        items
+    }
+
+    /// Calculates the total number of visible items in the connection tree.
+    pub fn get_total_visible_items(&self) -> usize {
+        self.visible_tree_items().len()
     }
 
     /// Gets the tree item at a specific visual index (considering expanded items).
     pub fn get_tree_item_at_visual_index(&self, visual_index: usize) -> Option<TreeItem> {
-        let mut current_visual_index = 0;
-
-        for (conn_idx, connection) in self.connection_tree.iter().enumerate() {
-            if current_visual_index == visual_index {
-                return Some(TreeItem::Connection(conn_idx));
-            }
-            current_visual_index += 1;
+        self.visible_tree_items().get(visual_index).copied()
+    }
 
-            if connection.is_expanded {
-                for (db_idx, database) in connection.databases.iter().enumerate() {
-                    if current_visual_index == visual_index {
-                        return Some(TreeItem::Database(conn_idx, db_idx));
-                    }
-                    current_visual_index += 1;
+    pub fn get_visual_index_for_connection(&self, connection_index: usize) -> Option<usize> {
+        self.visible_tree_items()
+            .iter()
+            .position(|item| *item == TreeItem::Connection(connection_index))
+    }
 
-                    if database.is_expanded {
-                        for (schema_idx, schema) in database.schemas.iter().enumerate() {
-                            if current_visual_index == visual_index {
-                                return Some(TreeItem::Schema(conn_idx, db_idx, schema_idx));
-                            }
-                            current_visual_index += 1;
+    /// Starts typing a filter for the connection tree, keeping the current one
+    pub fn start_tree_filter(&mut self) {
+        self.tree_filter.get_or_insert_with(String::new);
+        self.tree_filter_editing = true;
+    }
 
-                            if schema.is_expanded {
-                                for (table_idx, _) in schema.tables.iter().enumerate() {
-                                    if current_visual_index == visual_index {
-                                        return Some(TreeItem::Table(
-                                            conn_idx, db_idx, schema_idx, table_idx,
-                                        ));
-                                    }
-                                    current_visual_index += 1;
-                                }
-                            }
-                        }
-                    }
-                }
+    /// Adds (or with `None` removes) the last character of the tree filter, selecting the
+    /// first item left
+    pub fn edit_tree_filter(&mut self, c: Option<char>) {
+        let filter = self.tree_filter.get_or_insert_with(String::new);
+        match c {
+            Some(c) => filter.push(c),
+            None => {
+                filter.pop();
             }
         }
-        None
+        self.selected_connection_idx = (self.get_total_visible_items() > 0).then_some(0);
     }
 
-    pub fn get_visual_index_for_connection(&self, connection_index: usize) -> Option<usize> {
-        let mut visual_index = 0;
-        for (idx, connection) in self.connection_tree.iter().enumerate() {
-            if idx == connection_index {
-                return Some(visual_index);
-            }
-            visual_index += 1;
-            if connection.is_expanded {
-                for database in &connection.databases {
-                    visual_index += 1;
-                    if database.is_expanded {
-                        for schema in &database.schemas {
-                            visual_index += 1;
-                            if schema.is_expanded {
-                                visual_index += schema.tables.len();
-                            }
-                        }
-                    }
-                }
-            }
+    /// Stops typing the filter; the tree stays filtered
+    pub fn finish_tree_filter(&mut self) {
+        self.tree_filter_editing = false;
+    }
+
+    /// Removes the filter, keeping the selected item selected in the full tree
+    pub fn clear_tree_filter(&mut self) {
+        let selected = self
+            .selected_connection_idx
+            .and_then(|idx| self.get_tree_item_at_visual_index(idx));
+        self.tree_filter = None;
+        self.tree_filter_editing = false;
+        if let Some(selected) = selected {
+            self.selected_connection_idx =
+                self.visible_tree_items().iter().position(|item| *item == selected);
         }
-        None
     }
 
     /// Gets the visual index for a given tree item.
@@ -1602,7 +2793,17 @@ impl App {
     }
 
     pub async fn sort_results(&mut self) -> Result<()> {
-        // Get current result and state
+        self.cycle_sort_column(false).await
+    }
+
+    /// Adds the column under the cursor as a further sort key, keeping the existing ones
+    pub async fn add_sort_column(&mut self) -> Result<()> {
+        self.cycle_sort_column(true).await
+    }
+
+    /// Advances the cursor column through descending, ascending and unsorted. Unless
+    /// `keep_others` is set, the other sort columns are dropped so it becomes the only one.
+    async fn cycle_sort_column(&mut self, keep_others: bool) -> Result<()> {
         let (current_result, query_state) = match self
             .selected_result_tab_index
             .and_then(|idx| self.result_tabs.get_mut(idx))
@@ -1613,57 +2814,182 @@ impl App {
         };
 
         // Use cursor position to determine which column to sort
-        let col_idx = self.cursor_position.0;
-        let current_col = current_result.columns.get(col_idx).cloned();
-
-        if let Some(current_col) = current_col {
-            // Update sort state
-            if query_state.sort_column.as_ref() != Some(&current_col) {
-                query_state.sort_column = Some(current_col.clone());
-                query_state.sort_order = Some(false); // default: descending
-                query_state.order_by_clause = format!("{} DESC", current_col);
-            } else {
-                if let Some(order) = query_state.sort_order {
-                    if !order {
-                        query_state.sort_order = Some(true);
-                        query_state.order_by_clause = format!("{} ASC", current_col);
-                    } else {
-                        query_state.sort_column = None;
-                        query_state.sort_order = None;
-                        query_state.order_by_clause.clear();
-                    }
-                }
+        let Some(current_col) = current_result.columns.get(self.cursor_position.0).cloned() else {
+            return Ok(());
+        };
+
+        let sort_columns = &mut query_state.sort_columns;
+        if !keep_others {
+            sort_columns.retain(|(column, _)| *column == current_col);
+        }
+        match sort_columns.iter().position(|(column, _)| *column == current_col) {
+            // New sort columns start descending
+            None => sort_columns.push((current_col, false)),
+            Some(index) if !sort_columns[index].1 => sort_columns[index].1 = true,
+            Some(index) => {
+                sort_columns.remove(index);
             }
+        }
+        Self::update_order_by_clause(query_state);
+
+        // Refresh the results with new sort
+        self.refresh_results().await
+    }
+
+    /// Sets where NULLs are placed for the current sort and refreshes the results
+    pub async fn set_nulls_order(&mut self, nulls: Option<NullsOrder>) -> Result<()> {
+        let Some(query_state) = self.current_query_state_mut() else {
+            return Ok(());
+        };
+        query_state.nulls_order = nulls;
+        let sorted = !query_state.sort_columns.is_empty();
+        Self::update_order_by_clause(query_state);
 
-            // Refresh the results with new sort
+        if sorted {
             self.refresh_results().await?;
         }
+        Ok(())
+    }
+
+    /// Rows per page for tables opened on the saved connection `conn_name`
+    fn page_size_for(&self, conn_name: &str) -> u32 {
+        self.saved_connections
+            .iter()
+            .find(|saved| saved.name == conn_name)
+            .map_or(DEFAULT_PAGE_SIZE, ConnectionConfig::page_size)
+    }
+
+    /// How long queries on `conn_name` may run (see `ConnectionConfig::statement_timeout`)
+    fn statement_timeout_for(&self, conn_name: &str) -> Option<std::time::Duration> {
+        self.saved_connections
+            .iter()
+            .find(|saved| saved.name == conn_name)
+            .map_or(ConnectionConfig::default().statement_timeout(), |saved| {
+                saved.statement_timeout()
+            })
+    }
+
+    /// Changes the current tab's page size (clamped to 1..=`MAX_PAGE_SIZE`) and re-fetches,
+    /// moving to the page that holds the row under the cursor
+    pub async fn set_page_size(&mut self, page_size: u32) -> Result<()> {
+        let page_size = clamp_page_size(page_size);
+        let row = self.cursor_position.1;
+        let query_state = self
+            .current_query_state_mut()
+            .ok_or_else(|| anyhow::anyhow!("No result tab"))?;
+        if query_state.table_info.is_none() {
+            return Err(anyhow::anyhow!("Only table tabs are paged"));
+        }
+        let first_row = (query_state.current_page.max(1) - 1) as usize
+            * query_state.page_size.max(1) as usize
+            + row;
+        query_state.page_size = page_size;
+        query_state.current_page = (first_row / page_size as usize) as u32 + 1;
+
+        self.refresh_results().await?;
+        self.cursor_position.1 = first_row % page_size as usize;
+        self.set_status_message(format!("Page size: {} rows", page_size));
+        Ok(())
+    }
+
+    /// Rebuilds the ORDER BY clause from the tab's sort columns, e.g. `a DESC, b ASC`
+    fn update_order_by_clause(query_state: &mut QueryState) {
+        query_state.order_by_clause = query_state
+            .sort_columns
+            .iter()
+            .map(|(column, ascending)| {
+                OrderByTerm {
+                    column: column.clone(),
+                    descending: !ascending,
+                    nulls: query_state.nulls_order,
+                }
+                .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+    }
+
+    /// Runs the current tab's query, first prompting for the values of any `$n` placeholders
+    /// in its WHERE clause
+    pub async fn execute_query_input(&mut self) -> Result<()> {
+        let (where_clause, previous) = match self.current_query_state() {
+            Some(state) => (state.where_clause.clone(), state.where_params.clone()),
+            None => return self.refresh_results().await,
+        };
+
+        // MongoDB tabs take JSON documents, checked here so a typo never reaches the server
+        if self.current_db_type() == Some(DatabaseType::MongoDB) {
+            let order_by = self
+                .current_query_state()
+                .map(|state| state.order_by_clause.clone())
+                .unwrap_or_default();
+            let parsed = crate::database::parse_mongo_filter(&where_clause)
+                .and_then(|_| crate::database::parse_mongo_sort(&order_by));
+            if let Err(e) = parsed {
+                self.set_status_message(e.to_string());
+                return Ok(());
+            }
+            if let Some(state) = self.current_query_state_mut() {
+                state.where_params.clear();
+            }
+            return self.refresh_results().await;
+        }
+
+        let count = placeholder_count(&where_clause);
+        if count == 0 {
+            if let Some(state) = self.current_query_state_mut() {
+                state.where_params.clear();
+            }
+            return self.refresh_results().await;
+        }
 
+        self.modal_manager
+            .push(Box::new(QueryParamsModal::new(&where_clause, count, &previous)));
         Ok(())
     }
 
+    /// Binds `values` to the current tab's `$n` placeholders and runs its query
+    pub async fn bind_query_params(&mut self, values: Vec<String>) -> Result<()> {
+        if let Some(state) = self.current_query_state_mut() {
+            state.where_params = values;
+        }
+        self.refresh_results().await
+    }
+
     /// Refreshes the results for the current tab
     pub async fn refresh_results(&mut self) -> Result<()> {
+        // Ad-hoc query tabs have no table to page through or filter
+        if self
+            .current_query_state()
+            .is_some_and(|state| state.table_info.is_none())
+        {
+            return Err(anyhow::anyhow!("Ad-hoc query results can't be refreshed; rerun ':sql'"));
+        }
+        if let Some((name, _, _)) = self.last_table_info.clone() {
+            self.ensure_alive(&name).await?;
+        }
         if let Some((name, schema, table)) = &self.last_table_info {
             if let Some(connection) = self.connection_manager.get_connection(name) {
                 let query_state = self
                     .current_query_state()
                     .ok_or_else(|| anyhow::anyhow!("No active query state"))?;
-
-                let params = QueryParams {
-                    where_clause: Some(query_state.where_clause.clone()),
-                    order_by: Some(query_state.order_by_clause.clone()),
-                    limit: Some(query_state.page_size),
-                    offset: Some((query_state.current_page - 1) * query_state.page_size),
-                };
-
-                let result = connection.fetch_table_data(schema, table, &params).await?;
+                let params = Self::page_params(query_state);
+                let timeout = self.statement_timeout_for(name);
+
+                let executed_at = chrono::Local::now();
+                let started = std::time::Instant::now();
+                let fetched =
+                    with_timeout(timeout, connection.fetch_table_data(schema, table, &params))
+                        .await;
+                self.query_log.record(
+                    name,
+                    Self::query_log_entry(schema, table, &params, executed_at, started, &fetched),
+                );
+                let result = fetched?;
 
                 // Update totals
-                let total_records = match connection
-                    .count_table_rows(schema, table, params.where_clause.as_deref())
-                    .await
-                {
+                let count = connection.count_matching_rows(schema, table, &params).await;
+                let total_records = match count {
                     Ok(count) => count,
                     Err(_) => {
                         // Fallback: infer at least the number of currently visible rows
@@ -1680,8 +3006,12 @@ impl App {
                 }
 
                 if let Some(idx) = self.selected_result_tab_index {
-                    if idx < self.result_tabs.len() {
-                        self.result_tabs[idx].1 = result;
+                    if let Some((_, result_slot, state)) = self.result_tabs.get_mut(idx) {
+                        // Marks refer to row positions, which the new data invalidates
+                        *result_slot = result;
+                        state.rows_marked_for_deletion.clear();
+                        state.cleared_deletion_marks.clear();
+                        state.selection_anchor = None;
                     }
                 }
             }
@@ -1689,26 +3019,234 @@ impl App {
         Ok(())
     }
 
-    pub fn is_editing_cell(&self) -> bool {
-        self.editing_cell_position.is_some()
+    /// Parameters fetching the tab's current page with its WHERE/ORDER BY
+    fn page_params(query_state: &QueryState) -> QueryParams {
+        QueryParams {
+            where_clause: Some(query_state.where_clause.clone()),
+            order_by: Some(query_state.order_by_clause.clone()),
+            limit: Some(query_state.page_size),
+            offset: Some((query_state.current_page - 1) * query_state.page_size),
+            base_query: query_state.base_query.clone(),
+            where_params: query_state.where_params.clone(),
+        }
     }
 
-    pub fn enter_cell_edit_mode(&mut self) {
-        if let Some(tab_idx) = self.selected_result_tab_index {
-            if let Some((_, result, _)) = self.result_tabs.get(tab_idx) {
-                let col = self.cursor_position.0;
-                let row = self.cursor_position.1;
+    /// Builds a query log entry for a table fetch, rendering the parameters as the SQL it runs
+    fn query_log_entry(
+        schema: &str,
+        table: &str,
+        params: &QueryParams,
+        executed_at: chrono::DateTime<chrono::Local>,
+        started: std::time::Instant,
+        fetched: &Result<QueryResult>,
+    ) -> QueryLogEntry {
+        let mut query = match params.base_query_source() {
+            Some(source) => format!("SELECT * FROM {}", source),
+            None => format!("SELECT * FROM {}.{}", schema, table),
+        };
+        let where_clause = params.where_clause.as_deref().unwrap_or("").trim();
+        if !where_clause.is_empty() {
+            query.push_str(&format!(" WHERE {}", where_clause));
+        }
+        let order_by = params.order_by.as_deref().unwrap_or("").trim();
+        if !order_by.is_empty() {
+            query.push_str(&format!(" ORDER BY {}", order_by));
+        }
+        if let Some(limit) = params.limit {
+            query.push_str(&format!(" LIMIT {}", limit));
+        }
+        if let Some(offset) = params.offset.filter(|&offset| offset > 0) {
+            query.push_str(&format!(" OFFSET {}", offset));
+        }
+        if !params.where_params.is_empty() {
+            let values = params
+                .where_params
+                .iter()
+                .enumerate()
+                .map(|(i, value)| {
+                    // A line break in a value would end the comment, running the rest as SQL
+                    let value = value
+                        .replace('\'', "''")
+                        .replace('\r', "\\r")
+                        .replace('\n', "\\n");
+                    format!("${} = '{}'", i + 1, value)
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            query.push_str(&format!(" -- {}", values));
+        }
 
-                if let Some(row_data) = result.rows.get(row) {
-                    if let Some(cell_value) = row_data.get(col) {
-                        self.editing_cell_position = Some((col, row));
-                        self.editing_cell_original = cell_value.clone();
-                        self.cell_text_input.set_content(cell_value.clone());
-                        self.cell_text_input.move_cursor_to_end();
-                        self.cell_text_input.set_mode(crate::navigation::types::VimMode::Insert);
-                        self.input_mode = InputMode::Insert;
+        QueryLogEntry {
+            query,
+            executed_at,
+            elapsed: started.elapsed(),
+            row_count: fetched.as_ref().ok().map(|result| result.rows.len()),
+            error: fetched.as_ref().err().map(|e| e.to_string()),
+        }
+    }
 
-                        crate::logging::debug(&format!(
+    /// Name of the connection queries currently run against: the open table's, otherwise the
+    /// connection selected in the tree
+    fn active_connection_name(&self) -> Option<String> {
+        if let Some((name, _, _)) = &self.last_table_info {
+            return Some(name.clone());
+        }
+        let conn_idx = match self.get_tree_item_at_visual_index(self.selected_connection_idx?)? {
+            TreeItem::Connection(c)
+            | TreeItem::Database(c, _)
+            | TreeItem::Schema(c, _, _)
+            | TreeItem::Table(c, _, _, _) => c,
+        };
+        self.connection_tree
+            .get(conn_idx)
+            .map(|connection| connection.connection_config.name.clone())
+    }
+
+    /// Whether `conn_name` is read-only and refuses `statement`
+    fn blocks_statement(&self, conn_name: &str, statement: &str) -> bool {
+        self.connection_tree
+            .iter()
+            .find(|item| item.connection_config.name == conn_name)
+            .is_some_and(|item| item.connection_config.blocks_statement(statement))
+    }
+
+    /// Opens the query log of the active connection
+    pub fn show_query_log(&mut self) {
+        use crate::ui::modals::QueryLogModal;
+
+        let Some(name) = self.active_connection_name() else {
+            self.set_status_message("No connection selected".to_string());
+            return;
+        };
+        let entries = self.query_log.entries(&name);
+        self.modal_manager.push(Box::new(QueryLogModal::new(&name, entries)));
+    }
+
+    pub fn clear_query_log(&mut self) {
+        let Some(name) = self.active_connection_name() else {
+            self.set_status_message("No connection selected".to_string());
+            return;
+        };
+        let removed = self.query_log.clear(&name);
+        self.set_status_message(format!("Cleared {} query log entries for {}", removed, name));
+    }
+
+    /// Opens a named base query as a pseudo-table on the active connection, so filtering,
+    /// sorting and pagination apply on top of it. Without `query`, a previously registered
+    /// query of that name is reopened.
+    pub async fn open_base_query(&mut self, name: &str, query: Option<&str>) -> Result<()> {
+        let base_query = match query {
+            Some(query) => crate::database::core::validate_base_query(query)?,
+            None => self
+                .base_queries
+                .get(name)
+                .cloned()
+                .ok_or_else(|| {
+                    anyhow::anyhow!("No query named '{0}' (use 'openquery {0} SELECT ...')", name)
+                })?,
+        };
+        let conn_name = self
+            .active_connection_name()
+            .ok_or_else(|| anyhow::anyhow!("No connection selected"))?;
+        self.ensure_alive(&conn_name).await?;
+        let connection = self
+            .connection_manager
+            .get_connection(&conn_name)
+            .ok_or_else(|| anyhow::anyhow!("Connection '{}' is not connected", conn_name))?;
+
+        let query_state = QueryState {
+            page_size: self.page_size_for(&conn_name),
+            current_page: 1,
+            base_query: Some(base_query.clone()),
+            ..QueryState::default()
+        };
+        let params = QueryParams {
+            where_clause: None,
+            order_by: None,
+            limit: Some(query_state.page_size),
+            offset: None,
+            base_query: Some(base_query.clone()),
+            where_params: Vec::new(),
+        };
+
+        let executed_at = chrono::Local::now();
+        let started = std::time::Instant::now();
+        let timeout = self.statement_timeout_for(&conn_name);
+        let fetched = with_timeout(timeout, connection.fetch_table_data("", name, &params)).await;
+        self.query_log.record(
+            &conn_name,
+            Self::query_log_entry("", name, &params, executed_at, started, &fetched),
+        );
+        let result = fetched?;
+
+        let total_records = connection
+            .count_base_query_rows(&base_query, None)
+            .await
+            .unwrap_or(result.rows.len() as u64);
+        let page_size = query_state.page_size.max(1) as u64;
+        let total_pages = total_records.div_ceil(page_size).max(1) as u32;
+
+        self.base_queries.insert(name.to_string(), base_query);
+
+        let tab_name = format!("{}:query:{}", conn_name, name);
+        let mut query_state = query_state;
+        query_state.total_records = Some(total_records);
+        query_state.total_pages = Some(total_pages);
+        match self.result_tabs.iter().position(|(tab, _, _)| tab == &tab_name) {
+            Some(index) => {
+                self.result_tabs[index] = (tab_name, result, query_state);
+                self.selected_result_tab_index = Some(index);
+            }
+            None => {
+                self.result_tabs.push((tab_name, result, query_state));
+                self.selected_result_tab_index = Some(self.result_tabs.len() - 1);
+            }
+        }
+
+        self.cursor_position = (0, 0);
+        self.set_current_table_info((conn_name, String::new(), name.to_string()));
+        self.active_pane = Pane::Results;
+        Ok(())
+    }
+
+    pub fn is_editing_cell(&self) -> bool {
+        self.editing_cell_position.is_some()
+    }
+
+    pub fn enter_cell_edit_mode(&mut self) {
+        if self
+            .current_query_state()
+            .is_some_and(|state| state.base_query.is_some())
+        {
+            self.set_status_message("Base query results are read-only".to_string());
+            return;
+        }
+        if self
+            .current_query_state()
+            .is_some_and(|state| state.table_info.is_none())
+        {
+            self.set_status_message("Ad-hoc query results are read-only".to_string());
+            return;
+        }
+        if let Some(tab_idx) = self.selected_result_tab_index {
+            if let Some((_, result, _)) = self.result_tabs.get(tab_idx) {
+                let col = self.cursor_position.0;
+                let row = self.cursor_position.1;
+
+                if let Some(row_data) = result.rows.get(row) {
+                    if let Some(cell_value) = row_data.get(col) {
+                        // A NULL cell opens with the NULL marker, so it stays NULL unless changed
+                        let cell_value = cell_value
+                            .clone()
+                            .unwrap_or_else(|| NULL_EDIT_MARKER.to_string());
+                        self.editing_cell_position = Some((col, row));
+                        self.editing_cell_original = cell_value.clone();
+                        self.cell_text_input.set_content(cell_value.clone());
+                        self.cell_text_input.move_cursor_to_end();
+                        self.cell_text_input.set_mode(crate::navigation::types::VimMode::Insert);
+                        self.input_mode = InputMode::Insert;
+
+                        crate::logging::debug(&format!(
                             "Entered cell edit at ({}, {}): '{}'",
                             col, row, cell_value
                         ));
@@ -1729,8 +3267,68 @@ impl App {
         }
     }
 
+    fn is_json_type(data_type: &str) -> bool {
+        matches!(data_type.to_lowercase().as_str(), "json" | "jsonb")
+    }
+
+    fn is_array_type(data_type: &str) -> bool {
+        data_type.ends_with("[]") || data_type.eq_ignore_ascii_case("array")
+    }
+
+    /// Checks JSON and array input, returning the reason it's invalid
+    fn validate_structured_value(value: &str, data_type: &str) -> Result<(), String> {
+        if Self::is_json_type(data_type) {
+            return serde_json::from_str::<serde_json::Value>(value)
+                .map(|_| ())
+                .map_err(|e| format!("invalid JSON: {}", e));
+        }
+        if Self::is_array_type(data_type) {
+            return Self::validate_array_literal(value.trim());
+        }
+        Ok(())
+    }
+
+    /// Validates the structure of a Postgres array literal such as `{1,2}` or `{{"a"},{"b"}}`
+    fn validate_array_literal(value: &str) -> Result<(), String> {
+        if !value.starts_with('{') || !value.ends_with('}') {
+            return Err("array literals must be wrapped in braces, e.g. {1,2,3}".to_string());
+        }
+
+        let mut depth = 0usize;
+        let mut in_quotes = false;
+        let mut escaped = false;
+        for (index, c) in value.char_indices() {
+            if escaped {
+                escaped = false;
+                continue;
+            }
+            match c {
+                '\\' if in_quotes => escaped = true,
+                '"' => in_quotes = !in_quotes,
+                '{' if !in_quotes => depth += 1,
+                '}' if !in_quotes => {
+                    depth = depth
+                        .checked_sub(1)
+                        .ok_or_else(|| format!("unexpected '}}' at position {}", index + 1))?;
+                    if depth == 0 && index + 1 != value.len() {
+                        return Err(format!("unexpected input after position {}", index + 1));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if in_quotes {
+            return Err("unterminated quoted element".to_string());
+        }
+        if depth != 0 {
+            return Err("unbalanced braces".to_string());
+        }
+        Ok(())
+    }
+
     fn validate_value_for_type(value: &str, data_type: &str) -> bool {
-        if value.is_empty() {
+        if Self::is_json_type(data_type) || Self::is_array_type(data_type) {
             return true;
         }
 
@@ -1768,6 +3366,13 @@ impl App {
         true
     }
 
+    /// Reopens the cell editor with its buffer intact after a rejected edit
+    fn reopen_cell_edit(&mut self, position: (usize, usize)) {
+        self.editing_cell_position = Some(position);
+        self.cell_text_input.set_mode(crate::navigation::types::VimMode::Insert);
+        self.input_mode = InputMode::Insert;
+    }
+
     pub async fn commit_cell_edit(&mut self) -> anyhow::Result<()> {
         let (edit_col, edit_row) = match self.editing_cell_position.take() {
             Some(pos) => pos,
@@ -1778,25 +3383,41 @@ impl App {
         self.input_mode = InputMode::Normal;
         self.cell_text_input.set_mode(crate::navigation::types::VimMode::Normal);
 
-        let (conn_name, schema, table) = match &self.last_table_info {
-            Some(info) => info.clone(),
+        // Only a tab showing a table has rows to write back to
+        let table_info = self
+            .current_query_state()
+            .and_then(|state| state.table_info.clone());
+        let (conn_name, schema, table) = match table_info {
+            Some(info) => info,
             None => {
                 self.set_status_message("No table context".to_string());
                 return Ok(());
             }
         };
+        if self.blocks_statement(&conn_name, "UPDATE") {
+            self.set_status_message(crate::database::READ_ONLY_MESSAGE.to_string());
+            return Ok(());
+        }
+        let is_mongo = self
+            .connection_tree
+            .iter()
+            .find(|item| item.connection_config.name == conn_name)
+            .is_some_and(|item| item.connection_config.db_type == DatabaseType::MongoDB);
+        if is_mongo {
+            self.set_status_message("MongoDB updates coming soon".to_string());
+            return Ok(());
+        }
 
-        let (columns, pk_columns, original_row) = if let Some(tab_idx) = self.selected_result_tab_index {
-            if let Some((_, result, state)) = self.result_tabs.get(tab_idx) {
-                let cols = result.columns.clone();
-                let pks = state.primary_key_columns.clone();
-                let row = result.rows.get(edit_row).cloned();
-                (cols, pks, row)
-            } else {
-                (Vec::new(), Vec::new(), None)
-            }
-        } else {
-            (Vec::new(), Vec::new(), None)
+        let Some(tab_idx) = self.selected_result_tab_index else {
+            return Ok(());
+        };
+        let (columns, pk_columns, original_row) = match self.result_tabs.get(tab_idx) {
+            Some((_, result, state)) => (
+                result.columns.clone(),
+                state.primary_key_columns.clone(),
+                result.rows.get(edit_row).cloned(),
+            ),
+            None => (Vec::new(), Vec::new(), None),
         };
 
         let original_row = match original_row {
@@ -1815,6 +3436,7 @@ impl App {
             }
         };
 
+        self.ensure_alive(&conn_name).await?;
         if let Some(connection) = self.connection_manager.get_connection(&conn_name) {
             let column_info = match connection.get_columns(&schema, &table).await {
                 Ok(cols) => cols.into_iter().find(|c| c.name == column_name),
@@ -1825,114 +3447,84 @@ impl App {
             };
 
             if let Some(col_info) = column_info {
-                if new_value.is_empty() {
+                if new_value == NULL_EDIT_MARKER {
                     if !col_info.is_nullable {
                         self.set_status_message(format!(
                             "Column '{}' does not allow NULL values",
                             column_name
                         ));
+                        self.reopen_cell_edit((edit_col, edit_row));
                         return Ok(());
                     }
+                } else if let Err(reason) =
+                    Self::validate_structured_value(&new_value, &col_info.data_type)
+                {
+                    self.set_status_message(format!(
+                        "Invalid {} value for '{}': {}",
+                        col_info.data_type, column_name, reason
+                    ));
+                    self.reopen_cell_edit((edit_col, edit_row));
+                    return Ok(());
                 } else if !Self::validate_value_for_type(&new_value, &col_info.data_type) {
                     self.set_status_message(format!(
                         "Invalid value for type {}: '{}'",
                         col_info.data_type, new_value
                     ));
+                    self.reopen_cell_edit((edit_col, edit_row));
                     return Ok(());
                 }
             }
         }
 
-        let where_clause = if !pk_columns.is_empty() {
-            let where_parts: Vec<String> = pk_columns
+        // The row is found by its primary key, or by every column when the table has none
+        let (key_columns, key): (Vec<String>, Vec<Option<String>>) = if pk_columns.is_empty() {
+            (columns.clone(), original_row.clone())
+        } else {
+            pk_columns
                 .iter()
                 .filter_map(|pk_col| {
                     let idx = columns.iter().position(|c| c == pk_col)?;
-                    let val = original_row.get(idx)?;
-                    Some(if val == "NULL" {
-                        format!("\"{}\" IS NULL", pk_col)
-                    } else {
-                        format!("\"{}\" = '{}'", pk_col, val.replace('\'', "''"))
-                    })
+                    Some((pk_col.clone(), original_row.get(idx)?.clone()))
                 })
-                .collect();
-            where_parts.join(" AND ")
-        } else {
-            let where_parts: Vec<String> = columns
-                .iter()
-                .zip(original_row.iter())
-                .map(|(col, val)| {
-                    if val == "NULL" {
-                        format!("\"{}\" IS NULL", col)
-                    } else {
-                        format!("\"{}\" = '{}'", col, val.replace('\'', "''"))
-                    }
-                })
-                .collect();
-            where_parts.join(" AND ")
+                .unzip()
         };
 
-        let db_type = self.connection_tree
-            .iter()
-            .find(|item| item.connection_config.name == conn_name)
-            .map(|item| item.connection_config.db_type.clone())
-            .unwrap_or(crate::database::DatabaseType::Postgres);
-
-        let update_query = match db_type {
-            crate::database::DatabaseType::SQLite => {
-                let escaped_value = if new_value.is_empty() {
-                    "''".to_string()
-                } else {
-                    format!("'{}'", new_value.replace('\'', "''"))
-                };
-                format!(
-                    "UPDATE \"{}\" SET \"{}\" = {} WHERE {}",
-                    table, column_name, escaped_value, where_clause
-                )
-            }
-            crate::database::DatabaseType::MongoDB => {
-                self.set_status_message("MongoDB updates coming soon".to_string());
-                return Ok(());
-            }
-            _ => {
-                let escaped_value = if new_value.is_empty() {
-                    "''".to_string()
-                } else {
-                    format!("'{}'", new_value.replace('\'', "''"))
-                };
-                format!(
-                    "UPDATE \"{}\".\"{}\" SET \"{}\" = {} WHERE {}",
-                    schema, table, column_name, escaped_value, where_clause
-                )
-            }
+        // The NULL marker sets NULL; an emptied cell is an empty string
+        let value = (new_value != NULL_EDIT_MARKER).then_some(new_value.as_str());
+        let Some(connection) = self.connection_manager.get_connection(&conn_name) else {
+            self.set_status_message(format!("Connection '{}' is not connected", conn_name));
+            return Ok(());
         };
-
-        crate::logging::info(&format!("Executing: {}", update_query));
-
-        if let Some(connection) = self.connection_manager.get_connection(&conn_name) {
-            match connection.execute_query(&update_query).await {
-                Ok(result) => {
-                    if result.affected_rows > 0 {
-                        self.set_status_message(format!("Updated {} row(s)", result.affected_rows));
-                        if let Err(e) = self.refresh_results().await {
-                            crate::logging::error(&format!("Refresh failed: {}", e));
-                        }
-                    } else {
-                        self.set_status_message("No rows updated".to_string());
-                    }
-                }
-                Err(e) => {
-                    self.set_status_message(format!("Update failed: {}", e));
-                    crate::logging::error(&format!("UPDATE error: {}", e));
+        let updated = connection
+            .update_cell(&schema, &table, &column_name, value, &key_columns, &key)
+            .await;
+
+        // On failure the cell keeps its original value
+        match updated {
+            Ok(0) => self.set_status_message("No rows updated".to_string()),
+            Ok(count) => {
+                if let Some(cell) = self
+                    .result_tabs
+                    .get_mut(tab_idx)
+                    .and_then(|(_, result, _)| result.rows.get_mut(edit_row))
+                    .and_then(|row| row.get_mut(edit_col))
+                {
+                    *cell = value.map(str::to_string);
                 }
+                self.set_status_message(format!("Updated {} row(s)", count));
+            }
+            Err(e) => {
+                self.set_status_message(format!("Update failed: {}", e));
+                crate::logging::error(&format!("UPDATE error: {}", e));
             }
         }
 
         Ok(())
     }
 
-    /// Toggles (expand/collapse) a tree item based on its visual index.
-    pub async fn toggle_tree_item(&mut self, visual_index: usize) -> Result<()> {
+    /// Toggles (expand/collapse) a tree item based on its visual index. Tables are opened in
+    /// a results tab, which with `activate` is also selected and focused.
+    pub async fn toggle_tree_item(&mut self, visual_index: usize, activate: bool) -> Result<()> {
         if let Some(tree_item) = self.get_tree_item_at_visual_index(visual_index) {
             logging::debug(&format!(
                 "Toggling tree item at visual index {}",
@@ -1941,6 +3533,7 @@ impl App {
             match tree_item {
                 TreeItem::Connection(conn_idx) => {
                     self.expand_connection(conn_idx).await?;
+                    self.load_scoped_schema(conn_idx).await?;
                 }
                 TreeItem::Database(conn_idx, db_idx) => {
                     self.expand_database(conn_idx, db_idx).await?;
@@ -1949,6 +3542,13 @@ impl App {
                     self.expand_schema(conn_idx, db_idx, schema_idx).await?;
                 }
                 TreeItem::Table(conn_idx, db_idx, schema_idx, table_idx) => {
+                    if let Some(name) = self
+                        .connection_tree
+                        .get(conn_idx)
+                        .map(|connection| connection.connection_config.name.clone())
+                    {
+                        self.ensure_alive(&name).await?;
+                    }
                     if let Some(connection) = self.connection_tree.get(conn_idx) {
                         if let Some(database) = connection.databases.get(db_idx) {
                             if let Some(schema) = database.schemas.get(schema_idx) {
@@ -1960,18 +3560,42 @@ impl App {
                                         let params = QueryParams {
                                             where_clause: None,
                                             order_by: None,
-                                            limit: Some(50), // Default page size
+                                            limit: Some(
+                                                connection.connection_config.page_size(),
+                                            ),
                                             offset: None,
+                                            base_query: None,
+                                            where_params: Vec::new(),
                                         };
                                         logging::debug(&format!(
                                             "Fetching table data for schema {}, table {}",
                                             schema.name, table
                                         ));
 
-                                        match db_connection
-                                            .fetch_table_data(&schema.name, table, &params)
-                                            .await
-                                        {
+                                        let executed_at = chrono::Local::now();
+                                        let started = std::time::Instant::now();
+                                        let fetched = with_timeout(
+                                            connection.connection_config.statement_timeout(),
+                                            db_connection.fetch_table_data(
+                                                &schema.name,
+                                                table,
+                                                &params,
+                                            ),
+                                        )
+                                        .await;
+                                        self.query_log.record(
+                                            &connection.connection_config.name,
+                                            Self::query_log_entry(
+                                                &schema.name,
+                                                table,
+                                                &params,
+                                                executed_at,
+                                                started,
+                                                &fetched,
+                                            ),
+                                        );
+
+                                        match fetched {
                                             Ok(result) => {
                                                 let tab_name = format!(
                                                     "{}:{}:{}.{}",
@@ -1987,24 +3611,41 @@ impl App {
 
                                                 // Initialize new query state
                                                 let mut query_state = QueryState {
-                                                    page_size: 50, // Default page size
+                                                    page_size: connection
+                                                        .connection_config
+                                                        .page_size(),
                                                     current_page: 1,
                                                     total_pages: Some(1),
                                                     total_records: Some(0),
-                                                    sort_column: None,
-                                                    sort_order: None,
+                                                    sort_columns: Vec::new(),
+                                                    nulls_order: None,
                                                     rows_marked_for_deletion: HashSet::new(),
+                                                    cleared_deletion_marks: HashSet::new(),
+                                                    committed_deletions: 0,
                                                     where_clause: String::new(),
                                                     order_by_clause: String::new(),
                                                     primary_key_columns: Vec::new(),
+                                                    base_query: None,
+                                                    revealed_columns: Vec::new(),
+                                                    hidden_columns: HashSet::new(),
+                                                    table_info: None,
+                                                    where_params: Vec::new(),
+                                                    needs_fetch: false,
+                                                    col_scroll_offset: 0,
+                                                    auto_refresh: None,
+                                                    last_auto_refresh: None,
+                                                    record_view: false,
+                                                    column_formats: HashMap::new(),
+                                                    selection_anchor: None,
+                                                    wrap_cells: false,
+                                                    capped_query: None,
                                                 };
 
-                                                let pk_columns = match db_connection.get_columns(&schema.name, table).await {
-                                                    Ok(cols) => cols
-                                                        .iter()
-                                                        .filter(|c| c.is_primary_key)
-                                                        .map(|c| c.name.clone())
-                                                        .collect::<Vec<String>>(),
+                                                let pk_columns = match db_connection
+                                                    .get_primary_keys(&schema.name, table)
+                                                    .await
+                                                {
+                                                    Ok(keys) => keys,
                                                     Err(e) => {
                                                         crate::logging::warn(&format!("Could not fetch PKs: {}", e));
                                                         Vec::new()
@@ -2012,23 +3653,11 @@ impl App {
                                                 };
                                                 query_state.primary_key_columns = pk_columns;
 
-                                                // Compute totals immediately
-                                                let total_records = match db_connection
-                                                    .count_table_rows(&schema.name, table, None)
-                                                    .await
-                                                {
-                                                    Ok(count) => count,
-                                                    Err(_) => result.rows.len() as u64,
-                                                };
-                                                let page_size = query_state.page_size.max(1);
-                                                let total_pages =
-                                                    ((total_records + page_size as u64 - 1)
-                                                        / page_size as u64)
-                                                        .max(1)
-                                                        as u32;
-
                                                 if let Some(index) = tab_index {
-                                                    self.selected_result_tab_index = Some(index);
+                                                    if activate {
+                                                        self.selected_result_tab_index =
+                                                            Some(index);
+                                                    }
                                                     if let Some((
                                                         _,
                                                         ref mut result_slot,
@@ -2036,34 +3665,82 @@ impl App {
                                                     )) = self.result_tabs.get_mut(index)
                                                     {
                                                         *result_slot = result;
-                                                        state.total_records = Some(total_records);
-                                                        state.total_pages = Some(total_pages);
+                                                        state.rows_marked_for_deletion.clear();
+                                                        state.cleared_deletion_marks.clear();
+                                                        state.total_records = None;
+                                                        state.total_pages = None;
                                                         state.current_page = 1;
                                                     }
                                                 } else {
                                                     // Create new tab with new query state
                                                     let mut new_state = query_state;
-                                                    new_state.total_records = Some(total_records);
-                                                    new_state.total_pages = Some(total_pages);
+                                                    new_state.total_records = None;
+                                                    new_state.total_pages = None;
                                                     self.result_tabs
                                                         .push((tab_name, result, new_state));
-                                                    self.selected_result_tab_index =
-                                                        Some(self.result_tabs.len() - 1);
-                                                    // Reset results cursor to top-left on newly opened table
-                                                    self.cursor_position = (0, 0);
-                                                    self.active_pane = Pane::Results;
+                                                    if activate {
+                                                        self.selected_result_tab_index =
+                                                            Some(self.result_tabs.len() - 1);
+                                                        // Reset results cursor to top-left on
+                                                        // newly opened table
+                                                        self.cursor_position = (0, 0);
+                                                        self.active_pane = Pane::Results;
+                                                    }
                                                 }
+                                                let opened_index =
+                                                    tab_index.unwrap_or(self.result_tabs.len() - 1);
 
-                                                self.last_table_info = Some((
+                                                let table_info = (
                                                     connection.connection_config.name.clone(),
                                                     schema.name.clone(),
                                                     table.clone(),
-                                                ));
+                                                );
+                                                // `connection` still borrows the tree, so
+                                                // set the fields rather than calling
+                                                // `set_current_table_info`
+                                                if let Some((_, _, state)) =
+                                                    self.result_tabs.get_mut(opened_index)
+                                                {
+                                                    state.table_info = Some(table_info.clone());
+                                                }
+                                                if activate {
+                                                    self.last_table_info = Some(table_info);
+                                                }
 
                                                 logging::info(&format!(
                                                     "Successfully fetched data from table {}",
                                                     table
                                                 ));
+
+                                                // The totals are filled in once the rows are
+                                                // counted, in the background where the
+                                                // backend can share its connection
+                                                let tab_name =
+                                                    self.result_tabs[opened_index].0.clone();
+                                                let timeout = connection
+                                                    .connection_config
+                                                    .statement_timeout();
+                                                match db_connection
+                                                    .detached_row_count(&schema.name, table)
+                                                {
+                                                    Some(count) => self
+                                                        .spawn_row_count(tab_name, timeout, count),
+                                                    None => {
+                                                        let count = with_timeout(
+                                                            timeout,
+                                                            db_connection.count_table_rows(
+                                                                &schema.name,
+                                                                table,
+                                                                None,
+                                                            ),
+                                                        )
+                                                        .await;
+                                                        self.apply_row_count(
+                                                            &tab_name,
+                                                            count.map_err(|err| err.to_string()),
+                                                        );
+                                                    }
+                                                }
                                             }
                                             Err(e) => {
                                                 let error_msg =
@@ -2160,86 +3837,501 @@ impl App {
     }
 
     pub fn select_tab(&mut self, index: usize) {
+        // The pinned tab already has its half of the split, so selecting it moves focus there
+        if self.split_active() && self.pinned_tab.is_some_and(|pinned| pinned.index == index) {
+            self.focus_other_split();
+            return;
+        }
         if index < self.result_tabs.len() {
             self.selected_result_tab_index = Some(index);
             self.cursor_position = (0, 0);
+            if let Some(info) = self.result_tabs[index].2.table_info.clone() {
+                self.last_table_info = Some(info);
+            }
+            // The reload fetches the rows of a tab restored from the last session
+            if self.result_tabs[index].2.needs_fetch {
+                self.reload_requested = true;
+            }
         }
     }
 
-    pub fn select_previous_connection(&mut self) {
-        if let Some(TreeItem::Connection(conn_idx)) = self
-            .selected_connection_idx
-            .and_then(|idx| self.get_tree_item_at_visual_index(idx))
-        {
-            if conn_idx > 0 {
-                self.selected_connection_idx = self
-                    .get_visual_index_for_connection(conn_idx - 1)
-                    .or(Some(conn_idx - 1));
+    /// Saves the open table tabs to `session.json`. Ad-hoc and metadata tabs have no table
+    /// to fetch their rows from again, so they are left out.
+    pub fn save_session(&self) -> Result<()> {
+        if !self.config.layout.restore_session {
+            return Ok(());
+        }
+        let mut selected_tab = None;
+        let mut tabs = Vec::new();
+        for (index, (name, _, state)) in self.result_tabs.iter().enumerate() {
+            let Some(table_info) = state.table_info.clone() else {
+                continue;
+            };
+            if self.selected_result_tab_index == Some(index) {
+                selected_tab = Some(tabs.len());
             }
+            tabs.push(SessionTab {
+                name: name.clone(),
+                table_info,
+                where_clause: state.where_clause.clone(),
+                where_params: state.where_params.clone(),
+                order_by_clause: state.order_by_clause.clone(),
+                sort_columns: state.sort_columns.clone(),
+                nulls_order: state.nulls_order,
+                page_size: state.page_size,
+                current_page: state.current_page,
+                base_query: state.base_query.clone(),
+                revealed_columns: state.revealed_columns.clone(),
+            });
         }
+        session::save(&Session {
+            tabs,
+            selected_tab,
+            last_table_info: self.last_table_info.clone(),
+        })
     }
 
-    pub fn select_next_connection(&mut self) {
-        if let Some(TreeItem::Connection(conn_idx)) = self
-            .selected_connection_idx
-            .and_then(|idx| self.get_tree_item_at_visual_index(idx))
-        {
-            let total_connections = self.connection_tree.len();
-            if conn_idx + 1 < total_connections {
-                self.selected_connection_idx = self
-                    .get_visual_index_for_connection(conn_idx + 1)
-                    .or(Some(conn_idx + 1));
+    /// Reopens the tabs saved by `save_session` without their rows, which are fetched when a
+    /// tab is first shown. Tabs on connections that no longer exist are skipped.
+    fn restore_session(&mut self) {
+        if !self.config.layout.restore_session {
+            return;
+        }
+        let session = match session::load() {
+            Ok(session) => session,
+            Err(err) => {
+                logging::handle_non_critical_error(&err);
+                return;
+            }
+        };
+
+        let mut selected = None;
+        for (index, tab) in session.tabs.into_iter().enumerate() {
+            let connection = &tab.table_info.0;
+            if !self.saved_connections.iter().any(|saved| &saved.name == connection) {
+                logging::warn(&format!(
+                    "Skipping restored tab {}: connection '{}' no longer exists",
+                    tab.name, connection
+                ));
+                continue;
             }
+            if session.selected_tab == Some(index) {
+                selected = Some(self.result_tabs.len());
+            }
+            if let Some(base_query) = &tab.base_query {
+                self.base_queries
+                    .insert(tab.table_info.2.clone(), base_query.clone());
+            }
+            let state = QueryState {
+                where_clause: tab.where_clause,
+                where_params: tab.where_params,
+                order_by_clause: tab.order_by_clause,
+                sort_columns: tab.sort_columns,
+                nulls_order: tab.nulls_order,
+                page_size: tab.page_size.max(1),
+                current_page: tab.current_page.max(1),
+                base_query: tab.base_query,
+                revealed_columns: tab.revealed_columns,
+                table_info: Some(tab.table_info),
+                needs_fetch: true,
+                ..QueryState::default()
+            };
+            let result = QueryResult {
+                columns: Vec::new(),
+                rows: Vec::new(),
+                affected_rows: 0,
+                column_types: Vec::new(),
+            };
+            self.result_tabs.push((tab.name, result, state));
         }
+        if self.result_tabs.is_empty() {
+            return;
+        }
+
+        self.last_table_info = session.last_table_info.filter(|(connection, _, _)| {
+            self.saved_connections
+                .iter()
+                .any(|saved| &saved.name == connection)
+        });
+        self.select_tab(selected.unwrap_or(0));
+        logging::info(&format!(
+            "Restored {} tabs from the last session",
+            self.result_tabs.len()
+        ));
     }
 
-    /// Moves the selection down in the connections tree.
-    pub fn move_selection_down(&mut self) {
-        let total_items = self.get_total_visible_items();
+    /// Connects to the saved connection `name` unless it is already connected
+    async fn ensure_connected(&mut self, name: &str) -> Result<()> {
+        if self.connection_manager.get_connection(name).is_some() {
+            return self.ensure_alive(name).await;
+        }
+        let cfg = self.resolved_connection_config(name)?;
+        self.connection_manager.connect(cfg).await
+    }
 
-        if let Some(current_idx) = self.selected_connection_idx {
-            if current_idx + 1 < total_items {
-                self.selected_connection_idx = Some(current_idx + 1);
+    /// Makes sure connection `name` still reaches its database before a query is sent over
+    /// it, reconnecting it if it dropped while idle (see `liveness_check_after`). A
+    /// transaction open on the lost connection is gone with it.
+    async fn ensure_alive(&mut self, name: &str) -> Result<()> {
+        let idle_after = std::time::Duration::from_secs(self.config.database.liveness_check_after);
+        match self.connection_manager.ensure_alive(name, idle_after).await {
+            Ok(false) => Ok(()),
+            Ok(true) => {
+                let message = if self.open_transactions.remove(name) {
+                    format!(
+                        "Connection to {} was lost and reconnected; its open transaction was \
+                         rolled back",
+                        name
+                    )
+                } else {
+                    format!("Connection to {} was lost and reconnected", name)
+                };
+                logging::info(&message);
+                self.set_status_message(message);
+                Ok(())
+            }
+            Err(e) => {
+                self.open_transactions.remove(name);
+                self.connection_failures
+                    .insert(name.to_string(), ConnectionFailure::from_error(&e));
+                if let Some(index) = self
+                    .connection_tree
+                    .iter()
+                    .position(|connection| connection.connection_config.name == name)
+                {
+                    self.set_tree_connection_status(index, name, ConnectionStatus::Failed);
+                }
+                Err(e.context(format!(
+                    "Lost the connection to {} and could not reconnect",
+                    name
+                )))
             }
-        } else if total_items > 0 {
-            // If nothing is selected, select the first item
-            self.selected_connection_idx = Some(0);
         }
     }
 
-    pub fn copy_cell(&mut self) -> anyhow::Result<()> {
-        if let Some(selected_tab_index) = self.selected_result_tab_index {
-            if let Some((_, result, _)) = self.result_tabs.get(selected_tab_index) {
-                if let Some(row) = result.rows.get(self.cursor_position.1) {
-                    if let Some(cell) = row.get(self.cursor_position.0) {
-                        // Store in internal clipboard
-                        self.clipboard = cell.clone();
-
-                        // Also copy to system clipboard
-                        let mut ctx: ClipboardContext = match ClipboardProvider::new() {
-                            Ok(ctx) => ctx,
-                            Err(e) => {
-                                let error_msg = format!("Failed to access clipboard: {}", e);
-                                logging::error(&error_msg);
-                                self.status_message = Some(error_msg);
-                                return Ok(());
-                            }
-                        };
-
-                        if let Err(e) = ctx.set_contents(cell.clone()) {
-                            let error_msg = format!("Failed to copy to clipboard: {}", e);
-                            logging::error(&error_msg);
-                            self.status_message = Some(error_msg);
-                            return Ok(());
-                        }
-
-                        self.status_message = Some("Cell copied to clipboard".to_string());
-                        logging::info(&format!("Copied cell content to clipboard: {}", cell));
-                    }
+    /// The saved configuration of connection `name`, with its named SSH tunnel and any
+    /// database chosen with `use` filled in
+    fn resolved_connection_config(&self, name: &str) -> Result<ConnectionConfig> {
+        let mut cfg = self
+            .saved_connections
+            .iter()
+            .find(|saved| saved.name == name)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Connection '{}' no longer exists", name))?;
+        if let Some(database) = self.active_databases.get(name) {
+            cfg.default_database = Some(database.clone());
+        }
+        if cfg.ssh_tunnel.is_none() {
+            if let Some(tunnel_name) = &cfg.ssh_tunnel_name {
+                if let Some(tunnel) = self
+                    .config
+                    .ssh_tunnels
+                    .iter()
+                    .find(|t| &t.name == tunnel_name)
+                    .cloned()
+                {
+                    cfg.ssh_tunnel = Some(tunnel.config);
                 }
             }
         }
-        Ok(())
+        Ok(cfg)
+    }
+
+    /// Prepares a tab restored from the last session for its first fetch: connects and loads
+    /// the primary keys. Returns false when its table no longer exists and the tab was closed.
+    async fn prepare_restored_tab(&mut self, index: usize) -> Result<bool> {
+        let (name, _, state) = &self.result_tabs[index];
+        let Some((conn_name, schema, table)) = state.table_info.clone() else {
+            return Ok(true);
+        };
+        let (name, is_base_query) = (name.clone(), state.base_query.is_some());
+        self.ensure_connected(&conn_name).await?;
+        let connection = self
+            .connection_manager
+            .get_connection(&conn_name)
+            .ok_or_else(|| anyhow::anyhow!("Connection '{}' is not connected", conn_name))?;
+
+        // Base queries have no table of their own to look up
+        if !is_base_query {
+            // Views, materialized views and partitions open in tabs too
+            let relations = connection.list_relations("", &schema).await?;
+            if !relations.iter().any(|(relation, _)| *relation == table) {
+                logging::warn(&format!(
+                    "Skipping restored tab {}: table {}.{} no longer exists",
+                    name, schema, table
+                ));
+                self.close_tab(index);
+                self.set_status_message(format!("Table {}.{} no longer exists", schema, table));
+                return Ok(false);
+            }
+            match connection.get_primary_keys(&schema, &table).await {
+                Ok(keys) => self.result_tabs[index].2.primary_key_columns = keys,
+                Err(e) => logging::warn(&format!("Could not fetch PKs: {}", e)),
+            }
+        }
+        Ok(true)
+    }
+
+    /// Records the table the selected tab reads from, so switching back to it restores
+    /// `last_table_info`
+    fn set_current_table_info(&mut self, info: (String, String, String)) {
+        if let Some(state) = self.current_query_state_mut() {
+            state.table_info = Some(info.clone());
+        }
+        self.last_table_info = Some(info);
+    }
+
+    /// Queues a reload of the current tab; it runs on the next tick so the indicator is drawn
+    pub fn request_reload_current_tab(&mut self) {
+        if self.selected_result_tab_index.is_none() {
+            self.set_status_message("No result tab to reload".to_string());
+            return;
+        }
+        self.reload_requested = true;
+        self.set_status_message("⟳ Reloading...".to_string());
+    }
+
+    /// Re-fetches the current tab every `seconds` while it is shown; `None` turns it off
+    pub fn set_auto_refresh(&mut self, seconds: Option<u64>) {
+        let Some(state) = self.current_query_state_mut() else {
+            self.set_status_message("No result tab to refresh".to_string());
+            return;
+        };
+        if state.table_info.is_none() {
+            self.set_status_message("Only table tabs can be refreshed automatically".to_string());
+            return;
+        }
+        state.auto_refresh = seconds.map(std::time::Duration::from_secs);
+        state.last_auto_refresh = Some(std::time::Instant::now());
+        let message = match seconds {
+            Some(seconds) => format!("Auto-refreshing every {}s", seconds),
+            None => "Auto-refresh off".to_string(),
+        };
+        self.set_status_message(message);
+    }
+
+    /// Queues a reload of the current tab once its auto-refresh interval has passed. Refreshes
+    /// wait while a cell is edited or rows are marked, since reloading would drop those.
+    pub fn check_auto_refresh(&mut self) {
+        if self.reload_requested || self.bulk_deletion.is_some() || self.is_editing_cell() {
+            return;
+        }
+        let Some(state) = self.current_query_state_mut() else {
+            return;
+        };
+        let Some(interval) = state.auto_refresh else {
+            return;
+        };
+        if !state.rows_marked_for_deletion.is_empty()
+            || state
+                .last_auto_refresh
+                .is_some_and(|last| last.elapsed() < interval)
+        {
+            return;
+        }
+        state.last_auto_refresh = Some(std::time::Instant::now());
+        self.reload_requested = true;
+    }
+
+    /// Re-fetches the current page of the selected tab with its WHERE/ORDER BY, keeping the
+    /// cursor where it was
+    pub async fn reload_current_tab(&mut self) -> Result<()> {
+        self.reload_requested = false;
+        let Some(index) = self.selected_result_tab_index else {
+            return Ok(());
+        };
+        if let Some(info) = self.result_tabs[index].2.table_info.clone() {
+            self.last_table_info = Some(info);
+        }
+        let restored = self.result_tabs[index].2.needs_fetch;
+        if restored && !self.prepare_restored_tab(index).await? {
+            return Ok(());
+        }
+
+        let cursor = self.cursor_position;
+        let started = std::time::Instant::now();
+        self.refresh_results().await?;
+        let elapsed = started.elapsed();
+        if restored {
+            self.result_tabs[index].2.needs_fetch = false;
+        }
+
+        let Some((_, result, state)) = self.result_tabs.get(index) else {
+            return Ok(());
+        };
+        let rows = result.rows.len();
+        let total = state.total_records.unwrap_or(rows as u64);
+        self.cursor_position = (cursor.0, cursor.1.min(rows.saturating_sub(1)));
+        self.set_status_message(format!(
+            "Reloaded {} rows ({} total) in {} ms",
+            rows,
+            total,
+            elapsed.as_millis()
+        ));
+        Ok(())
+    }
+
+    pub fn select_previous_connection(&mut self) {
+        if let Some(TreeItem::Connection(conn_idx)) = self
+            .selected_connection_idx
+            .and_then(|idx| self.get_tree_item_at_visual_index(idx))
+        {
+            if conn_idx > 0 {
+                self.selected_connection_idx = self
+                    .get_visual_index_for_connection(conn_idx - 1)
+                    .or(Some(conn_idx - 1));
+            }
+        }
+    }
+
+    pub fn select_next_connection(&mut self) {
+        if let Some(TreeItem::Connection(conn_idx)) = self
+            .selected_connection_idx
+            .and_then(|idx| self.get_tree_item_at_visual_index(idx))
+        {
+            let total_connections = self.connection_tree.len();
+            if conn_idx + 1 < total_connections {
+                self.selected_connection_idx = self
+                    .get_visual_index_for_connection(conn_idx + 1)
+                    .or(Some(conn_idx + 1));
+            }
+        }
+    }
+
+    /// Moves the selection down in the connections tree.
+    pub fn move_selection_down(&mut self) {
+        let total_items = self.get_total_visible_items();
+
+        if let Some(current_idx) = self.selected_connection_idx {
+            if current_idx + 1 < total_items {
+                self.selected_connection_idx = Some(current_idx + 1);
+            }
+        } else if total_items > 0 {
+            // If nothing is selected, select the first item
+            self.selected_connection_idx = Some(0);
+        }
+    }
+
+    /// Copies the selected connection's DSN to the clipboard
+    pub fn copy_connection_dsn(&mut self, include_password: bool) {
+        let conn_idx = match self
+            .selected_connection_idx
+            .and_then(|idx| self.get_tree_item_at_visual_index(idx))
+        {
+            Some(
+                TreeItem::Connection(c)
+                | TreeItem::Database(c, _)
+                | TreeItem::Schema(c, _, _)
+                | TreeItem::Table(c, _, _, _),
+            ) => c,
+            None => {
+                self.set_status_message("No connection selected".to_string());
+                return;
+            }
+        };
+        let Some(connection) = self.connection_tree.get(conn_idx) else {
+            return;
+        };
+        let dsn = connection.connection_config.dsn(include_password);
+        let name = connection.connection_config.name.clone();
+
+        let copied = ClipboardProvider::new()
+            .and_then(|mut ctx: ClipboardContext| ctx.set_contents(dsn.clone()));
+        match copied {
+            Ok(()) => {
+                self.clipboard = dsn;
+                let suffix = if include_password { " (with password)" } else { "" };
+                self.set_status_message(format!("Copied DSN for {}{}", name, suffix));
+            }
+            Err(e) => {
+                let error_msg = format!("Failed to copy to clipboard: {}", e);
+                logging::error(&error_msg);
+                self.set_status_message(error_msg);
+            }
+        }
+    }
+
+    /// Text copied for a cell; NULL copies `null_copy_value` from the `[database]` config
+    pub fn cell_copy_text(&self, cell: Option<&str>) -> String {
+        cell.unwrap_or(&self.config.database.null_copy_value).to_string()
+    }
+
+    /// Row cells joined with tabs for easy pasting into spreadsheets
+    pub fn row_copy_text(&self, row: &[Option<String>]) -> String {
+        row.iter()
+            .map(|cell| self.cell_copy_text(cell.as_deref()))
+            .collect::<Vec<_>>()
+            .join("\t")
+    }
+
+    /// Inserts the system clipboard at the cursor of the cell being edited, or of the current
+    /// query field. Results are read-only, so anywhere else nothing is pasted. Returns whether
+    /// the paste was handled.
+    pub fn paste_from_clipboard(&mut self) -> bool {
+        let editing_cell = self.is_editing_cell();
+        if !editing_cell && self.active_pane != Pane::QueryInput {
+            return false;
+        }
+
+        let contents =
+            ClipboardProvider::new().and_then(|mut ctx: ClipboardContext| ctx.get_contents());
+        let text = match contents {
+            Ok(text) => text,
+            Err(e) => {
+                let error_msg = format!("Failed to read clipboard: {}", e);
+                logging::error(&error_msg);
+                self.set_status_message(error_msg);
+                return true;
+            }
+        };
+
+        // The fields are single-line, so pasted line breaks become spaces
+        let text = text
+            .trim_end_matches(['\r', '\n'])
+            .replace("\r\n", " ")
+            .replace('\n', " ");
+        if editing_cell {
+            self.cell_text_input.insert_str_at_cursor(&text);
+        } else {
+            self.query_input_pane.insert_text(&text);
+        }
+        true
+    }
+
+    pub fn copy_cell(&mut self) -> anyhow::Result<()> {
+        if let Some(selected_tab_index) = self.selected_result_tab_index {
+            if let Some((_, result, _)) = self.result_tabs.get(selected_tab_index) {
+                if let Some(row) = result.rows.get(self.cursor_position.1) {
+                    if let Some(cell) = row.get(self.cursor_position.0) {
+                        let cell = self.cell_copy_text(cell.as_deref());
+                        // Store in internal clipboard
+                        self.clipboard = cell.clone();
+
+                        // Also copy to system clipboard
+                        let mut ctx: ClipboardContext = match ClipboardProvider::new() {
+                            Ok(ctx) => ctx,
+                            Err(e) => {
+                                let error_msg = format!("Failed to access clipboard: {}", e);
+                                logging::error(&error_msg);
+                                self.status_message = Some(error_msg);
+                                return Ok(());
+                            }
+                        };
+
+                        if let Err(e) = ctx.set_contents(cell.clone()) {
+                            let error_msg = format!("Failed to copy to clipboard: {}", e);
+                            logging::error(&error_msg);
+                            self.status_message = Some(error_msg);
+                            return Ok(());
+                        }
+
+                        self.status_message = Some("Cell copied to clipboard".to_string());
+                        logging::info(&format!("Copied cell content to clipboard: {}", cell));
+                    }
+                }
+            }
+        }
+        Ok(())
     }
 
     pub fn copy_row(&mut self) -> anyhow::Result<()> {
@@ -2247,7 +4339,7 @@ impl App {
             if let Some((_, result, _)) = self.result_tabs.get(selected_tab_index) {
                 if let Some(row) = result.rows.get(self.cursor_position.1) {
                     // Join row cells with tabs for easy pasting into spreadsheets
-                    let row_content = row.join("\t");
+                    let row_content = self.row_copy_text(row);
 
                     // Store in internal clipboard
                     self.clipboard = row_content.clone();
@@ -2278,6 +4370,219 @@ impl App {
         Ok(())
     }
 
+    /// Copies the current column's values on the loaded page, one per line. Only the page is
+    /// in memory, so other pages are left out; `:export` writes every row.
+    pub fn copy_column(&mut self) -> anyhow::Result<()> {
+        let Some((_, result, _)) = self
+            .selected_result_tab_index
+            .and_then(|index| self.result_tabs.get(index))
+        else {
+            return Ok(());
+        };
+        let column = self.cursor_position.0;
+        let Some(name) = result.columns.get(column).cloned() else {
+            return Ok(());
+        };
+        let values: Vec<String> = result
+            .rows
+            .iter()
+            .map(|row| self.cell_copy_text(row.get(column).and_then(|cell| cell.as_deref())))
+            .collect();
+        let column_content = values.join("\n");
+
+        // Store in internal clipboard
+        self.clipboard = column_content.clone();
+
+        // Also copy to system clipboard
+        let mut ctx: ClipboardContext = match ClipboardProvider::new() {
+            Ok(ctx) => ctx,
+            Err(e) => {
+                let error_msg = format!("Failed to access clipboard: {}", e);
+                logging::error(&error_msg);
+                self.status_message = Some(error_msg);
+                return Ok(());
+            }
+        };
+
+        if let Err(e) = ctx.set_contents(column_content) {
+            let error_msg = format!("Failed to copy to clipboard: {}", e);
+            logging::error(&error_msg);
+            self.status_message = Some(error_msg);
+            return Ok(());
+        }
+
+        self.set_status_message(format!("Copied column '{}' ({} values)", name, values.len()));
+        logging::info(&format!("Copied column {} to clipboard: {} values", name, values.len()));
+        Ok(())
+    }
+
+    /// Starts a visual selection in the selected tab at the cursor, or ends the current one
+    pub fn toggle_result_selection(&mut self) {
+        let cursor = self.cursor_position;
+        let Some(state) = self.current_query_state_mut() else {
+            return;
+        };
+        if state.record_view {
+            self.set_status_message("Visual selection needs the grid (Alt+x)".to_string());
+            return;
+        }
+        if state.selection_anchor.take().is_none() {
+            state.selection_anchor = Some(cursor);
+            self.set_status_message(
+                "Visual selection; h/j/k/l extend it, y copies it, Esc ends it".to_string(),
+            );
+        }
+    }
+
+    /// Whether the selected tab has a visual selection
+    pub fn has_result_selection(&self) -> bool {
+        self.current_query_state()
+            .is_some_and(|state| state.selection_anchor.is_some())
+    }
+
+    /// Ends the visual selection of the selected tab, returning whether there was one
+    pub fn clear_result_selection(&mut self) -> bool {
+        self.current_query_state_mut()
+            .and_then(|state| state.selection_anchor.take())
+            .is_some()
+    }
+
+    /// Copies the visible cells of the visual selection as tab-separated lines, one per row,
+    /// and ends it
+    pub fn copy_result_selection(&mut self) -> anyhow::Result<()> {
+        let cursor = self.cursor_position;
+        let visible = self.visible_column_indices();
+        let Some((_, result, state)) = self
+            .selected_result_tab_index
+            .and_then(|index| self.result_tabs.get(index))
+        else {
+            return Ok(());
+        };
+        let Some(anchor) = state.selection_anchor else {
+            return Ok(());
+        };
+        let columns: Vec<usize> = visible
+            .into_iter()
+            .filter(|&column| state.in_selection(cursor, (column, anchor.1)))
+            .collect();
+        let rows = anchor.1.min(cursor.1)..=anchor.1.max(cursor.1);
+        let lines: Vec<String> = result
+            .rows
+            .get(rows)
+            .unwrap_or_default()
+            .iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .map(|&column| self.cell_copy_text(row.get(column).and_then(|c| c.as_deref())))
+                    .collect::<Vec<_>>()
+                    .join("\t")
+            })
+            .collect();
+        let content = lines.join("\n");
+        self.clear_result_selection();
+
+        // Store in internal clipboard
+        self.clipboard = content.clone();
+
+        // Also copy to system clipboard
+        let mut ctx: ClipboardContext = match ClipboardProvider::new() {
+            Ok(ctx) => ctx,
+            Err(e) => {
+                let error_msg = format!("Failed to access clipboard: {}", e);
+                logging::error(&error_msg);
+                self.status_message = Some(error_msg);
+                return Ok(());
+            }
+        };
+
+        if let Err(e) = ctx.set_contents(content) {
+            let error_msg = format!("Failed to copy to clipboard: {}", e);
+            logging::error(&error_msg);
+            self.status_message = Some(error_msg);
+            return Ok(());
+        }
+
+        self.set_status_message(format!(
+            "Copied {} rows x {} columns",
+            lines.len(),
+            columns.len()
+        ));
+        Ok(())
+    }
+
+    /// Copies an INSERT into the tab's table for each row marked with `d`, or for the row under
+    /// the cursor when none are marked, one statement per line
+    pub fn copy_insert_statements(&mut self) -> anyhow::Result<()> {
+        let Some((_, result, state)) = self
+            .selected_result_tab_index
+            .and_then(|index| self.result_tabs.get(index))
+        else {
+            return Ok(());
+        };
+        let (Some((_, schema, table)), Some(db_type)) =
+            (state.table_info.as_ref(), self.current_db_type())
+        else {
+            self.set_status_message("This tab has no table to insert into".to_string());
+            return Ok(());
+        };
+        if db_type == DatabaseType::MongoDB {
+            self.set_status_message("MongoDB collections have no INSERT statements".to_string());
+            return Ok(());
+        }
+
+        let mut rows: Vec<usize> = state.rows_marked_for_deletion.iter().copied().collect();
+        let marked = !rows.is_empty();
+        if !marked {
+            rows.push(self.cursor_position.1);
+        }
+        rows.sort_unstable();
+        let statements: Vec<String> = rows
+            .iter()
+            .filter_map(|&index| result.rows.get(index))
+            .map(|row| {
+                crate::database::core::insert_statement(&db_type, schema, table, result, row)
+            })
+            .collect();
+        if statements.is_empty() {
+            return Ok(());
+        }
+        let content = statements.join("\n");
+
+        // Store in internal clipboard
+        self.clipboard = content.clone();
+
+        // Also copy to system clipboard
+        let mut ctx: ClipboardContext = match ClipboardProvider::new() {
+            Ok(ctx) => ctx,
+            Err(e) => {
+                let error_msg = format!("Failed to access clipboard: {}", e);
+                logging::error(&error_msg);
+                self.status_message = Some(error_msg);
+                return Ok(());
+            }
+        };
+
+        if let Err(e) = ctx.set_contents(content) {
+            let error_msg = format!("Failed to copy to clipboard: {}", e);
+            logging::error(&error_msg);
+            self.status_message = Some(error_msg);
+            return Ok(());
+        }
+
+        let message = if marked {
+            format!("Copied INSERTs for {} marked rows", statements.len())
+        } else {
+            "Copied an INSERT for the current row".to_string()
+        };
+        self.set_status_message(message);
+        logging::info(&format!(
+            "Copied {} INSERT statements to clipboard",
+            statements.len()
+        ));
+        Ok(())
+    }
+
     /// Lists available themes
     pub fn list_themes(&mut self) -> anyhow::Result<()> {
         self.toggle_themes_modal();
@@ -2288,6 +4593,7 @@ impl App {
     pub fn switch_theme(&mut self, theme_name: &str) -> anyhow::Result<()> {
         match self.config.switch_theme(theme_name) {
             Ok(()) => {
+                let theme_name = &self.config.theme_name;
                 self.status_message = Some(format!("Switched to theme: {}", theme_name));
                 logging::info(&format!("Theme switched to: {}", theme_name));
             }
@@ -2300,22 +4606,215 @@ impl App {
         Ok(())
     }
 
-    /// Updates command suggestions based on current input
-    pub fn update_command_suggestions(&mut self) {
-        // Only show suggestions if there's meaningful input (more than just the command prompt)
-        if !self.command_input.is_empty() {
-            self.command_suggestions =
-                crate::command::CommandProcessor::get_suggestions(&self.command_input);
-        } else {
-            self.command_suggestions.clear();
+    /// Applies edits to the current theme's file as soon as it is saved
+    pub fn check_theme_reload(&mut self) {
+        match self.config.reload_theme_if_changed() {
+            Ok(true) => {
+                self.set_status_message(format!("Reloaded theme: {}", self.config.theme_name));
+            }
+            Ok(false) => {}
+            Err(e) => {
+                let error_msg = format!("Failed to reload theme: {}", e);
+                logging::error(&error_msg);
+                self.set_status_message(error_msg);
+            }
         }
-
-        self.selected_suggestion = if self.command_suggestions.is_empty() {
-            None
-        } else {
-            Some(0)
-        };
-        self.suggestions_scroll_offset = 0; // Reset scroll when suggestions change
+    }
+
+    /// Toggles the main panel between the stacked and split layouts
+    pub fn toggle_main_panel_layout(&mut self) -> anyhow::Result<()> {
+        use crate::config::MainPanelLayout;
+
+        self.config.layout.main_panel = match self.config.layout.main_panel {
+            MainPanelLayout::Stacked => MainPanelLayout::Split,
+            MainPanelLayout::Split => MainPanelLayout::Stacked,
+        };
+        self.set_status_message(format!("Layout: {:?}", self.config.layout.main_panel));
+        self.save_layout();
+        Ok(())
+    }
+
+    /// Moves the split divider by `delta` percent (split layout only)
+    pub fn adjust_query_split(&mut self, delta: i16) {
+        if self.config.layout.main_panel != crate::config::MainPanelLayout::Split {
+            return;
+        }
+        let current = self.config.layout.query_split_percent as i16;
+        self.set_query_split_percent(current.saturating_add(delta).max(0) as u16);
+        self.save_layout();
+    }
+
+    /// Sets the split divider position without persisting it
+    pub fn set_query_split_percent(&mut self, percent: u16) {
+        self.config.layout.query_split_percent =
+            crate::config::LayoutConfig::clamp_split_percent(percent);
+    }
+
+    /// Widens or narrows the connections sidebar by `delta` percent
+    pub fn adjust_sidebar_width(&mut self, delta: i16) {
+        let current = self.config.layout.sidebar_percent as i16;
+        self.set_sidebar_percent(current.saturating_add(delta).max(0) as u16);
+        self.save_layout();
+    }
+
+    /// Sets the sidebar width without persisting it
+    pub fn set_sidebar_percent(&mut self, percent: u16) {
+        self.config.layout.sidebar_percent =
+            crate::config::LayoutConfig::clamp_sidebar_percent(percent);
+    }
+
+    /// Persists the current layout settings to the config file
+    pub fn save_layout(&mut self) {
+        if let Err(e) = self.config.save_layout() {
+            let error_msg = format!("Failed to save layout: {}", e);
+            logging::error(&error_msg);
+            self.set_status_message(error_msg);
+        }
+    }
+
+    /// Searches the loaded result rows; an empty query clears the search
+    pub fn search_results(&mut self, query: &str) {
+        self.result_search.set_query(query);
+        self.report_search_status();
+    }
+
+    pub fn toggle_search_regex(&mut self) {
+        self.result_search.toggle_regex();
+        self.report_search_status();
+    }
+
+    pub fn toggle_search_case_sensitive(&mut self) {
+        self.result_search.toggle_case_sensitive();
+        self.report_search_status();
+    }
+
+    pub fn toggle_search_whole_word(&mut self) {
+        self.result_search.toggle_whole_word();
+        self.report_search_status();
+    }
+
+    /// Starts typing a search in the results pane, from an empty query
+    pub fn start_result_search(&mut self) {
+        self.result_search.set_query("");
+        self.result_search_editing = true;
+    }
+
+    /// Adds (or with `None` removes) the last character of the search being typed; matches
+    /// are highlighted as it changes
+    pub fn edit_result_search(&mut self, c: Option<char>) {
+        let mut query = self.result_search.query.clone();
+        match c {
+            Some(c) => query.push(c),
+            None => {
+                query.pop();
+            }
+        }
+        self.result_search.set_query(&query);
+    }
+
+    /// Stops typing the search and moves to the first match from the cursor on
+    pub fn finish_result_search(&mut self) {
+        self.result_search_editing = false;
+        if self.result_search.is_active() {
+            self.jump_to_search_match(true, true);
+        } else {
+            self.report_search_status();
+        }
+    }
+
+    /// Stops typing the search and clears it
+    pub fn cancel_result_search(&mut self) {
+        self.result_search_editing = false;
+        self.search_results("");
+    }
+
+    /// Moves the cursor to the next (or previous) matching cell of the current tab, wrapping
+    /// around at the ends. Returns false when there is no search in effect.
+    pub fn search_next_match(&mut self, forward: bool) -> bool {
+        if !self.result_search.is_active() {
+            return false;
+        }
+        self.jump_to_search_match(forward, false);
+        true
+    }
+
+    /// Moves to the nearest match after (or before) the cursor, or onto it when
+    /// `include_current` is set
+    fn jump_to_search_match(&mut self, forward: bool, include_current: bool) {
+        let visible = self.visible_column_indices();
+        let matches = self
+            .selected_result_tab_index
+            .and_then(|index| self.result_tabs.get(index))
+            .map(|(_, result, _)| self.result_search.matching_cells(&result.rows, &visible))
+            .unwrap_or_default();
+        if matches.is_empty() {
+            self.set_status_message(format!("No matches for {}", self.result_search.describe()));
+            return;
+        }
+
+        // Reading order compares rows first, so the positions are flipped to (row, column)
+        let (column, row) = self.cursor_position;
+        let key = |&(column, row): &(usize, usize)| (row, column);
+        let cursor = (row, column);
+        let found = if forward {
+            matches
+                .iter()
+                .position(|cell| key(cell) > cursor || (include_current && key(cell) == cursor))
+        } else {
+            matches.iter().rposition(|cell| key(cell) < cursor)
+        };
+        let wrapped = found.is_none();
+        let index = found.unwrap_or(if forward { 0 } else { matches.len() - 1 });
+        self.cursor_position = matches[index];
+
+        let mut message = format!(
+            "Match {} of {} for {}",
+            index + 1,
+            matches.len(),
+            self.result_search.describe()
+        );
+        if wrapped {
+            message.push_str(if forward {
+                " (wrapped to the top)"
+            } else {
+                " (wrapped to the bottom)"
+            });
+        }
+        self.set_status_message(message);
+    }
+
+    fn report_search_status(&mut self) {
+        let message = if let Some(error) = &self.result_search.error {
+            format!("Invalid regex: {}", error)
+        } else if self.result_search.query.is_empty() {
+            "Search cleared".to_string()
+        } else {
+            let matches = self
+                .selected_result_tab_index
+                .and_then(|idx| self.result_tabs.get(idx))
+                .map(|(_, result, _)| self.result_search.count_matching_cells(&result.rows))
+                .unwrap_or(0);
+            format!("Search {}: {} matching cells", self.result_search.describe(), matches)
+        };
+        self.set_status_message(message);
+    }
+
+    /// Updates command suggestions based on current input
+    pub fn update_command_suggestions(&mut self) {
+        // Only show suggestions if there's meaningful input (more than just the command prompt)
+        if !self.command_input.is_empty() {
+            self.command_suggestions =
+                crate::command::CommandProcessor::get_suggestions(&self.command_input);
+        } else {
+            self.command_suggestions.clear();
+        }
+
+        self.selected_suggestion = if self.command_suggestions.is_empty() {
+            None
+        } else {
+            Some(0)
+        };
+        self.suggestions_scroll_offset = 0; // Reset scroll when suggestions change
     }
 
     /// Moves suggestion selection up
@@ -2411,75 +4910,1616 @@ impl App {
                 }
             }
         }
-    }
+    }
+
+    pub fn get_current_theme_name(&self) -> &str {
+        "default" // Placeholder - implement based on your theme system
+    }
+
+    pub async fn first_page(&mut self) -> Result<()> {
+        self.go_to_page(|_, _| 1).await
+    }
+
+    pub async fn previous_page(&mut self) -> Result<()> {
+        self.go_to_page(|current, _| current.saturating_sub(1)).await
+    }
+
+    pub async fn next_page(&mut self) -> Result<()> {
+        self.go_to_page(|current, _| current.saturating_add(1)).await
+    }
+
+    pub async fn last_page(&mut self) -> Result<()> {
+        if self
+            .current_query_state()
+            .is_some_and(|state| state.total_pages.is_none())
+        {
+            self.set_status_message("The number of pages is not known".to_string());
+            return Ok(());
+        }
+        self.go_to_page(|_, total| total).await
+    }
+
+    /// Pages of `state` that may exist: its page count, or as many as offsets can address
+    /// while the rows haven't been counted
+    fn page_limit(state: &QueryState) -> u32 {
+        state
+            .total_pages
+            .unwrap_or(u32::MAX / state.page_size.max(1))
+            .max(1)
+    }
+
+    /// Moves the current tab to page `page`, clamping it to the pages there are
+    pub async fn go_to_page_number(&mut self, page: u64) -> Result<()> {
+        let Some(state) = self.current_query_state() else {
+            self.set_status_message("No result tab selected".to_string());
+            return Ok(());
+        };
+        let limit = Self::page_limit(state);
+        let total = state
+            .total_pages
+            .map_or_else(|| "?".to_string(), |pages| pages.to_string());
+        let target = u32::try_from(page).unwrap_or(u32::MAX).clamp(1, limit);
+        self.go_to_page(|_, _| target).await?;
+        let message = if u64::from(target) == page {
+            format!("Page {} of {}", target, total)
+        } else {
+            format!("Page {} of {} (there is no page {})", target, total, page)
+        };
+        self.set_status_message(message);
+        Ok(())
+    }
+
+    /// Moves the cursor to row `row` of the current tab, counting from 1 across all pages and
+    /// clamped to the rows there are, fetching the page holding it
+    pub async fn go_to_row(&mut self, row: u64) -> Result<()> {
+        let Some(state) = self.current_query_state() else {
+            self.set_status_message("No result tab selected".to_string());
+            return Ok(());
+        };
+        let page_size = u64::from(state.page_size.max(1));
+        let total_pages = u64::from(Self::page_limit(state));
+        let total = state.total_records.unwrap_or(total_pages * page_size).max(1);
+        let known_total = state.total_records.is_some();
+        let target = row.clamp(1, total);
+        let page = ((target - 1) / page_size + 1).min(total_pages);
+        let page_number = u32::try_from(page).unwrap_or(u32::MAX);
+        self.go_to_page(|_, _| page_number).await?;
+
+        let rows = self.cursor_page_bounds().map_or(0, |(rows, _)| rows);
+        // Ad-hoc tabs hold every row on their one page, whatever the page size
+        let index = usize::try_from(target - 1 - (page - 1) * page_size).unwrap_or(usize::MAX);
+        self.cursor_position.1 = index.min(rows.saturating_sub(1));
+        let message = if !known_total {
+            format!("Row {}", target)
+        } else if target == row {
+            format!("Row {} of {}", target, total)
+        } else {
+            format!("Row {} of {} (there is no row {})", target, total, row)
+        };
+        self.set_status_message(message);
+        Ok(())
+    }
+
+    /// Moves the current tab to the page `target(current_page, total_pages)`, clamped to the
+    /// page count (see `page_limit`), and fetches it. The cursor goes to the first row,
+    /// keeping its column.
+    async fn go_to_page(&mut self, target: impl Fn(u32, u32) -> u32) -> Result<()> {
+        let Some(state) = self.current_query_state_mut() else {
+            return Ok(());
+        };
+        let current = state.current_page.max(1);
+        let total = Self::page_limit(state);
+        let page = target(current, total).clamp(1, total);
+        if page == current {
+            return Ok(());
+        }
+        state.current_page = page;
+
+        if let Err(e) = self.refresh_results().await {
+            if let Some(state) = self.current_query_state_mut() {
+                state.current_page = current;
+            }
+            return Err(e);
+        }
+        self.cursor_position.1 = 0;
+        Ok(())
+    }
+
+    /// Asks for confirmation before deleting the rows marked in the current tab
+    pub fn prompt_delete_marked_rows(&mut self) {
+        let Some((_, _, state)) = self
+            .selected_result_tab_index
+            .and_then(|index| self.result_tabs.get(index))
+        else {
+            self.set_status_message("No result tab selected".to_string());
+            return;
+        };
+        let marked = state.rows_marked_for_deletion.len();
+        if marked == 0 {
+            self.set_status_message("No rows marked for deletion".to_string());
+            return;
+        }
+        if state.base_query.is_some() {
+            self.set_status_message("Base query results are read-only".to_string());
+            return;
+        }
+        if state.table_info.is_none() {
+            self.set_status_message("Ad-hoc query results are read-only".to_string());
+            return;
+        }
+        let Some((conn_name, schema, table)) = &self.last_table_info else {
+            self.set_status_message("No table context".to_string());
+            return;
+        };
+        if self.blocks_statement(conn_name, "DELETE") {
+            self.set_status_message(crate::database::READ_ONLY_MESSAGE.to_string());
+            return;
+        }
+
+        let target = if schema.is_empty() {
+            table.clone()
+        } else {
+            format!("{}.{}", schema, table)
+        };
+        self.modal_manager
+            .push(Box::new(crate::ui::modals::DeletionModal::new(&target, marked)));
+    }
+
+    /// Starts deleting the marked rows of the current tab in batches inside a transaction.
+    /// The batches are run by `run_bulk_deletion_step`, one per UI tick.
+    pub async fn confirm_deletions(&mut self) -> Result<()> {
+        if self.bulk_deletion.is_some() {
+            return Ok(());
+        }
+
+        let (conn_name, schema, table) = self
+            .last_table_info
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("No table context"))?;
+        if self.blocks_statement(&conn_name, "DELETE") {
+            self.set_status_message(crate::database::READ_ONLY_MESSAGE.to_string());
+            return Ok(());
+        }
+        self.ensure_alive(&conn_name).await?;
+        let (_, result, state) = self
+            .selected_result_tab_index
+            .and_then(|index| self.result_tabs.get(index))
+            .ok_or_else(|| anyhow::anyhow!("No result tab selected"))?;
+
+        let db_type = self
+            .connection_tree
+            .iter()
+            .find(|item| item.connection_config.name == conn_name)
+            .map(|item| item.connection_config.db_type.clone())
+            .unwrap_or(crate::database::DatabaseType::Postgres);
+        let qualified_table = match db_type {
+            crate::database::DatabaseType::MongoDB => {
+                return Err(anyhow::anyhow!("Deleting MongoDB documents is not supported yet"));
+            }
+            _ => crate::database::core::dialect_table(&db_type, &schema, &table),
+        };
+
+        let mut rows: Vec<usize> = state.rows_marked_for_deletion.iter().copied().collect();
+        rows.sort_unstable();
+        let marked_rows: Vec<&Vec<Option<String>>> =
+            rows.iter().filter_map(|&index| result.rows.get(index)).collect();
+        let chunks = crate::deletion::build_delete_chunks(
+            &db_type,
+            &qualified_table,
+            &result.columns,
+            &state.primary_key_columns,
+            &marked_rows,
+        );
+        let mut deletion =
+            BulkDeletion::new(&conn_name, &schema, &table, &state.primary_key_columns, chunks);
+        deletion.in_transaction = self.open_transactions.contains(&conn_name);
+
+        let connection = self
+            .connection_manager
+            .get_connection(&conn_name)
+            .ok_or_else(|| anyhow::anyhow!("Connection '{}' is not connected", conn_name))?;
+        connection
+            .execute_statement(&deletion.begin_statement())
+            .await?;
+
+        logging::info(&format!(
+            "Deleting {} rows from {} in {} batches",
+            rows.len(),
+            deletion.target,
+            deletion.chunk_count()
+        ));
+        self.bulk_deletion = Some(deletion);
+        Ok(())
+    }
+
+    /// Cancels the running deletion; the next step rolls the transaction back
+    pub fn cancel_bulk_deletion(&mut self) {
+        if let Some(deletion) = self.bulk_deletion.as_mut() {
+            deletion.cancel_requested = true;
+        }
+    }
+
+    /// Runs the next batch of the current deletion, committing after the last one and
+    /// rolling back on cancel or error
+    pub async fn run_bulk_deletion_step(&mut self) -> Result<()> {
+        let Some(deletion) = self.bulk_deletion.as_mut() else {
+            return Ok(());
+        };
+        let Some(connection) = self.connection_manager.get_connection(&deletion.connection) else {
+            self.bulk_deletion = None;
+            self.modal_manager.close_active();
+            return Err(anyhow::anyhow!("Connection lost during deletion"));
+        };
+
+        let failure = if deletion.cancel_requested {
+            Some("Deletion cancelled".to_string())
+        } else if let Some(chunk) = deletion.next_chunk() {
+            let row_count = chunk.row_count as u64;
+            let deleted = match &chunk.predicate {
+                DeletePredicate::Keys(keys) => {
+                    connection
+                        .delete_rows(&deletion.schema, &deletion.table, &deletion.key_columns, keys)
+                        .await
+                }
+                DeletePredicate::Statement(statement) => {
+                    connection.execute_statement(statement).await
+                }
+            };
+            match deleted {
+                // Matching on every column may hit duplicates the user never marked
+                Ok(deleted) if deleted > row_count => Some(format!(
+                    "Deletion aborted: a batch matched {} rows but only {} were marked",
+                    deleted, row_count
+                )),
+                Ok(deleted) => {
+                    deletion.advance(deleted);
+                    None
+                }
+                Err(e) => Some(format!("Deletion failed: {}", e)),
+            }
+        } else {
+            None
+        };
+
+        if let Some(reason) = failure {
+            let rollback = connection
+                .execute_statement(&deletion.abort_statement())
+                .await;
+            let deletion = self.bulk_deletion.take().expect("deletion in progress");
+            self.modal_manager.close_active();
+            rollback?;
+            logging::warn(&format!("{} on {}", reason, deletion.target));
+            self.set_status_message(format!(
+                "{}; rolled back, 0 of {} rows deleted",
+                reason, deletion.requested
+            ));
+            return Ok(());
+        }
+
+        if !deletion.is_finished() {
+            return Ok(());
+        }
+
+        let commit = connection
+            .execute_statement(&deletion.finish_statement())
+            .await;
+        let deletion = self.bulk_deletion.take().expect("deletion in progress");
+        self.modal_manager.close_active();
+        commit?;
+
+        logging::info(&format!(
+            "Deleted {} of {} rows from {}",
+            deletion.deleted, deletion.requested, deletion.target
+        ));
+        // Refreshing also drops the marks, which pointed at the deleted rows
+        self.refresh_results().await?;
+        if deletion.in_transaction {
+            self.set_status_message(format!(
+                "Deleted {} of {} requested rows from {} (uncommitted)",
+                deletion.deleted, deletion.requested, deletion.target
+            ));
+            return Ok(());
+        }
+        if let Some(state) = self.current_query_state_mut() {
+            state.committed_deletions += deletion.deleted;
+        }
+        self.set_status_message(format!(
+            "Deleted {} of {} requested rows from {}",
+            deletion.deleted, deletion.requested, deletion.target
+        ));
+        Ok(())
+    }
+
+    /// Sets the current tab's WHERE clause from the cell under the cursor, `column = 'value'`
+    /// (`IS NULL` for NULL) or with `exclude` `!=` (`IS NOT NULL`), and reloads it from the
+    /// first page. With `append` the predicate is ANDed onto the existing clause, which is
+    /// parenthesised.
+    pub async fn filter_by_cell(&mut self, exclude: bool, append: bool) -> Result<()> {
+        let db_type = self.current_db_type();
+        let Some((_, result, state)) = self
+            .selected_result_tab_index
+            .and_then(|index| self.result_tabs.get(index))
+        else {
+            return Ok(());
+        };
+        if state.table_info.is_none() {
+            return Err(anyhow::anyhow!("Ad-hoc query results can't be filtered"));
+        }
+        let Some(db_type) = db_type else {
+            return Ok(());
+        };
+        if db_type == DatabaseType::MongoDB {
+            return Err(anyhow::anyhow!("Filtering by a cell needs a SQL connection"));
+        }
+        let (col_idx, row_idx) = self.cursor_position;
+        let (Some(column), Some(cell)) = (
+            result.columns.get(col_idx),
+            result.rows.get(row_idx).and_then(|row| row.get(col_idx)),
+        ) else {
+            return Ok(());
+        };
+
+        let predicate =
+            crate::database::core::cell_predicate(&db_type, column, cell.as_deref(), exclude);
+        let existing = state.where_clause.trim();
+        // Wrapped whole so an OR, even one hidden in a string or subquery, can't swallow it
+        let where_clause = if !append || existing.is_empty() {
+            predicate
+        } else {
+            format!("({}) AND {}", existing, predicate)
+        };
+
+        self.query_input_pane.set_where_content(&where_clause);
+        if let Some(state) = self.current_query_state_mut() {
+            state.where_clause = where_clause.clone();
+            state.current_page = 1;
+        }
+        self.cursor_position.1 = 0;
+        self.execute_query_input().await?;
+        self.set_status_message(format!("WHERE {}", where_clause));
+        Ok(())
+    }
+
+    /// Opens the quick filter prompt over the current table tab's columns, starting at the
+    /// cursor's column
+    pub fn open_quick_filter(&mut self) -> Result<()> {
+        let Some((_, result, state)) = self
+            .selected_result_tab_index
+            .and_then(|index| self.result_tabs.get(index))
+        else {
+            return Err(anyhow::anyhow!("No result tab to filter"));
+        };
+        if state.table_info.is_none() {
+            return Err(anyhow::anyhow!("Ad-hoc query results can't be filtered"));
+        }
+        if self.current_db_type() == Some(DatabaseType::MongoDB) {
+            return Err(anyhow::anyhow!("Quick filters need a SQL connection"));
+        }
+        if result.columns.is_empty() {
+            return Err(anyhow::anyhow!("The tab has no columns to filter on"));
+        }
+        let modal = crate::ui::modals::QuickFilterModal::new(
+            result.columns.clone(),
+            self.cursor_position.0,
+        );
+        self.modal_manager.push(Box::new(modal));
+        Ok(())
+    }
+
+    /// Sets the current tab's WHERE clause to `column` containing `pattern`, ignoring case
+    /// (see `contains_predicate`), and reloads it from the first page. An empty pattern clears
+    /// the WHERE clause instead.
+    pub async fn quick_filter(&mut self, column: &str, pattern: &str) -> Result<()> {
+        let Some(db_type) = self.current_db_type() else {
+            return Ok(());
+        };
+        let where_clause = if pattern.is_empty() {
+            String::new()
+        } else {
+            crate::database::core::contains_predicate(&db_type, column, pattern)
+        };
+
+        self.query_input_pane.set_where_content(&where_clause);
+        if let Some(state) = self.current_query_state_mut() {
+            state.where_clause = where_clause.clone();
+            state.current_page = 1;
+        }
+        self.cursor_position.1 = 0;
+        self.execute_query_input().await?;
+        if where_clause.is_empty() {
+            self.set_status_message("Filter cleared".to_string());
+        } else {
+            self.set_status_message(format!("WHERE {}", where_clause));
+        }
+        Ok(())
+    }
+
+    /// Tab completion in the query fields: completes the word before the cursor with the
+    /// current tab's column names and the tables of its schema
+    pub async fn complete_query_word(&mut self, reverse: bool) {
+        let db_type = self.current_db_type();
+        let Some((_, result, state)) = self
+            .selected_result_tab_index
+            .and_then(|index| self.result_tabs.get(index))
+        else {
+            return;
+        };
+        let mut candidates = result.columns.clone();
+        let table_info = state.table_info.clone();
+
+        if let Some((conn_name, schema, table)) = table_info {
+            // Tabs fetched before the columns were known, e.g. restored ones, describe the table
+            if candidates.is_empty() {
+                let timeout = self.statement_timeout_for(&conn_name);
+                if let Some(db) = self.connection_manager.get_connection(&conn_name) {
+                    match with_timeout(timeout, db.get_columns(&schema, &table)).await {
+                        Ok(columns) => {
+                            candidates.extend(columns.into_iter().map(|column| column.name))
+                        }
+                        Err(e) => logging::warn(&format!("Could not fetch columns: {}", e)),
+                    }
+                }
+            }
+            let tables = self
+                .connection_tree
+                .iter()
+                .filter(|item| item.connection_config.name == conn_name)
+                .flat_map(|item| &item.databases)
+                .flat_map(|database| &database.schemas)
+                .find(|item| item.name == schema && item.tables.contains(&table))
+                .map(|item| item.tables.clone())
+                .unwrap_or_default();
+            candidates.extend(tables);
+        }
+
+        let quote = |name: &str| completion_identifier(db_type.as_ref(), name);
+        if !self
+            .query_input_pane
+            .complete_word(&candidates, quote, reverse)
+        {
+            self.set_status_message("No completions".to_string());
+        }
+    }
+
+    /// Type of the database the current tab reads from; `None` for ad-hoc and metadata tabs
+    pub fn current_db_type(&self) -> Option<DatabaseType> {
+        let (conn_name, _, _) = self.current_query_state()?.table_info.as_ref()?;
+        self.connection_tree
+            .iter()
+            .find(|item| &item.connection_config.name == conn_name)
+            .map(|item| item.connection_config.db_type.clone())
+    }
+
+    /// Index in the connection tree of the connection holding the selected tree item
+    pub fn selected_tree_connection(&self) -> Option<usize> {
+        let item = self.get_tree_item_at_visual_index(self.selected_connection_idx?)?;
+        match item {
+            TreeItem::Connection(conn_idx)
+            | TreeItem::Database(conn_idx, _)
+            | TreeItem::Schema(conn_idx, _, _)
+            | TreeItem::Table(conn_idx, _, _, _) => Some(conn_idx),
+        }
+    }
+
+    /// Connects the connection at `index` in the tree now, without expanding it, and lists
+    /// its databases. A failed connect is reported in the status bar with the error.
+    pub async fn connect_to_database(&mut self, index: usize) -> Result<()> {
+        let Some(name) = self
+            .connection_tree
+            .get(index)
+            .map(|connection| connection.connection_config.name.clone())
+        else {
+            self.set_status_message("No connection selected".to_string());
+            return Ok(());
+        };
+        if self.connection_manager.get_connection(&name).is_some() {
+            self.set_status_message(format!("Already connected to {}", name));
+            return Ok(());
+        }
+
+        // Let an in-flight background prefetch finish instead of racing it with a second connect
+        self.await_in_flight_prefetch(&name).await;
+        let cfg = self.resolved_connection_config(&name)?;
+        self.set_tree_connection_status(index, &name, ConnectionStatus::Connecting);
+        logging::info(&format!("Connecting to database: {}", name));
+
+        if let Err(e) = self.connection_manager.connect(cfg.clone()).await {
+            self.set_tree_connection_status(index, &name, ConnectionStatus::Failed);
+            self.connection_failures
+                .insert(name.clone(), ConnectionFailure::from_error(&e));
+            logging::error(&format!("Failed to connect to {}: {}", name, e));
+            self.set_status_message(format!("Failed to connect to {}: {}", name, e));
+            return Ok(());
+        }
+        self.set_tree_connection_status(index, &name, ConnectionStatus::Connected);
+
+        let databases = match self.connection_manager.get_connection(&name) {
+            Some(connection) => connection.list_databases().await,
+            None => Ok(Vec::new()),
+        };
+        match databases {
+            Ok(mut databases) => {
+                databases.retain(|database| cfg.should_show_database(database));
+                // Stored like a background prefetch, so expanding later doesn't fetch again
+                let prefetched = PrefetchedStructure {
+                    connection_name: name.clone(),
+                    databases: databases
+                        .iter()
+                        .map(|database| PrefetchedDatabase {
+                            name: database.clone(),
+                            schemas: Vec::new(),
+                        })
+                        .collect(),
+                };
+                self.prefetched_structures.insert(name.clone(), prefetched);
+                if let Some(connection) = self
+                    .connection_tree
+                    .get_mut(index)
+                    .filter(|connection| !connection.is_expanded)
+                {
+                    connection.databases = databases
+                        .iter()
+                        .map(|database| DatabaseTreeItem {
+                            name: database.clone(),
+                            schemas: Vec::new(),
+                            is_expanded: false,
+                        })
+                        .collect();
+                }
+                self.set_status_message(format!(
+                    "Connected to {} ({} databases)",
+                    name,
+                    databases.len()
+                ));
+            }
+            Err(e) => {
+                logging::warn(&format!("Could not list databases of {}: {}", name, e));
+                self.set_status_message(format!(
+                    "Connected to {}, but listing its databases failed: {}",
+                    name, e
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Closes connection `index` entirely, SSH tunnel included, and connects it again, e.g. after
+    /// its credentials changed or its socket died. Its cached structure is dropped and fetched
+    /// anew, keeping what was expanded; a transaction open on it is rolled back by the close.
+    /// The status bar tells which stage failed, if any.
+    pub async fn reconnect_connection(&mut self, index: usize) -> Result<()> {
+        let Some(name) = self
+            .connection_tree
+            .get(index)
+            .map(|connection| connection.connection_config.name.clone())
+        else {
+            self.set_status_message("No connection selected".to_string());
+            return Ok(());
+        };
+
+        // Resolved first, so an unknown SSH tunnel name leaves the live connection alone
+        let cfg = self.resolved_connection_config(&name)?;
+        self.await_in_flight_prefetch(&name).await;
+        logging::info(&format!("Disconnecting {} to reconnect it", name));
+        if let Err(e) = self.connection_manager.disconnect(&name).await {
+            logging::warn(&format!("Error disconnecting {}: {}", name, e));
+        }
+        let rolled_back = self.open_transactions.remove(&name);
+        self.prefetched_structures.remove(&name);
+
+        self.set_tree_connection_status(index, &name, ConnectionStatus::Connecting);
+        logging::info(&format!("Reconnecting to database: {}", name));
+        if let Err(e) = self.connection_manager.connect(cfg).await {
+            self.set_tree_connection_status(index, &name, ConnectionStatus::Failed);
+            self.connection_failures
+                .insert(name.clone(), ConnectionFailure::from_error(&e));
+            logging::error(&format!("Failed to reconnect to {}: {}", name, e));
+            self.set_status_message(format!(
+                "Disconnected {}, but connecting failed: {}",
+                name, e
+            ));
+            return Ok(());
+        }
+        self.set_tree_connection_status(index, &name, ConnectionStatus::Connected);
+
+        if let Err(e) = self.refresh_connection_tree(index).await {
+            logging::warn(&format!("Could not reload the tree of {}: {}", name, e));
+            self.set_status_message(format!(
+                "Reconnected to {}, but loading its databases failed: {}",
+                name, e
+            ));
+            return Ok(());
+        }
+        self.set_status_message(if rolled_back {
+            format!(
+                "Reconnected to {}; its open transaction was rolled back",
+                name
+            )
+        } else {
+            format!("Reconnected to {}", name)
+        });
+        Ok(())
+    }
+
+    /// Reconnects the active connection to `database`, which queries and table tabs then read
+    /// from. A server session can't change its database, so this opens a new connection; the
+    /// tree's schemas are reloaded on the next expand since they came from the old one.
+    pub async fn use_database(&mut self, database: &str) -> Result<()> {
+        let name = self
+            .active_connection_name()
+            .ok_or_else(|| anyhow::anyhow!("No connection selected"))?;
+        // Reconnecting would silently drop the transaction's changes
+        if self.open_transactions.contains(&name) {
+            return Err(anyhow::anyhow!(
+                "Commit or roll back the transaction on {} first",
+                name
+            ));
+        }
+        let index = self
+            .connection_tree
+            .iter()
+            .position(|connection| connection.connection_config.name == name)
+            .ok_or_else(|| anyhow::anyhow!("Connection '{}' no longer exists", name))?;
+        if matches!(
+            self.connection_tree[index].connection_config.db_type,
+            DatabaseType::SQLite
+        ) {
+            return Err(anyhow::anyhow!("SQLite connections have a single database"));
+        }
+
+        self.await_in_flight_prefetch(&name).await;
+        self.ensure_connected(&name).await?;
+        let databases = match self.connection_manager.get_connection(&name) {
+            Some(connection) => connection.list_databases().await?,
+            None => Vec::new(),
+        };
+        if !databases.iter().any(|existing| existing == database) {
+            return Err(anyhow::anyhow!("Database '{}' does not exist on {}", database, name));
+        }
+
+        // The current connection stays in use until the new one is up
+        let mut cfg = self.resolved_connection_config(&name)?;
+        cfg.default_database = Some(database.to_string());
+        logging::info(&format!("Connecting {} to database {}", name, database));
+        self.connection_manager.replace(cfg.clone()).await?;
+        self.active_databases
+            .insert(name.clone(), database.to_string());
+        self.set_tree_connection_status(index, &name, ConnectionStatus::Connected);
+
+        let databases: Vec<String> = databases
+            .into_iter()
+            .filter(|database| cfg.should_show_database(database))
+            .collect();
+        self.prefetched_structures.insert(
+            name.clone(),
+            PrefetchedStructure {
+                connection_name: name.clone(),
+                databases: databases
+                    .iter()
+                    .map(|database| PrefetchedDatabase {
+                        name: database.clone(),
+                        schemas: Vec::new(),
+                    })
+                    .collect(),
+            },
+        );
+        let connection = &mut self.connection_tree[index];
+        connection.connection_config.default_database = Some(database.to_string());
+        connection.databases = databases
+            .into_iter()
+            .map(|database| DatabaseTreeItem {
+                name: database,
+                schemas: Vec::new(),
+                is_expanded: false,
+            })
+            .collect();
+        connection.is_expanded = true;
+
+        // The open table belonged to the old database
+        if self
+            .last_table_info
+            .as_ref()
+            .is_some_and(|(connection, _, _)| *connection == name)
+        {
+            self.last_table_info = None;
+        }
+        self.set_status_message(format!("Using database {} on {}", database, name));
+        Ok(())
+    }
+
+    /// Loads the databases, schemas and tables of the selected connection again, or with `all`
+    /// those of every connected one, so objects created elsewhere show up. Databases and
+    /// schemas that were expanded are expanded again, and the selected row stays selected
+    /// while it still exists.
+    pub async fn refresh_tree(&mut self, all: bool) -> Result<()> {
+        let connected = |app: &Self, index: usize| {
+            app.connection_tree[index].status == ConnectionStatus::Connected
+        };
+        let indices: Vec<usize> = if all {
+            (0..self.connection_tree.len())
+                .filter(|&index| connected(self, index))
+                .collect()
+        } else {
+            match self.selected_tree_connection() {
+                Some(index) if connected(self, index) => vec![index],
+                Some(index) => {
+                    self.set_status_message(format!(
+                        "{} is not connected; expand it or use :connect first",
+                        self.connection_tree[index].connection_config.name
+                    ));
+                    return Ok(());
+                }
+                None => {
+                    self.set_status_message("No connection selected".to_string());
+                    return Ok(());
+                }
+            }
+        };
+        if indices.is_empty() {
+            self.set_status_message("No connected connections to refresh".to_string());
+            return Ok(());
+        }
+
+        let selected = self
+            .selected_connection_idx
+            .and_then(|visual_index| self.get_tree_item_at_visual_index(visual_index))
+            .map(|item| self.tree_item_names(item));
+
+        let mut failed = Vec::new();
+        for &index in &indices {
+            if let Err(e) = self.refresh_connection_tree(index).await {
+                let name = self.connection_tree[index].connection_config.name.clone();
+                logging::error(&format!("Failed to refresh {}: {}", name, e));
+                failed.push(format!("{}: {}", name, e));
+            }
+        }
+
+        // Rows may have appeared or gone above the selected one
+        if let Some(names) = selected {
+            let items = self.visible_tree_items();
+            let position = items
+                .iter()
+                .position(|&item| self.tree_item_names(item) == names);
+            self.selected_connection_idx = position
+                .or_else(|| {
+                    self.selected_connection_idx
+                        .map(|index| index.min(items.len().saturating_sub(1)))
+                })
+                .filter(|_| !items.is_empty());
+        }
+
+        let message = if !failed.is_empty() {
+            format!("Failed to refresh {}", failed.join("; "))
+        } else if let [index] = indices[..] {
+            format!(
+                "Refreshed {}",
+                self.connection_tree[index].connection_config.name
+            )
+        } else {
+            format!("Refreshed {} connections", indices.len())
+        };
+        self.set_status_message(message);
+        Ok(())
+    }
+
+    /// Lists the databases of connection `index` again, then expands the databases and
+    /// schemas that were expanded, fetching their schemas and tables anew. The open
+    /// connection is reused, so a transaction on it survives.
+    async fn refresh_connection_tree(&mut self, index: usize) -> Result<()> {
+        let connection = &self.connection_tree[index];
+        let name = connection.connection_config.name.clone();
+        let was_expanded = connection.is_expanded;
+        let expanded: Vec<(String, Vec<String>)> = connection
+            .databases
+            .iter()
+            .filter(|database| database.is_expanded)
+            .map(|database| {
+                let schemas = database
+                    .schemas
+                    .iter()
+                    .filter(|schema| schema.is_expanded)
+                    .map(|schema| schema.name.clone())
+                    .collect();
+                (database.name.clone(), schemas)
+            })
+            .collect();
+
+        self.await_in_flight_prefetch(&name).await;
+        self.ensure_connected(&name).await?;
+        let mut databases = match self.connection_manager.get_connection(&name) {
+            Some(connection) => connection.list_databases().await?,
+            None => Vec::new(),
+        };
+        let cfg = self.resolved_connection_config(&name)?;
+        databases.retain(|database| cfg.should_show_database(database));
+        logging::info(&format!("Refreshing the tree of {}", name));
+
+        // Empty schemas make expanding fetch them, and their tables, again
+        self.prefetched_structures.insert(
+            name.clone(),
+            PrefetchedStructure {
+                connection_name: name.clone(),
+                databases: databases
+                    .iter()
+                    .map(|database| PrefetchedDatabase {
+                        name: database.clone(),
+                        schemas: Vec::new(),
+                    })
+                    .collect(),
+            },
+        );
+        let connection = &mut self.connection_tree[index];
+        connection.is_expanded = false;
+        connection.databases = databases
+            .into_iter()
+            .map(|database| DatabaseTreeItem {
+                name: database,
+                schemas: Vec::new(),
+                is_expanded: false,
+            })
+            .collect();
+        if !was_expanded {
+            return Ok(());
+        }
+
+        self.expand_connection(index).await?;
+        for (database, schemas) in expanded {
+            let Some(db_idx) = self.connection_tree[index]
+                .databases
+                .iter()
+                .position(|existing| existing.name == database)
+            else {
+                continue;
+            };
+            self.expand_database(index, db_idx).await?;
+            for schema in schemas {
+                let Some(schema_idx) = self.connection_tree[index].databases[db_idx]
+                    .schemas
+                    .iter()
+                    .position(|existing| existing.name == schema)
+                else {
+                    continue;
+                };
+                self.expand_schema(index, db_idx, schema_idx).await?;
+            }
+        }
+        self.load_scoped_schema(index).await
+    }
+
+    /// Names of the connection, database, schema and table leading to `item`, which stay
+    /// the same when the tree is reloaded while indices may not
+    fn tree_item_names(&self, item: TreeItem) -> Vec<String> {
+        let (conn_idx, db_idx, schema_idx, table_idx) = match item {
+            TreeItem::Connection(conn_idx) => (conn_idx, None, None, None),
+            TreeItem::Database(conn_idx, db_idx) => (conn_idx, Some(db_idx), None, None),
+            TreeItem::Schema(conn_idx, db_idx, schema_idx) => {
+                (conn_idx, Some(db_idx), Some(schema_idx), None)
+            }
+            TreeItem::Table(conn_idx, db_idx, schema_idx, table_idx) => {
+                (conn_idx, Some(db_idx), Some(schema_idx), Some(table_idx))
+            }
+        };
+        let Some(connection) = self.connection_tree.get(conn_idx) else {
+            return Vec::new();
+        };
+        let database = db_idx.and_then(|db_idx| connection.databases.get(db_idx));
+        let schema = database
+            .zip(schema_idx)
+            .and_then(|(database, schema_idx)| database.schemas.get(schema_idx));
+        let table = schema
+            .zip(table_idx)
+            .and_then(|(schema, table_idx)| schema.tables.get(table_idx));
+
+        std::iter::once(&connection.connection_config.name)
+            .chain(database.map(|database| &database.name))
+            .chain(schema.map(|schema| &schema.name))
+            .chain(table)
+            .cloned()
+            .collect()
+    }
+
+    fn set_tree_connection_status(&mut self, index: usize, name: &str, status: ConnectionStatus) {
+        self.connection_statuses.insert(name.to_string(), status);
+        if status == ConnectionStatus::Connected {
+            self.connection_failures.remove(name);
+        }
+        if let Some(connection) = self.connection_tree.get_mut(index) {
+            connection.status = status;
+        }
+    }
+
+    /// Writes every row of the current tab to `path`: tabs backed by a table or base query are
+    /// re-fetched without pagination, honouring their WHERE/ORDER BY. Every column is written
+    /// unless `visible_only` leaves out the hidden ones.
+    pub async fn export_current_tab(
+        &mut self,
+        format: ExportFormat,
+        path: &str,
+        visible_only: bool,
+    ) -> Result<()> {
+        let visible = self.visible_column_indices();
+        if let Some((conn_name, _, _)) = self
+            .current_query_state()
+            .and_then(|state| state.table_info.clone())
+        {
+            self.ensure_alive(&conn_name).await?;
+        }
+        let Some((_, loaded, state)) = self
+            .selected_result_tab_index
+            .and_then(|index| self.result_tabs.get(index))
+        else {
+            self.set_status_message("No results to export".to_string());
+            return Ok(());
+        };
+
+        let result = match &state.table_info {
+            Some((conn_name, schema, table)) => {
+                let connection = self
+                    .connection_manager
+                    .get_connection(conn_name)
+                    .ok_or_else(|| anyhow::anyhow!("Connection '{}' is not connected", conn_name))?;
+                let params = QueryParams {
+                    where_clause: Some(state.where_clause.clone()),
+                    order_by: Some(state.order_by_clause.clone()),
+                    limit: None,
+                    offset: None,
+                    base_query: state.base_query.clone(),
+                    where_params: state.where_params.clone(),
+                };
+                let timeout = self.statement_timeout_for(conn_name);
+                with_timeout(timeout, connection.fetch_table_data(schema, table, &params)).await?
+            }
+            // Ad-hoc query results are exported as loaded
+            None => loaded.clone(),
+        };
+        let result = if visible_only {
+            // Matched by name, as the re-fetched columns need not be in the loaded order
+            let names: Vec<&String> = visible
+                .iter()
+                .filter_map(|&index| loaded.columns.get(index))
+                .collect();
+            result.select_columns(&names)
+        } else {
+            result
+        };
+
+        let path = crate::export::resolve_path(path);
+        std::fs::write(&path, format.render(&result))
+            .map_err(|e| anyhow::anyhow!("Could not write {}: {}", path.display(), e))?;
+        self.set_status_message(format!(
+            "Exported {} rows to {}",
+            result.rows.len(),
+            path.display()
+        ));
+        Ok(())
+    }
+
+    /// Runs `query` as is on the active connection and shows its result in a new tab
+    pub async fn run_query(&mut self) -> Result<()> {
+        let query = self.query.trim().to_string();
+        if query.is_empty() {
+            self.set_status_message("No query to run (use 'sql <statement>')".to_string());
+            return Ok(());
+        }
+        let conn_name = self
+            .active_connection_name()
+            .ok_or_else(|| anyhow::anyhow!("No connection selected"))?;
+        if self.blocks_statement(&conn_name, &query) {
+            self.set_status_message(crate::database::READ_ONLY_MESSAGE.to_string());
+            return Ok(());
+        }
+        self.ensure_alive(&conn_name).await?;
+        let connection = self
+            .connection_manager
+            .get_connection(&conn_name)
+            .ok_or_else(|| anyhow::anyhow!("Connection '{}' is not connected", conn_name))?;
+
+        // Row-returning queries fetch one row past the cap to tell whether there are more
+        let max_rows = self.config.database.max_adhoc_rows;
+        let db_type = self
+            .connection_tree
+            .iter()
+            .find(|item| item.connection_config.name == conn_name)
+            .map(|item| item.connection_config.db_type.clone())
+            .unwrap_or(DatabaseType::Postgres);
+        let capped = crate::database::core::row_query(&db_type, &query).filter(|_| max_rows > 0);
+
+        let executed_at = chrono::Local::now();
+        let started = std::time::Instant::now();
+        let timeout = self.statement_timeout_for(&conn_name);
+        let executed = match capped {
+            Some(row_query) => {
+                self.fetch_row_chunk(&conn_name, row_query, max_rows + 1, 0)
+                    .await
+            }
+            None => with_timeout(timeout, connection.execute_query(&query)).await,
+        };
+        if let Err(err) = self.query_history.record(&query) {
+            logging::handle_non_critical_error(&err);
+        }
+        self.query_log.record(
+            &conn_name,
+            QueryLogEntry {
+                query: query.clone(),
+                executed_at,
+                elapsed: started.elapsed(),
+                row_count: executed.as_ref().ok().map(|result| result.rows.len()),
+                error: executed.as_ref().err().map(|e| e.to_string()),
+            },
+        );
+
+        let mut result = match executed {
+            Ok(result) => result,
+            Err(e) => {
+                self.set_status_message(format!("Query failed: {}", e));
+                return Ok(());
+            }
+        };
+
+        let rows = result.rows.len();
+        let Some(row_query) = capped.filter(|_| rows > max_rows) else {
+            self.open_adhoc_tab(&conn_name, result);
+            self.set_status_message(format!("Returned {} rows", rows));
+            return Ok(());
+        };
+        result.rows.truncate(max_rows);
+        let total = with_timeout(timeout, connection.count_base_query_rows(row_query, None))
+            .await
+            .map_err(|e| logging::warn(&format!("Could not count the query's rows: {}", e)))
+            .ok();
+        let row_query = row_query.to_string();
+        self.open_adhoc_tab(&conn_name, result);
+        if let Some((_, _, state)) = self.result_tabs.last_mut() {
+            state.total_records = total;
+            state.capped_query = Some((conn_name, row_query));
+        }
+        let total = total.map_or_else(|| format!("more than {}", max_rows), |t| t.to_string());
+        self.set_status_message(format!(
+            "Showing the first {} of {} rows; Alt+m or :more loads the next {}",
+            max_rows, total, max_rows
+        ));
+        Ok(())
+    }
+
+    /// Rows `offset..offset + limit` of the capped `row_query` on `conn_name`. A LIMIT is
+    /// appended to it unless it limits or locks its own rows; those are read up to the last
+    /// row wanted and the rows before `offset` dropped.
+    async fn fetch_row_chunk(
+        &self,
+        conn_name: &str,
+        row_query: &str,
+        limit: usize,
+        offset: usize,
+    ) -> Result<QueryResult> {
+        let connection = self
+            .connection_manager
+            .get_connection(conn_name)
+            .ok_or_else(|| anyhow::anyhow!("Connection '{}' is not connected", conn_name))?;
+        let db_type = self
+            .connection_tree
+            .iter()
+            .find(|item| item.connection_config.name == conn_name)
+            .map(|item| item.connection_config.db_type.clone())
+            .unwrap_or(DatabaseType::Postgres);
+        let timeout = self.statement_timeout_for(conn_name);
+        if !crate::database::core::limits_own_rows(&db_type, row_query) {
+            let statement = crate::database::core::row_chunk_query(row_query, limit, offset);
+            return with_timeout(timeout, connection.execute_query(&statement)).await;
+        }
+        let in_transaction = self.open_transactions.contains(conn_name);
+        let mut result = with_timeout(
+            timeout,
+            connection.execute_query_capped(row_query, offset + limit, in_transaction),
+        )
+        .await?;
+        result.rows.drain(..offset.min(result.rows.len()));
+        Ok(result)
+    }
+
+    /// Fetches the next `max_adhoc_rows` rows of the selected ad-hoc tab's capped query and
+    /// appends them to the tab
+    pub async fn load_more_rows(&mut self) -> Result<()> {
+        let Some((conn_name, row_query, loaded)) = self
+            .selected_result_tab_index
+            .and_then(|index| self.result_tabs.get(index))
+            .and_then(|(_, result, state)| {
+                let (conn_name, row_query) = state.capped_query.clone()?;
+                Some((conn_name, row_query, result.rows.len()))
+            })
+        else {
+            self.set_status_message("This tab has no more rows to load".to_string());
+            return Ok(());
+        };
+        let max_rows = self.config.database.max_adhoc_rows.max(1);
+        self.ensure_alive(&conn_name).await?;
+
+        let executed_at = chrono::Local::now();
+        let started = std::time::Instant::now();
+        let executed = self
+            .fetch_row_chunk(&conn_name, &row_query, max_rows + 1, loaded)
+            .await;
+        self.query_log.record(
+            &conn_name,
+            QueryLogEntry {
+                query: row_query,
+                executed_at,
+                elapsed: started.elapsed(),
+                row_count: executed.as_ref().ok().map(|result| result.rows.len()),
+                error: executed.as_ref().err().map(|e| e.to_string()),
+            },
+        );
+        let mut chunk = executed?;
+
+        let more = chunk.rows.len() > max_rows;
+        chunk.rows.truncate(max_rows);
+        let Some((_, result, state)) = self
+            .selected_result_tab_index
+            .and_then(|index| self.result_tabs.get_mut(index))
+        else {
+            return Ok(());
+        };
+        let added = chunk.rows.len();
+        result.rows.append(&mut chunk.rows);
+        let loaded = result.rows.len();
+        if !more {
+            state.capped_query = None;
+            state.total_records = Some(loaded as u64);
+        }
+        let message = match state.total_records {
+            _ if !more => format!("Loaded the last {} rows ({} in all)", added, loaded),
+            Some(total) => format!("Loaded {} more rows ({} of {})", added, loaded, total),
+            None => format!("Loaded {} more rows ({} so far)", added, loaded),
+        };
+        self.set_status_message(message);
+        Ok(())
+    }
+
+    /// Opens the rows of a statement run on `conn_name` in a new tab and selects it
+    fn open_adhoc_tab(&mut self, conn_name: &str, result: QueryResult) {
+        // Ad-hoc results have no table behind them, so `table_info` stays unset
+        let query_state = QueryState {
+            page_size: DEFAULT_PAGE_SIZE,
+            current_page: 1,
+            total_pages: Some(1),
+            total_records: Some(result.rows.len() as u64),
+            ..QueryState::default()
+        };
+        self.adhoc_query_count += 1;
+        let tab_name = format!("{}:adhoc-{}", conn_name, self.adhoc_query_count);
+        self.result_tabs.push((tab_name, result, query_state));
+        self.selected_result_tab_index = Some(self.result_tabs.len() - 1);
+
+        self.cursor_position = (0, 0);
+        self.active_pane = Pane::Results;
+    }
+
+    /// Runs the statements of the script at `path` on the active connection in order,
+    /// stopping at the first that fails. The rows of the last statement returning any open
+    /// in a new tab.
+    pub async fn run_script(&mut self, path: &str) -> Result<()> {
+        let path = crate::export::resolve_path(path);
+        let script = std::fs::read_to_string(&path)
+            .map_err(|e| anyhow::anyhow!("Could not read {}: {}", path.display(), e))?;
+        let conn_name = self
+            .active_connection_name()
+            .ok_or_else(|| anyhow::anyhow!("No connection selected"))?;
+        let db_type = self
+            .connection_tree
+            .iter()
+            .find(|item| item.connection_config.name == conn_name)
+            .map(|item| item.connection_config.db_type.clone())
+            .unwrap_or(DatabaseType::Postgres);
+        let statements = crate::database::core::split_statements(&script, &db_type);
+        if statements.is_empty() {
+            self.set_status_message(format!("No statements in {}", path.display()));
+            return Ok(());
+        }
+        // Refuse the whole script up front rather than stop half way through it
+        if let Some(index) = statements
+            .iter()
+            .position(|statement| self.blocks_statement(&conn_name, statement))
+        {
+            self.set_status_message(format!(
+                "Statement {}: {}",
+                index + 1,
+                crate::database::READ_ONLY_MESSAGE
+            ));
+            return Ok(());
+        }
+
+        self.ensure_alive(&conn_name).await?;
+        let timeout = self.statement_timeout_for(&conn_name);
+        let connection = self
+            .connection_manager
+            .get_connection(&conn_name)
+            .ok_or_else(|| anyhow::anyhow!("Connection '{}' is not connected", conn_name))?;
+        let mut last_rows = None;
+        for (index, statement) in statements.iter().enumerate() {
+            let executed_at = chrono::Local::now();
+            let started = std::time::Instant::now();
+            let executed = with_timeout(timeout, connection.execute_query(statement)).await;
+            self.query_log.record(
+                &conn_name,
+                QueryLogEntry {
+                    query: statement.clone(),
+                    executed_at,
+                    elapsed: started.elapsed(),
+                    row_count: executed.as_ref().ok().map(|result| result.rows.len()),
+                    error: executed.as_ref().err().map(|e| e.to_string()),
+                },
+            );
+            match executed {
+                Ok(result) if !result.columns.is_empty() => last_rows = Some(result),
+                Ok(_) => {}
+                Err(e) => {
+                    let first_line = statement.lines().next().unwrap_or_default();
+                    let preview: String = first_line.chars().take(60).collect();
+                    let cut = preview.len() < statement.len();
+                    let ellipsis = if cut { "..." } else { "" };
+                    self.set_status_message(format!(
+                        "Statement {} of {} failed ({} ran): {}{}: {}",
+                        index + 1,
+                        statements.len(),
+                        index,
+                        preview,
+                        ellipsis,
+                        e
+                    ));
+                    return Ok(());
+                }
+            }
+        }
+
+        let mut message = format!("Executed {} statements", statements.len());
+        if let Some(result) = last_rows {
+            message.push_str(&format!("; the last query returned {} rows", result.rows.len()));
+            self.open_adhoc_tab(&conn_name, result);
+        }
+        self.set_status_message(message);
+        Ok(())
+    }
+
+    /// The current tab's table, or the last table opened, with the connected database
+    fn table_for_metadata(&self) -> Result<(String, String, String, &dyn DatabaseConnection)> {
+        let (conn_name, schema, table) = self
+            .current_query_state()
+            .and_then(|state| state.table_info.clone())
+            .or_else(|| self.last_table_info.clone())
+            .ok_or_else(|| anyhow::anyhow!("No table selected"))?;
+        let connection = self
+            .connection_manager
+            .get_connection(&conn_name)
+            .ok_or_else(|| anyhow::anyhow!("Connection '{}' is not connected", conn_name))?;
+        Ok((conn_name, schema, table, connection.as_ref()))
+    }
+
+    /// Opens a tab listing the columns of the current tab's table, or of the last table
+    /// opened, with their types, nullability, defaults and key flags. Describing the same
+    /// table again refreshes its tab.
+    pub async fn describe_table(&mut self) -> Result<()> {
+        let (conn_name, schema, table, connection) = self.table_for_metadata()?;
+        let result = connection.describe_table(&schema, &table).await?;
+        let rows = result.rows.len();
+        let tab_name = format!("{}:{}.{} (structure)", conn_name, schema, table);
+        self.show_metadata_tab(tab_name, result);
+        self.set_status_message(format!("{} columns in {}.{}", rows, schema, table));
+        Ok(())
+    }
+
+    /// Table the current tab reads from with the parameters of its current page
+    fn current_table_query(&self) -> Result<(String, String, String, QueryParams)> {
+        let state = self
+            .current_query_state()
+            .ok_or_else(|| anyhow::anyhow!("No result tab"))?;
+        let (conn_name, schema, table) = state
+            .table_info
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Only table tabs have a query to explain"))?;
+        Ok((conn_name, schema, table, Self::page_params(state)))
+    }
+
+    /// Opens a tab with the plan of the query behind the current table tab: its SELECT with
+    /// the WHERE/ORDER BY and page. `analyze` runs the query, so it is only passed once the
+    /// user confirmed (see `prompt_explain_analyze`).
+    pub async fn explain_current_query(&mut self, analyze: bool) -> Result<()> {
+        let (conn_name, schema, table, params) = self.current_table_query()?;
+        self.ensure_alive(&conn_name).await?;
+        let connection = self
+            .connection_manager
+            .get_connection(&conn_name)
+            .ok_or_else(|| anyhow::anyhow!("Connection '{}' is not connected", conn_name))?;
+        let result = connection
+            .explain_query(&schema, &table, &params, analyze)
+            .await?;
+        let kind = if analyze { "analyze" } else { "plan" };
+        let tab_name = format!("{}:{}.{} ({})", conn_name, schema, table, kind);
+        self.show_metadata_tab(tab_name, result);
+        self.set_status_message(format!("Query plan for {}.{}", schema, table));
+        Ok(())
+    }
+
+    /// Asks before running EXPLAIN ANALYZE, which executes the current tab's query
+    pub fn prompt_explain_analyze(&mut self) {
+        match self.current_table_query() {
+            Ok((_, schema, table, _)) => {
+                let message = format!(
+                    "EXPLAIN ANALYZE runs the query behind {}.{} to measure it. Run it?",
+                    schema, table
+                );
+                self.modal_manager
+                    .push(Box::new(crate::ui::modals::ConfirmModal::new(
+                        "Explain Analyze",
+                        &message,
+                        "explain_analyze",
+                    )));
+            }
+            Err(e) => self.set_status_message(format!("Explain failed: {}", e)),
+        }
+    }
+
+    /// Opens a tab listing the indexes of the current tab's table, or of the last table opened
+    pub async fn list_indexes(&mut self) -> Result<()> {
+        let (conn_name, schema, table, connection) = self.table_for_metadata()?;
+        let result = connection.list_indexes(&schema, &table).await?;
+        let rows = result.rows.len();
+        let tab_name = format!("{}:{}.{} (indexes)", conn_name, schema, table);
+        self.show_metadata_tab(tab_name, result);
+        self.set_status_message(format!("{} indexes on {}.{}", rows, schema, table));
+        Ok(())
+    }
+
+    /// Shows `result` in the tab `tab_name`, replacing that tab's result if it is already open
+    fn show_metadata_tab(&mut self, tab_name: String, result: QueryResult) {
+        // Like ad-hoc results, metadata has no table behind it to edit or page through
+        let rows = result.rows.len();
+        let query_state = QueryState {
+            page_size: DEFAULT_PAGE_SIZE,
+            current_page: 1,
+            total_pages: Some(1),
+            total_records: Some(rows as u64),
+            ..QueryState::default()
+        };
+        match self.result_tabs.iter().position(|(tab, _, _)| tab == &tab_name) {
+            Some(index) => {
+                self.result_tabs[index] = (tab_name, result, query_state);
+                self.selected_result_tab_index = Some(index);
+            }
+            None => {
+                self.result_tabs.push((tab_name, result, query_state));
+                self.selected_result_tab_index = Some(self.result_tabs.len() - 1);
+            }
+        }
 
-    pub fn get_current_theme_name(&self) -> &str {
-        "default" // Placeholder - implement based on your theme system
+        self.cursor_position = (0, 0);
+        self.active_pane = Pane::Results;
     }
 
-    pub async fn first_page(&mut self) -> Result<()> {
-        self.mark_unimplemented("first_page");
-        Ok(())
+    /// Pulls the next older `:sql` statement from the history into `query`, for `:sql` to run
+    pub fn query_history_up(&mut self) {
+        let entry = self.query_history.previous().map(str::to_string);
+        self.show_history_entry(entry);
     }
 
-    pub async fn previous_page(&mut self) -> Result<()> {
-        self.mark_unimplemented("previous_page");
-        Ok(())
+    /// Pulls the next newer `:sql` statement from the history into `query`
+    pub fn query_history_down(&mut self) {
+        let entry = self.query_history.next().map(str::to_string);
+        self.show_history_entry(entry);
     }
 
-    pub async fn next_page(&mut self) -> Result<()> {
-        self.mark_unimplemented("next_page");
-        Ok(())
+    fn show_history_entry(&mut self, entry: Option<String>) {
+        let message = match entry {
+            Some(query) => {
+                let message = format!(
+                    "History {}/{}: {} (F9 or ':sql' runs it)",
+                    self.query_history.position().unwrap_or(1),
+                    self.query_history.len(),
+                    query.replace('\n', " ")
+                );
+                self.query = query;
+                message
+            }
+            None if self.query_history.is_empty() => "Query history is empty".to_string(),
+            None => "End of query history".to_string(),
+        };
+        self.set_status_message(message);
     }
 
-    pub async fn last_page(&mut self) -> Result<()> {
-        self.mark_unimplemented("last_page");
-        Ok(())
-    }
+    /// Opens the searchable list of `:sql` statements run so far
+    pub fn show_query_history(&mut self) {
+        use crate::ui::modals::QueryHistoryModal;
 
-    pub async fn confirm_deletions(&mut self) -> Result<()> {
-        self.mark_unimplemented("confirm_deletions");
-        Ok(())
+        let entries = self.query_history.entries();
+        self.modal_manager.push(Box::new(QueryHistoryModal::new(entries)));
     }
 
-    pub async fn connect_to_database(&mut self, _index: usize) -> Result<()> {
-        self.mark_unimplemented("connect_to_database");
-        Ok(())
+    /// Opens a popup with why the selected connection last failed to connect
+    pub fn show_connection_error(&mut self) {
+        use crate::ui::modals::ConnectionErrorModal;
+
+        let Some(index) = self.selected_tree_connection() else {
+            self.set_status_message("No connection selected".to_string());
+            return;
+        };
+        let connection = &self.connection_tree[index];
+        let name = &connection.connection_config.name;
+        match self.connection_failures.get(name) {
+            Some(failure) => {
+                let modal = ConnectionErrorModal::new(&connection.connection_config, failure);
+                self.modal_manager.push(Box::new(modal));
+            }
+            None => self.set_status_message(format!("{} has not failed to connect", name)),
+        }
     }
 
-    pub async fn run_query(&mut self) -> Result<()> {
-        self.mark_unimplemented("run_query");
-        Ok(())
+    /// Opens a popup with the full value of the cell under the cursor
+    pub fn show_cell_detail(&mut self) {
+        use crate::ui::modals::CellDetailModal;
+
+        let Some((_, result, _)) = self
+            .selected_result_tab_index
+            .and_then(|index| self.result_tabs.get(index))
+        else {
+            return;
+        };
+        let (col, row) = self.cursor_position;
+        let (Some(column), Some(value)) = (
+            result.columns.get(col),
+            result.rows.get(row).and_then(|row| row.get(col)),
+        ) else {
+            return;
+        };
+        let modal = CellDetailModal::new(column, value.as_deref());
+        self.modal_manager.push(Box::new(modal));
     }
 
     pub fn clear_query(&mut self) {
         self.query.clear();
     }
 
-    pub async fn save_query(&mut self) -> Result<()> {
-        self.mark_unimplemented("save_query");
+    /// Opens the command line prefilled with `savequery ` to name the query being saved
+    pub fn prompt_save_query(&mut self) {
+        self.input_mode = InputMode::Command;
+        self.command_input = "savequery ".to_string();
+        self.command_buffer.clear();
+        self.update_command_suggestions();
+        self.modal_manager
+            .push(Box::new(crate::ui::modals::CommandModal::new()));
+    }
+
+    /// Saves the query pane's WHERE/ORDER BY, the last `:sql` statement and the current tab's
+    /// table as `name` in `queries.toml`. An existing query is only replaced with `overwrite`.
+    pub fn save_query(&mut self, name: &str, overwrite: bool) -> Result<()> {
+        let table_info = self
+            .current_query_state()
+            .and_then(|state| state.table_info.clone())
+            .or_else(|| self.last_table_info.clone());
+        let (connection, schema, table) = match table_info {
+            Some((connection, schema, table)) => (Some(connection), Some(schema), Some(table)),
+            None => (self.active_connection_name(), None, None),
+        };
+        let query = SavedQuery {
+            name: name.to_string(),
+            query: self.query.clone(),
+            where_clause: self.query_input_pane.get_where_content(),
+            order_by: self.query_input_pane.get_order_by_content(),
+            connection,
+            schema,
+            table,
+        };
+
+        let message = if saved_queries::save(query, overwrite)? {
+            format!("Overwrote saved query '{}'", name)
+        } else {
+            format!("Saved query '{}'", name)
+        };
+        self.set_status_message(message);
+        Ok(())
+    }
+
+    /// Opens the list of saved queries to pick one to load
+    pub fn load_query(&mut self) -> Result<()> {
+        use crate::ui::modals::SavedQueriesModal;
+
+        let queries = saved_queries::load()?;
+        self.modal_manager.push(Box::new(SavedQueriesModal::new(queries)));
         Ok(())
     }
 
-    pub async fn load_query(&mut self) -> Result<()> {
-        self.mark_unimplemented("load_query");
+    /// Puts the saved query `name` back into the query pane, ready to run with Enter
+    pub fn apply_saved_query(&mut self, name: &str) -> Result<()> {
+        let query = saved_queries::load()?
+            .into_iter()
+            .find(|query| query.name == name)
+            .ok_or_else(|| anyhow::anyhow!("No saved query named '{}'", name))?;
+
+        self.query_input_pane.set_where_content(&query.where_clause);
+        self.query_input_pane.set_order_by_content(&query.order_by);
+        self.query = query.query.clone();
+        self.active_pane = Pane::QueryInput;
+        let message = match query.source_label() {
+            Some(source) => format!("Loaded query '{}' (saved from {})", name, source),
+            None => format!("Loaded query '{}'", name),
+        };
+        self.set_status_message(message);
         Ok(())
     }
 
+    /// Opens the list of the active key bindings, grouped by what they do
     pub fn show_help(&mut self) {
-        self.mark_unimplemented("show_help");
+        use crate::ui::modals::HelpModal;
+
+        let modal = HelpModal::new(&self.navigation_manager.config().key_mapping);
+        self.modal_manager.push(Box::new(modal));
     }
 
+    /// Toggles the deletion mark on the row under the cursor in the current tab
     pub fn toggle_row_deletion_mark(&mut self) {
-        self.mark_unimplemented("toggle_row_deletion_mark");
+        let row = self.cursor_position.1;
+        let Some((_, result, state)) = self
+            .selected_result_tab_index
+            .and_then(|idx| self.result_tabs.get_mut(idx))
+        else {
+            return;
+        };
+        if row < result.rows.len() {
+            if !state.rows_marked_for_deletion.remove(&row) {
+                state.rows_marked_for_deletion.insert(row);
+            }
+            let count = state.rows_marked_for_deletion.len();
+            self.set_status_message(format!("{} row(s) marked for deletion", count));
+        }
     }
 
     pub fn clear_deletion_marks(&mut self) {
-        self.mark_unimplemented("clear_deletion_marks");
+        if let Some(state) = self.current_query_state_mut() {
+            let count = state.rows_marked_for_deletion.len();
+            if count > 0 {
+                state.cleared_deletion_marks = std::mem::take(&mut state.rows_marked_for_deletion);
+                self.set_status_message(format!("Cleared {} deletion mark(s)", count));
+            }
+        }
+    }
+
+    /// Moves the cursor to the next (or previous) marked row, wrapping around
+    pub fn jump_to_marked_row(&mut self, forward: bool) {
+        let current = self.cursor_position.1;
+        let Some(state) = self.current_query_state() else {
+            return;
+        };
+        let mut marked: Vec<usize> = state.rows_marked_for_deletion.iter().copied().collect();
+        if marked.is_empty() {
+            self.set_status_message("No rows marked for deletion".to_string());
+            return;
+        }
+        marked.sort_unstable();
+
+        let target = if forward {
+            marked.iter().find(|&&row| row > current).or(marked.first())
+        } else {
+            marked.iter().rev().find(|&&row| row < current).or(marked.last())
+        };
+        if let Some(&row) = target {
+            self.cursor_position.1 = row;
+        }
     }
 
+    /// Runs the command in `command_buffer` through the command registry, reporting unknown
+    /// commands and failures in the status bar, and clears the buffer
     pub fn execute_command(&mut self) -> Result<()> {
-        self.mark_unimplemented("execute_command");
+        let command = self.command_buffer.as_str().to_string();
+        if command.is_empty() {
+            return Ok(());
+        }
+
+        match CommandProcessor::process_command(self) {
+            Ok(true) => {}
+            Ok(false) => self.set_status_message(format!("Unknown command: {}", command)),
+            Err(e) => {
+                logging::error(&format!("Error processing command: {}", e));
+                self.set_status_message(format!("Error: {}", e));
+            }
+        }
+        self.command_buffer.clear();
         Ok(())
     }
 
@@ -2491,40 +6531,149 @@ impl App {
         self.mark_unimplemented("command_history_down");
     }
 
-    pub fn cycle_suggestions(&mut self) {
-        self.mark_unimplemented("cycle_suggestions");
+    /// Cycles through command suggestions with wraparound, filling the command input
+    pub fn cycle_suggestions(&mut self, reverse: bool) {
+        let total = self.command_suggestions.len();
+        if total == 0 {
+            return;
+        }
+
+        let next = match self.selected_suggestion {
+            // The first Tab completes the already highlighted suggestion
+            Some(selected) if self.command_input != self.command_suggestions[selected] => selected,
+            Some(selected) if reverse => (selected + total - 1) % total,
+            Some(selected) => (selected + 1) % total,
+            None if reverse => total - 1,
+            None => 0,
+        };
+
+        self.selected_suggestion = Some(next);
+        self.update_scroll_offset();
+        self.command_input = self.command_suggestions[next].clone();
     }
 
+    /// Deletes the marked rows after confirming, or the cursor row when none are marked
     pub fn delete_selected_rows(&mut self) {
-        self.mark_unimplemented("delete_selected_rows");
+        let nothing_marked = self
+            .current_query_state()
+            .is_some_and(|state| state.rows_marked_for_deletion.is_empty());
+        if nothing_marked {
+            self.toggle_row_deletion_mark();
+        }
+        self.prompt_delete_marked_rows();
     }
 
+    /// Restores the marks removed by the last clear. Deletions are committed as soon as they
+    /// finish, so those can only be reported, not undone.
     pub fn undo_deletion(&mut self) {
-        self.mark_unimplemented("undo_deletion");
+        let Some(state) = self.current_query_state_mut() else {
+            return;
+        };
+        let message = if !state.cleared_deletion_marks.is_empty() {
+            let restored = std::mem::take(&mut state.cleared_deletion_marks);
+            let count = restored.len();
+            state.rows_marked_for_deletion.extend(restored);
+            format!("Restored {} deletion mark(s)", count)
+        } else if state.committed_deletions > 0 {
+            format!(
+                "{} deleted row(s) were committed and can't be undone",
+                state.committed_deletions
+            )
+        } else {
+            "Nothing to undo".to_string()
+        };
+        self.set_status_message(message);
+    }
+
+    /// Rows on the current page and whether the cursor is on its last row
+    fn cursor_page_bounds(&self) -> Option<(usize, bool)> {
+        let (_, result, _) = self
+            .selected_result_tab_index
+            .and_then(|index| self.result_tabs.get(index))?;
+        let rows = result.rows.len();
+        Some((rows, rows > 0 && self.cursor_position.1 + 1 >= rows))
     }
 
-    pub fn move_cursor_down(&mut self) {
-        self.mark_unimplemented("move_cursor_down");
+    // Results cursor movement; key bindings, navigation actions and counted motions (`3j`)
+    // all go through these so they share the same clamping
+
+    /// Moves down a row; from the last row of a page the next page is fetched and the cursor
+    /// lands on its first row
+    pub async fn move_cursor_down(&mut self) {
+        let (rows, at_bottom) = self.cursor_page_bounds().unwrap_or_default();
+        // Until the rows are counted, only a short page is known to be the last
+        let has_next_page = self.current_query_state().is_some_and(|state| {
+            state
+                .total_pages
+                .map_or(rows >= state.page_size as usize, |total| {
+                    state.current_page < total
+                })
+        });
+        if at_bottom && has_next_page {
+            if let Err(e) = self.next_page().await {
+                self.set_status_message(format!("Failed to load next page: {}", e));
+            }
+            return;
+        }
+        self.move_cursor_in_results(Direction::Down);
     }
 
-    pub fn move_cursor_up(&mut self) {
-        self.mark_unimplemented("move_cursor_up");
+    /// Moves up a row; from the first row of a page the previous page is fetched and the
+    /// cursor lands on its last row
+    pub async fn move_cursor_up(&mut self) {
+        let at_top = self.cursor_position.1 == 0;
+        let has_previous_page = self
+            .current_query_state()
+            .is_some_and(|state| state.current_page > 1);
+        if at_top && has_previous_page {
+            match self.previous_page().await {
+                Ok(()) => {
+                    let rows = self.cursor_page_bounds().map_or(0, |(rows, _)| rows);
+                    self.cursor_position.1 = rows.saturating_sub(1);
+                }
+                Err(e) => {
+                    self.set_status_message(format!("Failed to load previous page: {}", e));
+                }
+            }
+            return;
+        }
+        self.move_cursor_in_results(Direction::Up);
     }
 
     pub fn move_cursor_left(&mut self) {
-        self.mark_unimplemented("move_cursor_left");
+        self.move_cursor_in_results(Direction::Left);
     }
 
     pub fn move_cursor_right(&mut self) {
-        self.mark_unimplemented("move_cursor_right");
+        self.move_cursor_in_results(Direction::Right);
     }
 
-    pub fn page_down(&mut self) {
-        self.mark_unimplemented("page_down");
+    /// Moves down one screen of rows; from the last row of a page the next page is fetched.
+    /// On the last row of the last page this does nothing.
+    pub async fn page_down(&mut self) {
+        let Some((rows, at_bottom)) = self.cursor_page_bounds() else {
+            return;
+        };
+        if at_bottom {
+            self.move_cursor_down().await;
+            return;
+        }
+        let step = self.results_visible_rows.max(1);
+        self.cursor_position.1 = (self.cursor_position.1 + step).min(rows.saturating_sub(1));
     }
 
-    pub fn page_up(&mut self) {
-        self.mark_unimplemented("page_up");
+    /// Moves up one screen of rows; from the first row of a page the previous page is fetched
+    /// and the cursor lands on its last row
+    pub async fn page_up(&mut self) {
+        if self.cursor_page_bounds().is_none() {
+            return;
+        }
+        if self.cursor_position.1 == 0 {
+            self.move_cursor_up().await;
+            return;
+        }
+        let step = self.results_visible_rows.max(1);
+        self.cursor_position.1 = self.cursor_position.1.saturating_sub(step);
     }
 
     pub fn move_cursor_to_start(&mut self) {
@@ -2535,17 +6684,259 @@ impl App {
         self.mark_unimplemented("move_cursor_to_end");
     }
 
+    /// Switches to the next result tab, wrapping around to the first
     pub fn select_next_tab(&mut self) {
-        // Implementation for selecting next tab
+        self.step_result_tab(true);
     }
 
+    /// Switches to the previous result tab, wrapping around to the last
     pub fn select_previous_tab(&mut self) {
-        // Implementation for selecting previous tab
+        self.step_result_tab(false);
+    }
+
+    fn step_result_tab(&mut self, forward: bool) {
+        let count = self.result_tabs.len();
+        if count == 0 {
+            return;
+        }
+        let index = match self.selected_result_tab_index {
+            Some(index) if forward => (index + 1) % count,
+            Some(index) => (index + count - 1) % count,
+            None => 0,
+        };
+        self.select_tab(index);
+    }
+
+    /// Closes the selected result tab and selects its right neighbour (or the new last tab)
+    pub fn close_current_tab(&mut self) {
+        match self.selected_result_tab_index {
+            Some(index) => self.close_tab(index),
+            None => self.set_status_message("No result tab to close".to_string()),
+        }
+    }
+
+    /// Closes the result tab at `index`. Closing the selected tab moves the selection to its
+    /// neighbour; `last_table_info` is cleared when it pointed at the closed tab's table.
+    pub fn close_tab(&mut self, index: usize) {
+        if index >= self.result_tabs.len() {
+            return;
+        }
+        self.cancel_cell_edit();
+        let (name, _, state) = self.result_tabs.remove(index);
+        if state.table_info.is_some() && state.table_info == self.last_table_info {
+            self.last_table_info = None;
+        }
+        self.pinned_tab = match self.pinned_tab.take() {
+            Some(pinned) if pinned.index == index => {
+                self.split_results = false;
+                None
+            }
+            Some(pinned) if pinned.index > index => Some(PinnedTab {
+                index: pinned.index - 1,
+                ..pinned
+            }),
+            pinned => pinned,
+        };
+
+        match self.selected_result_tab_index {
+            _ if self.result_tabs.is_empty() => {
+                self.selected_result_tab_index = None;
+                self.cursor_position = (0, 0);
+            }
+            Some(selected) if selected == index => match self.pinned_tab.take() {
+                // Closing one half of the split leaves the other, with its cursor
+                Some(pinned) if self.split_results => {
+                    self.split_results = false;
+                    self.select_tab(pinned.index);
+                    self.cursor_position = pinned.cursor;
+                }
+                pinned => {
+                    self.pinned_tab = pinned;
+                    self.select_tab(index.min(self.result_tabs.len() - 1));
+                }
+            },
+            Some(selected) if selected > index => {
+                self.selected_result_tab_index = Some(selected - 1);
+            }
+            _ => {}
+        }
+        self.set_status_message(format!("Closed tab {}", name));
+    }
+
+    /// Opens a copy of the selected tab as the last tab and selects it, so its filter and sort
+    /// can be changed while the original stays as it is. The copy is named like the tab with
+    /// the first free ` (n)` suffix, counting from 2.
+    pub fn duplicate_current_tab(&mut self) {
+        let Some(index) = self.selected_result_tab_index else {
+            self.set_status_message("No result tab to duplicate".to_string());
+            return;
+        };
+        let (name, result, mut state) = self.result_tabs[index].clone();
+        // Marked rows belong to the original; deleting them from the copy would be a surprise
+        state.rows_marked_for_deletion.clear();
+        state.cleared_deletion_marks.clear();
+        state.committed_deletions = 0;
+
+        let base = crate::ui::split_duplicate_suffix(&name).map_or(name.as_str(), |(base, _)| base);
+        let mut copy = 2;
+        while self
+            .result_tabs
+            .iter()
+            .any(|(existing, _, _)| *existing == format!("{} ({})", base, copy))
+        {
+            copy += 1;
+        }
+        let copy_name = format!("{} ({})", base, copy);
+
+        let cursor = self.cursor_position;
+        self.result_tabs.push((copy_name.clone(), result, state));
+        self.select_tab(self.result_tabs.len() - 1);
+        self.cursor_position = cursor;
+        self.set_status_message(format!("Opened {}", copy_name));
+    }
+
+    /// Pins the selected tab as the one compared against in a split results pane, or unpins
+    /// it when it already is
+    pub fn pin_current_tab(&mut self) {
+        let Some(index) = self.selected_result_tab_index else {
+            self.set_status_message("No result tab to pin".to_string());
+            return;
+        };
+        let name = self.result_tabs[index].0.clone();
+        if self.pinned_tab.is_some_and(|pinned| pinned.index == index) {
+            self.pinned_tab = None;
+            self.split_results = false;
+            self.set_status_message(format!("Unpinned {}", name));
+            return;
+        }
+        self.pinned_tab = Some(PinnedTab {
+            index,
+            cursor: self.cursor_position,
+        });
+        self.split_results = false;
+        self.set_status_message(format!(
+            "Pinned {}; select another tab and split (Alt+v) to compare",
+            name
+        ));
+    }
+
+    /// Shows the selected tab and the pinned one side by side, or goes back to one tab. When
+    /// the pinned tab is the selected one, the next tab is selected to compare it with.
+    pub fn toggle_split_results(&mut self) {
+        if self.split_results {
+            self.split_results = false;
+            self.set_status_message("Split closed".to_string());
+            return;
+        }
+        let Some(pinned) = self.pinned_tab else {
+            self.set_status_message("Pin a tab to compare first (Alt+p or :pin)".to_string());
+            return;
+        };
+        if self.result_tabs.len() < 2 {
+            self.set_status_message("Open another tab to compare with the pinned one".to_string());
+            return;
+        }
+        if self.selected_result_tab_index == Some(pinned.index) {
+            self.select_tab((pinned.index + 1) % self.result_tabs.len());
+        }
+        self.split_results = true;
+        // The pinned tab keeps the left half when the split opens
+        self.split_focus_right = true;
+        self.set_status_message("Comparing with the pinned tab; Alt+o switches halves".to_string());
     }
 
+    /// Switches the selected tab between the grid and the record view of the cursor's row
+    pub fn toggle_record_view(&mut self) {
+        let Some(index) = self.selected_result_tab_index else {
+            self.set_status_message("No result tab to show as a record".to_string());
+            return;
+        };
+        let state = &mut self.result_tabs[index].2;
+        state.record_view = !state.record_view;
+        let message = if state.record_view {
+            "Record view; j/k move between rows, Alt+x goes back to the grid"
+        } else {
+            "Grid view"
+        };
+        self.set_status_message(message.to_string());
+    }
+
+    /// Switches whether the selected tab wraps the long values of the cursor's row
+    pub fn toggle_cell_wrap(&mut self) {
+        let Some(index) = self.selected_result_tab_index else {
+            self.set_status_message("No result tab to wrap".to_string());
+            return;
+        };
+        let state = &mut self.result_tabs[index].2;
+        state.wrap_cells = !state.wrap_cells;
+        let message = if state.wrap_cells {
+            "Wrapping the cursor's row; Alt+z cuts long values again"
+        } else {
+            "Long values are cut to their column"
+        };
+        self.set_status_message(message.to_string());
+    }
+
+    /// Whether the results pane is split between the selected tab and a different pinned one
+    pub fn split_active(&self) -> bool {
+        self.split_results
+            && self
+                .pinned_tab
+                .is_some_and(|pinned| Some(pinned.index) != self.selected_result_tab_index)
+    }
+
+    /// Moves focus to the other half of the split: the pinned tab becomes the selected one
+    /// and the selected one stays drawn as the pinned tab, each keeping its cursor
+    pub fn focus_other_split(&mut self) {
+        if !self.split_active() {
+            self.set_status_message("The results pane is not split".to_string());
+            return;
+        }
+        let (Some(pinned), Some(selected)) = (self.pinned_tab, self.selected_result_tab_index)
+        else {
+            return;
+        };
+        self.cancel_cell_edit();
+        self.pinned_tab = Some(PinnedTab {
+            index: selected,
+            cursor: self.cursor_position,
+        });
+        self.selected_result_tab_index = Some(pinned.index);
+        self.cursor_position = pinned.cursor;
+        self.split_focus_right = !self.split_focus_right;
+        if let Some(info) = self.result_tabs[pinned.index].2.table_info.clone() {
+            self.last_table_info = Some(info);
+        }
+        if self.result_tabs[pinned.index].2.needs_fetch {
+            self.reload_requested = true;
+        }
+    }
+
+    /// Rows marked for deletion in the current tab, in row order, after a header row with the
+    /// column names. NULL cells read `NULL`.
     pub fn get_deletion_preview(&self) -> Option<Vec<Vec<String>>> {
-        // Implementation for getting deletion preview
-        Some(vec![vec!["No items selected for deletion".to_string()]])
+        let placeholder = vec![vec!["No items selected for deletion".to_string()]];
+        let Some((_, result, state)) = self
+            .selected_result_tab_index
+            .and_then(|index| self.result_tabs.get(index))
+        else {
+            return Some(placeholder);
+        };
+        if state.rows_marked_for_deletion.is_empty() {
+            return Some(placeholder);
+        }
+
+        let mut marked: Vec<usize> = state.rows_marked_for_deletion.iter().copied().collect();
+        marked.sort_unstable();
+        let rows = marked
+            .iter()
+            .filter_map(|&index| result.rows.get(index))
+            .map(|row| {
+                row.iter()
+                    .map(|cell| cell.as_deref().unwrap_or("NULL").to_string())
+                    .collect()
+            });
+        Some(std::iter::once(result.columns.clone()).chain(rows).collect())
     }
 
     pub fn highlight_selected_item(&self, visible_index: usize) -> bool {
@@ -2582,3 +6973,386 @@ impl App {
         }
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::sync::OnceLock;
+
+    /// Rows of the `users` table `mock_app` serves
+    pub(crate) const USERS: usize = 25;
+
+    /// An `App` with its config in a temporary home and one saved Mock connection, `demo`,
+    /// serving a `users` table: `id` 1 to `USERS`, `name`, `team` (`a` for odd ids, `b` for
+    /// even ones) and `score`, NULL for every fifth id
+    pub(crate) fn mock_app() -> App {
+        static HOME: OnceLock<tempfile::TempDir> = OnceLock::new();
+        let home = HOME.get_or_init(|| {
+            let home = tempfile::tempdir().expect("temporary home");
+            std::env::set_var("HOME", home.path());
+            let users: Vec<serde_json::Value> = (1..=USERS)
+                .map(|id| {
+                    serde_json::json!({
+                        "id": id,
+                        "name": format!("user{}", id),
+                        "team": if id % 2 == 1 { "a" } else { "b" },
+                        "score": if id % 5 == 0 { None } else { Some(id * 10) },
+                    })
+                })
+                .collect();
+            let data = serde_json::json!({ "users": users });
+            std::fs::write(home.path().join("mock.json"), data.to_string()).expect("mock data");
+            home
+        });
+
+        let mut app = App::new();
+        let config = ConnectionConfig {
+            name: "demo".to_string(),
+            db_type: DatabaseType::Mock,
+            host: home.path().join("mock.json").to_string_lossy().to_string(),
+            ..ConnectionConfig::default()
+        };
+        app.saved_connections = vec![config.clone()];
+        app.connection_tree = vec![ConnectionTreeItem {
+            connection_config: config,
+            status: ConnectionStatus::NotConnected,
+            databases: Vec::new(),
+            is_expanded: false,
+        }];
+        app
+    }
+
+    /// `mock_app` with the tree expanded down to `users` and that table opened
+    pub(crate) async fn mock_app_with_users() -> App {
+        let mut app = mock_app();
+        app.expand_connection(0).await.expect("expand connection");
+        app.expand_database(0, 0).await.expect("expand database");
+        app.expand_schema(0, 0, 0).await.expect("expand schema");
+        // demo, mock, main, users
+        app.toggle_tree_item(3, true).await.expect("open users");
+        app
+    }
+
+    /// Values of column `column` on the current tab's page
+    pub(crate) fn column_values(app: &App, column: &str) -> Vec<Option<String>> {
+        let (_, result, _) = &app.result_tabs[app.selected_result_tab_index.expect("a tab")];
+        let index = result
+            .columns
+            .iter()
+            .position(|name| name == column)
+            .expect("column");
+        result.rows.iter().map(|row| row[index].clone()).collect()
+    }
+
+    fn database_names(app: &App) -> Vec<String> {
+        app.connection_tree[0]
+            .databases
+            .iter()
+            .map(|database| database.name.clone())
+            .collect()
+    }
+
+    fn prefetched(databases: &[&str]) -> PrefetchResult {
+        PrefetchResult::Success(
+            "demo".to_string(),
+            PrefetchedStructure {
+                connection_name: "demo".to_string(),
+                databases: databases
+                    .iter()
+                    .map(|name| PrefetchedDatabase {
+                        name: name.to_string(),
+                        schemas: Vec::new(),
+                    })
+                    .collect(),
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn expand_waits_for_the_background_prefetch_in_flight() {
+        let mut app = mock_app();
+        let (sender, receiver) = mpsc::unbounded_channel();
+        app.prefetch_receiver = Some(receiver);
+        app.prefetch_in_flight.insert("demo".to_string());
+
+        // The prefetch completes while the expansion is waiting for it
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            let _ = sender.send(prefetched(&["mock"]));
+        });
+        app.expand_connection(0).await.unwrap();
+
+        assert!(app.prefetch_in_flight.is_empty());
+        assert!(app.connection_tree[0].is_expanded);
+        assert_eq!(database_names(&app), ["mock"]);
+        // The prefetched databases were used instead of connecting a second time
+        assert!(app.connection_manager.get_connection("demo").is_none());
+    }
+
+    #[tokio::test]
+    async fn late_prefetch_leaves_the_expanded_tree_alone() {
+        let mut app = mock_app_with_users().await;
+        app.prefetch_in_flight.insert("demo".to_string());
+
+        app.apply_prefetch_result(prefetched(&["stale"]));
+
+        assert!(app.prefetch_in_flight.is_empty());
+        assert_eq!(database_names(&app), ["mock"]);
+        assert_eq!(
+            app.connection_tree[0].databases[0].schemas[0].tables,
+            ["users"]
+        );
+    }
+
+    fn current_page(app: &App) -> u32 {
+        app.current_query_state().expect("a tab").current_page
+    }
+
+    #[tokio::test]
+    async fn cursor_clamps_at_the_edges_and_crosses_pages() {
+        let mut app = mock_app_with_users().await;
+        app.set_page_size(10).await.unwrap();
+        let last_column = app.result_tabs[0].1.columns.len() - 1;
+
+        // Top-left corner of the first page
+        app.move_cursor_up().await;
+        app.move_cursor_left();
+        assert_eq!((current_page(&app), app.cursor_position), (1, (0, 0)));
+        for _ in 0..=last_column {
+            app.move_cursor_right();
+        }
+        assert_eq!(app.cursor_position.0, last_column);
+        app.cursor_position.0 = 0;
+
+        // Down off the bottom row fetches the next page and lands on its first row
+        for _ in 0..9 {
+            app.move_cursor_down().await;
+        }
+        assert_eq!((current_page(&app), app.cursor_position.1), (1, 9));
+        app.move_cursor_down().await;
+        assert_eq!((current_page(&app), app.cursor_position.1), (2, 0));
+        assert_eq!(column_values(&app, "id")[0].as_deref(), Some("11"));
+
+        // Up off the top row goes back to the previous page's last row
+        app.move_cursor_up().await;
+        assert_eq!((current_page(&app), app.cursor_position.1), (1, 9));
+
+        // The last page is short and the cursor stops on its last row
+        app.last_page().await.unwrap();
+        assert_eq!(current_page(&app), 3);
+        assert_eq!(column_values(&app, "id").len(), USERS - 20);
+        for _ in 0..10 {
+            app.move_cursor_down().await;
+        }
+        assert_eq!((current_page(&app), app.cursor_position.1), (3, 4));
+    }
+
+    #[tokio::test]
+    async fn where_placeholders_are_prompted_for_and_bound() {
+        use crate::navigation::NavigationInputHandler;
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        let mut app = mock_app_with_users().await;
+        app.current_query_state_mut().unwrap().where_clause =
+            "team = $1 AND score >= $2".to_string();
+        app.execute_query_input().await.unwrap();
+        assert_eq!(
+            app.modal_manager.get_active_title().as_deref(),
+            Some("Query Parameters")
+        );
+
+        let keys = [
+            KeyCode::Char('b'),
+            KeyCode::Tab,
+            KeyCode::Char('1'),
+            KeyCode::Char('0'),
+            KeyCode::Char('0'),
+            KeyCode::Enter,
+        ];
+        for key in keys {
+            NavigationInputHandler::handle_key(key, KeyModifiers::NONE, &mut app)
+                .await
+                .unwrap();
+        }
+
+        assert!(!app.modal_manager.has_modals());
+        assert_eq!(
+            app.current_query_state().unwrap().where_params,
+            ["b", "100"]
+        );
+        // Even ids from 10 on, less those with a NULL score
+        let ids: Vec<String> = column_values(&app, "id").into_iter().flatten().collect();
+        assert_eq!(ids, ["12", "14", "16", "18", "22", "24"]);
+    }
+
+    #[test]
+    fn logged_parameters_stay_inside_their_comment() {
+        let params = QueryParams {
+            where_clause: Some("name = $1".to_string()),
+            order_by: None,
+            limit: None,
+            offset: None,
+            base_query: None,
+            where_params: vec!["x'\nDROP TABLE users".to_string()],
+        };
+        let entry = App::query_log_entry(
+            "main",
+            "users",
+            &params,
+            chrono::Local::now(),
+            std::time::Instant::now(),
+            &Err(anyhow::anyhow!("timed out")),
+        );
+        assert_eq!(
+            entry.query,
+            "SELECT * FROM main.users WHERE name = $1 -- $1 = 'x''\\nDROP TABLE users'"
+        );
+    }
+
+    #[tokio::test]
+    async fn mock_table_opens_filters_and_pages() {
+        let mut app = mock_app_with_users().await;
+        let state = app.current_query_state().unwrap();
+        assert_eq!(state.total_records, Some(USERS as u64));
+        assert_eq!(column_values(&app, "name")[0].as_deref(), Some("user1"));
+        assert_eq!(column_values(&app, "score")[4], None);
+
+        app.set_page_size(10).await.unwrap();
+        app.current_query_state_mut().unwrap().where_clause = "team = 'a'".to_string();
+        app.execute_query_input().await.unwrap();
+        let state = app.current_query_state().unwrap();
+        assert_eq!(
+            (state.total_records, state.total_pages),
+            (Some(13), Some(2))
+        );
+
+        app.next_page().await.unwrap();
+        let ids: Vec<String> = column_values(&app, "id").into_iter().flatten().collect();
+        assert_eq!(ids, ["21", "23", "25"]);
+        app.next_page().await.unwrap();
+        assert_eq!(app.current_query_state().unwrap().current_page, 2);
+    }
+
+    #[tokio::test]
+    async fn large_tables_warn_once_their_rows_are_counted() {
+        let mut app = mock_app();
+        app.config.database.large_table_rows = 20;
+        app.expand_connection(0).await.unwrap();
+        app.expand_database(0, 0).await.unwrap();
+        app.expand_schema(0, 0, 0).await.unwrap();
+        app.toggle_tree_item(3, true).await.unwrap();
+        // Mock connections can't share themselves, so they count inline
+        assert!(app
+            .status_message
+            .as_deref()
+            .is_some_and(|message| message.contains("has 25 rows")));
+
+        let tab_name = app.result_tabs[0].0.clone();
+        let state = app.current_query_state_mut().unwrap();
+        (state.total_records, state.total_pages) = (None, None);
+        app.spawn_row_count(tab_name, None, Box::pin(async { Ok(1000) }));
+        while app.current_query_state().unwrap().total_records.is_none() {
+            tokio::task::yield_now().await;
+            app.check_background_row_counts();
+        }
+        assert_eq!(app.current_query_state().unwrap().total_pages, Some(20));
+    }
+
+    #[test]
+    fn connection_form_saves_the_ssh_passphrase_and_agent() {
+        let mut app = mock_app();
+        app.config.database.use_keyring = false;
+        app.submit_connection_form(ConnectionForm {
+            name: "tunnelled".to_string(),
+            db_type: DatabaseType::Mock,
+            ssh_enabled: true,
+            ssh_host: "bastion".to_string(),
+            ssh_port: "2222".to_string(),
+            ssh_passphrase: "hunter2".to_string(),
+            ssh_use_agent: true,
+            ..ConnectionForm::default()
+        });
+
+        let ssh = app.saved_connections[1]
+            .ssh_tunnel
+            .clone()
+            .expect("a tunnel");
+        assert_eq!(ssh.port, 2222);
+        assert_eq!(ssh.passphrase.as_deref(), Some("hunter2"));
+        assert_eq!(ssh.password, None);
+        assert!(ssh.use_agent);
+    }
+
+    fn run_command(app: &mut App, command: &str) {
+        command.chars().for_each(|c| app.command_buffer.push(c));
+        app.execute_command().unwrap();
+        assert!(app.command_buffer.is_empty());
+    }
+
+    #[tokio::test]
+    async fn execute_command_runs_known_commands_and_reports_unknown_ones() {
+        let mut app = mock_app_with_users().await;
+
+        run_command(&mut app, "help");
+        assert_eq!(
+            app.modal_manager.get_active_title().as_deref(),
+            Some("Key Bindings")
+        );
+        app.modal_manager.close_active();
+
+        run_command(&mut app, "3j");
+        assert_eq!(app.cursor_position.1, 3);
+
+        run_command(&mut app, "frobnicate");
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Unknown command: frobnicate")
+        );
+        assert!(!app.should_quit);
+    }
+
+    #[tokio::test]
+    async fn appended_cell_filters_keep_the_existing_clause_whole() {
+        let mut app = mock_app_with_users().await;
+        let columns = app.result_tabs[0].1.columns.clone();
+        let column = |name: &str| columns.iter().position(|c| c == name).unwrap();
+
+        // user1 is on team a
+        app.cursor_position = (column("team"), 0);
+        app.filter_by_cell(false, false).await.unwrap();
+        app.cursor_position = (column("id"), 1);
+        app.filter_by_cell(true, true).await.unwrap();
+
+        assert_eq!(
+            app.current_query_state().unwrap().where_clause,
+            "(team = 'a') AND id != '3'"
+        );
+        let ids: Vec<String> = column_values(&app, "id").into_iter().flatten().collect();
+        assert_eq!(ids[..3], ["1", "5", "7"]);
+
+        // MySQL reads backslashes in literals as escapes
+        assert_eq!(
+            crate::database::core::cell_predicate(&DatabaseType::MySQL, "path", Some("C:\\"), true),
+            "`path` != 'C:\\\\'"
+        );
+    }
+
+    #[tokio::test]
+    async fn f9_in_the_query_pane_runs_the_statement() {
+        use crate::navigation::NavigationInputHandler;
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        let mut app = mock_app_with_users().await;
+        app.active_pane = Pane::QueryInput;
+        app.query = "SELECT 1".to_string();
+        NavigationInputHandler::handle_key(KeyCode::F(9), KeyModifiers::NONE, &mut app)
+            .await
+            .unwrap();
+
+        // The mock can't run SQL, which shows the statement reached it
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Query failed: Mock connections can only browse tables, not run `SELECT 1`")
+        );
+    }
+}
@@ -1,7 +1,19 @@
 use crate::app::App;
-use crate::ui::types::Direction;
+use crate::database::core::NullsOrder;
+use crate::export::ExportFormat;
 use anyhow::Result;
 
+const EXPORT_USAGE: &str =
+    "Usage: export csv [--visible] <path> | export json [--typed] [--visible] <path>";
+const PAGE_SIZE_USAGE: &str = "Usage: pagesize <rows>";
+const AUTO_REFRESH_USAGE: &str = "Usage: autorefresh <seconds>|off";
+const EXPORT_CONNECTIONS_USAGE: &str = "Usage: export connections [--with-passwords] <path>";
+const IMPORT_CONNECTIONS_USAGE: &str = "Usage: import connections [--replace] <path>";
+const RUN_FILE_USAGE: &str = "Usage: run file <path>";
+const PAGE_USAGE: &str = "Usage: page <n>";
+const ROW_USAGE: &str = "Usage: row <n>";
+const FORMAT_USAGE: &str = "Usage: format <column> thousands|decimals <n>|left|center|right|clear";
+
 /// Simple fuzzy matching function
 /// Returns true if the pattern can be found in the text with characters in order
 fn fuzzy_match(pattern: &str, text: &str) -> bool {
@@ -81,16 +93,30 @@ impl CommandRegistry {
             },
         ));
 
+        // Help commands
+        self.register(Command::new(
+            "help",
+            "List the key bindings (also '?' or F1)",
+            |app| {
+                app.show_help();
+                Ok(())
+            },
+        ));
+
         // Quit commands
         self.register(Command::new("quit", "Quit the application", |app| {
             app.quit();
-            app.set_status_message("Quitting...".to_string());
+            if app.should_quit {
+                app.set_status_message("Quitting...".to_string());
+            }
             Ok(())
         }));
 
         self.register(Command::new("q", "Quit the application", |app| {
             app.quit();
-            app.set_status_message("Quitting...".to_string());
+            if app.should_quit {
+                app.set_status_message("Quitting...".to_string());
+            }
             Ok(())
         }));
 
@@ -104,6 +130,419 @@ impl CommandRegistry {
                 Ok(())
             },
         ));
+
+        self.register(Command::new(
+            "connect",
+            "Connect the selected connection without expanding it (use 'connect [<name>]')",
+            |app| match app.selected_tree_connection() {
+                Some(index) => futures::executor::block_on(app.connect_to_database(index)),
+                None => {
+                    app.set_status_message("No connection selected".to_string());
+                    Ok(())
+                }
+            },
+        ));
+
+        self.register(Command::new(
+            "reconnect",
+            "Close and reopen the selected connection and its tunnel (use 'reconnect [<name>]')",
+            |app| match app.selected_tree_connection() {
+                Some(index) => futures::executor::block_on(app.reconnect_connection(index)),
+                None => {
+                    app.set_status_message("No connection selected".to_string());
+                    Ok(())
+                }
+            },
+        ));
+
+        self.register(Command::new(
+            "refresh tree",
+            "Reload the selected connection's databases, schemas and tables (also Ctrl+r)",
+            |app| futures::executor::block_on(app.refresh_tree(false)),
+        ));
+
+        self.register(Command::new(
+            "refresh tree all",
+            "Reload the databases, schemas and tables of every connected connection",
+            |app| futures::executor::block_on(app.refresh_tree(true)),
+        ));
+
+        self.register(Command::new(
+            "connerror",
+            "Show why the selected connection last failed to connect (also Alt+e)",
+            |app| {
+                app.show_connection_error();
+                Ok(())
+            },
+        ));
+
+        self.register(Command::new(
+            "use",
+            "Reconnect the current connection to another database (use 'use <database>')",
+            |app| {
+                app.set_status_message("Usage: use <database>".to_string());
+                Ok(())
+            },
+        ));
+
+        // Transaction commands
+        self.register(Command::new(
+            "begin",
+            "Open a transaction on the current connection; edits and deletions wait for commit",
+            |app| {
+                if let Err(e) = futures::executor::block_on(app.begin_transaction()) {
+                    app.set_status_message(format!("Begin failed: {}", e));
+                }
+                Ok(())
+            },
+        ));
+
+        self.register(Command::new(
+            "commit",
+            "Commit the transaction opened with 'begin'",
+            |app| {
+                if let Err(e) = futures::executor::block_on(app.end_transaction(true)) {
+                    app.set_status_message(format!("Commit failed: {}", e));
+                }
+                Ok(())
+            },
+        ));
+
+        self.register(Command::new(
+            "rollback",
+            "Undo everything since 'begin' and close the transaction",
+            |app| {
+                if let Err(e) = futures::executor::block_on(app.end_transaction(false)) {
+                    app.set_status_message(format!("Rollback failed: {}", e));
+                }
+                Ok(())
+            },
+        ));
+
+        // Sorting commands
+        self.register(Command::new(
+            "nulls",
+            "Set NULL ordering for the current sort (use 'nulls first|last|default')",
+            |_app| {
+                // This is handled specially for the ordering parameter
+                Ok(())
+            },
+        ));
+
+        // Result search commands
+        self.register(Command::new(
+            "find",
+            "Search loaded results (use 'find <text>', 'find' alone clears)",
+            |app| {
+                app.search_results("");
+                Ok(())
+            },
+        ));
+
+        // Filter commands
+        self.register(Command::new(
+            "filter",
+            "Filter the current table to rows whose chosen column contains some text (also Alt+f)",
+            |app| app.open_quick_filter(),
+        ));
+
+        // Paging commands
+        self.register(Command::new(
+            "pagesize",
+            "Set the rows per page of the current tab (use 'pagesize <n>', at most 1000)",
+            |app| {
+                app.set_status_message(PAGE_SIZE_USAGE.to_string());
+                Ok(())
+            },
+        ));
+
+        self.register(Command::new(
+            "page",
+            "Go to a page of the current tab (use 'page <n>')",
+            |app| {
+                app.set_status_message(PAGE_USAGE.to_string());
+                Ok(())
+            },
+        ));
+
+        self.register(Command::new(
+            "row",
+            "Move to a row of the current tab, counted across pages (use 'row <n>')",
+            |app| {
+                app.set_status_message(ROW_USAGE.to_string());
+                Ok(())
+            },
+        ));
+
+        self.register(Command::new(
+            "autorefresh",
+            "Re-fetch the current tab periodically (use 'autorefresh <seconds>|off')",
+            |app| {
+                app.set_status_message(AUTO_REFRESH_USAGE.to_string());
+                Ok(())
+            },
+        ));
+
+        self.register(Command::new(
+            "autorefresh off",
+            "Stop re-fetching the current tab periodically",
+            |app| {
+                app.set_auto_refresh(None);
+                Ok(())
+            },
+        ));
+
+        // Column visibility commands
+        self.register(Command::new(
+            "showcol",
+            "Reveal a hidden column, or list them (use 'showcol <column>|all')",
+            |app| {
+                app.reveal_column("");
+                Ok(())
+            },
+        ));
+
+        self.register(Command::new(
+            "hidecol",
+            "Hide the cursor's column; 'showcol all' shows it again (also Alt+h)",
+            |app| {
+                app.hide_cursor_column();
+                Ok(())
+            },
+        ));
+
+        // Clipboard commands
+        self.register(Command::new(
+            "copyinsert",
+            "Copy an INSERT for the current row, or one per marked row (also Alt+i)",
+            |app| app.copy_insert_statements(),
+        ));
+
+        // Column format commands
+        self.register(Command::new(
+            "format",
+            "Group digits, round or align a column of the current tab \
+             (use 'format <column> thousands|decimals <n>|left|center|right|clear')",
+            |app| {
+                app.set_status_message(FORMAT_USAGE.to_string());
+                Ok(())
+            },
+        ));
+
+        // Query log commands
+        self.register(Command::new(
+            "querylog",
+            "Show queries run on the current connection (use 'querylog clear' to reset)",
+            |app| {
+                app.show_query_log();
+                Ok(())
+            },
+        ));
+
+        self.register(Command::new(
+            "querylog clear",
+            "Clear the current connection's query log",
+            |app| {
+                app.clear_query_log();
+                Ok(())
+            },
+        ));
+
+        // Base query commands
+        self.register(Command::new(
+            "openquery",
+            "Browse a SELECT like a table (use 'openquery <name> [SELECT ...]')",
+            |_app| {
+                // This is handled specially for the name and query parameters
+                Ok(())
+            },
+        ));
+
+        // Export commands
+        self.register(Command::new(
+            "export",
+            "Write every row of the current tab to a file (use 'export csv|json [--typed] <path>')",
+            |app| {
+                app.set_status_message(EXPORT_USAGE.to_string());
+                Ok(())
+            },
+        ));
+
+        // Connection sharing commands
+        self.register(Command::new(
+            "export connections",
+            "Write the saved connections to a TOML or JSON file without passwords \
+             (use 'export connections [--with-passwords] <path>')",
+            |app| {
+                app.set_status_message(EXPORT_CONNECTIONS_USAGE.to_string());
+                Ok(())
+            },
+        ));
+
+        self.register(Command::new(
+            "import connections",
+            "Add the connections of an exported file, keeping existing ones with the same name \
+             (use 'import connections [--replace] <path>')",
+            |app| {
+                app.set_status_message(IMPORT_CONNECTIONS_USAGE.to_string());
+                Ok(())
+            },
+        ));
+
+        // Ad-hoc query commands
+        self.register(Command::new(
+            "sql",
+            "Run a statement on the current connection (use 'sql <statement>', 'sql' reruns it)",
+            |app| {
+                if let Err(e) = futures::executor::block_on(app.run_query()) {
+                    app.set_status_message(format!("Query failed: {}", e));
+                }
+                Ok(())
+            },
+        ));
+
+        self.register(Command::new(
+            "more",
+            "Fetch the next rows of a 'sql' result cut at max_adhoc_rows (also Alt+m)",
+            |app| {
+                if let Err(e) = futures::executor::block_on(app.load_more_rows()) {
+                    app.set_status_message(format!("Loading more rows failed: {}", e));
+                }
+                Ok(())
+            },
+        ));
+
+        self.register(Command::new(
+            "run file",
+            "Run the statements of a SQL script in order, stopping at the first error \
+             (use 'run file <path>')",
+            |app| {
+                app.set_status_message(RUN_FILE_USAGE.to_string());
+                Ok(())
+            },
+        ));
+
+        self.register(Command::new(
+            "history",
+            "Search the statements run with 'sql' and run one again",
+            |app| {
+                app.show_query_history();
+                Ok(())
+            },
+        ));
+
+        // Table structure commands
+        self.register(Command::new(
+            "describe",
+            "Open a tab with the current table's columns, types, defaults and keys",
+            |app| {
+                if let Err(e) = futures::executor::block_on(app.describe_table()) {
+                    app.set_status_message(format!("Describe failed: {}", e));
+                }
+                Ok(())
+            },
+        ));
+
+        self.register(Command::new(
+            "indexes",
+            "Open a tab with the current table's indexes, their columns, uniqueness and type",
+            |app| {
+                if let Err(e) = futures::executor::block_on(app.list_indexes()) {
+                    app.set_status_message(format!("Listing indexes failed: {}", e));
+                }
+                Ok(())
+            },
+        ));
+
+        // Query plan commands
+        self.register(Command::new(
+            "explain",
+            "Open a tab with the plan of the current tab's query (PostgreSQL)",
+            |app| {
+                if let Err(e) = futures::executor::block_on(app.explain_current_query(false)) {
+                    app.set_status_message(format!("Explain failed: {}", e));
+                }
+                Ok(())
+            },
+        ));
+
+        self.register(Command::new(
+            "explain analyze",
+            "Run the current tab's query and show its plan with actual timings, after confirming",
+            |app| {
+                app.prompt_explain_analyze();
+                Ok(())
+            },
+        ));
+
+        // Saved query commands
+        self.register(Command::new(
+            "savequery",
+            "Save the current query for later sessions (use 'savequery <name>', '!' overwrites)",
+            |app| {
+                app.prompt_save_query();
+                Ok(())
+            },
+        ));
+
+        self.register(Command::new(
+            "loadquery",
+            "Load a saved query into the query pane (use 'loadquery [<name>]')",
+            |app| app.load_query(),
+        ));
+
+        // Comparison commands
+        self.register(Command::new(
+            "dup",
+            "Open a copy of the current tab to filter or sort apart from it (also Alt+t)",
+            |app| {
+                app.duplicate_current_tab();
+                Ok(())
+            },
+        ));
+
+        self.register(Command::new(
+            "pin",
+            "Pin the current tab to compare it side by side with another ('pin' again unpins)",
+            |app| {
+                app.pin_current_tab();
+                Ok(())
+            },
+        ));
+
+        self.register(Command::new(
+            "split",
+            "Show the current tab and the pinned tab side by side, or close the split",
+            |app| {
+                app.toggle_split_results();
+                Ok(())
+            },
+        ));
+
+        // Record view commands
+        self.register(Command::new(
+            "record",
+            "Show the current row as one column per line, or go back to the grid (also Alt+x)",
+            |app| {
+                app.toggle_record_view();
+                Ok(())
+            },
+        ));
+        self.register(Command::new(
+            "wrap",
+            "Wrap the long values of the current row, or cut them at the column width (also Alt+z)",
+            |app| {
+                app.toggle_cell_wrap();
+                Ok(())
+            },
+        ));
+
+        // Layout commands
+        self.register(Command::new(
+            "toggleLayout",
+            "Toggle stacked/split query and results layout",
+            |app| app.toggle_main_panel_layout(),
+        ));
     }
 
     pub fn register(&mut self, command: Command) {
@@ -139,6 +578,14 @@ impl CommandRegistry {
             }
         }
 
+        if let Some(query) = input_lower.strip_prefix("nulls ") {
+            for option in ["first", "last", "default"] {
+                if query.is_empty() || fuzzy_match(query, option) {
+                    suggestions.push(format!("nulls {}", option));
+                }
+            }
+        }
+
         suggestions.sort();
         suggestions
     }
@@ -150,20 +597,262 @@ impl CommandRegistry {
         if command_name_lower.starts_with("theme ")
             || command_name_lower.starts_with("switchtheme ")
         {
-            let theme_name = if command_name_lower.starts_with("theme ") {
-                command_name_lower.strip_prefix("theme ").unwrap_or("")
-            } else {
-                command_name_lower
-                    .strip_prefix("switchtheme ")
-                    .unwrap_or("")
-            };
+            // Custom theme files may use upper case, so the name keeps its case
+            let (_, theme_name) = command_name.split_once(' ').unwrap_or_default();
+            let theme_name = theme_name.trim();
             if !theme_name.is_empty() {
                 app.switch_theme(theme_name)?;
-                app.set_status_message(format!("Switched to theme: {}", theme_name));
                 return Ok(true);
             }
         }
 
+        // Handle result search; the pattern keeps its original case
+        if command_name_lower.starts_with("find ") {
+            app.search_results(command_name[5..].trim());
+            return Ok(true);
+        }
+
+        if let Some(size) = command_name_lower.strip_prefix("pagesize ") {
+            // Sizes too big for a u32 are clamped like any other oversized page
+            match size.trim().parse::<u64>() {
+                Ok(size) => {
+                    let size = u32::try_from(size).unwrap_or(u32::MAX);
+                    if let Err(e) = futures::executor::block_on(app.set_page_size(size)) {
+                        app.set_status_message(format!("Failed to set page size: {}", e));
+                    }
+                }
+                Err(_) => app.set_status_message(PAGE_SIZE_USAGE.to_string()),
+            }
+            return Ok(true);
+        }
+
+        if let Some(page) = command_name_lower.strip_prefix("page ") {
+            match page.trim().parse::<u64>() {
+                Ok(page) => {
+                    if let Err(e) = futures::executor::block_on(app.go_to_page_number(page)) {
+                        app.set_status_message(format!("Failed to load page: {}", e));
+                    }
+                }
+                Err(_) => app.set_status_message(PAGE_USAGE.to_string()),
+            }
+            return Ok(true);
+        }
+
+        if let Some(row) = command_name_lower.strip_prefix("row ") {
+            match row.trim().parse::<u64>() {
+                Ok(row) => {
+                    if let Err(e) = futures::executor::block_on(app.go_to_row(row)) {
+                        app.set_status_message(format!("Failed to load page: {}", e));
+                    }
+                }
+                Err(_) => app.set_status_message(ROW_USAGE.to_string()),
+            }
+            return Ok(true);
+        }
+
+        if let Some(interval) = command_name_lower.strip_prefix("autorefresh ") {
+            match interval.trim() {
+                "off" | "0" => app.set_auto_refresh(None),
+                seconds => match seconds.parse::<u64>() {
+                    Ok(seconds) => app.set_auto_refresh(Some(seconds)),
+                    Err(_) => app.set_status_message(AUTO_REFRESH_USAGE.to_string()),
+                },
+            }
+            return Ok(true);
+        }
+
+        // Handle connecting by name; names keep their original case
+        if command_name_lower.starts_with("connect ") {
+            let name = command_name[8..].trim();
+            match app
+                .connection_tree
+                .iter()
+                .position(|connection| connection.connection_config.name == name)
+            {
+                Some(index) => futures::executor::block_on(app.connect_to_database(index))?,
+                None => app.set_status_message(format!("No connection named '{}'", name)),
+            }
+            return Ok(true);
+        }
+
+        // Handle reconnecting by name; names keep their original case
+        if command_name_lower.starts_with("reconnect ") {
+            let name = command_name[10..].trim();
+            match app
+                .connection_tree
+                .iter()
+                .position(|connection| connection.connection_config.name == name)
+            {
+                Some(index) => futures::executor::block_on(app.reconnect_connection(index))?,
+                None => app.set_status_message(format!("No connection named '{}'", name)),
+            }
+            return Ok(true);
+        }
+
+        // Handle switching databases; database names keep their original case
+        if command_name_lower.starts_with("use ") {
+            let database = command_name[4..].trim();
+            if let Err(e) = futures::executor::block_on(app.use_database(database)) {
+                app.set_status_message(format!("Failed to switch database: {}", e));
+            }
+            return Ok(true);
+        }
+
+        // Handle column reveal; column names keep their original case
+        if command_name_lower.starts_with("showcol ") {
+            app.reveal_column(command_name[8..].trim());
+            return Ok(true);
+        }
+
+        // Handle column formats; column names keep their original case
+        if command_name_lower.starts_with("format ") {
+            let args = command_name[7..].trim();
+            match args.split_once(char::is_whitespace) {
+                Some((column, option)) => app.format_column(column, option.trim()),
+                None => app.set_status_message(FORMAT_USAGE.to_string()),
+            }
+            return Ok(true);
+        }
+
+        // Handle base queries; the SQL keeps its original case
+        if command_name_lower.starts_with("openquery ") {
+            let args = command_name[10..].trim();
+            let (name, query) = match args.split_once(char::is_whitespace) {
+                Some((name, query)) => (name, Some(query)),
+                None => (args, None),
+            };
+            if name.is_empty() {
+                app.set_status_message("Usage: openquery <name> [SELECT ...]".to_string());
+            } else if let Err(e) = futures::executor::block_on(app.open_base_query(name, query)) {
+                app.set_status_message(format!("Failed to open query: {}", e));
+            } else {
+                app.set_status_message(format!("Opened query '{}'", name));
+            }
+            return Ok(true);
+        }
+
+        // Handle sharing connections; paths keep their original case
+        if command_name_lower.starts_with("export connections ") {
+            let args = command_name[19..].trim();
+            let (include_passwords, path) = match args.strip_prefix("--with-passwords") {
+                Some(path) => (true, path.trim()),
+                None => (false, args),
+            };
+            if path.is_empty() {
+                app.set_status_message(EXPORT_CONNECTIONS_USAGE.to_string());
+            } else if let Err(e) = app.export_connections(path, include_passwords) {
+                app.set_status_message(format!("Export failed: {}", e));
+            }
+            return Ok(true);
+        }
+
+        if command_name_lower.starts_with("import connections ") {
+            let args = command_name[19..].trim();
+            let (replace, path) = match args.strip_prefix("--replace") {
+                Some(path) => (true, path.trim()),
+                None => (false, args),
+            };
+            if path.is_empty() {
+                app.set_status_message(IMPORT_CONNECTIONS_USAGE.to_string());
+            } else if let Err(e) = app.import_connections(path, replace) {
+                app.set_status_message(format!("Import failed: {}", e));
+            }
+            return Ok(true);
+        }
+
+        // Handle exports; the path keeps its original case
+        if command_name_lower.starts_with("export ") {
+            let args = command_name[7..].trim();
+            let (format, rest) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+            let rest = rest.trim();
+            let (mut typed, mut visible_only, mut path) = (false, false, rest.trim());
+            loop {
+                if let Some(after) = path.strip_prefix("--typed") {
+                    typed = true;
+                    path = after.trim();
+                } else if let Some(after) = path.strip_prefix("--visible") {
+                    visible_only = true;
+                    path = after.trim();
+                } else {
+                    break;
+                }
+            }
+            match ExportFormat::parse(format, typed) {
+                Some(format) if !path.is_empty() => {
+                    let exported = futures::executor::block_on(app.export_current_tab(
+                        format,
+                        path,
+                        visible_only,
+                    ));
+                    if let Err(e) = exported {
+                        app.set_status_message(format!("Export failed: {}", e));
+                    }
+                }
+                _ => app.set_status_message(EXPORT_USAGE.to_string()),
+            }
+            return Ok(true);
+        }
+
+        // Handle ad-hoc SQL; the statement keeps its original case
+        if command_name_lower.starts_with("sql ") {
+            app.query = command_name[4..].trim().to_string();
+            if let Err(e) = futures::executor::block_on(app.run_query()) {
+                app.set_status_message(format!("Query failed: {}", e));
+            }
+            return Ok(true);
+        }
+
+        // Handle scripts; the path keeps its original case
+        if command_name_lower.starts_with("run file ") {
+            let path = command_name[9..].trim();
+            if path.is_empty() {
+                app.set_status_message(RUN_FILE_USAGE.to_string());
+            } else if let Err(e) = futures::executor::block_on(app.run_script(path)) {
+                app.set_status_message(format!("Script failed: {}", e));
+            }
+            return Ok(true);
+        }
+
+        // Handle saved queries; names keep their original case
+        let save = if command_name_lower.starts_with("savequery! ") {
+            Some((command_name[11..].trim(), true))
+        } else if command_name_lower.starts_with("savequery ") {
+            Some((command_name[10..].trim(), false))
+        } else {
+            None
+        };
+        if let Some((name, overwrite)) = save {
+            if name.is_empty() {
+                app.prompt_save_query();
+            } else if let Err(e) = app.save_query(name, overwrite) {
+                app.set_status_message(format!("Save failed: {}", e));
+            }
+            return Ok(true);
+        }
+
+        if command_name_lower.starts_with("loadquery ") {
+            if let Err(e) = app.apply_saved_query(command_name[10..].trim()) {
+                app.set_status_message(format!("Load failed: {}", e));
+            }
+            return Ok(true);
+        }
+
+        // Handle NULL ordering with parameters
+        if let Some(option) = command_name_lower.strip_prefix("nulls ") {
+            let nulls = match option.trim() {
+                "first" => Some(NullsOrder::First),
+                "last" => Some(NullsOrder::Last),
+                "default" | "off" => None,
+                other => {
+                    app.set_status_message(format!("Unknown NULL ordering: {}", other));
+                    return Ok(true);
+                }
+            };
+            futures::executor::block_on(app.set_nulls_order(nulls))?;
+            app.set_status_message(format!("NULL ordering: {}", option.trim()));
+            return Ok(true);
+        }
+
         // Handle exact command matches
         for command in &self.commands {
             if command.name.to_lowercase() == command_name_lower {
@@ -268,7 +957,7 @@ impl CommandProcessor {
                 'j' => {
                     // Move down count times
                     for _ in 0..count {
-                        app.move_cursor_in_results(Direction::Down);
+                        futures::executor::block_on(app.move_cursor_down());
                     }
                     app.command_buffer.clear();
                     return Ok(true);
@@ -276,7 +965,7 @@ impl CommandProcessor {
                 'k' => {
                     // Move up count times
                     for _ in 0..count {
-                        app.move_cursor_in_results(Direction::Up);
+                        futures::executor::block_on(app.move_cursor_up());
                     }
                     app.command_buffer.clear();
                     return Ok(true);
@@ -284,7 +973,7 @@ impl CommandProcessor {
                 'h' => {
                     // Move left count times
                     for _ in 0..count {
-                        app.move_cursor_in_results(Direction::Left);
+                        app.move_cursor_left();
                     }
                     app.command_buffer.clear();
                     return Ok(true);
@@ -292,7 +981,7 @@ impl CommandProcessor {
                 'l' => {
                     // Move right count times
                     for _ in 0..count {
-                        app.move_cursor_in_results(Direction::Right);
+                        app.move_cursor_right();
                     }
                     app.command_buffer.clear();
                     return Ok(true);
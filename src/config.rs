@@ -1,10 +1,13 @@
 use crate::database::{ConnectionConfig, SSHConfig};
 use crate::input::KeyConfig;
 use crate::navigation::NavigationConfig;
+use crate::secrets;
 use crate::theme::Theme;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use chrono::TimeZone;
+use std::time::SystemTime;
 
 #[derive(Deserialize, Serialize)]
 pub struct ConfigFile {
@@ -19,23 +22,297 @@ pub struct ConfigFile {
     pub keymap: KeyConfig,
     #[serde(default)]
     pub navigation: NavigationConfig,
+    #[serde(default)]
+    pub layout: LayoutConfig,
+    #[serde(default)]
+    pub display: DisplayConfig,
 }
 
-#[derive(Deserialize, Serialize, Default, Clone)]
+#[derive(Deserialize, Serialize, Clone)]
 pub struct DefaultPortConfig {
     pub default_port_postgres: u16,
     pub default_port_mongodb: u16,
+    /// Maximum number of connections prefetched in the background at once
+    #[serde(default = "DefaultPortConfig::default_prefetch_concurrency")]
+    pub prefetch_concurrency: usize,
+    /// Whether copied connection DSNs include the password
+    #[serde(default)]
+    pub dsn_include_password: bool,
+    /// Text copied for a NULL cell
+    #[serde(default)]
+    pub null_copy_value: String,
+    /// Row count from which opening a table warns about its size (0 never warns)
+    #[serde(default = "DefaultPortConfig::default_large_table_rows")]
+    pub large_table_rows: u64,
+    /// Whether passwords are kept in the OS keyring, with `config.toml` only referencing them
+    #[serde(default = "DefaultPortConfig::default_use_keyring")]
+    pub use_keyring: bool,
+    /// Times a connection whose background connect failed is retried (0 never retries)
+    #[serde(default = "DefaultPortConfig::default_connect_retries")]
+    pub connect_retries: u32,
+    /// Seconds before the first retry, doubled for each one after it
+    #[serde(default = "DefaultPortConfig::default_connect_retry_delay")]
+    pub connect_retry_delay: u64,
+    /// Seconds a connection may sit idle before it is checked, and reconnected if it has
+    /// dropped, ahead of the next query (0 checks before every query)
+    #[serde(default = "DefaultPortConfig::default_liveness_check_after")]
+    pub liveness_check_after: u64,
+    /// Rows fetched at a time for a `:sql` query, the rest loaded on request (0 fetches all)
+    #[serde(default = "DefaultPortConfig::default_max_adhoc_rows")]
+    pub max_adhoc_rows: usize,
+}
+
+impl DefaultPortConfig {
+    fn default_prefetch_concurrency() -> usize {
+        4
+    }
+
+    fn default_large_table_rows() -> u64 {
+        1_000_000
+    }
+
+    fn default_use_keyring() -> bool {
+        true
+    }
+
+    fn default_connect_retries() -> u32 {
+        3
+    }
+
+    fn default_connect_retry_delay() -> u64 {
+        2
+    }
+
+    fn default_liveness_check_after() -> u64 {
+        30
+    }
+
+    fn default_max_adhoc_rows() -> usize {
+        10_000
+    }
+}
+
+impl Default for DefaultPortConfig {
+    fn default() -> Self {
+        Self {
+            default_port_postgres: 0,
+            default_port_mongodb: 0,
+            prefetch_concurrency: Self::default_prefetch_concurrency(),
+            dsn_include_password: false,
+            null_copy_value: String::new(),
+            large_table_rows: Self::default_large_table_rows(),
+            use_keyring: Self::default_use_keyring(),
+            connect_retries: Self::default_connect_retries(),
+            connect_retry_delay: Self::default_connect_retry_delay(),
+            liveness_check_after: Self::default_liveness_check_after(),
+            max_adhoc_rows: Self::default_max_adhoc_rows(),
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct Config {
     pub theme_name: String,
     pub theme: Theme,
+    /// Modification time of the theme file when it was loaded (see `reload_theme_if_changed`)
+    pub theme_modified: Option<SystemTime>,
     pub database: DefaultPortConfig,
     pub connections: Vec<ConnectionConfig>,
     pub ssh_tunnels: Vec<SSHTunnelProfile>,
     pub keymap: KeyConfig,
     pub navigation: NavigationConfig,
+    pub layout: LayoutConfig,
+    pub display: DisplayConfig,
+}
+
+/// How the query editor and results are arranged in the main panel.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MainPanelLayout {
+    /// Query editor stacked above the results (fixed height).
+    #[default]
+    Stacked,
+    /// Query editor and results side by side with a resizable divider.
+    Split,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(default)]
+pub struct LayoutConfig {
+    pub main_panel: MainPanelLayout,
+    /// Width of the query editor in split layout, as a percentage of the main panel.
+    pub query_split_percent: u16,
+    /// Width of the connections sidebar, as a percentage of the terminal.
+    pub sidebar_percent: u16,
+    /// Result columns rendered before the rest are hidden (0 shows all). Primary keys and
+    /// columns revealed with `:showcol` are always shown.
+    pub max_visible_columns: usize,
+    /// Whether the table tabs open on quit are reopened on the next start (see `session.json`)
+    pub restore_session: bool,
+}
+
+impl LayoutConfig {
+    pub const MIN_SPLIT_PERCENT: u16 = 10;
+    pub const MAX_SPLIT_PERCENT: u16 = 90;
+    pub const MIN_SIDEBAR_PERCENT: u16 = 10;
+    pub const MAX_SIDEBAR_PERCENT: u16 = 60;
+
+    pub fn clamp_split_percent(percent: u16) -> u16 {
+        percent.clamp(Self::MIN_SPLIT_PERCENT, Self::MAX_SPLIT_PERCENT)
+    }
+
+    pub fn clamp_sidebar_percent(percent: u16) -> u16 {
+        percent.clamp(Self::MIN_SIDEBAR_PERCENT, Self::MAX_SIDEBAR_PERCENT)
+    }
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            main_panel: MainPanelLayout::Stacked,
+            query_split_percent: 40,
+            sidebar_percent: 20,
+            max_visible_columns: 20,
+            restore_session: true,
+        }
+    }
+}
+
+/// How cell values are drawn in the results grid. Only the drawing changes: copied and edited
+/// values stay the raw data.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(default)]
+pub struct DisplayConfig {
+    /// Text drawn for a NULL cell
+    pub null_display: String,
+    /// Text drawn for a true value in a boolean column, e.g. `✓` or `t`
+    pub true_display: String,
+    /// Text drawn for a false value in a boolean column, e.g. `✗` or `f`
+    pub false_display: String,
+    /// strftime format of timestamp cells, e.g. `%Y-%m-%d %H:%M`; empty draws them as fetched
+    pub timestamp_format: String,
+    /// strftime format of date cells, e.g. `%d.%m.%Y`; empty draws them as fetched
+    pub date_format: String,
+    /// Zone timestamps with a time zone are shown in: `local`, `UTC` or an offset such as
+    /// `+02:00`; empty keeps the zone they are fetched in
+    pub timezone: String,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            null_display: "NULL".to_string(),
+            true_display: "true".to_string(),
+            false_display: "false".to_string(),
+            timestamp_format: String::new(),
+            date_format: String::new(),
+            timezone: String::new(),
+        }
+    }
+}
+
+impl DisplayConfig {
+    /// Drops invalid formats and time zones with a warning, so their cells are drawn as fetched
+    fn validate(&mut self) {
+        for (key, format) in [
+            ("timestamp_format", &mut self.timestamp_format),
+            ("date_format", &mut self.date_format),
+        ] {
+            let invalid = chrono::format::StrftimeItems::new(format)
+                .any(|item| matches!(item, chrono::format::Item::Error));
+            if invalid {
+                crate::logging::warn(&format!(
+                    "Ignoring {} = \"{}\" under [display]: not a valid strftime format",
+                    key, format
+                ));
+                format.clear();
+            }
+        }
+        let known = self.timezone.is_empty()
+            || self.timezone.eq_ignore_ascii_case("local")
+            || self.timezone.eq_ignore_ascii_case("utc")
+            || self.timezone.parse::<chrono::FixedOffset>().is_ok();
+        if !known {
+            crate::logging::warn(&format!(
+                "Ignoring timezone = \"{}\" under [display]: use local, UTC or an offset (+02:00)",
+                self.timezone
+            ));
+            self.timezone.clear();
+        }
+    }
+
+    /// `value` of a column of type `type_name` drawn with the configured date or timestamp
+    /// format and time zone, or `None` when none apply or the value doesn't parse
+    pub fn temporal_text(&self, type_name: &str, value: &str) -> Option<String> {
+        let type_name = type_name.trim().to_ascii_lowercase();
+        if type_name == "date" {
+            if self.date_format.is_empty() {
+                return None;
+            }
+            let date = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+            return Some(date.format(&self.date_format).to_string());
+        }
+        if !type_name.starts_with("timestamp") && type_name != "datetime" {
+            return None;
+        }
+        let zoned = type_name == "timestamptz" || type_name.ends_with("with time zone");
+        if !zoned || self.timezone.is_empty() {
+            if self.timestamp_format.is_empty() {
+                return None;
+            }
+            let naive = Self::parse_naive_timestamp(value)?;
+            return Some(naive.format(&self.timestamp_format).to_string());
+        }
+
+        let utc = Self::parse_zoned_timestamp(value)?;
+        let offset = if self.timezone.eq_ignore_ascii_case("local") {
+            *chrono::Local.from_utc_datetime(&utc.naive_utc()).offset()
+        } else if self.timezone.eq_ignore_ascii_case("utc") {
+            chrono::FixedOffset::east_opt(0)?
+        } else {
+            self.timezone.parse().ok()?
+        };
+        let local = utc.with_timezone(&offset);
+        let format = if self.timestamp_format.is_empty() {
+            "%Y-%m-%d %H:%M:%S%.f %:z"
+        } else {
+            &self.timestamp_format
+        };
+        Some(local.format(format).to_string())
+    }
+
+    /// A timestamp as the backends print it, keeping the wall time of any zone after it
+    fn parse_naive_timestamp(value: &str) -> Option<chrono::NaiveDateTime> {
+        let value = value.trim();
+        let value = value.strip_suffix(" UTC").unwrap_or(value);
+        ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"]
+            .iter()
+            .find_map(|format| chrono::NaiveDateTime::parse_from_str(value, format).ok())
+            .or_else(|| Self::parse_offset_timestamp(value).map(|zoned| zoned.naive_local()))
+    }
+
+    /// A timestamp with a zone as the backends print it: `... UTC`, `...+02` or RFC 3339
+    fn parse_zoned_timestamp(value: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+        let value = value.trim();
+        match value.strip_suffix(" UTC") {
+            Some(utc) => Self::parse_naive_timestamp(utc).map(|naive| naive.and_utc()),
+            None => Self::parse_offset_timestamp(value).map(|zoned| zoned.to_utc()),
+        }
+    }
+
+    fn parse_offset_timestamp(value: &str) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        ["%Y-%m-%d %H:%M:%S%.f%#z", "%Y-%m-%dT%H:%M:%S%.f%#z"]
+            .iter()
+            .find_map(|format| chrono::DateTime::parse_from_str(value, format).ok())
+            .or_else(|| chrono::DateTime::parse_from_rfc3339(value).ok())
+    }
+}
+
+/// Connections written by `export connections` and read by `import connections`
+#[derive(Deserialize, Serialize)]
+struct SharedConnections {
+    #[serde(default)]
+    connections: Vec<ConnectionConfig>,
 }
 
 #[derive(Deserialize, Serialize, Default, Clone)]
@@ -45,8 +322,13 @@ pub struct SSHTunnelProfile {
     pub config: SSHConfig,
 }
 
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+}
+
 impl Config {
-    fn get_config_dir() -> PathBuf {
+    pub fn get_config_dir() -> PathBuf {
         // Get the home directory
         let home = std::env::var("HOME").unwrap_or_else(|_| String::new());
         if home.is_empty() {
@@ -98,19 +380,44 @@ impl Config {
         Ok(())
     }
 
+    fn theme_path(theme_name: &str) -> PathBuf {
+        Self::get_config_dir()
+            .join("themes")
+            .join(format!("{}.toml", theme_name))
+    }
+
+    /// When the file of `theme_name` was last modified, if it exists
+    fn theme_modified(theme_name: &str) -> Option<SystemTime> {
+        std::fs::metadata(Self::theme_path(theme_name))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
     pub fn load_theme(theme_name: &str) -> Result<Theme> {
-        let theme_dir = Self::get_config_dir().join("themes");
-        let theme_path = theme_dir.join(format!("{}.toml", theme_name));
+        let theme_path = Self::theme_path(theme_name);
 
         if theme_path.exists() {
             let content =
                 std::fs::read_to_string(&theme_path).context("Failed to read theme file")?;
-            toml::from_str(&content).context("Failed to parse theme file")
+            Theme::from_toml(theme_name, &content)
         } else {
             Ok(Theme::default())
         }
     }
 
+    /// Reloads the current theme when its file changed since it was loaded, so edits show up
+    /// without restarting; returns whether it was reloaded
+    pub fn reload_theme_if_changed(&mut self) -> Result<bool> {
+        let modified = Self::theme_modified(&self.theme_name);
+        if modified == self.theme_modified {
+            return Ok(false);
+        }
+        // Remember the change even if the file doesn't parse, so it's reported once
+        self.theme_modified = modified;
+        self.theme = Self::load_theme(&self.theme_name)?;
+        Ok(true)
+    }
+
     fn load_config() -> Result<ConfigFile> {
         let config_dir = Self::get_config_dir();
         let config_path = config_dir.join("config.toml");
@@ -132,11 +439,22 @@ impl Config {
                 database: DefaultPortConfig {
                     default_port_postgres: 5432,
                     default_port_mongodb: 27017,
+                    prefetch_concurrency: DefaultPortConfig::default_prefetch_concurrency(),
+                    dsn_include_password: false,
+                    null_copy_value: String::new(),
+                    large_table_rows: DefaultPortConfig::default_large_table_rows(),
+                    use_keyring: DefaultPortConfig::default_use_keyring(),
+                    connect_retries: DefaultPortConfig::default_connect_retries(),
+                    connect_retry_delay: DefaultPortConfig::default_connect_retry_delay(),
+                    liveness_check_after: DefaultPortConfig::default_liveness_check_after(),
+                    max_adhoc_rows: DefaultPortConfig::default_max_adhoc_rows(),
                 },
                 connections: Vec::new(),
                 ssh_tunnels: Vec::new(),
                 keymap: KeyConfig::default(),
                 navigation: NavigationConfig::default(),
+                layout: LayoutConfig::default(),
+                display: DisplayConfig::default(),
             };
 
             let toml_string = toml::to_string_pretty(&default_config)
@@ -149,7 +467,7 @@ impl Config {
     }
 
     pub fn new() -> Self {
-        let config_file = match Self::load_config() {
+        let mut config_file = match Self::load_config() {
             Ok(cfg) => cfg,
             Err(err) => {
                 crate::logging::handle_non_critical_error(&err);
@@ -160,6 +478,8 @@ impl Config {
                     ssh_tunnels: Vec::new(),
                     keymap: KeyConfig::default(),
                     navigation: NavigationConfig::default(),
+                    layout: LayoutConfig::default(),
+                    display: DisplayConfig::default(),
                 }
             }
         };
@@ -172,15 +492,87 @@ impl Config {
             }
         };
 
+        config_file.display.validate();
+        if config_file.database.use_keyring {
+            if let Err(err) = Self::migrate_plaintext_secrets(&mut config_file) {
+                crate::logging::handle_non_critical_error(&err);
+            }
+        }
+        Self::resolve_secrets(&mut config_file.connections);
+        for tunnel in &mut config_file.ssh_tunnels {
+            if let Err(err) = secrets::resolve_ssh(&mut tunnel.config) {
+                crate::logging::handle_non_critical_error(&err);
+            }
+        }
+
         Self {
             theme,
+            theme_modified: Self::theme_modified(&config_file.theme),
             theme_name: config_file.theme,
             database: config_file.database,
             connections: config_file.connections,
             ssh_tunnels: config_file.ssh_tunnels,
             keymap: config_file.keymap,
             navigation: config_file.navigation,
+            layout: config_file.layout,
+            display: config_file.display,
+        }
+    }
+
+    /// Moves plaintext passwords of a config written before the keyring was used into it,
+    /// rewriting the file with references in their place
+    fn migrate_plaintext_secrets(config_file: &mut ConfigFile) -> Result<()> {
+        let plaintext = config_file
+            .connections
+            .iter()
+            .any(secrets::has_plaintext_connection)
+            || config_file
+                .ssh_tunnels
+                .iter()
+                .any(|t| secrets::has_plaintext_ssh(&t.config));
+        if !plaintext {
+            return Ok(());
+        }
+
+        for connection in &mut config_file.connections {
+            secrets::stash_connection(connection)?;
+        }
+        for tunnel in &mut config_file.ssh_tunnels {
+            secrets::stash_ssh(&mut tunnel.config, &secrets::tunnel_owner(&tunnel.name))?;
+        }
+
+        let config_path = Self::get_config_dir().join("config.toml");
+        let toml_string = toml::to_string_pretty(&config_file)
+            .context("Failed to serialize config with keyring references")?;
+        std::fs::write(&config_path, toml_string)
+            .context("Failed to write config file with keyring references")?;
+        crate::logging::info("Moved plaintext passwords from config.toml to the OS keyring");
+        Ok(())
+    }
+
+    /// Fetches the passwords `connections` reference from the keyring; ones that can't be
+    /// read are logged and left as references, which connecting then refuses
+    fn resolve_secrets(connections: &mut [ConnectionConfig]) {
+        for connection in connections {
+            if let Err(err) = secrets::resolve_connection(connection) {
+                crate::logging::handle_non_critical_error(&err);
+            }
+        }
+    }
+
+    /// `connections` as written to the config file: with `use_keyring`, their passwords are
+    /// stored in the keyring and replaced by references
+    fn stored_connections(
+        &self,
+        connections: &[ConnectionConfig],
+    ) -> Result<Vec<ConnectionConfig>> {
+        let mut connections = connections.to_vec();
+        if self.database.use_keyring {
+            for connection in &mut connections {
+                secrets::stash_connection(connection)?;
+            }
         }
+        Ok(connections)
     }
 
     /// Save connections to config file
@@ -189,7 +581,7 @@ impl Config {
         let config_path = config_dir.join("config.toml");
 
         let mut config_file = Self::load_config()?;
-        config_file.connections = connections.clone();
+        config_file.connections = self.stored_connections(connections)?;
 
         let toml_string = toml::to_string_pretty(&config_file)
             .context("Failed to serialize config with connections")?;
@@ -200,9 +592,54 @@ impl Config {
         Ok(())
     }
 
-    /// Load connections from config file
+    /// Writes `connections` to `path`, as JSON when it ends in `.json` and TOML otherwise
+    pub fn export_connections(path: &Path, connections: &[ConnectionConfig]) -> Result<()> {
+        let shared = SharedConnections {
+            connections: connections.to_vec(),
+        };
+        let content = if is_json(path) {
+            serde_json::to_string_pretty(&shared).context("Failed to serialize connections")?
+        } else {
+            toml::to_string_pretty(&shared).context("Failed to serialize connections")?
+        };
+        std::fs::write(path, content).with_context(|| format!("Could not write {}", path.display()))
+    }
+
+    /// Reads the connections of a file written by `export_connections`
+    pub fn import_connections(path: &Path) -> Result<Vec<ConnectionConfig>> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Could not read {}", path.display()))?;
+        let shared: SharedConnections = if is_json(path) {
+            serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?
+        } else {
+            toml::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?
+        };
+        Ok(shared.connections)
+    }
+
+    /// Save layout settings to config file
+    pub fn save_layout(&self) -> Result<()> {
+        let config_dir = Self::get_config_dir();
+        let config_path = config_dir.join("config.toml");
+
+        let mut config_file = Self::load_config()?;
+        config_file.layout = self.layout.clone();
+
+        let toml_string = toml::to_string_pretty(&config_file)
+            .context("Failed to serialize config with layout")?;
+
+        std::fs::write(&config_path, toml_string)
+            .context("Failed to write config file with layout")?;
+
+        Ok(())
+    }
+
+    /// Load connections from config file, with the passwords they reference in the keyring
     pub fn load_connections(&self) -> Result<Vec<ConnectionConfig>> {
-        let config_file = Self::load_config()?;
+        let mut config_file = Self::load_config()?;
+        Self::resolve_secrets(&mut config_file.connections);
         Ok(config_file.connections)
     }
 
@@ -211,13 +648,22 @@ impl Config {
         let config_dir = Self::get_config_dir();
         let config_path = config_dir.join("config.toml");
 
+        let mut ssh_tunnels = self.ssh_tunnels.clone();
+        if self.database.use_keyring {
+            for tunnel in &mut ssh_tunnels {
+                secrets::stash_ssh(&mut tunnel.config, &secrets::tunnel_owner(&tunnel.name))?;
+            }
+        }
+
         let config_file = ConfigFile {
             theme: self.theme_name.clone(),
             database: self.database.clone(),
-            connections: self.connections.clone(),
-            ssh_tunnels: self.ssh_tunnels.clone(),
+            connections: self.stored_connections(&self.connections)?,
+            ssh_tunnels,
             keymap: self.keymap.clone(),
             navigation: self.navigation.clone(),
+            layout: self.layout.clone(),
+            display: self.display.clone(),
         };
 
         let toml_string =
@@ -253,10 +699,21 @@ impl Config {
         Ok(themes)
     }
 
-    /// Switch theme
+    /// Switch theme, matching `theme_name` to a theme file regardless of case
     pub fn switch_theme(&mut self, theme_name: &str) -> Result<()> {
+        let themes = Self::list_themes()?;
+        let theme_name = themes
+            .iter()
+            .find(|theme| *theme == theme_name)
+            .or_else(|| {
+                themes
+                    .iter()
+                    .find(|theme| theme.eq_ignore_ascii_case(theme_name))
+            })
+            .ok_or_else(|| anyhow::anyhow!("No theme named '{}'", theme_name))?;
         let theme = Self::load_theme(theme_name)?;
         self.theme = theme;
+        self.theme_modified = Self::theme_modified(theme_name);
         self.theme_name = theme_name.to_string();
         self.save()?;
         Ok(())
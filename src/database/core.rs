@@ -1,6 +1,8 @@
+use super::DatabaseType;
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct QueryParams {
@@ -8,6 +10,554 @@ pub struct QueryParams {
     pub order_by: Option<String>,
     pub limit: Option<u32>,
     pub offset: Option<u32>,
+    /// SELECT to read from instead of the table, wrapped as `(<base>) AS t`
+    #[serde(default)]
+    pub base_query: Option<String>,
+    /// Values bound to the `$1`, `$2`, ... placeholders of `where_clause`
+    #[serde(default)]
+    pub where_params: Vec<String>,
+}
+
+impl QueryParams {
+    /// FROM source for a base query, if one is set
+    pub fn base_query_source(&self) -> Option<String> {
+        self.base_query.as_deref().map(|base| format!("({}) AS t", base))
+    }
+}
+
+/// Normalises a user supplied base query, rejecting anything that isn't a single SELECT
+pub fn validate_base_query(query: &str) -> Result<String> {
+    let query = query.trim().trim_end_matches(';').trim();
+    let first_word = query
+        .split_whitespace()
+        .next()
+        .unwrap_or("")
+        .to_ascii_uppercase();
+    if first_word != "SELECT" && first_word != "WITH" {
+        return Err(anyhow::anyhow!("Base query must be a SELECT statement"));
+    }
+    if query.contains(';') {
+        return Err(anyhow::anyhow!("Base query must be a single statement"));
+    }
+    Ok(query.to_string())
+}
+
+/// Highest `$n` placeholder used in `sql`, skipping quoted strings and identifiers
+pub fn placeholder_count(sql: &str) -> usize {
+    let mut highest = 0;
+    let mut quote: Option<char> = None;
+    let mut chars = sql.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '$') => {
+                let mut digits = String::new();
+                while let Some(d) = chars.peek().filter(|d| d.is_ascii_digit()) {
+                    digits.push(*d);
+                    chars.next();
+                }
+                if let Ok(index) = digits.parse::<usize>() {
+                    highest = highest.max(index);
+                }
+            }
+            _ => {}
+        }
+    }
+    highest
+}
+
+/// Statements of a script, split at the semicolons outside quoted strings and identifiers,
+/// comments and (PostgreSQL) `$tag$ ... $tag$` bodies. MySQL scripts also have backslash
+/// escapes and `#` comments. Statements are trimmed; ones with nothing but comments are
+/// dropped.
+pub fn split_statements(script: &str, db_type: &DatabaseType) -> Vec<String> {
+    let postgres = matches!(db_type, DatabaseType::Postgres);
+    let mysql = matches!(db_type, DatabaseType::MySQL);
+    let bytes = script.as_bytes();
+    let mut statements = Vec::new();
+    let (mut start, mut i, mut has_code) = (0, 0, false);
+    // Every delimiter is ASCII, so `i` only lands inside a character within a quoted string
+    while i < bytes.len() {
+        let rest = &script[i..];
+        if rest.starts_with("--") || (mysql && rest.starts_with('#')) {
+            i += rest.find('\n').unwrap_or(rest.len());
+            continue;
+        }
+        if let Some(comment) = rest.strip_prefix("/*") {
+            i += comment.find("*/").map_or(rest.len(), |end| end + 4);
+            continue;
+        }
+        match bytes[i] {
+            quote @ (b'\'' | b'"' | b'`') => {
+                has_code = true;
+                i += 1;
+                while i < bytes.len() {
+                    if mysql && bytes[i] == b'\\' {
+                        i += 2;
+                        continue;
+                    }
+                    i += 1;
+                    // A doubled quote is an escaped one, not the end
+                    if bytes[i - 1] == quote {
+                        if bytes.get(i) != Some(&quote) {
+                            break;
+                        }
+                        i += 1;
+                    }
+                }
+            }
+            b'$' if postgres => {
+                has_code = true;
+                match dollar_tag(rest) {
+                    Some(tag) => {
+                        let body = &rest[tag.len()..];
+                        i += tag.len() + body.find(tag).map_or(body.len(), |end| end + tag.len());
+                    }
+                    None => i += 1,
+                }
+            }
+            b';' => {
+                if has_code {
+                    statements.push(script[start..i].trim().to_string());
+                }
+                i += 1;
+                start = i;
+                has_code = false;
+            }
+            _ => {
+                let c = rest.chars().next().unwrap_or(' ');
+                has_code |= !c.is_whitespace();
+                i += c.len_utf8();
+            }
+        }
+    }
+    if has_code {
+        statements.push(script[start.min(script.len())..].trim().to_string());
+    }
+    statements
+}
+
+/// The `$tag$` (or `$$`) opening a dollar-quoted string at the start of `text`; `$1` and
+/// other placeholders aren't tags
+fn dollar_tag(text: &str) -> Option<&str> {
+    let end = text[1..].find('$')? + 2;
+    let name = &text[1..end - 1];
+    let valid = !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+    valid.then_some(&text[..end])
+}
+
+/// `query` without its trailing semicolon when it is a single SELECT (or `WITH ... SELECT`)
+/// whose rows can be fetched a chunk at a time (see `row_chunk_query`). Statements naming
+/// INSERT, UPDATE, DELETE, MERGE or INTO anywhere are left alone in case they write, and so
+/// is everything on MongoDB and mock connections.
+pub fn row_query<'a>(db_type: &DatabaseType, query: &'a str) -> Option<&'a str> {
+    if !matches!(
+        db_type,
+        DatabaseType::Postgres | DatabaseType::MySQL | DatabaseType::SQLite
+    ) || split_statements(query, db_type).len() != 1
+    {
+        return None;
+    }
+    let query = query.trim().trim_end_matches(';').trim_end();
+    let lower = query.to_lowercase();
+    let mut words = lower.split(|c: char| !c.is_alphanumeric() && c != '_');
+    let reads = matches!(words.next(), Some("select" | "with"))
+        && !words.any(|word| matches!(word, "insert" | "update" | "delete" | "merge" | "into"));
+    reads.then_some(query)
+}
+
+/// Whether the `row_query` `query` limits or locks its own rows at the top level (`LIMIT`,
+/// `OFFSET`, `FETCH`, `FOR UPDATE`, ...), so `row_chunk_query` can't append a LIMIT to it.
+/// Words in subqueries, CTEs, quotes and comments don't count.
+pub fn limits_own_rows(db_type: &DatabaseType, query: &str) -> bool {
+    top_level_words(query, db_type)
+        .iter()
+        .any(|word| matches!(word.as_str(), "limit" | "offset" | "fetch" | "for" | "lock"))
+}
+
+/// Lowercased words of `sql` outside parentheses, quoted strings and identifiers, comments
+/// and (PostgreSQL) dollar-quoted bodies
+fn top_level_words(sql: &str, db_type: &DatabaseType) -> Vec<String> {
+    let postgres = matches!(db_type, DatabaseType::Postgres);
+    let mysql = matches!(db_type, DatabaseType::MySQL);
+    let bytes = sql.as_bytes();
+    let mut words = Vec::new();
+    let (mut depth, mut i) = (0usize, 0);
+    while i < bytes.len() {
+        let rest = &sql[i..];
+        if rest.starts_with("--") || (mysql && rest.starts_with('#')) {
+            i += rest.find('\n').unwrap_or(rest.len());
+            continue;
+        }
+        if let Some(comment) = rest.strip_prefix("/*") {
+            i += comment.find("*/").map_or(rest.len(), |end| end + 4);
+            continue;
+        }
+        match bytes[i] {
+            quote @ (b'\'' | b'"' | b'`') => {
+                i += 1;
+                while i < bytes.len() {
+                    if mysql && bytes[i] == b'\\' {
+                        i += 2;
+                        continue;
+                    }
+                    i += 1;
+                    if bytes[i - 1] == quote {
+                        if bytes.get(i) != Some(&quote) {
+                            break;
+                        }
+                        i += 1;
+                    }
+                }
+            }
+            b'$' if postgres => match dollar_tag(rest) {
+                Some(tag) => {
+                    let body = &rest[tag.len()..];
+                    i += tag.len() + body.find(tag).map_or(body.len(), |end| end + tag.len());
+                }
+                None => i += 1,
+            },
+            b'(' => {
+                depth += 1;
+                i += 1;
+            }
+            b')' => {
+                depth = depth.saturating_sub(1);
+                i += 1;
+            }
+            _ => {
+                let word_len = rest
+                    .find(|c: char| !c.is_alphanumeric() && c != '_')
+                    .unwrap_or(rest.len());
+                if word_len == 0 {
+                    i += rest.chars().next().map_or(1, char::len_utf8);
+                    continue;
+                }
+                if depth == 0 {
+                    words.push(rest[..word_len].to_lowercase());
+                }
+                i += word_len;
+            }
+        }
+    }
+    words
+}
+
+/// Rows `offset..offset + limit` of the `row_query` `query`, which doesn't `limits_own_rows`.
+/// The LIMIT goes on a line of its own so a trailing `--` comment can't swallow it.
+pub fn row_chunk_query(query: &str, limit: usize, offset: usize) -> String {
+    format!("{}\nLIMIT {} OFFSET {}", query, limit, offset)
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// `name` quoted as an identifier of `db_type`: in backticks on MySQL, which reads double
+/// quotes as a string literal, and in double quotes elsewhere
+pub fn dialect_identifier(db_type: &DatabaseType, name: &str) -> String {
+    match db_type {
+        DatabaseType::MySQL => format!("`{}`", name.replace('`', "``")),
+        _ => quote_identifier(name),
+    }
+}
+
+/// `value` as a string literal of `db_type`; MySQL also treats backslashes as escapes
+pub fn dialect_literal(db_type: &DatabaseType, value: &str) -> String {
+    match db_type {
+        DatabaseType::MySQL => format!("'{}'", value.replace('\\', "\\\\").replace('\'', "''")),
+        _ => format!("'{}'", value.replace('\'', "''")),
+    }
+}
+
+/// `schema.table` quoted for `db_type`; SQLite tables and an empty schema get no schema
+pub fn dialect_table(db_type: &DatabaseType, schema: &str, table: &str) -> String {
+    if schema.is_empty() || matches!(db_type, DatabaseType::SQLite) {
+        dialect_identifier(db_type, table)
+    } else {
+        format!(
+            "{}.{}",
+            dialect_identifier(db_type, schema),
+            dialect_identifier(db_type, table)
+        )
+    }
+}
+
+/// `DELETE FROM "schema"."table" WHERE ("k1", "k2") IN ((..), ..)` for the rows identified by
+/// `keys`, rendering each key value with `value(position, value)`, where `position` counts
+/// values across all rows from 0
+pub fn delete_by_keys_statement(
+    schema: &str,
+    table: &str,
+    key_columns: &[String],
+    keys: &[Vec<String>],
+    value: impl Fn(usize, &str) -> String,
+) -> String {
+    let target = if schema.is_empty() {
+        quote_identifier(table)
+    } else {
+        format!("{}.{}", quote_identifier(schema), quote_identifier(table))
+    };
+    let key_list = key_columns
+        .iter()
+        .map(|column| quote_identifier(column))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let tuples = keys
+        .iter()
+        .enumerate()
+        .map(|(row, values)| {
+            let tuple = values
+                .iter()
+                .enumerate()
+                .map(|(column, v)| value(row * key_columns.len() + column, v))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("({})", tuple)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("DELETE FROM {} WHERE ({}) IN ({})", target, key_list, tuples)
+}
+
+/// `UPDATE "schema"."table" SET "column" = .. WHERE "k1" = .. AND "k2" IS NULL` for the row
+/// identified by `key`, rendering each non-NULL value with `value(position, value)`, where
+/// `position` counts those values from 0, starting with the new one
+pub fn update_by_key_statement(
+    schema: &str,
+    table: &str,
+    column: &str,
+    new_value: Option<&str>,
+    key_columns: &[String],
+    key: &[Option<String>],
+    value: impl Fn(usize, &str) -> String,
+) -> String {
+    let target = if schema.is_empty() {
+        quote_identifier(table)
+    } else {
+        format!("{}.{}", quote_identifier(schema), quote_identifier(table))
+    };
+    let mut position = 0;
+    let mut render = |v: Option<&str>| match v {
+        Some(v) => {
+            position += 1;
+            Some(value(position - 1, v))
+        }
+        None => None,
+    };
+    let assignment = render(new_value).unwrap_or_else(|| "NULL".to_string());
+    let conditions = key_columns
+        .iter()
+        .zip(key)
+        .map(|(column, v)| match render(v.as_deref()) {
+            Some(v) => format!("{} = {}", quote_identifier(column), v),
+            None => format!("{} IS NULL", quote_identifier(column)),
+        })
+        .collect::<Vec<_>>()
+        .join(" AND ");
+    format!(
+        "UPDATE {} SET {} = {} WHERE {}",
+        target,
+        quote_identifier(column),
+        assignment,
+        conditions
+    )
+}
+
+/// `INSERT INTO "schema"."table" ("c1", ..) VALUES (..);` recreating `row` of `result`. Values
+/// of numeric columns that are plain numbers go in bare, NULL as `NULL` and the rest as string
+/// literals. MySQL gets backtick identifiers and escaped backslashes, and SQLite tables no
+/// schema.
+pub fn insert_statement(
+    db_type: &DatabaseType,
+    schema: &str,
+    table: &str,
+    result: &QueryResult,
+    row: &[Option<String>],
+) -> String {
+    let target = dialect_table(db_type, schema, table);
+    let columns = result
+        .columns
+        .iter()
+        .map(|column| dialect_identifier(db_type, column))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let values = row
+        .iter()
+        .enumerate()
+        .map(|(index, cell)| match cell.as_deref() {
+            None => "NULL".to_string(),
+            Some(value)
+                if result.column_kind(index) == ColumnKind::Numeric
+                    && ColumnFormat::default().format_number(value).is_some() =>
+            {
+                value.to_string()
+            }
+            Some(value) => dialect_literal(db_type, value),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("INSERT INTO {} ({}) VALUES ({});", target, columns, values)
+}
+
+/// `column ILIKE '%pattern%'`, matching rows whose `column` contains `pattern` whatever its
+/// case. The pattern's `%`, `_` and quotes are escaped so they match themselves. MySQL and
+/// SQLite already compare case-insensitively with `LIKE`, and PostgreSQL casts the column to
+/// text so numbers and dates match too.
+pub fn contains_predicate(db_type: &DatabaseType, column: &str, pattern: &str) -> String {
+    let mut escaped = String::with_capacity(pattern.len() + 2);
+    for c in pattern.chars() {
+        match c {
+            '!' | '%' | '_' => {
+                escaped.push('!');
+                escaped.push(c);
+            }
+            '\\' if matches!(db_type, DatabaseType::MySQL) => escaped.push_str("\\\\"),
+            '\'' => escaped.push_str("''"),
+            c => escaped.push(c),
+        }
+    }
+    let (column, operator) = match db_type {
+        DatabaseType::Postgres => (format!("{}::text", quote_identifier(column)), "ILIKE"),
+        DatabaseType::MySQL => (format!("`{}`", column.replace('`', "``")), "LIKE"),
+        // The mock WHERE parser only knows bare column names
+        DatabaseType::Mock => (column.to_string(), "ILIKE"),
+        _ => (quote_identifier(column), "LIKE"),
+    };
+    format!("{} {} '%{}%' ESCAPE '!'", column, operator, escaped)
+}
+
+/// `column = 'value'` (`!=` with `exclude`) matching `cell`, or `IS [NOT] NULL` for a NULL one
+pub fn cell_predicate(
+    db_type: &DatabaseType,
+    column: &str,
+    cell: Option<&str>,
+    exclude: bool,
+) -> String {
+    let column = match db_type {
+        // The mock WHERE parser only knows bare column names
+        DatabaseType::Mock => column.to_string(),
+        _ => dialect_identifier(db_type, column),
+    };
+    match (cell, exclude) {
+        (None, false) => format!("{} IS NULL", column),
+        (None, true) => format!("{} IS NOT NULL", column),
+        (Some(value), exclude) => format!(
+            "{} {} {}",
+            column,
+            if exclude { "!=" } else { "=" },
+            dialect_literal(db_type, value)
+        ),
+    }
+}
+
+/// Placement of NULL values within a sorted column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum NullsOrder {
+    First,
+    Last,
+}
+
+/// A single `column [ASC|DESC] [NULLS FIRST|LAST]` term of an ORDER BY clause
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderByTerm {
+    pub column: String,
+    pub descending: bool,
+    pub nulls: Option<NullsOrder>,
+}
+
+impl OrderByTerm {
+    /// Parses a comma separated ORDER BY clause, ignoring unrecognised modifiers
+    pub fn parse_list(order_by: &str) -> Vec<Self> {
+        order_by
+            .split(',')
+            .filter_map(|order| {
+                let mut parts = order.split_whitespace();
+                let column = parts.next()?.to_string();
+                let mut term = OrderByTerm {
+                    column,
+                    descending: false,
+                    nulls: None,
+                };
+                while let Some(part) = parts.next() {
+                    match part.to_ascii_uppercase().as_str() {
+                        "ASC" => term.descending = false,
+                        "DESC" => term.descending = true,
+                        "NULLS" => {
+                            term.nulls = match parts.next().map(|p| p.to_ascii_uppercase()) {
+                                Some(p) if p == "FIRST" => Some(NullsOrder::First),
+                                Some(p) if p == "LAST" => Some(NullsOrder::Last),
+                                _ => term.nulls,
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Some(term)
+            })
+            .collect()
+    }
+
+    pub fn direction(&self) -> &'static str {
+        if self.descending {
+            "DESC"
+        } else {
+            "ASC"
+        }
+    }
+
+    /// Renders the term for databases without NULLS FIRST/LAST, sorting on
+    /// the null check before the already quoted `column`
+    pub fn to_sql_emulating_nulls(&self, column: &str) -> String {
+        let order = format!("{} {}", column, self.direction());
+        match self.nulls {
+            Some(NullsOrder::First) => format!("{} IS NULL DESC, {}", column, order),
+            Some(NullsOrder::Last) => format!("{} IS NULL ASC, {}", column, order),
+            None => order,
+        }
+    }
+}
+
+impl fmt::Display for OrderByTerm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.column, self.direction())?;
+        match self.nulls {
+            Some(NullsOrder::First) => write!(f, " NULLS FIRST"),
+            Some(NullsOrder::Last) => write!(f, " NULLS LAST"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Kind of relation listed under a schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RelationKind {
+    Table,
+    View,
+    MaterializedView,
+    ForeignTable,
+    /// Parent of a declaratively partitioned table
+    PartitionedTable,
+    /// Child partition of a partitioned table
+    Partition,
+}
+
+impl RelationKind {
+    /// Short tag shown after the name in the tree; plain tables have none
+    pub fn tag(&self) -> Option<&'static str> {
+        match self {
+            RelationKind::Table => None,
+            RelationKind::View => Some("view"),
+            RelationKind::MaterializedView => Some("matview"),
+            RelationKind::ForeignTable => Some("foreign"),
+            RelationKind::PartitionedTable => Some("partitioned"),
+            RelationKind::Partition => Some("partition"),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -39,8 +589,222 @@ pub struct ColumnInfo {
 #[derive(Debug, Clone)]
 pub struct QueryResult {
     pub columns: Vec<String>,
-    pub rows: Vec<Vec<String>>,
+    /// `None` is SQL NULL, kept apart from an empty string
+    pub rows: Vec<Vec<Option<String>>>,
     pub affected_rows: u64,
+    /// Type name the backend reported for each column, e.g. `int4` or `varchar(20)`; empty
+    /// when it reports none
+    pub column_types: Vec<String>,
+}
+
+impl QueryResult {
+    /// Kind of the values in column `index`, `Text` when its type is unknown
+    pub fn column_kind(&self, index: usize) -> ColumnKind {
+        self.column_types
+            .get(index)
+            .map_or(ColumnKind::Text, |type_name| ColumnKind::from_type_name(type_name))
+    }
+
+    /// The result with only the columns named in `names`, in that order; names it lacks are
+    /// skipped
+    pub fn select_columns(self, names: &[&String]) -> QueryResult {
+        let indices: Vec<usize> = names
+            .iter()
+            .filter_map(|name| self.columns.iter().position(|column| column == *name))
+            .collect();
+        QueryResult {
+            columns: indices
+                .iter()
+                .map(|&index| self.columns[index].clone())
+                .collect(),
+            rows: self
+                .rows
+                .into_iter()
+                .map(|row| {
+                    indices
+                        .iter()
+                        .map(|&index| row.get(index).cloned().flatten())
+                        .collect()
+                })
+                .collect(),
+            affected_rows: self.affected_rows,
+            column_types: indices
+                .iter()
+                .filter_map(|&index| self.column_types.get(index).cloned())
+                .collect(),
+        }
+    }
+}
+
+/// What a column holds, which decides how its values line up in the results grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnKind {
+    Numeric,
+    Temporal,
+    Boolean,
+    Text,
+}
+
+impl ColumnKind {
+    /// Classifies a type name from any backend, e.g. `int8`, `DECIMAL(10,2)` or
+    /// `timestamp with time zone`
+    pub fn from_type_name(type_name: &str) -> Self {
+        let type_name = type_name.trim().to_ascii_lowercase();
+        // MySQL's BOOLEAN is an alias of TINYINT(1)
+        if type_name == "tinyint(1)" {
+            return ColumnKind::Boolean;
+        }
+        match type_name.split(['(', ' ']).next().unwrap_or_default() {
+            "int2" | "int4" | "int8" | "smallint" | "int" | "integer" | "bigint" | "tinyint"
+            | "mediumint" | "serial" | "bigserial" | "float4" | "float8" | "real" | "float"
+            | "double" | "numeric" | "decimal" | "money" | "oid" => ColumnKind::Numeric,
+            "date" | "time" | "timetz" | "timestamp" | "timestamptz" | "datetime" | "interval"
+            | "year" => ColumnKind::Temporal,
+            "bool" | "boolean" => ColumnKind::Boolean,
+            _ => ColumnKind::Text,
+        }
+    }
+}
+
+/// Where a column's values sit in their cells, overriding the alignment of its kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnAlignment {
+    Left,
+    Center,
+    Right,
+}
+
+/// How the values of a result column are drawn, set with `format <column> ...`. Only the
+/// drawn text changes; copies and exports keep the values as fetched.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ColumnFormat {
+    pub alignment: Option<ColumnAlignment>,
+    /// Whether the integer digits of numbers are grouped in threes with commas
+    pub thousands: bool,
+    /// Digits numbers are rounded or padded to after the decimal point
+    pub decimals: Option<usize>,
+}
+
+impl ColumnFormat {
+    /// Whether any option applies to numbers only
+    pub fn formats_numbers(&self) -> bool {
+        self.thousands || self.decimals.is_some()
+    }
+
+    /// `value` with the number options applied, or `None` when it isn't a plain decimal
+    /// number (e.g. `1e5` or `NaN`) and is drawn as is. Rounding works on the digits, so
+    /// values past the precision of a float keep every digit.
+    pub fn format_number(&self, value: &str) -> Option<String> {
+        let value = value.trim();
+        let (negative, digits) = match value.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, value.strip_prefix('+').unwrap_or(value)),
+        };
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if integer.is_empty() && fraction.is_empty()
+            || !is_digits(integer)
+            || !is_digits(fraction)
+        {
+            return None;
+        }
+
+        let mut integer = if integer.is_empty() { "0" } else { integer }.to_string();
+        let mut fraction = fraction.to_string();
+        if let Some(decimals) = self.decimals {
+            if fraction.len() > decimals {
+                let round_up = fraction.as_bytes()[decimals] >= b'5';
+                fraction.truncate(decimals);
+                if round_up {
+                    let mut kept = format!("{}{}", integer, fraction).into_bytes();
+                    // Carry the 1 left through any trailing 9s
+                    let mut carry = true;
+                    for digit in kept.iter_mut().rev() {
+                        if *digit == b'9' {
+                            *digit = b'0';
+                        } else {
+                            *digit += 1;
+                            carry = false;
+                            break;
+                        }
+                    }
+                    if carry {
+                        kept.insert(0, b'1');
+                    }
+                    let kept = String::from_utf8(kept).unwrap_or_default();
+                    let (rounded_integer, rounded_fraction) = kept.split_at(kept.len() - decimals);
+                    integer = rounded_integer.to_string();
+                    fraction = rounded_fraction.to_string();
+                }
+            } else {
+                fraction.push_str(&"0".repeat(decimals - fraction.len()));
+            }
+        }
+
+        if self.thousands {
+            let len = integer.len();
+            integer = integer
+                .chars()
+                .enumerate()
+                .flat_map(|(index, digit)| {
+                    let comma = index > 0 && (len - index) % 3 == 0;
+                    comma.then_some(',').into_iter().chain(Some(digit))
+                })
+                .collect();
+        }
+        // Rounding can leave nothing but zeros, which takes no sign
+        let zero = integer
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b == b'0' || b == b',');
+        let sign = if negative && !zero { "-" } else { "" };
+        if fraction.is_empty() {
+            Some(format!("{}{}", sign, integer))
+        } else {
+            Some(format!("{}{}.{}", sign, integer, fraction))
+        }
+    }
+}
+
+/// Header of the result returned by `describe_table`
+pub const STRUCTURE_COLUMNS: [&str; 6] = [
+    "column",
+    "type",
+    "nullable",
+    "default",
+    "primary_key",
+    "foreign_key",
+];
+
+/// One `describe_table` row; a missing default or foreign key is NULL
+pub fn structure_row(
+    name: String,
+    data_type: String,
+    is_nullable: bool,
+    default: Option<String>,
+    is_primary_key: bool,
+    foreign_key: Option<String>,
+) -> Vec<Option<String>> {
+    vec![
+        Some(name),
+        Some(data_type),
+        Some(if is_nullable { "YES" } else { "NO" }.to_string()),
+        default,
+        Some(if is_primary_key { "YES" } else { "NO" }.to_string()),
+        foreign_key,
+    ]
+}
+
+/// Header of the result returned by `list_indexes`
+pub const INDEX_COLUMNS: [&str; 4] = ["index", "columns", "unique", "type"];
+
+pub fn structure_result(rows: Vec<Vec<Option<String>>>) -> QueryResult {
+    QueryResult {
+        columns: STRUCTURE_COLUMNS.iter().map(|column| column.to_string()).collect(),
+        rows,
+        affected_rows: 0,
+        column_types: Vec::new(),
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -50,6 +814,9 @@ pub struct ForeignKeyTarget {
     pub column: String,
 }
 
+/// Row count owning the client it reads through (see `DatabaseConnection::detached_row_count`)
+pub type RowCountFuture = futures::future::BoxFuture<'static, Result<u64>>;
+
 #[async_trait]
 pub trait DatabaseConnection: Send + Sync {
     /// Connect to the database
@@ -58,18 +825,71 @@ pub trait DatabaseConnection: Send + Sync {
     /// Disconnect from the database
     async fn disconnect(&mut self) -> Result<()>;
 
+    /// Whether the connection still reaches the database, e.g. after sitting idle long
+    /// enough for the server or a firewall to drop it. The default assumes it does.
+    async fn is_alive(&self) -> bool {
+        true
+    }
+
     /// List all available databases
     async fn list_databases(&self) -> Result<Vec<String>>;
 
     /// List all schemas in a database
     async fn list_schemas(&self, database: &str) -> Result<Vec<String>>;
 
-    /// List all tables in a schema
-    async fn list_tables(&self, schema: &str) -> Result<Vec<String>>;
+    /// List all tables in a schema of `database` (empty for the connected one)
+    async fn list_tables(&self, database: &str, schema: &str) -> Result<Vec<String>>;
+
+    /// List the relations in a schema of `database` that can be browsed like tables, with
+    /// their kind
+    async fn list_relations(
+        &self,
+        database: &str,
+        schema: &str,
+    ) -> Result<Vec<(String, RelationKind)>> {
+        Ok(self
+            .list_tables(database, schema)
+            .await?
+            .into_iter()
+            .map(|table| (table, RelationKind::Table))
+            .collect())
+    }
 
     /// Execute a query with parameters
     async fn execute_query(&self, query: &str) -> Result<QueryResult>;
 
+    /// Execute a statement that returns no rows, returning the number of rows it affected
+    async fn execute_statement(&self, statement: &str) -> Result<u64> {
+        Ok(self.execute_query(statement).await?.affected_rows)
+    }
+
+    /// Execute a row-returning query, keeping only its first `max_rows` rows.
+    /// `in_transaction` tells whether a transaction is open on the connection. The default
+    /// reads every row and drops the rest; backends that can stop reading at the cap do.
+    async fn execute_query_capped(
+        &self,
+        query: &str,
+        max_rows: usize,
+        _in_transaction: bool,
+    ) -> Result<QueryResult> {
+        let mut result = self.execute_query(query).await?;
+        result.rows.truncate(max_rows);
+        Ok(result)
+    }
+
+    /// Execute a query, binding `params` to its `$1`, `$2`, ... placeholders
+    async fn execute_query_with_params(
+        &self,
+        query: &str,
+        params: &[String],
+    ) -> Result<QueryResult> {
+        if params.is_empty() {
+            self.execute_query(query).await
+        } else {
+            Err(anyhow::anyhow!("Query parameters are not supported by this database"))
+        }
+    }
+
     /// Fetch table data with optional filtering and sorting
     async fn fetch_table_data(
         &self,
@@ -86,6 +906,50 @@ pub trait DatabaseConnection: Send + Sync {
         where_clause: Option<&str>,
     ) -> Result<u64>;
 
+    /// Count all rows of a table through a handle sharing this connection, so the count can
+    /// run in the background while the connection goes on serving the UI. `None` when the
+    /// backend can't share its connection; `count_table_rows` counts inline then.
+    fn detached_row_count(&self, _schema: &str, _table: &str) -> Option<RowCountFuture> {
+        None
+    }
+
+    /// Count rows of a base query matching the optional filter
+    async fn count_base_query_rows(
+        &self,
+        base_query: &str,
+        where_clause: Option<&str>,
+    ) -> Result<u64> {
+        let mut query = format!("SELECT COUNT(*) FROM ({}) AS t", base_query);
+        if let Some(w) = where_clause.filter(|w| !w.trim().is_empty()) {
+            query.push_str(&format!(" WHERE {}", w));
+        }
+        let result = self.execute_query(&query).await?;
+        result
+            .rows
+            .first()
+            .and_then(|row| row.first())
+            .and_then(|count| count.as_deref()?.parse::<u64>().ok())
+            .ok_or_else(|| anyhow::anyhow!("Could not read base query row count"))
+    }
+
+    /// Count the rows `params` pages through: its base query or table, filtered by its WHERE
+    /// clause with `where_params` bound
+    async fn count_matching_rows(
+        &self,
+        schema: &str,
+        table: &str,
+        params: &QueryParams,
+    ) -> Result<u64> {
+        if !params.where_params.is_empty() {
+            return Err(anyhow::anyhow!("Query parameters are not supported by this database"));
+        }
+        let where_clause = params.where_clause.as_deref();
+        match &params.base_query {
+            Some(base) => self.count_base_query_rows(base, where_clause).await,
+            None => self.count_table_rows(schema, table, where_clause).await,
+        }
+    }
+
     /// Lookup the referenced table/column for a foreign key on a specific column
     async fn lookup_foreign_key(
         &self,
@@ -96,4 +960,162 @@ pub trait DatabaseConnection: Send + Sync {
 
     /// Get column information including primary key status
     async fn get_columns(&self, schema: &str, table: &str) -> Result<Vec<ColumnInfo>>;
+
+    /// Primary key columns of a table, in key order
+    async fn get_primary_keys(&self, schema: &str, table: &str) -> Result<Vec<String>> {
+        Ok(self
+            .get_columns(schema, table)
+            .await?
+            .into_iter()
+            .filter(|column| column.is_primary_key)
+            .map(|column| column.name)
+            .collect())
+    }
+
+    /// Structure of a table as a result: one row per column with its type, nullability,
+    /// default and key flags. The default is built from `get_columns`, with no defaults or
+    /// foreign keys.
+    async fn describe_table(&self, schema: &str, table: &str) -> Result<QueryResult> {
+        let rows = self
+            .get_columns(schema, table)
+            .await?
+            .into_iter()
+            .map(|column| {
+                structure_row(
+                    column.name,
+                    column.data_type,
+                    column.is_nullable,
+                    None,
+                    column.is_primary_key,
+                    None,
+                )
+            })
+            .collect();
+        Ok(structure_result(rows))
+    }
+
+    /// Indexes of a table as a result: one row per index with its columns, uniqueness and type
+    async fn list_indexes(&self, _schema: &str, _table: &str) -> Result<QueryResult> {
+        Err(anyhow::anyhow!("Listing indexes is not supported for this database"))
+    }
+
+    /// Plan of the page `params` fetches from a table, one line per row. With `analyze` the
+    /// query is run to report actual row counts and timings.
+    async fn explain_query(
+        &self,
+        _schema: &str,
+        _table: &str,
+        _params: &QueryParams,
+        _analyze: bool,
+    ) -> Result<QueryResult> {
+        Err(anyhow::anyhow!("Query plans are not supported for this database"))
+    }
+
+    /// Set `column` to `value` (NULL for `None`) in the row whose `key_columns` equal `key`,
+    /// returning how many rows were updated. The default inlines the values as escaped
+    /// literals.
+    async fn update_cell(
+        &self,
+        schema: &str,
+        table: &str,
+        column: &str,
+        value: Option<&str>,
+        key_columns: &[String],
+        key: &[Option<String>],
+    ) -> Result<u64> {
+        let statement =
+            update_by_key_statement(schema, table, column, value, key_columns, key, |_, value| {
+                format!("'{}'", value.replace('\'', "''"))
+            });
+        self.execute_statement(&statement).await
+    }
+
+    /// Delete the rows whose `key_columns` equal one of the tuples in `keys`, returning how
+    /// many were deleted. The default inlines the values as escaped literals.
+    async fn delete_rows(
+        &self,
+        schema: &str,
+        table: &str,
+        key_columns: &[String],
+        keys: &[Vec<String>],
+    ) -> Result<u64> {
+        if keys.is_empty() {
+            return Ok(0);
+        }
+        let statement = delete_by_keys_statement(schema, table, key_columns, keys, |_, value| {
+            format!("'{}'", value.replace('\'', "''"))
+        });
+        self.execute_statement(&statement).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_own_rows_only_counts_top_level_words() {
+        let postgres = DatabaseType::Postgres;
+        assert!(!limits_own_rows(
+            &postgres,
+            "SELECT * FROM users ORDER BY id"
+        ));
+        assert!(limits_own_rows(&postgres, "SELECT * FROM users LIMIT 10"));
+        assert!(limits_own_rows(
+            &postgres,
+            "SELECT * FROM users OFFSET 5 ROWS"
+        ));
+        assert!(limits_own_rows(
+            &postgres,
+            "SELECT * FROM t FETCH FIRST 3 ROWS ONLY"
+        ));
+        assert!(limits_own_rows(&postgres, "SELECT * FROM t FOR UPDATE"));
+        assert!(!limits_own_rows(
+            &postgres,
+            "SELECT * FROM (SELECT * FROM t LIMIT 5) AS s -- limit\nWHERE note = 'limit'"
+        ));
+        assert!(!limits_own_rows(
+            &postgres,
+            "SELECT $$ limit $$, \"offset\" FROM t"
+        ));
+        assert!(!limits_own_rows(
+            &DatabaseType::MySQL,
+            "SELECT 'it\\'s limit' AS `limit` FROM t # limit"
+        ));
+    }
+
+    #[test]
+    fn order_by_terms_render_nulls_placement() {
+        let terms = OrderByTerm::parse_list("name desc nulls last, score NULLS FIRST, id");
+        let rendered: Vec<String> = terms.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            rendered,
+            ["name DESC NULLS LAST", "score ASC NULLS FIRST", "id ASC"]
+        );
+
+        let emulated: Vec<String> = terms
+            .iter()
+            .map(|term| term.to_sql_emulating_nulls(&format!("`{}`", term.column)))
+            .collect();
+        assert_eq!(
+            emulated,
+            [
+                "`name` IS NULL ASC, `name` DESC",
+                "`score` IS NULL DESC, `score` ASC",
+                "`id` ASC",
+            ]
+        );
+    }
+
+    #[test]
+    fn row_chunk_query_appends_limit_on_its_own_line() {
+        assert_eq!(
+            row_chunk_query("SELECT * FROM t -- all", 100, 200),
+            "SELECT * FROM t -- all\nLIMIT 100 OFFSET 200"
+        );
+        assert_eq!(
+            row_query(&DatabaseType::SQLite, "SELECT * FROM t;  "),
+            Some("SELECT * FROM t")
+        );
+    }
 }
@@ -1,23 +1,46 @@
 use super::{
-    core::DatabaseConnection, mongodb::MongoConnection, postgres::PostgresConnection,
-    sqlite::SqliteConnection, ConnectionConfig, ConnectionStatus, DatabaseType,
+    core::{DatabaseConnection, RelationKind},
+    mock::MockConnection,
+    mongodb::MongoConnection,
+    mysql::MySqlConnection,
+    postgres::PostgresConnection,
+    sqlite::SqliteConnection,
+    ConnectionConfig, ConnectionStatus, DatabaseType,
 };
+use crate::logging;
 use anyhow::Result;
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 use tokio::task::JoinSet;
 
+/// Longest a liveness check may take before the connection is taken for dropped
+const LIVENESS_TIMEOUT: Duration = Duration::from_secs(5);
+
 pub fn create_database_connection(config: ConnectionConfig) -> Box<dyn DatabaseConnection> {
     match config.db_type {
         DatabaseType::Postgres => Box::new(PostgresConnection::new(config)),
         DatabaseType::MongoDB => Box::new(MongoConnection::new(config)),
         DatabaseType::SQLite => Box::new(SqliteConnection::new(config)),
+        DatabaseType::MySQL => Box::new(MySqlConnection::new(config)),
+        DatabaseType::Mock => Box::new(MockConnection::new(config)),
     }
 }
 
+/// Creates and connects a connection for `config`, refusing one whose secrets are keyring
+/// references the OS keyring couldn't resolve
+async fn open_connection(config: ConnectionConfig) -> Result<Box<dyn DatabaseConnection>> {
+    config.check_secrets_resolved()?;
+    let mut connection = create_database_connection(config);
+    connection.connect().await?;
+    Ok(connection)
+}
+
 // Connection manager to handle database connections
 pub struct ConnectionManager {
     pub connections: HashMap<String, Box<dyn DatabaseConnection>>,
     pub connection_statuses: HashMap<String, ConnectionStatus>,
+    /// When each connection was last made sure of, before the query that followed
+    last_used: HashMap<String, Instant>,
 }
 
 impl ConnectionManager {
@@ -25,16 +48,17 @@ impl ConnectionManager {
         Self {
             connections: HashMap::new(),
             connection_statuses: HashMap::new(),
+            last_used: HashMap::new(),
         }
     }
 
     pub async fn connect(&mut self, config: ConnectionConfig) -> Result<()> {
         self.connection_statuses
             .insert(config.name.clone(), ConnectionStatus::Connecting);
-        let mut connection = create_database_connection(config.clone());
-        match connection.connect().await {
-            Ok(_) => {
+        match open_connection(config.clone()).await {
+            Ok(connection) => {
                 self.connections.insert(config.name.clone(), connection);
+                self.last_used.insert(config.name.clone(), Instant::now());
                 self.connection_statuses
                     .insert(config.name.clone(), ConnectionStatus::Connected);
                 Ok(())
@@ -47,16 +71,77 @@ impl ConnectionManager {
         }
     }
 
-    pub async fn disconnect(&mut self, name: &str) -> Result<()> {
-        if let Some(connection) = self.connections.get_mut(name) {
-            connection.disconnect().await?;
-            self.connections.remove(name);
+    /// Connects `config` anew and only then closes the connection of the same name it
+    /// replaces, so a failed connect leaves that one in use
+    pub async fn replace(&mut self, config: ConnectionConfig) -> Result<()> {
+        let connection = open_connection(config.clone()).await?;
+        if let Some(mut previous) = self.connections.insert(config.name.clone(), connection) {
+            if let Err(e) = previous.disconnect().await {
+                logging::warn(&format!("Error disconnecting {}: {}", config.name, e));
+            }
         }
+        self.last_used.insert(config.name.clone(), Instant::now());
         self.connection_statuses
-            .insert(name.to_string(), ConnectionStatus::NotConnected);
+            .insert(config.name, ConnectionStatus::Connected);
         Ok(())
     }
 
+    pub async fn disconnect(&mut self, name: &str) -> Result<()> {
+        // Forgotten even if closing fails, so the next connect starts from scratch
+        let connection = self.connections.remove(name);
+        self.last_used.remove(name);
+        self.connection_statuses
+            .insert(name.to_string(), ConnectionStatus::NotConnected);
+        match connection {
+            Some(mut connection) => connection.disconnect().await,
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that connection `name` still reaches its database when it has been idle for
+    /// `idle_after` or longer, and reconnects it (SSH tunnel included) if it doesn't.
+    /// Returns whether it was reconnected; unknown connections are left alone.
+    pub async fn ensure_alive(&mut self, name: &str, idle_after: Duration) -> Result<bool> {
+        let Some(connection) = self.connections.get_mut(name) else {
+            return Ok(false);
+        };
+        let idle = self
+            .last_used
+            .insert(name.to_string(), Instant::now())
+            .is_none_or(|last_used| last_used.elapsed() >= idle_after);
+        if !idle {
+            return Ok(false);
+        }
+        let alive = tokio::time::timeout(LIVENESS_TIMEOUT, connection.is_alive())
+            .await
+            .unwrap_or(false);
+        if alive {
+            return Ok(false);
+        }
+
+        logging::warn(&format!("Connection {} was lost, reconnecting", name));
+        if let Err(e) = connection.disconnect().await {
+            logging::warn(&format!("Error closing lost connection {}: {}", name, e));
+        }
+        self.connection_statuses
+            .insert(name.to_string(), ConnectionStatus::Connecting);
+        match connection.connect().await {
+            Ok(()) => {
+                self.connection_statuses
+                    .insert(name.to_string(), ConnectionStatus::Connected);
+                Ok(true)
+            }
+            Err(e) => {
+                // Dropped so the next use connects from scratch instead of reusing a dead client
+                self.connections.remove(name);
+                self.last_used.remove(name);
+                self.connection_statuses
+                    .insert(name.to_string(), ConnectionStatus::Failed);
+                Err(e)
+            }
+        }
+    }
+
     pub fn get_connection(&self, name: &str) -> Option<&Box<dyn DatabaseConnection>> {
         self.connections.get(name)
     }
@@ -78,16 +163,15 @@ impl ConnectionManager {
         for config in configs {
             let config_clone = config.clone();
             join_set.spawn(async move {
-                let mut connection = create_database_connection(config_clone.clone());
-                let result = connection.connect().await;
-                (config_clone.name, result, connection)
+                let result = open_connection(config_clone.clone()).await;
+                (config_clone.name, result)
             });
         }
 
         while let Some(result) = join_set.join_next().await {
             match result {
-                Ok((name, connection_result, connection)) => match connection_result {
-                    Ok(_) => {
+                Ok((name, connection_result)) => match connection_result {
+                    Ok(connection) => {
                         self.connections.insert(name.clone(), connection);
                         self.connection_statuses
                             .insert(name.clone(), ConnectionStatus::Connected);
@@ -117,8 +201,7 @@ impl ConnectionManager {
         let timeout_duration = std::time::Duration::from_secs(5);
 
         let prefetch_result = tokio::time::timeout(timeout_duration, async {
-            let mut connection = create_database_connection(config.clone());
-            connection.connect().await?;
+            let connection = open_connection(config.clone()).await?;
 
             let databases = connection.list_databases().await?;
             let mut prefetched_databases = Vec::new();
@@ -159,8 +242,7 @@ impl ConnectionManager {
         let timeout_duration = std::time::Duration::from_secs(30);
 
         let prefetch_result = tokio::time::timeout(timeout_duration, async {
-            let mut connection = create_database_connection(config.clone());
-            connection.connect().await?;
+            let connection = open_connection(config.clone()).await?;
 
             let databases = connection.list_databases().await?;
             let mut prefetched_databases = Vec::new();
@@ -170,11 +252,11 @@ impl ConnectionManager {
                 let mut prefetched_schemas = Vec::new();
 
                 for schema_name in schemas {
-                    let tables = connection.list_tables(&schema_name).await?;
-                    prefetched_schemas.push(PrefetchedSchema {
-                        name: schema_name,
-                        tables,
-                    });
+                    let relations = connection.list_relations(&db_name, &schema_name).await?;
+                    prefetched_schemas.push(PrefetchedSchema::with_relations(
+                        schema_name,
+                        relations,
+                    ));
                 }
 
                 prefetched_databases.push(PrefetchedDatabase {
@@ -215,6 +297,7 @@ impl ConnectionManager {
                 prefetched_schemas.push(PrefetchedSchema {
                     name: schema_name,
                     tables: Vec::new(), // Empty - will be loaded on-demand
+                    relation_kinds: HashMap::new(),
                 });
             }
 
@@ -228,10 +311,11 @@ impl ConnectionManager {
     pub async fn prefetch_tables_for_schema(
         &mut self,
         connection_name: &str,
+        database_name: &str,
         schema_name: &str,
-    ) -> Result<Vec<String>> {
+    ) -> Result<Vec<(String, RelationKind)>> {
         if let Some(connection) = self.connections.get_mut(connection_name) {
-            connection.list_tables(schema_name).await
+            connection.list_relations(database_name, schema_name).await
         } else {
             Err(anyhow::anyhow!("Connection not found: {}", connection_name)
                 .context(format!("Failed to prefetch schemas for connection: {}", connection_name)))
@@ -246,8 +330,7 @@ impl ConnectionManager {
         let timeout_duration = std::time::Duration::from_secs(3);
 
         let prefetch_result = tokio::time::timeout(timeout_duration, async {
-            let mut connection = create_database_connection(config.clone());
-            connection.connect().await?;
+            let connection = open_connection(config.clone()).await?;
 
             let databases = connection.list_databases().await?;
             let mut prefetched_databases = Vec::new();
@@ -282,8 +365,7 @@ impl ConnectionManager {
         let timeout_duration = std::time::Duration::from_secs(5);
 
         let validation_result = tokio::time::timeout(timeout_duration, async {
-            let mut connection = create_database_connection(config.clone());
-            connection.connect().await?;
+            let mut connection = open_connection(config.clone()).await?;
 
             // Try to list databases to ensure the connection is working
             let _databases = connection.list_databases().await?;
@@ -320,4 +402,16 @@ pub struct PrefetchedDatabase {
 pub struct PrefetchedSchema {
     pub name: String,
     pub tables: Vec<String>,
+    /// Kind of each entry in `tables`
+    pub relation_kinds: HashMap<String, RelationKind>,
+}
+
+impl PrefetchedSchema {
+    pub fn with_relations(name: String, relations: Vec<(String, RelationKind)>) -> Self {
+        Self {
+            name,
+            tables: relations.iter().map(|(table, _)| table.clone()).collect(),
+            relation_kinds: relations.into_iter().collect(),
+        }
+    }
 }
@@ -0,0 +1,621 @@
+//! Offline connection serving tables from a JSON or CSV file, for demos, screenshots and
+//! exercising the UI without a live database.
+//!
+//! A JSON file maps table names to arrays of row objects, e.g.
+//! `{"users": [{"id": 1, "name": "Ann"}], "orders": [...]}`; a table's columns are the keys in
+//! the order they first appear. A CSV file is a single table named after the file, with a
+//! header row. Every table lives in database `mock`, schema `main`.
+//!
+//! WHERE, ORDER BY and pagination are applied in memory. The WHERE interpreter is deliberately
+//! small: conditions joined by `AND`, each either `column <op> value` with `<op>` one of
+//! `= != <> < <= > >=`, or `column IS [NOT] NULL`. Values are numbers, `'quoted strings'` or
+//! `$n` parameters; two values compare as numbers when both parse as one, otherwise as text.
+//! Parentheses are skipped, as with only AND they can't change what a clause means.
+
+use super::core::*;
+use anyhow::Result;
+use async_trait::async_trait;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+const MOCK_DATABASE: &str = "mock";
+const MOCK_SCHEMA: &str = "main";
+
+/// Stored cell value for SQL NULL; it becomes `None` in query results
+const NULL: &str = "NULL";
+
+#[derive(Debug, Clone, Default)]
+struct MockTable {
+    columns: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl MockTable {
+    fn column_index(&self, name: &str) -> Result<usize> {
+        let name = name.trim_matches('"');
+        self.columns
+            .iter()
+            .position(|column| column == name)
+            .ok_or_else(|| anyhow::anyhow!("Column '{}' does not exist", name))
+    }
+
+    /// Rows matching `where_clause`, sorted by `order_by`
+    fn select(
+        &self,
+        where_clause: Option<&str>,
+        order_by: Option<&str>,
+        params: &[String],
+    ) -> Result<Vec<&Vec<String>>> {
+        let conditions = match where_clause.filter(|w| !w.trim().is_empty()) {
+            Some(w) => parse_where(w, params)?,
+            None => Vec::new(),
+        };
+        let conditions = conditions
+            .into_iter()
+            .map(|condition| Ok((self.column_index(&condition.column)?, condition)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut rows: Vec<&Vec<String>> = self
+            .rows
+            .iter()
+            .filter(|row| {
+                conditions
+                    .iter()
+                    .all(|(index, condition)| condition.matches(&row[*index]))
+            })
+            .collect();
+
+        let terms = OrderByTerm::parse_list(order_by.unwrap_or(""))
+            .into_iter()
+            .map(|term| Ok((self.column_index(&term.column)?, term)))
+            .collect::<Result<Vec<_>>>()?;
+        if !terms.is_empty() {
+            rows.sort_by(|a, b| {
+                terms
+                    .iter()
+                    .map(|(index, term)| compare_for_sort(&a[*index], &b[*index], term))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            });
+        }
+        Ok(rows)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CompareOp {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    IsNull,
+    IsNotNull,
+    /// `LIKE`, or `ILIKE` with `ignore_case`, with the pattern's optional `ESCAPE` character
+    Like {
+        ignore_case: bool,
+        escape: Option<char>,
+    },
+}
+
+/// One `column <op> value` condition of a WHERE clause; `value` is `None` for NULL
+#[derive(Debug, Clone)]
+struct Condition {
+    column: String,
+    op: CompareOp,
+    value: Option<String>,
+}
+
+impl Condition {
+    fn matches(&self, cell: &str) -> bool {
+        let is_null = cell == NULL;
+        match self.op {
+            CompareOp::IsNull => is_null,
+            CompareOp::IsNotNull => !is_null,
+            // As in SQL, comparing with NULL is never true
+            _ if is_null => false,
+            CompareOp::Like {
+                ignore_case,
+                escape,
+            } => self.value.as_deref().is_some_and(|pattern| {
+                let (cell, pattern) = if ignore_case {
+                    (cell.to_lowercase(), pattern.to_lowercase())
+                } else {
+                    (cell.to_string(), pattern.to_string())
+                };
+                let cell: Vec<char> = cell.chars().collect();
+                let pattern: Vec<char> = pattern.chars().collect();
+                like_matches(&cell, &pattern, escape)
+            }),
+            op => match &self.value {
+                None => false,
+                Some(value) => {
+                    let ordering = compare_values(cell, value);
+                    match op {
+                        CompareOp::Eq => ordering.is_eq(),
+                        CompareOp::NotEq => ordering.is_ne(),
+                        CompareOp::Lt => ordering.is_lt(),
+                        CompareOp::LtEq => ordering.is_le(),
+                        CompareOp::Gt => ordering.is_gt(),
+                        CompareOp::GtEq => ordering.is_ge(),
+                        CompareOp::IsNull | CompareOp::IsNotNull | CompareOp::Like { .. } => false,
+                    }
+                }
+            },
+        }
+    }
+}
+
+/// Whether `text` matches the LIKE `pattern`: `%` stands for any run of characters, `_` for
+/// any one, and `escape` makes the character after it match itself
+fn like_matches(text: &[char], pattern: &[char], escape: Option<char>) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        [c, literal, rest @ ..] if Some(*c) == escape => {
+            text.first() == Some(literal) && like_matches(&text[1..], rest, escape)
+        }
+        ['%', rest @ ..] => (0..=text.len()).any(|skip| like_matches(&text[skip..], rest, escape)),
+        ['_', rest @ ..] => !text.is_empty() && like_matches(&text[1..], rest, escape),
+        [c, rest @ ..] => text.first() == Some(c) && like_matches(&text[1..], rest, escape),
+    }
+}
+
+/// Compares as numbers when both values are numeric, otherwise as text
+fn compare_values(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        _ => a.cmp(b),
+    }
+}
+
+/// Sort order of two cells for `term`; NULLs sort like PostgreSQL unless placed explicitly
+fn compare_for_sort(a: &str, b: &str, term: &OrderByTerm) -> Ordering {
+    let nulls_first = match term.nulls {
+        Some(NullsOrder::First) => true,
+        Some(NullsOrder::Last) => false,
+        None => term.descending,
+    };
+    match (a == NULL, b == NULL) {
+        (true, true) => Ordering::Equal,
+        (true, false) if nulls_first => Ordering::Less,
+        (true, false) => Ordering::Greater,
+        (false, true) if nulls_first => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) if term.descending => compare_values(b, a),
+        (false, false) => compare_values(a, b),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Identifier(String),
+    Operator(String),
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() || c == '(' || c == ')' {
+            chars.next();
+        } else if c == '\'' || c == '"' {
+            chars.next();
+            let mut text = String::new();
+            loop {
+                match chars.next() {
+                    // A doubled quote inside a quoted value is an escaped quote
+                    Some(q) if q == c && chars.peek() == Some(&c) => {
+                        chars.next();
+                        text.push(c);
+                    }
+                    Some(q) if q == c => break,
+                    Some(other) => text.push(other),
+                    None => return Err(anyhow::anyhow!("Unterminated quote in WHERE clause")),
+                }
+            }
+            tokens.push(if c == '\'' {
+                Token::Quoted(text)
+            } else {
+                Token::Identifier(text)
+            });
+        } else if "=<>!".contains(c) {
+            let mut op = String::new();
+            while let Some(&c) = chars.peek().filter(|c| "=<>!".contains(**c)) {
+                op.push(c);
+                chars.next();
+            }
+            tokens.push(Token::Operator(op));
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars
+                .peek()
+                .filter(|c| !c.is_whitespace() && !"=<>!'\"()".contains(**c))
+            {
+                word.push(c);
+                chars.next();
+            }
+            tokens.push(Token::Word(word));
+        }
+    }
+    Ok(tokens)
+}
+
+fn is_keyword(token: Option<&Token>, keyword: &str) -> bool {
+    matches!(token, Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword))
+}
+
+/// Parses the supported subset of WHERE clauses, resolving `$n` against `params`
+fn parse_where(where_clause: &str, params: &[String]) -> Result<Vec<Condition>> {
+    let unsupported = || {
+        anyhow::anyhow!(
+            "Unsupported WHERE clause for a mock connection: {} (use `column <op> value` \
+             conditions joined by AND)",
+            where_clause
+        )
+    };
+
+    let tokens = tokenize(where_clause)?;
+    let mut tokens = tokens.iter().peekable();
+    let mut conditions = Vec::new();
+    loop {
+        let column = match tokens.next() {
+            Some(Token::Word(word)) | Some(Token::Identifier(word)) => word.clone(),
+            _ => return Err(unsupported()),
+        };
+
+        let condition = if is_keyword(tokens.peek().copied(), "IS") {
+            tokens.next();
+            let op = if is_keyword(tokens.peek().copied(), "NOT") {
+                tokens.next();
+                CompareOp::IsNotNull
+            } else {
+                CompareOp::IsNull
+            };
+            if !is_keyword(tokens.next(), "NULL") {
+                return Err(unsupported());
+            }
+            Condition {
+                column,
+                op,
+                value: None,
+            }
+        } else if is_keyword(tokens.peek().copied(), "LIKE")
+            || is_keyword(tokens.peek().copied(), "ILIKE")
+        {
+            let ignore_case = is_keyword(tokens.next(), "ILIKE");
+            let Some(Token::Quoted(pattern)) = tokens.next() else {
+                return Err(unsupported());
+            };
+            let escape = if is_keyword(tokens.peek().copied(), "ESCAPE") {
+                tokens.next();
+                match tokens.next() {
+                    Some(Token::Quoted(escape)) if escape.chars().count() == 1 => {
+                        escape.chars().next()
+                    }
+                    _ => return Err(unsupported()),
+                }
+            } else {
+                None
+            };
+            Condition {
+                column,
+                op: CompareOp::Like {
+                    ignore_case,
+                    escape,
+                },
+                value: Some(pattern.clone()),
+            }
+        } else {
+            let op = match tokens.next() {
+                Some(Token::Operator(op)) => match op.as_str() {
+                    "=" => CompareOp::Eq,
+                    "!=" | "<>" => CompareOp::NotEq,
+                    "<" => CompareOp::Lt,
+                    "<=" => CompareOp::LtEq,
+                    ">" => CompareOp::Gt,
+                    ">=" => CompareOp::GtEq,
+                    _ => return Err(unsupported()),
+                },
+                _ => return Err(unsupported()),
+            };
+            let value = match tokens.next() {
+                Some(Token::Quoted(text)) => Some(text.clone()),
+                Some(Token::Word(word)) if word.eq_ignore_ascii_case(NULL) => None,
+                Some(Token::Word(word)) => match word.strip_prefix('$') {
+                    Some(index) => {
+                        let value = index
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|index| params.get(index.wrapping_sub(1)))
+                            .ok_or_else(|| anyhow::anyhow!("No value bound to {}", word))?;
+                        Some(value.clone())
+                    }
+                    None => Some(word.clone()),
+                },
+                _ => return Err(unsupported()),
+            };
+            Condition { column, op, value }
+        };
+        conditions.push(condition);
+
+        match tokens.next() {
+            None => return Ok(conditions),
+            token if is_keyword(token, "AND") => {}
+            _ => return Err(unsupported()),
+        }
+    }
+}
+
+/// Splits CSV text into records, handling quoted fields with embedded commas, quotes and
+/// newlines
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => record.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes => {}
+            '\n' if !in_quotes => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+fn load_csv(name: &str, text: &str) -> Result<BTreeMap<String, MockTable>> {
+    let mut records = parse_csv(text).into_iter();
+    let columns = records
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("CSV file has no header row"))?;
+    let rows = records
+        .filter(|record| record.iter().any(|field| !field.is_empty()))
+        .map(|mut record| {
+            record.resize(columns.len(), NULL.to_string());
+            record
+        })
+        .collect();
+
+    let mut tables = BTreeMap::new();
+    tables.insert(name.to_string(), MockTable { columns, rows });
+    Ok(tables)
+}
+
+fn json_to_cell(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => NULL.to_string(),
+        serde_json::Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn load_json(text: &str) -> Result<BTreeMap<String, MockTable>> {
+    let root: serde_json::Value = serde_json::from_str(text)?;
+    let root = root
+        .as_object()
+        .ok_or_else(|| anyhow::anyhow!("Mock data must be an object of table name to rows"))?;
+
+    let mut tables = BTreeMap::new();
+    for (name, rows) in root {
+        let rows = rows
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("Table '{}' must be an array of rows", name))?;
+        let objects = rows
+            .iter()
+            .map(|row| {
+                row.as_object()
+                    .ok_or_else(|| anyhow::anyhow!("Rows of table '{}' must be objects", name))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut table = MockTable::default();
+        for object in &objects {
+            for key in object.keys() {
+                if !table.columns.contains(key) {
+                    table.columns.push(key.clone());
+                }
+            }
+        }
+        table.rows = objects
+            .iter()
+            .map(|object| {
+                table
+                    .columns
+                    .iter()
+                    .map(|column| object.get(column).map_or(NULL.to_string(), json_to_cell))
+                    .collect()
+            })
+            .collect();
+        tables.insert(name.clone(), table);
+    }
+    Ok(tables)
+}
+
+pub struct MockConnection {
+    config: super::ConnectionConfig,
+    tables: Option<BTreeMap<String, MockTable>>,
+}
+
+impl MockConnection {
+    pub fn new(config: super::ConnectionConfig) -> Self {
+        Self {
+            config,
+            tables: None,
+        }
+    }
+
+    /// The data file is stored as the database or the host, like a SQLite path
+    fn resolve_path(&self) -> String {
+        if let Some(path) = &self.config.default_database {
+            path.clone()
+        } else {
+            self.config.host.clone()
+        }
+    }
+
+    fn table(&self, table: &str) -> Result<&MockTable> {
+        self.tables
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Not connected to database"))?
+            .get(table)
+            .ok_or_else(|| anyhow::anyhow!("Table '{}' does not exist", table))
+    }
+}
+
+#[async_trait]
+impl DatabaseConnection for MockConnection {
+    async fn connect(&mut self) -> Result<()> {
+        let path = self.resolve_path();
+        let text = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|e| anyhow::anyhow!("Could not read mock data file {}: {}", path, e))?;
+
+        let path = Path::new(&path);
+        let is_csv = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"));
+        let tables = if is_csv {
+            let name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| "data".to_string());
+            load_csv(&name, &text)?
+        } else {
+            load_json(&text)?
+        };
+        self.tables = Some(tables);
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        self.tables = None;
+        Ok(())
+    }
+
+    async fn list_databases(&self) -> Result<Vec<String>> {
+        Ok(vec![MOCK_DATABASE.to_string()])
+    }
+
+    async fn list_schemas(&self, _database: &str) -> Result<Vec<String>> {
+        Ok(vec![MOCK_SCHEMA.to_string()])
+    }
+
+    async fn list_tables(&self, _database: &str, _schema: &str) -> Result<Vec<String>> {
+        let tables = self
+            .tables
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Not connected to database"))?;
+        Ok(tables.keys().cloned().collect())
+    }
+
+    async fn execute_query(&self, query: &str) -> Result<QueryResult> {
+        Err(anyhow::anyhow!(
+            "Mock connections can only browse tables, not run `{}`",
+            query
+        ))
+    }
+
+    async fn fetch_table_data(
+        &self,
+        _schema: &str,
+        table: &str,
+        params: &QueryParams,
+    ) -> Result<QueryResult> {
+        if params.base_query.is_some() {
+            return Err(anyhow::anyhow!("Mock connections do not support base queries"));
+        }
+
+        let table = self.table(table)?;
+        let rows = table.select(
+            params.where_clause.as_deref(),
+            params.order_by.as_deref(),
+            &params.where_params,
+        )?;
+        let rows: Vec<Vec<Option<String>>> = rows
+            .into_iter()
+            .skip(params.offset.unwrap_or(0) as usize)
+            .take(params.limit.map_or(usize::MAX, |limit| limit as usize))
+            .map(|row| {
+                row.iter()
+                    .map(|cell| Some(cell.clone()).filter(|cell| cell != NULL))
+                    .collect()
+            })
+            .collect();
+
+        Ok(QueryResult {
+            columns: table.columns.clone(),
+            affected_rows: rows.len() as u64,
+            rows,
+            column_types: Vec::new(),
+        })
+    }
+
+    async fn count_table_rows(
+        &self,
+        _schema: &str,
+        table: &str,
+        where_clause: Option<&str>,
+    ) -> Result<u64> {
+        let rows = self.table(table)?.select(where_clause, None, &[])?;
+        Ok(rows.len() as u64)
+    }
+
+    async fn count_matching_rows(
+        &self,
+        _schema: &str,
+        table: &str,
+        params: &QueryParams,
+    ) -> Result<u64> {
+        if params.base_query.is_some() {
+            return Err(anyhow::anyhow!("Mock connections do not support base queries"));
+        }
+        let rows = self.table(table)?.select(
+            params.where_clause.as_deref(),
+            None,
+            &params.where_params,
+        )?;
+        Ok(rows.len() as u64)
+    }
+
+    async fn lookup_foreign_key(
+        &self,
+        _schema: &str,
+        _table: &str,
+        _column: &str,
+    ) -> Result<Option<ForeignKeyTarget>> {
+        Ok(None)
+    }
+
+    async fn get_columns(&self, _schema: &str, table: &str) -> Result<Vec<ColumnInfo>> {
+        let table = self.table(table)?;
+        Ok(table
+            .columns
+            .iter()
+            .map(|name| ColumnInfo {
+                name: name.clone(),
+                data_type: "text".to_string(),
+                is_nullable: true,
+                is_primary_key: false,
+            })
+            .collect())
+    }
+}
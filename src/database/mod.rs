@@ -1,10 +1,13 @@
 pub mod core;
-pub use core::{DatabaseConnection, QueryParams, QueryResult};
+pub use core::{DatabaseConnection, QueryParams, QueryResult, RelationKind};
 
 // Database implementations
+mod mock;
 mod mongodb;
+mod mysql;
 mod postgres;
 mod sqlite;
+pub use mongodb::{parse_filter as parse_mongo_filter, parse_sort as parse_mongo_sort};
 
 // SSH tunneling support
 pub mod ssh_tunnel;
@@ -15,13 +18,20 @@ pub use factory::{ConnectionManager, PrefetchedDatabase, PrefetchedSchema, Prefe
 
 
 // Common types and configurations
+use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::Duration;
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub enum DatabaseType {
     Postgres,
     MongoDB,
     SQLite,
+    /// MySQL or MariaDB
+    MySQL,
+    /// Offline tables loaded from a JSON or CSV file (see `mock.rs`)
+    Mock,
 }
 
 impl Default for DatabaseType {
@@ -30,6 +40,34 @@ impl Default for DatabaseType {
     }
 }
 
+impl DatabaseType {
+    /// Port the server listens on by default; 0 for file backed databases
+    pub fn default_port(&self) -> u16 {
+        match self {
+            DatabaseType::Postgres => 5432,
+            DatabaseType::MongoDB => 27017,
+            DatabaseType::MySQL => 3306,
+            DatabaseType::SQLite | DatabaseType::Mock => 0,
+        }
+    }
+
+    /// Parses a port typed into the connection form. File backed databases need none, so an
+    /// empty port falls back to their default; servers need one in 1-65535.
+    pub fn parse_port(&self, port: &str) -> Result<u16, String> {
+        let port = port.trim();
+        if port.is_empty() {
+            return match self.default_port() {
+                0 => Ok(0),
+                _ => Err("Port is required".to_string()),
+            };
+        }
+        match port.parse::<u16>() {
+            Ok(port) if port > 0 => Ok(port),
+            _ => Err(format!("Port must be a number from 1 to 65535, not '{}'", port)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ConnectionStatus {
     NotConnected,
@@ -38,6 +76,41 @@ pub enum ConnectionStatus {
     Failed,
 }
 
+/// Part of a connection that failed to come up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailedStage {
+    SshTunnel,
+    Database,
+}
+
+/// Why a connection last failed, kept until it connects again
+#[derive(Debug, Clone)]
+pub struct ConnectionFailure {
+    pub stage: FailedStage,
+    /// The error followed by its causes, one per line
+    pub message: String,
+    pub at: chrono::DateTime<chrono::Local>,
+}
+
+impl ConnectionFailure {
+    pub fn from_error(err: &anyhow::Error) -> Self {
+        let stage = if err.downcast_ref::<ssh_tunnel::TunnelError>().is_some() {
+            FailedStage::SshTunnel
+        } else {
+            FailedStage::Database
+        };
+        Self {
+            stage,
+            message: err
+                .chain()
+                .map(|cause| cause.to_string())
+                .collect::<Vec<_>>()
+                .join("\ncaused by: "),
+            at: chrono::Local::now(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct SSHConfig {
     #[serde(default)]
@@ -50,6 +123,12 @@ pub struct SSHConfig {
     pub private_key_path: Option<String>,
     #[serde(default)]
     pub password: Option<String>,
+    /// Passphrase of an encrypted `private_key_path`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub passphrase: Option<String>,
+    /// Authenticate with the running ssh-agent, ignoring the key and password
+    #[serde(default)]
+    pub use_agent: bool,
 }
 
 fn default_ssh_port() -> u16 {
@@ -99,6 +178,29 @@ pub struct ConnectionConfig {
     pub ssh_tunnel: Option<SSHConfig>,
     #[serde(default)]
     pub ssh_tunnel_name: Option<String>,
+    /// SQL statements executed in order right after connecting (e.g. `SET statement_timeout = '30s'`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub on_connect: Vec<String>,
+    /// Schema to scope the tree to; its tables are listed directly under the connection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope_schema: Option<String>,
+    /// Relation kinds listed in the tree (e.g. `["table", "view"]`); empty lists every kind
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relation_kinds: Vec<RelationKind>,
+    /// Rejects statements that change data or schema (see `blocks_statement`)
+    #[serde(default)]
+    pub read_only: bool,
+    /// Rows per page of the tables opened on this connection (see `page_size`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_page_size: Option<u32>,
+    /// Seconds a query may run before it is cancelled (0 never cancels); see
+    /// `statement_timeout`
+    #[serde(default = "default_statement_timeout")]
+    pub statement_timeout: u64,
+    /// Times a failed background connect is retried, overriding `connect_retries` under
+    /// `[database]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_retries: Option<u32>,
     /// Legacy field for backward compatibility
     /// If set, it will be used as default_database and added to databases
     #[serde(default, skip_serializing)]
@@ -118,12 +220,104 @@ impl Default for ConnectionConfig {
             databases: std::collections::HashMap::new(),
             ssh_tunnel: None,
             ssh_tunnel_name: None,
+            on_connect: Vec::new(),
+            scope_schema: None,
+            relation_kinds: Vec::new(),
+            read_only: false,
+            default_page_size: None,
+            statement_timeout: default_statement_timeout(),
+            connect_retries: None,
             database: None,
         }
     }
 }
 
+fn default_statement_timeout() -> u64 {
+    30
+}
+
+/// Runs `query`, giving up after `timeout` so a runaway statement can't hang the app. The
+/// server may still be running it; Postgres cancels it with its own `statement_timeout`.
+pub async fn with_timeout<T>(
+    timeout: Option<Duration>,
+    query: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    let Some(timeout) = timeout else {
+        return query.await;
+    };
+    tokio::time::timeout(timeout, query)
+        .await
+        .unwrap_or_else(|_| Err(anyhow!("Query timed out after {}s", timeout.as_secs())))
+}
+
+/// Rows per page when a connection doesn't set `default_page_size`
+pub const DEFAULT_PAGE_SIZE: u32 = 50;
+
+/// Largest page size accepted; bigger pages make every fetch and redraw slow
+pub const MAX_PAGE_SIZE: u32 = 1000;
+
+/// Keeps a page size between 1 and `MAX_PAGE_SIZE`
+pub fn clamp_page_size(page_size: u32) -> u32 {
+    page_size.clamp(1, MAX_PAGE_SIZE)
+}
+
+/// Prefix of a password naming an OS keyring entry (`keyring:<entry>`) instead of holding it
+pub const KEYRING_REFERENCE_PREFIX: &str = "keyring:";
+
+/// Status message for a statement refused on a read-only connection
+pub const READ_ONLY_MESSAGE: &str = "Blocked: connection is read-only";
+
+/// Leading keywords of statements a read-only connection refuses
+const WRITE_KEYWORDS: [&str; 6] = ["INSERT", "UPDATE", "DELETE", "DROP", "TRUNCATE", "ALTER"];
+
 impl ConnectionConfig {
+    /// Whether this read-only connection refuses `statement`, judged by its first keyword
+    pub fn blocks_statement(&self, statement: &str) -> bool {
+        let Some(keyword) = statement
+            .split(|c: char| !c.is_ascii_alphabetic())
+            .find(|word| !word.is_empty())
+        else {
+            return false;
+        };
+        self.read_only
+            && WRITE_KEYWORDS
+                .iter()
+                .any(|write| keyword.eq_ignore_ascii_case(write))
+    }
+
+    /// Fails when the password, SSH password or passphrase is still a keyring reference,
+    /// i.e. the OS keyring couldn't resolve it, so it is never sent as the secret itself
+    pub fn check_secrets_resolved(&self) -> Result<()> {
+        let ssh = self.ssh_tunnel.as_ref();
+        let unresolved = [
+            self.password.as_deref(),
+            ssh.and_then(|ssh| ssh.password.as_deref()),
+            ssh.and_then(|ssh| ssh.passphrase.as_deref()),
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(|secret| secret.strip_prefix(KEYRING_REFERENCE_PREFIX));
+        match unresolved {
+            Some(entry) => Err(anyhow!("keyring entry not found: {}", entry)),
+            None => Ok(()),
+        }
+    }
+
+    /// Rows per page for tables opened on this connection
+    pub fn page_size(&self) -> u32 {
+        clamp_page_size(self.default_page_size.unwrap_or(DEFAULT_PAGE_SIZE))
+    }
+
+    /// How long a query may run, `None` when `statement_timeout` is 0
+    pub fn statement_timeout(&self) -> Option<Duration> {
+        (self.statement_timeout > 0).then(|| Duration::from_secs(self.statement_timeout))
+    }
+
+    /// Whether relations of `kind` are listed in the tree
+    pub fn shows_relation(&self, kind: RelationKind) -> bool {
+        self.relation_kinds.is_empty() || self.relation_kinds.contains(&kind)
+    }
+
     /// Migrate from old format to new format
     /// This handles backward compatibility with the old `database` field
     pub fn migrate_from_legacy(&mut self) {
@@ -161,4 +355,57 @@ impl ConnectionConfig {
     pub fn get_database_config(&self, db_name: &str) -> Option<&DatabaseConfig> {
         self.databases.get(db_name)
     }
+
+    /// Connection string for external tools, e.g. `postgres://user@host:5432/db`
+    pub fn dsn(&self, include_password: bool) -> String {
+        let database = self
+            .default_database
+            .as_deref()
+            .or(self.database.as_deref())
+            .unwrap_or("");
+
+        let scheme = match self.db_type {
+            DatabaseType::Postgres => "postgres",
+            DatabaseType::MongoDB => "mongodb",
+            DatabaseType::MySQL => "mysql",
+            // SQLite connections are a file path, stored as the database or the host
+            DatabaseType::SQLite => {
+                let path = if database.is_empty() { &self.host } else { database };
+                return format!("sqlite://{}", path);
+            }
+            DatabaseType::Mock => {
+                let path = if database.is_empty() { &self.host } else { database };
+                return format!("mock://{}", path);
+            }
+        };
+
+        let mut userinfo = encode_userinfo(&self.username);
+        if include_password {
+            if let Some(password) = self.password.as_deref().filter(|p| !p.is_empty()) {
+                userinfo.push(':');
+                userinfo.push_str(&encode_userinfo(password));
+            }
+        }
+        if !userinfo.is_empty() {
+            userinfo.push('@');
+        }
+
+        let mut dsn = format!("{}://{}{}:{}", scheme, userinfo, self.host, self.port);
+        if !database.is_empty() {
+            dsn.push('/');
+            dsn.push_str(database);
+        }
+        dsn
+    }
+}
+
+/// Percent-encodes the characters that would break the user info part of a URL
+fn encode_userinfo(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            ':' | '@' | '/' | '?' | '#' | '%' | '[' | ']' | ' ' => format!("%{:02X}", c as u32),
+            c => c.to_string(),
+        })
+        .collect()
 }
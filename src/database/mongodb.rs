@@ -9,7 +9,55 @@ use mongodb::{
     options::ClientOptions,
     Client, Database,
 };
-use std::collections::HashSet;
+
+/// Documents read to infer a collection's fields (see `describe_table` and `sample_columns`)
+const STRUCTURE_SAMPLE_SIZE: i64 = 100;
+
+/// Filter document typed in the WHERE field of a MongoDB tab, e.g. `{"status": "active"}`.
+/// An empty filter matches every document.
+pub fn parse_filter(filter: &str) -> Result<Document> {
+    if filter.trim().is_empty() {
+        return Ok(doc! {});
+    }
+    serde_json::from_str(filter).map_err(|e| anyhow::anyhow!("Invalid MongoDB filter: {}", e))
+}
+
+/// Sort typed in the ORDER BY field of a MongoDB tab: a sort document such as
+/// `{"age": -1}`, or fields with an optional direction like `age DESC, name` (what sorting
+/// by a column writes). `None` keeps the natural order.
+pub fn parse_sort(order_by: &str) -> Result<Option<Document>> {
+    let order_by = order_by.trim();
+    if order_by.is_empty() {
+        return Ok(None);
+    }
+    if order_by.starts_with('{') {
+        let sort: Document = serde_json::from_str(order_by)
+            .map_err(|e| anyhow::anyhow!("Invalid MongoDB sort: {}", e))?;
+        return Ok(Some(sort).filter(|sort| !sort.is_empty()));
+    }
+
+    let mut sort_doc = Document::new();
+
+    // Split by comma to handle multiple fields
+    for order in order_by.split(',') {
+        let parts: Vec<&str> = order.trim().split_whitespace().collect();
+        if !parts.is_empty() {
+            let field = parts[0].trim();
+            if !field.is_empty() {
+                // Default to ascending (1) if no direction specified or invalid
+                let value = if parts.get(1).map(|s| s.to_uppercase()) == Some("DESC".to_string())
+                {
+                    -1
+                } else {
+                    1
+                };
+                sort_doc.insert(field, value);
+            }
+        }
+    }
+
+    Ok(Some(sort_doc).filter(|sort| !sort.is_empty()))
+}
 
 pub struct MongoConnection {
     config: super::ConnectionConfig,
@@ -54,81 +102,9 @@ impl MongoConnection {
         Ok(Client::with_options(client_options)?)
     }
 
-    async fn parse_sort_expression(&self, order_by: &str) -> Option<Document> {
-        if order_by.trim().is_empty() {
-            return None;
-        }
-
-        let mut sort_doc = Document::new();
-
-        // Split by comma to handle multiple fields
-        for order in order_by.split(',') {
-            let parts: Vec<&str> = order.trim().split_whitespace().collect();
-            if !parts.is_empty() {
-                let field = parts[0].trim();
-                if !field.is_empty() {
-                    // Default to ascending (1) if no direction specified or invalid
-                    let value =
-                        if parts.get(1).map(|s| s.to_uppercase()) == Some("DESC".to_string()) {
-                            -1
-                        } else {
-                            1
-                        };
-                    sort_doc.insert(field, value);
-                }
-            }
-        }
-
-        if sort_doc.is_empty() {
-            None
-        } else {
-            Some(sort_doc)
-        }
-    }
-
-    fn collect_field_names(&self, prefix: &str, doc: &Document, fields: &mut HashSet<String>) {
-        for (key, value) in doc {
-            let field_name = if prefix.is_empty() {
-                key.to_string()
-            } else {
-                format!("{}.{}", prefix, key)
-            };
-
-            fields.insert(field_name.clone());
-
-            // Recursively process nested documents
-            if let Bson::Document(nested_doc) = value {
-                self.collect_field_names(&field_name, nested_doc, fields);
-            }
-        }
-    }
-
-    // Helper function to get nested field values
-    fn get_nested_field(&self, doc: &Document, field_path: &str) -> String {
-        let parts: Vec<&str> = field_path.split('.').collect();
-        let mut current = doc;
-
-        for (i, &part) in parts.iter().enumerate() {
-            if i == parts.len() - 1 {
-                // Last part - get the value
-                return match current.get(part) {
-                    Some(value) => MongoConnection::bson_to_string(value),
-                    None => "null".to_string(),
-                };
-            } else {
-                // Navigate to nested document
-                match current.get(part) {
-                    Some(&Bson::Document(ref nested)) => current = nested,
-                    _ => return "null".to_string(),
-                }
-            }
-        }
-
-        "null".to_string()
-    }
-
-    fn bson_to_string(bson: &Bson) -> String {
-        match bson {
+    /// Cell text of a field value: NULL for null, compact JSON for nested documents and arrays
+    fn bson_to_string(bson: &Bson) -> Option<String> {
+        Some(match bson {
             Bson::Int32(v) => v.to_string(),
             Bson::Int64(v) => v.to_string(),
             Bson::Double(v) => v.to_string(),
@@ -136,9 +112,55 @@ impl MongoConnection {
             Bson::Boolean(v) => v.to_string(),
             Bson::ObjectId(v) => v.to_string(),
             Bson::DateTime(v) => v.to_string(),
-            Bson::Null => "NULL".to_string(),
+            Bson::Null => return None,
+            Bson::Document(_) | Bson::Array(_) => bson.clone().into_relaxed_extjson().to_string(),
             _ => bson.to_string(),
+        })
+    }
+
+    /// Columns of `documents`, whose fields vary: every top-level field in the order first
+    /// seen, with `_id` first
+    fn document_columns<'a>(documents: impl IntoIterator<Item = &'a Document>) -> Vec<String> {
+        let mut columns: Vec<String> = Vec::new();
+        for document in documents {
+            for key in document.keys() {
+                if !columns.contains(key) {
+                    columns.push(key.clone());
+                }
+            }
         }
+        if let Some(index) = columns.iter().position(|column| column == "_id") {
+            let id = columns.remove(index);
+            columns.insert(0, id);
+        }
+        columns
+    }
+
+    /// Rows of `documents` under `columns`, with the fields a document lacks as NULL
+    fn document_rows(documents: &[Document], columns: &[String]) -> Vec<Vec<Option<String>>> {
+        documents
+            .iter()
+            .map(|document| {
+                columns
+                    .iter()
+                    .map(|column| document.get(column).and_then(Self::bson_to_string))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Columns of the first `STRUCTURE_SAMPLE_SIZE` documents of `collection` matching `filter`
+    async fn sample_columns(
+        collection: &mongodb::Collection<Document>,
+        filter: Document,
+    ) -> Result<Vec<String>> {
+        let sample: Vec<Document> = collection
+            .find(filter)
+            .limit(STRUCTURE_SAMPLE_SIZE)
+            .await?
+            .try_collect()
+            .await?;
+        Ok(Self::document_columns(&sample))
     }
 }
 
@@ -166,6 +188,17 @@ impl DatabaseConnection for MongoConnection {
         Ok(())
     }
 
+    async fn is_alive(&self) -> bool {
+        match &self.client {
+            Some(client) => client
+                .database("admin")
+                .run_command(doc! { "ping": 1 })
+                .await
+                .is_ok(),
+            None => false,
+        }
+    }
+
     async fn list_databases(&self) -> Result<Vec<String>> {
         if let Some(client) = &self.client {
             let mut names = Vec::new();
@@ -189,7 +222,7 @@ impl DatabaseConnection for MongoConnection {
         Ok(vec![database.to_string()])
     }
 
-    async fn list_tables(&self, _schema: &str) -> Result<Vec<String>> {
+    async fn list_tables(&self, _database: &str, _schema: &str) -> Result<Vec<String>> {
         // For MongoDB, list collections under the default schema
         if let Some(client) = &self.client {
             let db = client.database(self.config.default_database.as_deref().unwrap_or("admin"));
@@ -203,29 +236,9 @@ impl DatabaseConnection for MongoConnection {
         if let Some(db) = &self.current_db {
             let filter: Document = serde_json::from_str(query)?;
             let collection = db.collection::<Document>("default_collection");
-            let mut cursor = collection.find(filter).await?;
-
-            let mut columns = Vec::new();
-            let mut rows = Vec::new();
-
-            // First document determines the columns
-            if let Some(doc) = cursor.try_next().await? {
-                columns = doc.keys().map(|k| k.to_string()).collect();
-                let row = columns
-                    .iter()
-                    .map(|k| Self::bson_to_string(doc.get(k).unwrap_or(&Bson::Null)))
-                    .collect();
-                rows.push(row);
-            }
-
-            // Process remaining documents
-            while let Some(doc) = cursor.try_next().await? {
-                let row = columns
-                    .iter()
-                    .map(|k| Self::bson_to_string(doc.get(k).unwrap_or(&Bson::Null)))
-                    .collect();
-                rows.push(row);
-            }
+            let documents: Vec<Document> = collection.find(filter).await?.try_collect().await?;
+            let columns = Self::document_columns(&documents);
+            let rows = Self::document_rows(&documents, &columns);
 
             // Get the length before moving rows
             let affected_rows = rows.len() as u64;
@@ -234,6 +247,7 @@ impl DatabaseConnection for MongoConnection {
                 columns,
                 rows,
                 affected_rows,
+                column_types: Vec::new(),
             })
         } else {
             Err(anyhow::anyhow!("Not connected to database"))
@@ -246,82 +260,47 @@ impl DatabaseConnection for MongoConnection {
         table: &str,
         params: &QueryParams,
     ) -> Result<QueryResult> {
+        if params.base_query.is_some() {
+            return Err(anyhow::anyhow!("Base queries are not supported for MongoDB"));
+        }
         if let Some(db) = &self.current_db {
             logging::debug(&format!("Fetching data from table: {}", table));
 
             let collection = db.collection::<Document>(table);
 
-            // Build filter from where clause
-            let filter = match &params.where_clause {
-                Some(where_clause) if !where_clause.trim().is_empty() => {
-                    match serde_json::from_str(where_clause) {
-                        Ok(filter) => filter,
-                        Err(e) => {
-                            logging::error(&format!("Invalid MongoDB filter: {}", e));
-                            doc! {}
-                        }
-                    }
-                }
-                _ => doc! {},
-            };
+            // A filter that doesn't parse is an error rather than silently matching everything
+            let filter = parse_filter(params.where_clause.as_deref().unwrap_or_default())?;
+            let sort = parse_sort(params.order_by.as_deref().unwrap_or_default())?;
 
             // Build query options via builder-style API
             let limit = params.limit.unwrap_or(50).max(1) as i64;
 
-            // First, get a sample document to determine the schema
-            let mut columns = HashSet::new();
-
-            // Sample a few documents to get a comprehensive schema
-            let mut sample_cursor = collection.find(doc! {}).limit(10).await?;
-
-            while let Some(doc) = sample_cursor.try_next().await? {
-                logging::debug(&format!("Sample doc: {:?}", doc));
-                self.collect_field_names("", &doc, &mut columns);
-            }
-
-            // Convert HashSet to Vec and sort for consistent column order
-            let mut columns: Vec<String> = columns.into_iter().collect();
-            columns.sort();
+            // Fields vary per document, so the columns are those of a sample of the matching
+            // documents plus any the page adds; the sample keeps them steady between pages
+            let mut columns = Self::sample_columns(&collection, filter.clone()).await?;
 
-            // Now fetch the actual data
             let mut find_builder = collection.find(filter).limit(limit);
-            if let Some(order_by) = &params.order_by {
-                if let Some(sort_doc) = self.parse_sort_expression(order_by).await {
-                    logging::debug(&format!("Applying sort: {:?}", sort_doc));
-                    find_builder = find_builder.sort(sort_doc);
-                }
+            if let Some(sort_doc) = sort {
+                logging::debug(&format!("Applying sort: {:?}", sort_doc));
+                find_builder = find_builder.sort(sort_doc);
             }
             if let Some(offset) = params.offset {
                 find_builder = find_builder.skip(offset as u64);
             }
-            let mut cursor = find_builder.await?;
-            let mut rows = Vec::new();
-            let mut affected_rows = 0;
-
-            while let Some(doc) = cursor.try_next().await? {
-                affected_rows += 1;
-                let mut row = Vec::new();
-
-                for column in &columns {
-                    let value = if column.contains('.') {
-                        // Handle nested fields
-                        self.get_nested_field(&doc, column)
-                    } else {
-                        // Handle top-level fields
-                        doc.get(column)
-                            .map(Self::bson_to_string)
-                            .unwrap_or_else(|| "null".to_string())
-                    };
-                    row.push(value);
+            let documents: Vec<Document> = find_builder.await?.try_collect().await?;
+            for column in Self::document_columns(&documents) {
+                if !columns.contains(&column) {
+                    columns.push(column);
                 }
-
-                rows.push(row);
             }
+            let rows = Self::document_rows(&documents, &columns);
+            let affected_rows = rows.len() as u64;
 
             Ok(QueryResult {
                 columns,
                 rows,
-                affected_rows: affected_rows as u64,
+                affected_rows,
+                column_types: Vec::new(),
             })
         } else {
             Err(anyhow::anyhow!("Not connected to database"))
@@ -336,13 +315,7 @@ impl DatabaseConnection for MongoConnection {
     ) -> Result<u64> {
         if let Some(db) = &self.current_db {
             let collection = db.collection::<Document>(table);
-            let filter = match where_clause {
-                Some(w) if !w.trim().is_empty() => match serde_json::from_str::<Document>(w) {
-                    Ok(f) => f,
-                    Err(_) => doc! {},
-                },
-                _ => doc! {},
-            };
+            let filter = parse_filter(where_clause.unwrap_or_default())?;
             let count = collection.count_documents(filter).await?;
             Ok(count as u64)
         } else {
@@ -350,6 +323,13 @@ impl DatabaseConnection for MongoConnection {
         }
     }
 
+    fn detached_row_count(&self, _schema: &str, table: &str) -> Option<RowCountFuture> {
+        let collection = self.current_db.as_ref()?.collection::<Document>(table);
+        Some(Box::pin(async move {
+            Ok(collection.count_documents(doc! {}).await?)
+        }))
+    }
+
     async fn lookup_foreign_key(
         &self,
         _schema: &str,
@@ -362,24 +342,68 @@ impl DatabaseConnection for MongoConnection {
     async fn get_columns(&self, _schema: &str, table: &str) -> Result<Vec<ColumnInfo>> {
         if let Some(db) = &self.current_db {
             let collection = db.collection::<Document>(table);
-            let mut cursor = collection.find(doc! {}).limit(1).await?;
-
-            let mut columns = Vec::new();
-
-            if let Some(doc) = cursor.try_next().await? {
-                for key in doc.keys() {
-                    columns.push(ColumnInfo {
-                        name: key.clone(),
-                        data_type: "Mixed".to_string(),
-                        is_nullable: true,
-                        is_primary_key: key == "_id",
-                    });
-                }
-            }
-
-            Ok(columns)
+            let columns = Self::sample_columns(&collection, doc! {}).await?;
+            Ok(columns
+                .into_iter()
+                .map(|name| ColumnInfo {
+                    is_primary_key: name == "_id",
+                    name,
+                    data_type: "Mixed".to_string(),
+                    is_nullable: true,
+                })
+                .collect())
         } else {
             Err(anyhow::anyhow!("Not connected to database"))
         }
     }
+
+    /// Collections have no schema, so fields are inferred from a sample of documents. A field
+    /// is nullable when it is null or missing in some of them.
+    async fn describe_table(&self, _schema: &str, table: &str) -> Result<QueryResult> {
+        let db = self
+            .current_db
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Not connected to database"))?;
+        let collection = db.collection::<Document>(table);
+        let mut cursor = collection.find(doc! {}).limit(STRUCTURE_SAMPLE_SIZE).await?;
+
+        // Field name, the types seen in order, and how many documents had it non-null
+        let mut fields: Vec<(String, Vec<String>, usize)> = Vec::new();
+        let mut sampled = 0;
+        while let Some(document) = cursor.try_next().await? {
+            sampled += 1;
+            for (key, value) in document.iter() {
+                let index = match fields.iter().position(|(name, _, _)| name == key) {
+                    Some(index) => index,
+                    None => {
+                        fields.push((key.clone(), Vec::new(), 0));
+                        fields.len() - 1
+                    }
+                };
+                let (_, types, present) = &mut fields[index];
+                if matches!(value, Bson::Null) {
+                    continue;
+                }
+                *present += 1;
+                let type_name = format!("{:?}", value.element_type());
+                if !types.contains(&type_name) {
+                    types.push(type_name);
+                }
+            }
+        }
+
+        let rows = fields
+            .into_iter()
+            .map(|(name, types, present)| {
+                let data_type = if types.is_empty() {
+                    "Null".to_string()
+                } else {
+                    types.join(" | ")
+                };
+                let is_primary_key = name == "_id";
+                structure_row(name, data_type, present < sampled, None, is_primary_key, None)
+            })
+            .collect();
+        Ok(structure_result(rows))
+    }
 }
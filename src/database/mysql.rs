@@ -0,0 +1,494 @@
+use super::core::*;
+use super::ssh_tunnel::SshTunnelProcess;
+use crate::logging;
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::StreamExt;
+use mysql_async::consts::ColumnType;
+use mysql_async::prelude::Queryable;
+use mysql_async::{Column, Conn, OptsBuilder, Params, Row, Value};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// MySQL and MariaDB, where schemas and databases are the same thing: each database is listed
+/// with a single schema of the same name
+pub struct MySqlConnection {
+    config: super::ConnectionConfig,
+    // One connection rather than a pool, so BEGIN/COMMIT wrap the statements between them,
+    // shared so a row count can go on in the background (see `detached_row_count`)
+    conn: Option<Arc<Mutex<Conn>>>,
+    ssh_tunnel: Option<SshTunnelProcess>,
+}
+
+impl MySqlConnection {
+    pub fn new(config: super::ConnectionConfig) -> Self {
+        Self {
+            config,
+            conn: None,
+            ssh_tunnel: None,
+        }
+    }
+
+    fn conn(&self) -> Result<&Arc<Mutex<Conn>>> {
+        self.conn
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Not connected to database"))
+    }
+
+    fn value_to_string(value: &Value) -> Option<String> {
+        Some(match value {
+            Value::NULL => return None,
+            Value::Bytes(bytes) => match std::str::from_utf8(bytes) {
+                Ok(text) => text.to_string(),
+                Err(_) => format!("0x{}", hex::encode(bytes)),
+            },
+            Value::Int(v) => v.to_string(),
+            Value::UInt(v) => v.to_string(),
+            Value::Float(v) => v.to_string(),
+            Value::Double(v) => v.to_string(),
+            Value::Date(year, month, day, hour, minute, second, micros) => {
+                let date = format!("{:04}-{:02}-{:02}", year, month, day);
+                match (*hour, *minute, *second, *micros) {
+                    (0, 0, 0, 0) => date,
+                    (_, _, _, 0) => format!("{} {:02}:{:02}:{:02}", date, hour, minute, second),
+                    _ => format!(
+                        "{} {:02}:{:02}:{:02}.{:06}",
+                        date, hour, minute, second, micros
+                    ),
+                }
+            }
+            Value::Time(negative, days, hours, minutes, seconds, micros) => {
+                let sign = if *negative { "-" } else { "" };
+                let hours = *days * 24 + u32::from(*hours);
+                let mut text = format!("{}{:02}:{:02}:{:02}", sign, hours, minutes, seconds);
+                if *micros > 0 {
+                    text.push_str(&format!(".{:06}", micros));
+                }
+                text
+            }
+        })
+    }
+
+    fn row_to_strings(row: Row) -> Vec<Option<String>> {
+        row.unwrap().iter().map(Self::value_to_string).collect()
+    }
+
+    /// Names of result columns with their type names (see `column_type_name`)
+    fn column_names_and_types(columns: &[Column]) -> (Vec<String>, Vec<String>) {
+        columns
+            .iter()
+            .map(|column| {
+                (
+                    column.name_str().to_string(),
+                    Self::column_type_name(column).to_string(),
+                )
+            })
+            .unzip()
+    }
+
+    /// SQL name of a result column's wire type, as `ColumnKind::from_type_name` reads it.
+    /// `BOOLEAN` columns are `TINYINT(1)`, so that width is kept.
+    fn column_type_name(column: &Column) -> &'static str {
+        use ColumnType::*;
+
+        match column.column_type() {
+            MYSQL_TYPE_TINY if column.column_length() == 1 => "tinyint(1)",
+            MYSQL_TYPE_TINY => "tinyint",
+            MYSQL_TYPE_SHORT => "smallint",
+            MYSQL_TYPE_INT24 => "mediumint",
+            MYSQL_TYPE_LONG => "int",
+            MYSQL_TYPE_LONGLONG => "bigint",
+            MYSQL_TYPE_DECIMAL | MYSQL_TYPE_NEWDECIMAL => "decimal",
+            MYSQL_TYPE_FLOAT => "float",
+            MYSQL_TYPE_DOUBLE => "double",
+            MYSQL_TYPE_DATE | MYSQL_TYPE_NEWDATE => "date",
+            MYSQL_TYPE_TIME | MYSQL_TYPE_TIME2 => "time",
+            MYSQL_TYPE_DATETIME | MYSQL_TYPE_DATETIME2 => "datetime",
+            MYSQL_TYPE_TIMESTAMP | MYSQL_TYPE_TIMESTAMP2 => "timestamp",
+            MYSQL_TYPE_YEAR => "year",
+            MYSQL_TYPE_JSON => "json",
+            MYSQL_TYPE_BIT => "bit",
+            MYSQL_TYPE_TINY_BLOB | MYSQL_TYPE_MEDIUM_BLOB | MYSQL_TYPE_LONG_BLOB
+            | MYSQL_TYPE_BLOB => "blob",
+            _ => "varchar",
+        }
+    }
+}
+
+fn quote_identifier(identifier: &str) -> String {
+    format!("`{}`", identifier.replace('`', "``"))
+}
+
+fn qualified_table(schema: &str, table: &str) -> String {
+    if schema.is_empty() {
+        quote_identifier(table)
+    } else {
+        format!("{}.{}", quote_identifier(schema), quote_identifier(table))
+    }
+}
+
+#[async_trait]
+impl DatabaseConnection for MySqlConnection {
+    async fn connect(&mut self) -> Result<()> {
+        if let Some(ssh) = &self.config.ssh_tunnel {
+            let tunnel = SshTunnelProcess::start(ssh, &self.config.host, self.config.port).await?;
+            self.ssh_tunnel = Some(tunnel);
+        }
+        let (host, port) = match &self.ssh_tunnel {
+            Some(tunnel) => ("127.0.0.1", tunnel.local_port),
+            None => (self.config.host.as_str(), self.config.port),
+        };
+        let database = self
+            .config
+            .default_database
+            .as_deref()
+            .filter(|db| !db.is_empty());
+        let opts = OptsBuilder::default()
+            .ip_or_hostname(host)
+            .tcp_port(port)
+            .user(Some(&self.config.username))
+            .pass(self.config.password.as_deref())
+            .db_name(database);
+
+        let mut conn = Conn::new(opts).await?;
+        if self.config.read_only {
+            conn.query_drop("SET SESSION TRANSACTION READ ONLY").await?;
+        }
+        for statement in &self.config.on_connect {
+            conn.query_drop(statement).await.map_err(|e| {
+                anyhow::anyhow!("On-connect statement `{}` failed: {}", statement, e)
+            })?;
+        }
+        self.conn = Some(Arc::new(Mutex::new(conn)));
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        // A background row count still holding the connection drops it once done
+        if let Some(conn) = self.conn.take().and_then(|conn| Arc::try_unwrap(conn).ok()) {
+            if let Err(e) = conn.into_inner().disconnect().await {
+                logging::warn(&format!("MySQL disconnect failed: {}", e));
+            }
+        }
+        if let Some(tunnel) = &mut self.ssh_tunnel {
+            let _ = tunnel.stop().await;
+        }
+        self.ssh_tunnel = None;
+        Ok(())
+    }
+
+    async fn is_alive(&self) -> bool {
+        match &self.conn {
+            Some(conn) => conn.lock().await.ping().await.is_ok(),
+            None => false,
+        }
+    }
+
+    async fn list_databases(&self) -> Result<Vec<String>> {
+        let mut conn = self.conn()?.lock().await;
+        Ok(conn
+            .query("SELECT schema_name FROM information_schema.schemata ORDER BY schema_name")
+            .await?)
+    }
+
+    async fn list_schemas(&self, database: &str) -> Result<Vec<String>> {
+        Ok(vec![database.to_string()])
+    }
+
+    async fn list_tables(&self, database: &str, schema: &str) -> Result<Vec<String>> {
+        Ok(self
+            .list_relations(database, schema)
+            .await?
+            .into_iter()
+            .filter(|(_, kind)| *kind == RelationKind::Table)
+            .map(|(table, _)| table)
+            .collect())
+    }
+
+    async fn list_relations(
+        &self,
+        _database: &str,
+        schema: &str,
+    ) -> Result<Vec<(String, RelationKind)>> {
+        let mut conn = self.conn()?.lock().await;
+        let rows: Vec<(String, String)> = conn
+            .exec(
+                "SELECT table_name, table_type FROM information_schema.tables
+                 WHERE table_schema = ?
+                 ORDER BY table_name",
+                (schema,),
+            )
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(name, table_type)| {
+                let kind = if table_type.ends_with("VIEW") {
+                    RelationKind::View
+                } else {
+                    RelationKind::Table
+                };
+                (name, kind)
+            })
+            .collect())
+    }
+
+    async fn execute_query(&self, query: &str) -> Result<QueryResult> {
+        let mut conn = self.conn()?.lock().await;
+        logging::debug(&format!("Executing query: {}", query));
+        let mut result = conn.query_iter(query).await?;
+        let (columns, column_types) = Self::column_names_and_types(result.columns_ref());
+        let rows: Vec<Row> = result.collect().await?;
+        let affected_rows = if rows.is_empty() {
+            result.affected_rows()
+        } else {
+            rows.len() as u64
+        };
+        // Drains any further result sets so the connection is ready for the next query
+        result.drop_result().await?;
+
+        Ok(QueryResult {
+            columns,
+            rows: rows.into_iter().map(Self::row_to_strings).collect(),
+            affected_rows,
+            column_types,
+        })
+    }
+
+    async fn execute_query_capped(
+        &self,
+        query: &str,
+        max_rows: usize,
+        _in_transaction: bool,
+    ) -> Result<QueryResult> {
+        let mut conn = self.conn()?.lock().await;
+        logging::debug(&format!(
+            "Executing query up to {} rows: {}",
+            max_rows, query
+        ));
+        let mut result = conn.query_iter(query).await?;
+        let (columns, column_types) = Self::column_names_and_types(result.columns_ref());
+        let mut rows = Vec::new();
+        if let Some(mut stream) = result.stream::<Row>().await? {
+            while rows.len() < max_rows {
+                match stream.next().await {
+                    Some(row) => rows.push(Self::row_to_strings(row?)),
+                    None => break,
+                }
+            }
+        }
+        // Rows past the cap are read off the wire and dropped, not kept
+        result.drop_result().await?;
+
+        Ok(QueryResult {
+            columns,
+            affected_rows: rows.len() as u64,
+            rows,
+            column_types,
+        })
+    }
+
+    async fn execute_statement(&self, statement: &str) -> Result<u64> {
+        let mut conn = self.conn()?.lock().await;
+        logging::debug(&format!("Executing statement: {}", statement));
+        conn.query_drop(statement).await?;
+        Ok(conn.affected_rows())
+    }
+
+    async fn fetch_table_data(
+        &self,
+        schema: &str,
+        table: &str,
+        params: &QueryParams,
+    ) -> Result<QueryResult> {
+        logging::debug(&format!(
+            "Fetching table data for schema {}, table {}",
+            schema, table
+        ));
+
+        let source = params
+            .base_query_source()
+            .unwrap_or_else(|| qualified_table(schema, table));
+        let mut query = format!("SELECT * FROM {}", source);
+
+        if let Some(where_clause) = params.where_clause.as_deref() {
+            if !where_clause.trim().is_empty() {
+                query.push_str(&format!(" WHERE {}", where_clause));
+            }
+        }
+
+        if let Some(order_by) = params.order_by.as_deref() {
+            // MySQL has no NULLS FIRST/LAST, so sort on the null check first
+            let orders: Vec<String> = OrderByTerm::parse_list(order_by)
+                .iter()
+                .map(|term| term.to_sql_emulating_nulls(&quote_identifier(&term.column)))
+                .collect();
+            if !orders.is_empty() {
+                query.push_str(" ORDER BY ");
+                query.push_str(&orders.join(", "));
+            }
+        }
+
+        // MySQL only accepts OFFSET after a LIMIT
+        match (params.limit, params.offset) {
+            (Some(limit), Some(offset)) => {
+                query.push_str(&format!(" LIMIT {} OFFSET {}", limit, offset))
+            }
+            (Some(limit), None) => query.push_str(&format!(" LIMIT {}", limit)),
+            (None, Some(offset)) => {
+                query.push_str(&format!(" LIMIT 18446744073709551615 OFFSET {}", offset))
+            }
+            (None, None) => {}
+        }
+
+        self.execute_query_with_params(&query, &params.where_params)
+            .await
+    }
+
+    async fn count_table_rows(
+        &self,
+        schema: &str,
+        table: &str,
+        where_clause: Option<&str>,
+    ) -> Result<u64> {
+        let mut query = format!("SELECT COUNT(*) FROM {}", qualified_table(schema, table));
+        if let Some(w) = where_clause.filter(|w| !w.trim().is_empty()) {
+            query.push_str(&format!(" WHERE {}", w));
+        }
+
+        let mut conn = self.conn()?.lock().await;
+        let count: Option<u64> = conn.query_first(query).await?;
+        Ok(count.unwrap_or(0))
+    }
+
+    fn detached_row_count(&self, schema: &str, table: &str) -> Option<RowCountFuture> {
+        let conn = Arc::clone(self.conn.as_ref()?);
+        let query = format!("SELECT COUNT(*) FROM {}", qualified_table(schema, table));
+        Some(Box::pin(async move {
+            let count: Option<u64> = conn.lock().await.query_first(query).await?;
+            Ok(count.unwrap_or(0))
+        }))
+    }
+
+    async fn lookup_foreign_key(
+        &self,
+        schema: &str,
+        table: &str,
+        column: &str,
+    ) -> Result<Option<ForeignKeyTarget>> {
+        let mut conn = self.conn()?.lock().await;
+        let target: Option<(String, String, String)> = conn
+            .exec_first(
+                "SELECT referenced_table_schema, referenced_table_name, referenced_column_name
+                 FROM information_schema.key_column_usage
+                 WHERE table_schema = ? AND table_name = ? AND column_name = ?
+                   AND referenced_table_name IS NOT NULL
+                 LIMIT 1",
+                (schema, table, column),
+            )
+            .await?;
+        Ok(target.map(|(schema, table, column)| ForeignKeyTarget {
+            schema,
+            table,
+            column,
+        }))
+    }
+
+    async fn get_primary_keys(&self, schema: &str, table: &str) -> Result<Vec<String>> {
+        let mut conn = self.conn()?.lock().await;
+        Ok(conn
+            .exec(
+                "SELECT column_name FROM information_schema.key_column_usage
+                 WHERE constraint_name = 'PRIMARY' AND table_schema = ? AND table_name = ?
+                 ORDER BY ordinal_position",
+                (schema, table),
+            )
+            .await?)
+    }
+
+    async fn get_columns(&self, schema: &str, table: &str) -> Result<Vec<ColumnInfo>> {
+        let mut conn = self.conn()?.lock().await;
+        let rows: Vec<(String, String, String, String)> = conn
+            .exec(
+                "SELECT column_name, data_type, is_nullable, column_key
+                 FROM information_schema.columns
+                 WHERE table_schema = ? AND table_name = ?
+                 ORDER BY ordinal_position",
+                (schema, table),
+            )
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(name, data_type, is_nullable, column_key)| ColumnInfo {
+                name,
+                data_type,
+                is_nullable: is_nullable == "YES",
+                is_primary_key: column_key == "PRI",
+            })
+            .collect())
+    }
+
+    async fn update_cell(
+        &self,
+        schema: &str,
+        table: &str,
+        column: &str,
+        value: Option<&str>,
+        key_columns: &[String],
+        key: &[Option<String>],
+    ) -> Result<u64> {
+        let conditions = key_columns
+            .iter()
+            .zip(key)
+            .map(|(column, v)| match v {
+                Some(_) => format!("{} = ?", quote_identifier(column)),
+                None => format!("{} IS NULL", quote_identifier(column)),
+            })
+            .collect::<Vec<_>>()
+            .join(" AND ");
+        let statement = format!(
+            "UPDATE {} SET {} = ? WHERE {}",
+            qualified_table(schema, table),
+            quote_identifier(column),
+            conditions
+        );
+        let values: Vec<Value> = std::iter::once(value.map(Value::from).unwrap_or(Value::NULL))
+            .chain(key.iter().flatten().map(|v| Value::from(v.as_str())))
+            .collect();
+
+        let mut conn = self.conn()?.lock().await;
+        logging::debug(&format!("Executing statement: {}", statement));
+        conn.exec_drop(statement, Params::Positional(values)).await?;
+        Ok(conn.affected_rows())
+    }
+
+    async fn delete_rows(
+        &self,
+        schema: &str,
+        table: &str,
+        key_columns: &[String],
+        keys: &[Vec<String>],
+    ) -> Result<u64> {
+        if keys.is_empty() {
+            return Ok(0);
+        }
+
+        let key_list = key_columns
+            .iter()
+            .map(|column| quote_identifier(column))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let tuple = format!("({})", vec!["?"; key_columns.len()].join(", "));
+        let statement = format!(
+            "DELETE FROM {} WHERE ({}) IN ({})",
+            qualified_table(schema, table),
+            key_list,
+            vec![tuple; keys.len()].join(", ")
+        );
+        let values: Vec<Value> = keys.iter().flatten().cloned().map(Value::from).collect();
+
+        let mut conn = self.conn()?.lock().await;
+        logging::debug(&format!("Executing statement: {}", statement));
+        conn.exec_drop(statement, Params::Positional(values)).await?;
+        Ok(conn.affected_rows())
+    }
+}
@@ -3,11 +3,42 @@ use super::ssh_tunnel::SshTunnelProcess;
 use crate::logging;
 use anyhow::Result;
 use async_trait::async_trait;
-use tokio_postgres::{Client, NoTls};
+use bytes::BytesMut;
+use std::sync::Arc;
+use tokio_postgres::error::SqlState;
+use tokio_postgres::types::{to_sql_checked, Format, IsNull, ToSql, Type};
+use tokio_postgres::{Client, NoTls, Row};
+
+/// A user-entered parameter value, sent as text so the server parses it as whatever type the
+/// placeholder has (int, date, uuid, ...)
+#[derive(Debug)]
+struct TextParam(String);
+
+impl ToSql for TextParam {
+    fn to_sql(
+        &self,
+        _ty: &Type,
+        out: &mut BytesMut,
+    ) -> std::result::Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        out.extend_from_slice(self.0.as_bytes());
+        Ok(IsNull::No)
+    }
+
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+
+    fn encode_format(&self, _ty: &Type) -> Format {
+        Format::Text
+    }
+
+    to_sql_checked!();
+}
 
 pub struct PostgresConnection {
     config: super::ConnectionConfig,
-    client: Option<Client>,
+    // Shared so a row count can go on in the background (see `detached_row_count`)
+    client: Option<Arc<Client>>,
     ssh_tunnel: Option<SshTunnelProcess>,
 }
 
@@ -20,7 +51,37 @@ impl PostgresConnection {
         }
     }
 
-    async fn setup_connection(&mut self) -> Result<Client> {
+    /// Database the main client is connected to
+    fn connected_database(&self) -> &str {
+        self.config.default_database.as_deref().unwrap_or("postgres")
+    }
+
+    /// A short-lived client for `database` when it isn't the connected one, since
+    /// information_schema and pg_catalog only cover the database a client is connected to
+    async fn other_database_client(&self, database: &str) -> Result<Option<Client>> {
+        if self.client.is_none() {
+            return Err(anyhow::anyhow!("Not connected to database"));
+        }
+        if database.is_empty() || database == self.connected_database() {
+            return Ok(None);
+        }
+        let client = self
+            .setup_connection(database)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to connect to database '{}': {}", database, e))?;
+        Ok(Some(client))
+    }
+
+    /// The client to read the catalog through: `other_client` from `other_database_client`,
+    /// or else the main one
+    fn catalog_client<'a>(&'a self, other_client: &'a Option<Client>) -> Result<&'a Client> {
+        other_client
+            .as_ref()
+            .or(self.client.as_deref())
+            .ok_or_else(|| anyhow::anyhow!("Not connected to database"))
+    }
+
+    async fn setup_connection(&self, dbname: &str) -> Result<Client> {
         let mut config = tokio_postgres::Config::new();
         let (effective_host, effective_port) = if let Some(ref tunnel) = self.ssh_tunnel {
             ("127.0.0.1", tunnel.local_port)
@@ -33,12 +94,7 @@ impl PostgresConnection {
             .port(effective_port)
             .user(&self.config.username)
             .password(self.config.password.as_deref().unwrap_or(""))
-            .dbname(
-                self.config
-                    .default_database
-                    .as_deref()
-                    .unwrap_or("postgres"),
-            );
+            .dbname(dbname);
 
         let (client, connection) = config.connect(NoTls).await?;
 
@@ -47,8 +103,137 @@ impl PostgresConnection {
                 let _ = logging::error(&format!("Connection error: {}", e));
             }
         });
+        // Let the server refuse writes too, e.g. from functions with side effects
+        if self.config.read_only {
+            client
+                .batch_execute("SET default_transaction_read_only = on")
+                .await?;
+        }
+        if let Some(timeout) = self.config.statement_timeout() {
+            client
+                .batch_execute(&format!("SET statement_timeout = {}", timeout.as_millis()))
+                .await?;
+        }
         Ok(client)
     }
+
+    /// SELECT of the page `params` fetches, each column cast to text, with the type of each
+    /// column as `format_type` names it, e.g. `numeric(10,2)`. A base query's columns keep
+    /// their own types, so none are returned for it.
+    async fn select_query(
+        &self,
+        schema: &str,
+        table: &str,
+        params: &QueryParams,
+    ) -> Result<(String, Vec<String>)> {
+        let schema_ident = sanitize_column_name(schema);
+        let table_ident = sanitize_column_name(table);
+
+        // Discover column names in ordinal order
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Not connected to database"))?;
+
+        // A base query's columns aren't in information_schema, so it is selected as is
+        let (column_names, column_types) = if params.base_query.is_some() {
+            (Vec::new(), Vec::new())
+        } else {
+            // pg_attribute also covers materialized views, which information_schema omits
+            client
+                .query(
+                    "SELECT a.attname::text, format_type(a.atttypid, a.atttypmod)
+                     FROM pg_catalog.pg_attribute a
+                     JOIN pg_catalog.pg_class c ON c.oid = a.attrelid
+                     JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+                     WHERE n.nspname = $1 AND c.relname = $2
+                       AND a.attnum > 0 AND NOT a.attisdropped
+                     ORDER BY a.attnum",
+                    &[&schema, &table],
+                )
+                .await?
+                .iter()
+                .map(|r| (r.get::<_, String>(0), r.get::<_, String>(1)))
+                .unzip()
+        };
+
+        // Build select list casting each column to text to ensure enums/json/uuid display correctly
+        let select_list = if column_names.is_empty() {
+            "*".to_string()
+        } else {
+            column_names
+                .iter()
+                .map(|c| {
+                    let ident = quote_identifier_exact(c);
+                    format!("({})::text AS {}", ident, ident)
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let source = params
+            .base_query_source()
+            .unwrap_or_else(|| format!("{}.{}", schema_ident, table_ident));
+        let mut query = format!("SELECT {} FROM {}", select_list, source);
+
+        if let Some(where_clause) = &params.where_clause {
+            if !where_clause.trim().is_empty() {
+                query.push_str(&format!(" WHERE {}", where_clause));
+            }
+        }
+
+        if let Some(order_by) = &params.order_by {
+            if !order_by.trim().is_empty() {
+                // Postgres supports NULLS FIRST/LAST natively
+                let sanitized_orders: Vec<String> = OrderByTerm::parse_list(order_by)
+                    .into_iter()
+                    .map(|mut term| {
+                        term.column = sanitize_column_name(&term.column);
+                        term.to_string()
+                    })
+                    .collect();
+
+                if !sanitized_orders.is_empty() {
+                    query.push_str(" ORDER BY ");
+                    query.push_str(&sanitized_orders.join(", "));
+                }
+            }
+        }
+
+        if let Some(limit) = params.limit {
+            query.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        if let Some(offset) = params.offset {
+            query.push_str(&format!(" OFFSET {}", offset));
+        }
+
+        Ok((query, column_types))
+    }
+}
+
+/// COUNT(*) of `schema.table`, filtered by `where_clause` if there is one
+fn count_query(schema: &str, table: &str, where_clause: Option<&str>) -> String {
+    let mut query = format!(
+        "SELECT COUNT(*)::bigint FROM {}.{}",
+        sanitize_column_name(schema),
+        sanitize_column_name(table)
+    );
+    if let Some(w) = where_clause {
+        if !w.trim().is_empty() {
+            query.push_str(&format!(" WHERE {}", w));
+        }
+    }
+    query
+}
+
+async fn read_count(client: &Client, query: &str) -> Result<u64> {
+    let rows = client.query(query, &[]).await?;
+    let count: i64 = rows
+        .get(0)
+        .and_then(|r| r.try_get::<_, i64>(0).ok())
+        .unwrap_or(0);
+    Ok(u64::try_from(count).unwrap_or(0))
 }
 
 fn sanitize_column_name(column: &str) -> String {
@@ -65,6 +250,117 @@ fn quote_identifier_exact(identifier: &str) -> String {
     format!("\"{}\"", escaped)
 }
 
+/// Text of each value of `row`, `None` for NULL, converting the types the driver can't read
+/// as text
+fn row_to_strings(row: &Row) -> Vec<Option<String>> {
+    (0..row.len())
+        .map(|i| {
+            let col = &row.columns()[i];
+            match col.type_().name() {
+                "int2" => {
+                    if let Ok(Some(val)) = row.try_get::<_, Option<i16>>(i) {
+                        Some(val.to_string())
+                    } else {
+                        None
+                    }
+                }
+                "int4" => {
+                    if let Ok(Some(val)) = row.try_get::<_, Option<i32>>(i) {
+                        Some(val.to_string())
+                    } else {
+                        None
+                    }
+                }
+                "int8" => {
+                    if let Ok(Some(val)) = row.try_get::<_, Option<i64>>(i) {
+                        Some(val.to_string())
+                    } else {
+                        None
+                    }
+                }
+                "float4" => {
+                    if let Ok(Some(val)) = row.try_get::<_, Option<f32>>(i) {
+                        Some(val.to_string())
+                    } else {
+                        None
+                    }
+                }
+                "float8" => {
+                    if let Ok(Some(val)) = row.try_get::<_, Option<f64>>(i) {
+                        Some(val.to_string())
+                    } else {
+                        None
+                    }
+                }
+                "bool" => {
+                    if let Ok(Some(val)) = row.try_get::<_, Option<bool>>(i) {
+                        Some(val.to_string())
+                    } else {
+                        None
+                    }
+                }
+                "varchar" | "text" | "name" | "char" => {
+                    if let Ok(Some(val)) = row.try_get::<_, Option<String>>(i) {
+                        Some(val)
+                    } else {
+                        None
+                    }
+                }
+                "json" | "jsonb" => {
+                    if let Ok(Some(val)) = row.try_get::<_, Option<serde_json::Value>>(i) {
+                        Some(val.to_string())
+                    } else if let Ok(Some(val)) = row.try_get::<_, Option<String>>(i) {
+                        Some(val)
+                    } else {
+                        None
+                    }
+                }
+                "uuid" => {
+                    if let Ok(Some(val)) = row.try_get::<_, Option<uuid::Uuid>>(i) {
+                        Some(val.to_string())
+                    } else {
+                        None
+                    }
+                }
+                "timestamptz" => {
+                    if let Ok(Some(val)) =
+                        row.try_get::<_, Option<chrono::DateTime<chrono::Utc>>>(i)
+                    {
+                        Some(val.to_string())
+                    } else {
+                        None
+                    }
+                }
+                "timestamp" => {
+                    if let Ok(Some(val)) = row.try_get::<_, Option<chrono::NaiveDateTime>>(i) {
+                        Some(val.to_string())
+                    } else {
+                        None
+                    }
+                }
+                "date" => {
+                    if let Ok(Some(val)) = row.try_get::<_, Option<chrono::NaiveDate>>(i) {
+                        Some(val.to_string())
+                    } else {
+                        None
+                    }
+                }
+                _ => {
+                    if let Ok(Some(val)) = row.try_get::<_, Option<String>>(i) {
+                        Some(val)
+                    } else if let Ok(Some(val)) = row.try_get::<_, Option<&str>>(i) {
+                        Some(val.to_string())
+                    } else if let Ok(Some(val)) = row.try_get::<_, Option<uuid::Uuid>>(i) {
+                        Some(val.to_string())
+                    } else {
+                        None
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
 #[async_trait]
 impl DatabaseConnection for PostgresConnection {
     async fn connect(&mut self) -> Result<()> {
@@ -72,7 +368,13 @@ impl DatabaseConnection for PostgresConnection {
             let tunnel = SshTunnelProcess::start(ssh, &self.config.host, self.config.port).await?;
             self.ssh_tunnel = Some(tunnel);
         }
-        self.client = Some(self.setup_connection().await?);
+        let client = self.setup_connection(self.connected_database()).await?;
+        for statement in &self.config.on_connect {
+            client.batch_execute(statement).await.map_err(|e| {
+                anyhow::anyhow!("On-connect statement `{}` failed: {}", statement, e)
+            })?;
+        }
+        self.client = Some(Arc::new(client));
         Ok(())
     }
 
@@ -85,6 +387,18 @@ impl DatabaseConnection for PostgresConnection {
         Ok(())
     }
 
+    async fn is_alive(&self) -> bool {
+        match &self.client {
+            Some(client) if !client.is_closed() => match client.simple_query("SELECT 1").await {
+                Ok(_) => true,
+                // An aborted transaction refuses every statement until ROLLBACK, yet the
+                // server answered, so the connection is still there
+                Err(e) => e.code() == Some(&SqlState::IN_FAILED_SQL_TRANSACTION),
+            },
+            _ => false,
+        }
+    }
+
     async fn list_databases(&self) -> Result<Vec<String>> {
         if let Some(client) = &self.client {
             let rows = client
@@ -102,285 +416,270 @@ impl DatabaseConnection for PostgresConnection {
         }
     }
 
-    async fn list_schemas(&self, _database: &str) -> Result<Vec<String>> {
-        if let Some(client) = &self.client {
-            let rows = client
-                .query(
-                    "SELECT schema_name 
-                 FROM information_schema.schemata 
+    async fn list_schemas(&self, database: &str) -> Result<Vec<String>> {
+        let other_client = self.other_database_client(database).await?;
+        let rows = self
+            .catalog_client(&other_client)?
+            .query(
+                "SELECT schema_name
+                 FROM information_schema.schemata
                  WHERE schema_name NOT IN ('information_schema', 'pg_catalog')
                  ORDER BY schema_name",
-                    &[],
-                )
-                .await?;
+                &[],
+            )
+            .await?;
 
-            Ok(rows.iter().map(|row| row.get::<_, String>(0)).collect())
-        } else {
-            Err(anyhow::anyhow!("Not connected to database"))
-        }
+        Ok(rows.iter().map(|row| row.get::<_, String>(0)).collect())
     }
 
-    async fn list_tables(&self, schema: &str) -> Result<Vec<String>> {
-        if let Some(client) = &self.client {
-            let rows = client
-                .query(
-                    "SELECT table_name 
+    async fn list_tables(&self, database: &str, schema: &str) -> Result<Vec<String>> {
+        let other_client = self.other_database_client(database).await?;
+        let rows = self
+            .catalog_client(&other_client)?
+            .query(
+                "SELECT table_name 
                  FROM information_schema.tables 
                  WHERE table_schema = $1 
                  AND table_type = 'BASE TABLE'
                  ORDER BY table_name",
-                    &[&schema],
-                )
-                .await?;
+                &[&schema],
+            )
+            .await?;
 
-            Ok(rows.iter().map(|row| row.get::<_, String>(0)).collect())
-        } else {
-            Err(anyhow::anyhow!("Not connected to database"))
-        }
+        Ok(rows.iter().map(|row| row.get::<_, String>(0)).collect())
+    }
+
+    async fn list_relations(
+        &self,
+        database: &str,
+        schema: &str,
+    ) -> Result<Vec<(String, RelationKind)>> {
+        let other_client = self.other_database_client(database).await?;
+        let rows = self
+            .catalog_client(&other_client)?
+            .query(
+                "SELECT c.relname, c.relkind::text, c.relispartition
+                 FROM pg_catalog.pg_class c
+                 JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+                 WHERE n.nspname = $1 AND c.relkind IN ('r', 'v', 'm', 'f', 'p')
+                 ORDER BY c.relname",
+                &[&schema],
+            )
+            .await?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                let kind = match (row.get::<_, String>(1).as_str(), row.get::<_, bool>(2)) {
+                    ("r", true) => RelationKind::Partition,
+                    ("r", false) => RelationKind::Table,
+                    ("v", _) => RelationKind::View,
+                    ("m", _) => RelationKind::MaterializedView,
+                    ("f", _) => RelationKind::ForeignTable,
+                    ("p", _) => RelationKind::PartitionedTable,
+                    _ => return None,
+                };
+                Some((row.get::<_, String>(0), kind))
+            })
+            .filter(|(_, kind)| self.config.shows_relation(*kind))
+            .collect())
     }
 
     async fn execute_query(&self, query: &str) -> Result<QueryResult> {
+        self.execute_query_with_params(query, &[]).await
+    }
+
+    async fn execute_query_capped(
+        &self,
+        query: &str,
+        max_rows: usize,
+        in_transaction: bool,
+    ) -> Result<QueryResult> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Not connected to database"))?;
+        logging::debug(&format!("Executing query through a cursor: {}", query));
+
+        // A cursor lets the server stop at the cap; it needs a transaction, and the user's
+        // one is kept open around it
+        if !in_transaction {
+            client.batch_execute("BEGIN").await?;
+        }
+        let fetched = async {
+            client
+                .batch_execute(&format!(
+                    "DECLARE lazylode_rows NO SCROLL CURSOR FOR {}",
+                    query
+                ))
+                .await?;
+            let rows = client
+                .query(&format!("FETCH {} FROM lazylode_rows", max_rows), &[])
+                .await?;
+            client.batch_execute("CLOSE lazylode_rows").await?;
+            Ok::<_, anyhow::Error>(rows)
+        }
+        .await;
+        if !in_transaction {
+            let end = if fetched.is_ok() {
+                "COMMIT"
+            } else {
+                "ROLLBACK"
+            };
+            client.batch_execute(end).await?;
+        }
+        let rows = fetched?;
+
+        let (columns, column_types) = match rows.first() {
+            Some(row) => row
+                .columns()
+                .iter()
+                .map(|col| (col.name().to_string(), col.type_().name().to_string()))
+                .unzip(),
+            None => (vec![], vec![]),
+        };
+        Ok(QueryResult {
+            columns,
+            rows: rows.iter().map(row_to_strings).collect(),
+            affected_rows: rows.len() as u64,
+            column_types,
+        })
+    }
+
+    async fn execute_query_with_params(
+        &self,
+        query: &str,
+        params: &[String],
+    ) -> Result<QueryResult> {
         if let Some(client) = &self.client {
             logging::debug(&format!("Executing query: {}", query));
-            let rows = client.query(query, &[]).await?;
+            let params: Vec<TextParam> = params.iter().cloned().map(TextParam).collect();
+            let param_refs: Vec<&(dyn ToSql + Sync)> =
+                params.iter().map(|p| p as &(dyn ToSql + Sync)).collect();
+            let rows = client.query(query, &param_refs).await?;
 
-            let columns = if !rows.is_empty() {
-                rows[0]
+            let (columns, column_types) = match rows.first() {
+                Some(row) => row
                     .columns()
                     .iter()
-                    .map(|col| col.name().to_string())
-                    .collect()
-            } else {
-                vec![]
+                    .map(|col| (col.name().to_string(), col.type_().name().to_string()))
+                    .unzip(),
+                None => (vec![], vec![]),
             };
 
-            let result_rows: Vec<Vec<String>> = rows
-                .iter()
-                .map(|row| {
-                    (0..row.len())
-                        .map(|i| {
-                            let col = &row.columns()[i];
-                            match col.type_().name() {
-                                "int2" => {
-                                    if let Ok(Some(val)) = row.try_get::<_, Option<i16>>(i) {
-                                        val.to_string()
-                                    } else {
-                                        "NULL".to_string()
-                                    }
-                                }
-                                "int4" => {
-                                    if let Ok(Some(val)) = row.try_get::<_, Option<i32>>(i) {
-                                        val.to_string()
-                                    } else {
-                                        "NULL".to_string()
-                                    }
-                                }
-                                "int8" => {
-                                    if let Ok(Some(val)) = row.try_get::<_, Option<i64>>(i) {
-                                        val.to_string()
-                                    } else {
-                                        "NULL".to_string()
-                                    }
-                                }
-                                "float4" => {
-                                    if let Ok(Some(val)) = row.try_get::<_, Option<f32>>(i) {
-                                        val.to_string()
-                                    } else {
-                                        "NULL".to_string()
-                                    }
-                                }
-                                "float8" => {
-                                    if let Ok(Some(val)) = row.try_get::<_, Option<f64>>(i) {
-                                        val.to_string()
-                                    } else {
-                                        "NULL".to_string()
-                                    }
-                                }
-                                "bool" => {
-                                    if let Ok(Some(val)) = row.try_get::<_, Option<bool>>(i) {
-                                        val.to_string()
-                                    } else {
-                                        "NULL".to_string()
-                                    }
-                                }
-                                "varchar" | "text" | "name" | "char" => {
-                                    if let Ok(Some(val)) = row.try_get::<_, Option<String>>(i) {
-                                        val
-                                    } else {
-                                        "NULL".to_string()
-                                    }
-                                }
-                                "json" | "jsonb" => {
-                                    if let Ok(Some(val)) =
-                                        row.try_get::<_, Option<serde_json::Value>>(i)
-                                    {
-                                        val.to_string()
-                                    } else if let Ok(Some(val)) =
-                                        row.try_get::<_, Option<String>>(i)
-                                    {
-                                        val
-                                    } else {
-                                        "NULL".to_string()
-                                    }
-                                }
-                                "uuid" => {
-                                    if let Ok(Some(val)) = row.try_get::<_, Option<uuid::Uuid>>(i) {
-                                        val.to_string()
-                                    } else {
-                                        "NULL".to_string()
-                                    }
-                                }
-                                "timestamptz" => {
-                                    if let Ok(Some(val)) =
-                                        row.try_get::<_, Option<chrono::DateTime<chrono::Utc>>>(i)
-                                    {
-                                        val.to_string()
-                                    } else {
-                                        "NULL".to_string()
-                                    }
-                                }
-                                "timestamp" => {
-                                    if let Ok(Some(val)) =
-                                        row.try_get::<_, Option<chrono::NaiveDateTime>>(i)
-                                    {
-                                        val.to_string()
-                                    } else {
-                                        "NULL".to_string()
-                                    }
-                                }
-                                "date" => {
-                                    if let Ok(Some(val)) =
-                                        row.try_get::<_, Option<chrono::NaiveDate>>(i)
-                                    {
-                                        val.to_string()
-                                    } else {
-                                        "NULL".to_string()
-                                    }
-                                }
-                                _ => {
-                                    if let Ok(Some(val)) = row.try_get::<_, Option<String>>(i) {
-                                        val
-                                    } else if let Ok(Some(val)) = row.try_get::<_, Option<&str>>(i)
-                                    {
-                                        val.to_string()
-                                    } else if let Ok(Some(val)) =
-                                        row.try_get::<_, Option<uuid::Uuid>>(i)
-                                    {
-                                        val.to_string()
-                                    } else {
-                                        "NULL".to_string()
-                                    }
-                                }
-                            }
-                        })
-                        .collect()
-                })
-                .collect();
+            let result_rows: Vec<Vec<Option<String>>> = rows.iter().map(row_to_strings).collect();
 
             Ok(QueryResult {
                 columns,
                 rows: result_rows,
                 affected_rows: rows.len() as u64,
+                column_types,
             })
         } else {
             Err(anyhow::anyhow!("Not connected to database"))
         }
     }
 
-    async fn fetch_table_data(
+    async fn execute_statement(&self, statement: &str) -> Result<u64> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Not connected to database"))?;
+        logging::debug(&format!("Executing statement: {}", statement));
+        Ok(client.execute(statement, &[]).await?)
+    }
+
+    async fn delete_rows(
         &self,
         schema: &str,
         table: &str,
-        params: &QueryParams,
-    ) -> Result<QueryResult> {
-        logging::debug(&format!(
-            "Fetching table data for schema {}, table {}",
-            schema, table
-        ));
-
-        // Prepare identifiers
-        let schema_ident = sanitize_column_name(schema);
-        let table_ident = sanitize_column_name(table);
-
-        // Discover column names in ordinal order
+        key_columns: &[String],
+        keys: &[Vec<String>],
+    ) -> Result<u64> {
         let client = self
             .client
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("Not connected to database"))?;
-
-        let col_rows = client
-            .query(
-                "SELECT column_name
-                 FROM information_schema.columns
-                 WHERE table_schema = $1 AND table_name = $2
-                 ORDER BY ordinal_position",
-                &[&schema, &table],
-            )
-            .await?;
-
-        let column_names: Vec<String> = col_rows.iter().map(|r| r.get::<_, String>(0)).collect();
-
-        // Build select list casting each column to text to ensure enums/json/uuid display correctly
-        let select_list = if column_names.is_empty() {
-            "*".to_string()
-        } else {
-            column_names
-                .iter()
-                .map(|c| {
-                    let ident = quote_identifier_exact(c);
-                    format!("({})::text AS {}", ident, ident)
-                })
-                .collect::<Vec<_>>()
-                .join(", ")
-        };
-
-        let mut query = format!(
-            "SELECT {} FROM {}.{}",
-            select_list, schema_ident, table_ident
-        );
-
-        if let Some(where_clause) = &params.where_clause {
-            if !where_clause.trim().is_empty() {
-                query.push_str(&format!(" WHERE {}", where_clause));
-            }
+        if keys.is_empty() {
+            return Ok(0);
         }
 
-        if let Some(order_by) = &params.order_by {
-            if !order_by.trim().is_empty() {
-                // Split by comma to handle multiple columns
-                let orders: Vec<&str> = order_by.split(',').collect();
-                let mut sanitized_orders = Vec::new();
-
-                for order in orders {
-                    let parts: Vec<&str> = order.trim().split_whitespace().collect();
-                    if !parts.is_empty() {
-                        let column = sanitize_column_name(parts[0]);
-                        let direction = parts
-                            .get(1)
-                            .map(|d| d.to_uppercase())
-                            .filter(|d| d == "ASC" || d == "DESC")
-                            .unwrap_or_else(|| "ASC".to_string());
-                        sanitized_orders.push(format!("{} {}", column, direction));
-                    }
-                }
+        let statement = delete_by_keys_statement(schema, table, key_columns, keys, |position, _| {
+            format!("${}", position + 1)
+        });
+        let params: Vec<TextParam> = keys.iter().flatten().cloned().map(TextParam).collect();
+        let param_refs: Vec<&(dyn ToSql + Sync)> =
+            params.iter().map(|p| p as &(dyn ToSql + Sync)).collect();
+        logging::debug(&format!("Executing statement: {}", statement));
+        Ok(client.execute(statement.as_str(), &param_refs).await?)
+    }
 
-                if !sanitized_orders.is_empty() {
-                    query.push_str(" ORDER BY ");
-                    query.push_str(&sanitized_orders.join(", "));
-                }
-            }
-        }
+    async fn update_cell(
+        &self,
+        schema: &str,
+        table: &str,
+        column: &str,
+        value: Option<&str>,
+        key_columns: &[String],
+        key: &[Option<String>],
+    ) -> Result<u64> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Not connected to database"))?;
 
-        if let Some(limit) = params.limit {
-            query.push_str(&format!(" LIMIT {}", limit));
-        }
+        // Text parameters take the column's type, so JSON and arrays need no cast
+        let statement =
+            update_by_key_statement(schema, table, column, value, key_columns, key, |position, _| {
+                format!("${}", position + 1)
+            });
+        let params: Vec<TextParam> = std::iter::once(value)
+            .chain(key.iter().map(Option::as_deref))
+            .flatten()
+            .map(|v| TextParam(v.to_string()))
+            .collect();
+        let param_refs: Vec<&(dyn ToSql + Sync)> =
+            params.iter().map(|p| p as &(dyn ToSql + Sync)).collect();
+        logging::debug(&format!("Executing statement: {}", statement));
+        Ok(client.execute(statement.as_str(), &param_refs).await?)
+    }
 
-        if let Some(offset) = params.offset {
-            query.push_str(&format!(" OFFSET {}", offset));
+    async fn fetch_table_data(
+        &self,
+        schema: &str,
+        table: &str,
+        params: &QueryParams,
+    ) -> Result<QueryResult> {
+        logging::debug(&format!(
+            "Fetching table data for schema {}, table {}",
+            schema, table
+        ));
+
+        let (query, column_types) = self.select_query(schema, table, params).await?;
+        let mut result = self
+            .execute_query_with_params(&query, &params.where_params)
+            .await?;
+        // The driver sees every column as text after the cast, so the catalog's types are kept
+        if result.columns.len() == column_types.len() {
+            result.column_types = column_types;
         }
+        Ok(result)
+    }
 
-        logging::debug(&format!("Executing query: {}", query));
-        self.execute_query(&query).await
+    async fn explain_query(
+        &self,
+        schema: &str,
+        table: &str,
+        params: &QueryParams,
+        analyze: bool,
+    ) -> Result<QueryResult> {
+        let query = format!(
+            "EXPLAIN (ANALYZE {}, FORMAT TEXT) {}",
+            analyze,
+            self.select_query(schema, table, params).await?.0
+        );
+        self.execute_query_with_params(&query, &params.where_params)
+            .await
     }
 
     async fn count_table_rows(
@@ -394,25 +693,42 @@ impl DatabaseConnection for PostgresConnection {
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("Not connected to database"))?;
 
-        let schema_ident = sanitize_column_name(schema);
-        let table_ident = sanitize_column_name(table);
+        read_count(client, &count_query(schema, table, where_clause)).await
+    }
 
-        let mut query = format!(
-            "SELECT COUNT(*)::bigint FROM {}.{}",
-            schema_ident, table_ident
-        );
-        if let Some(w) = where_clause {
-            if !w.trim().is_empty() {
-                query.push_str(&format!(" WHERE {}", w));
-            }
+    fn detached_row_count(&self, schema: &str, table: &str) -> Option<RowCountFuture> {
+        let client = Arc::clone(self.client.as_ref()?);
+        let query = count_query(schema, table, None);
+        Some(Box::pin(async move { read_count(&client, &query).await }))
+    }
+
+    async fn count_matching_rows(
+        &self,
+        schema: &str,
+        table: &str,
+        params: &QueryParams,
+    ) -> Result<u64> {
+        let source = params.base_query_source().unwrap_or_else(|| {
+            format!(
+                "{}.{}",
+                sanitize_column_name(schema),
+                sanitize_column_name(table)
+            )
+        });
+        let mut query = format!("SELECT COUNT(*)::bigint FROM {}", source);
+        if let Some(w) = params.where_clause.as_deref().filter(|w| !w.trim().is_empty()) {
+            query.push_str(&format!(" WHERE {}", w));
         }
 
-        let rows = client.query(&query, &[]).await?;
-        let count: i64 = rows
-            .get(0)
-            .and_then(|r| r.try_get::<_, i64>(0).ok())
-            .unwrap_or(0);
-        Ok(u64::try_from(count).unwrap_or(0))
+        let result = self
+            .execute_query_with_params(&query, &params.where_params)
+            .await?;
+        result
+            .rows
+            .first()
+            .and_then(|row| row.first())
+            .and_then(|count| count.as_deref()?.parse::<u64>().ok())
+            .ok_or_else(|| anyhow::anyhow!("Could not read row count"))
     }
 
     async fn lookup_foreign_key(
@@ -467,6 +783,27 @@ impl DatabaseConnection for PostgresConnection {
         }
     }
 
+    async fn get_primary_keys(&self, schema: &str, table: &str) -> Result<Vec<String>> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Not connected to database"))?;
+        let rows = client
+            .query(
+                "SELECT kcu.column_name::text
+                 FROM information_schema.table_constraints tc
+                 JOIN information_schema.key_column_usage kcu
+                   ON kcu.constraint_schema = tc.constraint_schema
+                  AND kcu.constraint_name = tc.constraint_name
+                 WHERE tc.constraint_type = 'PRIMARY KEY'
+                   AND tc.table_schema = $1 AND tc.table_name = $2
+                 ORDER BY kcu.ordinal_position",
+                &[&schema, &table],
+            )
+            .await?;
+        Ok(rows.iter().map(|row| row.get::<_, String>(0)).collect())
+    }
+
     async fn get_columns(&self, schema: &str, table: &str) -> Result<Vec<ColumnInfo>> {
         let client = self
             .client
@@ -476,7 +813,9 @@ impl DatabaseConnection for PostgresConnection {
         let query = "
             SELECT
                 c.column_name,
-                c.data_type,
+                -- Report arrays by element type (e.g. int4[]) so edits can be cast correctly
+                CASE WHEN c.data_type = 'ARRAY' THEN substr(c.udt_name, 2) || '[]'
+                     ELSE c.data_type END AS data_type,
                 c.is_nullable,
                 CASE WHEN tc.constraint_type = 'PRIMARY KEY' THEN true ELSE false END as is_primary_key
             FROM information_schema.columns c
@@ -510,4 +849,115 @@ impl DatabaseConnection for PostgresConnection {
 
         Ok(columns)
     }
+
+    async fn describe_table(&self, schema: &str, table: &str) -> Result<QueryResult> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Not connected to database"))?;
+
+        // Key flags come from subqueries so a column in several constraints stays one row
+        let query = "
+            SELECT
+                c.column_name::text,
+                CASE WHEN c.data_type = 'ARRAY' THEN substr(c.udt_name, 2) || '[]'
+                     ELSE c.data_type END::text AS data_type,
+                c.is_nullable::text,
+                c.column_default::text,
+                EXISTS (
+                    SELECT 1
+                    FROM information_schema.table_constraints tc
+                    JOIN information_schema.key_column_usage kcu
+                      ON kcu.constraint_schema = tc.constraint_schema
+                     AND kcu.constraint_name = tc.constraint_name
+                    WHERE tc.constraint_type = 'PRIMARY KEY'
+                      AND tc.table_schema = c.table_schema
+                      AND tc.table_name = c.table_name
+                      AND kcu.column_name = c.column_name
+                ) AS is_primary_key,
+                (
+                    SELECT string_agg(
+                        ccu.table_schema || '.' || ccu.table_name || '.' || ccu.column_name,
+                        ', '
+                    )
+                    FROM information_schema.table_constraints tc
+                    JOIN information_schema.key_column_usage kcu
+                      ON kcu.constraint_schema = tc.constraint_schema
+                     AND kcu.constraint_name = tc.constraint_name
+                    JOIN information_schema.constraint_column_usage ccu
+                      ON ccu.constraint_schema = tc.constraint_schema
+                     AND ccu.constraint_name = tc.constraint_name
+                    WHERE tc.constraint_type = 'FOREIGN KEY'
+                      AND tc.table_schema = c.table_schema
+                      AND tc.table_name = c.table_name
+                      AND kcu.column_name = c.column_name
+                )::text AS foreign_key
+            FROM information_schema.columns c
+            WHERE c.table_schema = $1 AND c.table_name = $2
+            ORDER BY c.ordinal_position
+        ";
+
+        let rows = client.query(query, &[&schema, &table]).await?;
+        let rows = rows
+            .iter()
+            .map(|row| {
+                let is_nullable: String = row.get(2);
+                structure_row(
+                    row.get(0),
+                    row.get(1),
+                    is_nullable == "YES",
+                    row.get(3),
+                    row.get(4),
+                    row.get(5),
+                )
+            })
+            .collect();
+        Ok(structure_result(rows))
+    }
+
+    async fn list_indexes(&self, schema: &str, table: &str) -> Result<QueryResult> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Not connected to database"))?;
+
+        // pg_get_indexdef gives each key as written, so expression indexes show their expression
+        let query = "
+            SELECT
+                i.relname::text AS index_name,
+                string_agg(pg_get_indexdef(ix.indexrelid, k.ord, true), ', ' ORDER BY k.ord)
+                    AS columns,
+                ix.indisunique,
+                am.amname::text AS index_type
+            FROM pg_index ix
+            JOIN pg_class t ON t.oid = ix.indrelid
+            JOIN pg_namespace n ON n.oid = t.relnamespace
+            JOIN pg_class i ON i.oid = ix.indexrelid
+            JOIN pg_am am ON am.oid = i.relam
+            CROSS JOIN LATERAL generate_series(1, ix.indnatts::int) AS k(ord)
+            WHERE n.nspname = $1 AND t.relname = $2
+            GROUP BY i.relname, ix.indisunique, am.amname
+            ORDER BY i.relname
+        ";
+
+        let rows = client.query(query, &[&schema, &table]).await?;
+        let rows = rows
+            .iter()
+            .map(|row| {
+                let is_unique: bool = row.get(2);
+                vec![
+                    row.get(0),
+                    row.get(1),
+                    Some(if is_unique { "YES" } else { "NO" }.to_string()),
+                    row.get(3),
+                ]
+            })
+            .collect();
+        Ok(QueryResult {
+            columns: INDEX_COLUMNS.iter().map(|column| column.to_string()).collect(),
+            rows,
+            affected_rows: 0,
+            column_types: Vec::new(),
+        })
+    }
 }
@@ -22,14 +22,14 @@ impl SqliteConnection {
         }
     }
 
-    fn value_ref_to_string(value: ValueRef<'_>) -> String {
-        match value {
-            ValueRef::Null => "NULL".to_string(),
+    fn value_ref_to_string(value: ValueRef<'_>) -> Option<String> {
+        Some(match value {
+            ValueRef::Null => return None,
             ValueRef::Integer(v) => v.to_string(),
             ValueRef::Real(v) => v.to_string(),
             ValueRef::Text(v) => String::from_utf8_lossy(v).to_string(),
             ValueRef::Blob(v) => format!("0x{}", hex::encode(v)),
-        }
+        })
     }
 
     fn sanitize_identifier(name: &str) -> String {
@@ -40,7 +40,21 @@ impl SqliteConnection {
         format!("\"{}\"", sanitized)
     }
 
-    fn map_row_to_strings(row: &SyncRow<'_>, col_count: usize) -> rusqlite::Result<Vec<String>> {
+    /// Storage class of a value, standing in for the column type SQLite doesn't report
+    fn value_type_name(value: ValueRef<'_>) -> Option<&'static str> {
+        match value {
+            ValueRef::Null => None,
+            ValueRef::Integer(_) => Some("integer"),
+            ValueRef::Real(_) => Some("real"),
+            ValueRef::Text(_) => Some("text"),
+            ValueRef::Blob(_) => Some("blob"),
+        }
+    }
+
+    fn map_row_to_strings(
+        row: &SyncRow<'_>,
+        col_count: usize,
+    ) -> rusqlite::Result<Vec<Option<String>>> {
         (0..col_count)
             .map(|i| {
                 row.get_ref(i)
@@ -49,6 +63,75 @@ impl SqliteConnection {
             })
             .collect()
     }
+
+    /// Runs `query` with `params` bound to its `$n` placeholders, reading at most `max_rows`
+    /// of its rows
+    async fn query_rows(
+        &self,
+        query: &str,
+        params: &[String],
+        max_rows: usize,
+    ) -> Result<QueryResult> {
+        if let Some(conn) = &self.conn {
+            let q = query.to_string();
+            let params = params.to_vec();
+            let result = conn.call(move |c: &mut rusqlite::Connection| -> tokio_rusqlite::Result<QueryResult> {
+                let mut stmt = c.prepare(&q)?;
+                // SQLite treats `$1` as a named parameter, so bind each value by its name
+                for (i, value) in params.iter().enumerate() {
+                    if let Some(index) = stmt.parameter_index(&format!("${}", i + 1))? {
+                        stmt.raw_bind_parameter(index, value)?;
+                    }
+                }
+                // Anything with result columns returns rows, e.g. a SELECT or WITH query
+                if stmt.column_count() > 0 {
+                    let col_count = stmt.column_count();
+                    let columns: Vec<String> = stmt
+                        .column_names()
+                        .iter()
+                        .map(|s| s.to_string())
+                        .collect();
+                    let mut rows_vec = Vec::new();
+                    // Columns of a table keep their declared type; the others, e.g.
+                    // expressions, take the type of their first non-NULL value
+                    let mut column_types: Vec<String> = stmt
+                        .columns()
+                        .iter()
+                        .map(|column| column.decl_type().unwrap_or_default().to_string())
+                        .collect();
+                    let mut rows = stmt.raw_query();
+                    while let Some(row) = rows.next()? {
+                        if rows_vec.len() == max_rows {
+                            break;
+                        }
+                        for (i, column_type) in column_types.iter_mut().enumerate() {
+                            if column_type.is_empty() {
+                                let value = row.get_ref(i)?;
+                                *column_type = SqliteConnection::value_type_name(value)
+                                    .unwrap_or_default()
+                                    .to_string();
+                            }
+                        }
+                        rows_vec.push(SqliteConnection::map_row_to_strings(row, col_count)?);
+                    }
+                    let affected_rows = rows_vec.len() as u64;
+                    Ok(QueryResult { columns, rows: rows_vec, affected_rows, column_types })
+                } else {
+                    let affected = stmt.raw_execute()? as u64;
+                    Ok(QueryResult {
+                        columns: Vec::new(),
+                        rows: Vec::new(),
+                        affected_rows: affected,
+                        column_types: Vec::new(),
+                    })
+                }
+            })
+            .await?;
+            Ok(result)
+        } else {
+            Err(anyhow::anyhow!("Not connected to database"))
+        }
+    }
 }
 
 #[async_trait]
@@ -56,6 +139,14 @@ impl DatabaseConnection for SqliteConnection {
     async fn connect(&mut self) -> Result<()> {
         let path = self.resolve_path();
         let conn = Connection::open(path).await?;
+        for statement in &self.config.on_connect {
+            let sql = statement.clone();
+            conn.call(move |conn| Ok(conn.execute_batch(&sql)?))
+                .await
+                .map_err(|e| {
+                    anyhow::anyhow!("On-connect statement `{}` failed: {}", statement, e)
+                })?;
+        }
         self.conn = Some(conn);
         Ok(())
     }
@@ -73,7 +164,7 @@ impl DatabaseConnection for SqliteConnection {
         Ok(vec!["main".to_string()])
     }
 
-    async fn list_tables(&self, _schema: &str) -> Result<Vec<String>> {
+    async fn list_tables(&self, _database: &str, _schema: &str) -> Result<Vec<String>> {
         if let Some(conn) = &self.conn {
             let tables = conn
                 .call(|c: &mut rusqlite::Connection| -> tokio_rusqlite::Result<Vec<String>> {
@@ -94,38 +185,62 @@ impl DatabaseConnection for SqliteConnection {
         }
     }
 
-    async fn execute_query(&self, query: &str) -> Result<QueryResult> {
-        if let Some(conn) = &self.conn {
-            let q = query.to_string();
-            let upper = q.trim_start().to_ascii_uppercase();
-            let is_select = upper.starts_with("SELECT");
-
-            let result = conn.call(move |c: &mut rusqlite::Connection| -> tokio_rusqlite::Result<QueryResult> {
-                if is_select {
-                    let mut stmt = c.prepare(&q)?;
-                    let col_count = stmt.column_count();
-                    let columns: Vec<String> = stmt
-                        .column_names()
-                        .iter()
-                        .map(|s| s.to_string())
-                        .collect();
-                    let mut rows_vec = Vec::new();
-                    let mut rows = stmt.query([])?;
-                    while let Some(row) = rows.next()? {
-                        rows_vec.push(SqliteConnection::map_row_to_strings(row, col_count)?);
-                    }
-                    let affected_rows = rows_vec.len() as u64;
-                    Ok(QueryResult { columns, rows: rows_vec, affected_rows })
-                } else {
-                    let affected = c.execute(&q, [])? as u64;
-                    Ok(QueryResult { columns: Vec::new(), rows: Vec::new(), affected_rows: affected })
+    async fn list_relations(
+        &self,
+        _database: &str,
+        _schema: &str,
+    ) -> Result<Vec<(String, RelationKind)>> {
+        let conn = self
+            .conn
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Not connected to database"))?;
+        let relations = conn
+            .call(|c: &mut rusqlite::Connection| -> tokio_rusqlite::Result<Vec<(String, String)>> {
+                let mut stmt = c.prepare(
+                    "SELECT name, type FROM sqlite_master WHERE type IN ('table', 'view') AND name NOT LIKE 'sqlite_%' ORDER BY name",
+                )?;
+                let iter = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+                let mut out = Vec::new();
+                for relation in iter {
+                    out.push(relation?);
                 }
+                Ok(out)
             })
             .await?;
-            Ok(result)
-        } else {
-            Err(anyhow::anyhow!("Not connected to database"))
-        }
+
+        Ok(relations
+            .into_iter()
+            .map(|(name, kind)| {
+                let kind = if kind == "view" {
+                    RelationKind::View
+                } else {
+                    RelationKind::Table
+                };
+                (name, kind)
+            })
+            .filter(|(_, kind)| self.config.shows_relation(*kind))
+            .collect())
+    }
+
+    async fn execute_query(&self, query: &str) -> Result<QueryResult> {
+        self.execute_query_with_params(query, &[]).await
+    }
+
+    async fn execute_query_with_params(
+        &self,
+        query: &str,
+        params: &[String],
+    ) -> Result<QueryResult> {
+        self.query_rows(query, params, usize::MAX).await
+    }
+
+    async fn execute_query_capped(
+        &self,
+        query: &str,
+        max_rows: usize,
+        _in_transaction: bool,
+    ) -> Result<QueryResult> {
+        self.query_rows(query, &[], max_rows).await
     }
 
     async fn fetch_table_data(
@@ -134,8 +249,10 @@ impl DatabaseConnection for SqliteConnection {
         table: &str,
         params: &QueryParams,
     ) -> Result<QueryResult> {
-        let table_ident = Self::sanitize_identifier(table);
-        let mut query = format!("SELECT * FROM {}", table_ident);
+        let source = params
+            .base_query_source()
+            .unwrap_or_else(|| Self::sanitize_identifier(table));
+        let mut query = format!("SELECT * FROM {}", source);
         if let Some(where_clause) = &params.where_clause {
             if !where_clause.trim().is_empty() {
                 query.push_str(&format!(" WHERE {}", where_clause));
@@ -143,20 +260,13 @@ impl DatabaseConnection for SqliteConnection {
         }
         if let Some(order_by) = &params.order_by {
             if !order_by.trim().is_empty() {
-                let orders: Vec<&str> = order_by.split(',').collect();
-                let mut sanitized_orders = Vec::new();
-                for order in orders {
-                    let parts: Vec<&str> = order.trim().split_whitespace().collect();
-                    if !parts.is_empty() {
-                        let col = Self::sanitize_identifier(parts[0]);
-                        let dir = parts
-                            .get(1)
-                            .map(|d| d.to_ascii_uppercase())
-                            .filter(|d| d == "ASC" || d == "DESC")
-                            .unwrap_or_else(|| "ASC".to_string());
-                        sanitized_orders.push(format!("{} {}", col, dir));
-                    }
-                }
+                // Emulate NULLS FIRST/LAST by sorting on the null check first
+                let sanitized_orders: Vec<String> = OrderByTerm::parse_list(order_by)
+                    .iter()
+                    .map(|term| {
+                        term.to_sql_emulating_nulls(&Self::sanitize_identifier(&term.column))
+                    })
+                    .collect();
                 if !sanitized_orders.is_empty() {
                     query.push_str(" ORDER BY ");
                     query.push_str(&sanitized_orders.join(", "));
@@ -169,7 +279,8 @@ impl DatabaseConnection for SqliteConnection {
         if let Some(offset) = params.offset {
             query.push_str(&format!(" OFFSET {}", offset));
         }
-        self.execute_query(&query).await
+        self.execute_query_with_params(&query, &params.where_params)
+            .await
     }
 
     async fn count_table_rows(
@@ -186,24 +297,44 @@ impl DatabaseConnection for SqliteConnection {
                     query.push_str(&format!(" WHERE {}", w));
                 }
             }
-            let count = conn
-                .call(move |c: &mut rusqlite::Connection| -> tokio_rusqlite::Result<u64> {
-                    let mut stmt = c.prepare(&query)?;
-                    let mut rows = stmt.query([])?;
-                    if let Some(row) = rows.next()? {
-                        let v: i64 = row.get(0)?;
-                        Ok(u64::try_from(v).unwrap_or(0))
-                    } else {
-                        Ok(0)
-                    }
-                })
-                .await?;
-            Ok(count)
+            read_count(conn, query).await
         } else {
             Err(anyhow::anyhow!("Not connected to database"))
         }
     }
 
+    fn detached_row_count(&self, _schema: &str, table: &str) -> Option<RowCountFuture> {
+        // Calls on a clone queue up on the same database thread
+        let conn = self.conn.clone()?;
+        let query = format!("SELECT COUNT(*) FROM {}", Self::sanitize_identifier(table));
+        Some(Box::pin(async move { read_count(&conn, query).await }))
+    }
+
+    async fn count_matching_rows(
+        &self,
+        _schema: &str,
+        table: &str,
+        params: &QueryParams,
+    ) -> Result<u64> {
+        let source = params
+            .base_query_source()
+            .unwrap_or_else(|| Self::sanitize_identifier(table));
+        let mut query = format!("SELECT COUNT(*) FROM {}", source);
+        if let Some(w) = params.where_clause.as_deref().filter(|w| !w.trim().is_empty()) {
+            query.push_str(&format!(" WHERE {}", w));
+        }
+
+        let result = self
+            .execute_query_with_params(&query, &params.where_params)
+            .await?;
+        result
+            .rows
+            .first()
+            .and_then(|row| row.first())
+            .and_then(|count| count.as_deref()?.parse::<u64>().ok())
+            .ok_or_else(|| anyhow::anyhow!("Could not read row count"))
+    }
+
     async fn lookup_foreign_key(
         &self,
         _schema: &str,
@@ -275,4 +406,21 @@ impl DatabaseConnection for SqliteConnection {
     }
 }
 
-
+/// Runs `query`, a COUNT(*), and reads the count it returns
+async fn read_count(conn: &Connection, query: String) -> Result<u64> {
+    let count = conn
+        .call(
+            move |c: &mut rusqlite::Connection| -> tokio_rusqlite::Result<u64> {
+                let mut stmt = c.prepare(&query)?;
+                let mut rows = stmt.query([])?;
+                if let Some(row) = rows.next()? {
+                    let v: i64 = row.get(0)?;
+                    Ok(u64::try_from(v).unwrap_or(0))
+                } else {
+                    Ok(0)
+                }
+            },
+        )
+        .await?;
+    Ok(count)
+}
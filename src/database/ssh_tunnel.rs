@@ -1,21 +1,189 @@
 use anyhow::{anyhow, Context, Result};
+use std::io::Write;
 use std::net::{SocketAddr, TcpListener};
-use tokio::process::{Child, Command};
+use std::path::Path;
+use std::process::ExitStatus;
+use tempfile::TempPath;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::process::{Child, ChildStderr, Command};
 use tokio::time::{sleep, Duration};
 
 use super::SSHConfig;
+use crate::logging;
+
+/// Environment variable the askpass helper reads the passphrase or password from, so the
+/// secret never appears on a command line
+const SECRET_ENV: &str = "LAZYLODE_SSH_SECRET";
+
+/// How the tunnel authenticates. The first that applies wins: the agent, then the key (with
+/// its passphrase), then the password; with none of them ssh uses its own configuration.
+enum SshAuth<'a> {
+    Agent,
+    Key {
+        path: &'a str,
+        passphrase: Option<&'a str>,
+    },
+    Password(&'a str),
+    Default,
+}
+
+impl<'a> SshAuth<'a> {
+    fn from_config(ssh: &'a SSHConfig) -> Result<Self> {
+        let non_empty = |value: &'a Option<String>| value.as_deref().filter(|v| !v.is_empty());
+        if ssh.use_agent {
+            if std::env::var_os("SSH_AUTH_SOCK").is_none() {
+                return Err(anyhow!(
+                    "use_agent is set but no ssh-agent is running (SSH_AUTH_SOCK is not set)"
+                ));
+            }
+            return Ok(SshAuth::Agent);
+        }
+        if let Some(path) = non_empty(&ssh.private_key_path) {
+            if !Path::new(path).is_file() {
+                return Err(anyhow!("SSH key file '{}' does not exist", path));
+            }
+            return Ok(SshAuth::Key {
+                path,
+                passphrase: non_empty(&ssh.passphrase),
+            });
+        }
+        Ok(match non_empty(&ssh.password) {
+            Some(password) => SshAuth::Password(password),
+            None => SshAuth::Default,
+        })
+    }
+
+    /// Passphrase or password ssh is to be given when it asks
+    fn secret(&self) -> Option<&'a str> {
+        match self {
+            SshAuth::Key { passphrase, .. } => *passphrase,
+            SshAuth::Password(password) => Some(password),
+            SshAuth::Agent | SshAuth::Default => None,
+        }
+    }
+
+    fn args(&self) -> Vec<String> {
+        let options: &[&str] = match self {
+            // Never prompt: the agent has the keys or authentication fails
+            SshAuth::Agent => &["BatchMode=yes"],
+            SshAuth::Key { path, .. } => {
+                return vec![
+                    "-i".into(),
+                    path.to_string(),
+                    "-o".into(),
+                    "IdentitiesOnly=yes".into(),
+                    "-o".into(),
+                    "NumberOfPasswordPrompts=1".into(),
+                ];
+            }
+            SshAuth::Password(_) => &[
+                "PreferredAuthentications=password,keyboard-interactive",
+                "PubkeyAuthentication=no",
+                "NumberOfPasswordPrompts=1",
+            ],
+            SshAuth::Default => &[],
+        };
+        options
+            .iter()
+            .flat_map(|option| ["-o".to_string(), option.to_string()])
+            .collect()
+    }
+
+    /// Error for an ssh process that exited before the tunnel was up
+    fn exit_error(&self, status: ExitStatus, stderr: &str) -> anyhow::Error {
+        let denied = stderr.contains("Permission denied") || stderr.contains("passphrase");
+        match self {
+            SshAuth::Key {
+                path,
+                passphrase: Some(_),
+            } if denied => anyhow!(
+                "Could not decrypt SSH key '{}' or it was rejected; check the passphrase ({})",
+                path,
+                stderr
+            ),
+            SshAuth::Key {
+                path,
+                passphrase: None,
+            } if denied => anyhow!(
+                "SSH key '{}' was rejected; if it is encrypted, set its passphrase ({})",
+                path,
+                stderr
+            ),
+            _ if stderr.is_empty() => anyhow!("ssh process exited early with status: {}", status),
+            _ => anyhow!("ssh process exited early with status {}: {}", status, stderr),
+        }
+    }
+}
+
+/// Script ssh runs as `SSH_ASKPASS`; it answers every prompt with `SECRET_ENV`. It is created
+/// under a fresh name only the user can access, and deleted when the returned path is dropped.
+fn askpass_script() -> Result<TempPath> {
+    let mut file = tempfile::Builder::new()
+        .prefix("lazylode-askpass-")
+        .suffix(".sh")
+        .tempfile()
+        .context("failed to create ssh askpass helper")?;
+    write!(file, "#!/bin/sh\nprintf '%s\\n' \"${}\"\n", SECRET_ENV)
+        .context("failed to write ssh askpass helper")?;
+    // Closed, since a file still open for writing can't be executed
+    let path = file.into_temp_path();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o700))
+            .context("failed to make ssh askpass helper executable")?;
+    }
+    Ok(path)
+}
+
+/// Logs what a running tunnel's ssh writes to stderr, so the pipe never fills up and stalls it
+async fn log_stderr(stderr: ChildStderr, host: String) {
+    let mut lines = BufReader::new(stderr).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        logging::warn(&format!("ssh {}: {}", host, line));
+    }
+}
+
+/// Context of the errors of `SshTunnelProcess::start`, telling a failed tunnel apart from a
+/// failed database login
+#[derive(Debug)]
+pub struct TunnelError {
+    pub host: String,
+}
+
+impl std::fmt::Display for TunnelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SSH tunnel through {} failed", self.host)
+    }
+}
 
 pub struct SshTunnelProcess {
     pub local_port: u16,
     child: Child,
+    /// Askpass helper ssh may still need, deleted with the tunnel
+    askpass: Option<TempPath>,
 }
 
 impl SshTunnelProcess {
+    /// Forwards a free local port to `target_host:target_port` through `ssh`
     pub async fn start(
         ssh: &SSHConfig,
         target_host: &str,
         target_port: u16,
     ) -> Result<SshTunnelProcess> {
+        Self::spawn(ssh, target_host, target_port)
+            .await
+            .context(TunnelError {
+                host: ssh.host.clone(),
+            })
+    }
+
+    async fn spawn(
+        ssh: &SSHConfig,
+        target_host: &str,
+        target_port: u16,
+    ) -> Result<SshTunnelProcess> {
+        let auth = SshAuth::from_config(ssh)?;
         let local_port = allocate_free_local_port()?;
 
         let mut args: Vec<String> = vec![
@@ -30,12 +198,7 @@ impl SshTunnelProcess {
             "StrictHostKeyChecking=accept-new".into(),
         ];
 
-        if let Some(key_path) = &ssh.private_key_path {
-            if !key_path.is_empty() {
-                args.push("-i".into());
-                args.push(key_path.clone());
-            }
-        }
+        args.extend(auth.args());
 
         // Build user@host target
         let user_at_host = if !ssh.username.is_empty() {
@@ -51,21 +214,53 @@ impl SshTunnelProcess {
             .stdout(std::process::Stdio::null())
             .stderr(std::process::Stdio::piped());
 
+        // Without a terminal, ssh asks for the passphrase or password through SSH_ASKPASS
+        let askpass = match auth.secret() {
+            Some(secret) => {
+                let askpass = askpass_script()?;
+                cmd.env("SSH_ASKPASS", &askpass)
+                    .env("SSH_ASKPASS_REQUIRE", "force")
+                    .env(SECRET_ENV, secret);
+                // Older ssh versions only use SSH_ASKPASS when DISPLAY is set
+                if std::env::var_os("DISPLAY").is_none() {
+                    cmd.env("DISPLAY", ":0");
+                }
+                Some(askpass)
+            }
+            None => None,
+        };
+
         let mut child = cmd.spawn().context("failed to spawn ssh process")?;
 
         // Give ssh some time to bind and establish forwarding; also detect early exit
         for _ in 0..10u8 {
             if let Some(status) = child.try_wait()? {
-                return Err(anyhow!("ssh process exited early with status: {}", status));
+                let mut stderr = String::new();
+                if let Some(mut pipe) = child.stderr.take() {
+                    pipe.read_to_string(&mut stderr).await.ok();
+                }
+                return Err(auth.exit_error(status, stderr.trim()));
             }
             sleep(Duration::from_millis(100)).await;
         }
 
-        Ok(SshTunnelProcess { local_port, child })
+        // ssh only binds the forwarded port once it has authenticated, after which it won't
+        // run the helper again; a slower login keeps it until the tunnel stops
+        let askpass = askpass.filter(|_| TcpListener::bind(("127.0.0.1", local_port)).is_ok());
+        if let Some(stderr) = child.stderr.take() {
+            tokio::spawn(log_stderr(stderr, ssh.host.clone()));
+        }
+
+        Ok(SshTunnelProcess {
+            local_port,
+            child,
+            askpass,
+        })
     }
 
     pub async fn stop(&mut self) -> Result<()> {
         let _ = self.child.kill().await;
+        self.askpass = None;
         Ok(())
     }
 }
@@ -0,0 +1,210 @@
+//! Chunked deletion of the rows marked in a result tab, run one statement per UI tick so it
+//! can report progress and be cancelled.
+
+use crate::database::core::{dialect_identifier, dialect_literal};
+use crate::database::DatabaseType;
+
+/// Rows removed by a single `DELETE` when the table has a primary key
+pub const DELETE_CHUNK_SIZE: usize = 200;
+
+/// Rows removed by a single `DELETE` when rows are matched on every column
+pub const DELETE_CHUNK_SIZE_WITHOUT_KEY: usize = 25;
+
+/// Savepoint a deletion runs under inside a transaction opened with `begin`
+const SAVEPOINT: &str = "lazylode_delete";
+
+/// How a chunk identifies the rows it removes
+#[derive(Debug, Clone)]
+pub enum DeletePredicate {
+    /// Primary key values of each row, removed through `DatabaseConnection::delete_rows`
+    Keys(Vec<Vec<String>>),
+    /// A `DELETE` matching every column, for tables without a primary key
+    Statement(String),
+}
+
+/// One batch of rows and the number of rows it is meant to remove
+#[derive(Debug, Clone)]
+pub struct DeleteChunk {
+    pub predicate: DeletePredicate,
+    pub row_count: usize,
+}
+
+/// A deletion in progress inside a transaction on `connection`
+#[derive(Debug, Clone)]
+pub struct BulkDeletion {
+    pub connection: String,
+    /// Whether the user's transaction was open, so a savepoint is used and nothing committed
+    pub in_transaction: bool,
+    pub schema: String,
+    pub table: String,
+    pub key_columns: Vec<String>,
+    /// Table shown in the progress modal, e.g. `public.orders`
+    pub target: String,
+    pub requested: usize,
+    pub processed: usize,
+    pub deleted: u64,
+    pub cancel_requested: bool,
+    chunks: Vec<DeleteChunk>,
+    next_chunk: usize,
+}
+
+impl BulkDeletion {
+    pub fn new(
+        connection: &str,
+        schema: &str,
+        table: &str,
+        key_columns: &[String],
+        chunks: Vec<DeleteChunk>,
+    ) -> Self {
+        let target = if schema.is_empty() {
+            table.to_string()
+        } else {
+            format!("{}.{}", schema, table)
+        };
+        Self {
+            connection: connection.to_string(),
+            in_transaction: false,
+            schema: schema.to_string(),
+            table: table.to_string(),
+            key_columns: key_columns.to_vec(),
+            target,
+            requested: chunks.iter().map(|chunk| chunk.row_count).sum(),
+            processed: 0,
+            deleted: 0,
+            cancel_requested: false,
+            chunks,
+            next_chunk: 0,
+        }
+    }
+
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    pub fn next_chunk(&self) -> Option<&DeleteChunk> {
+        self.chunks.get(self.next_chunk)
+    }
+
+    /// Records the result of the current chunk and moves on to the next one
+    pub fn advance(&mut self, deleted: u64) {
+        if let Some(chunk) = self.chunks.get(self.next_chunk) {
+            self.processed += chunk.row_count;
+            self.deleted += deleted;
+            self.next_chunk += 1;
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_chunk >= self.chunks.len()
+    }
+
+    /// Statement run before the first chunk
+    pub fn begin_statement(&self) -> String {
+        if self.in_transaction {
+            format!("SAVEPOINT {}", SAVEPOINT)
+        } else {
+            "BEGIN".to_string()
+        }
+    }
+
+    /// Statement run after the last chunk
+    pub fn finish_statement(&self) -> String {
+        if self.in_transaction {
+            format!("RELEASE SAVEPOINT {}", SAVEPOINT)
+        } else {
+            "COMMIT".to_string()
+        }
+    }
+
+    /// Statement undoing the chunks run so far, leaving an open transaction open
+    pub fn abort_statement(&self) -> String {
+        if self.in_transaction {
+            format!("ROLLBACK TO SAVEPOINT {}", SAVEPOINT)
+        } else {
+            "ROLLBACK".to_string()
+        }
+    }
+
+    /// Completed share of the requested rows, from 0.0 to 1.0
+    pub fn progress(&self) -> f64 {
+        if self.requested == 0 {
+            1.0
+        } else {
+            self.processed as f64 / self.requested as f64
+        }
+    }
+}
+
+/// Splits `rows` of `qualified_table` into chunks to delete one at a time.
+///
+/// With key columns each chunk lists the rows' key values; without them every column is
+/// matched by a statement generated in the quoting of `db_type`, which is slower and may
+/// also hit duplicate rows, so chunks are smaller.
+pub fn build_delete_chunks(
+    db_type: &DatabaseType,
+    qualified_table: &str,
+    columns: &[String],
+    key_columns: &[String],
+    rows: &[&Vec<Option<String>>],
+) -> Vec<DeleteChunk> {
+    let key_indices: Vec<usize> = key_columns
+        .iter()
+        .filter_map(|key| columns.iter().position(|column| column == key))
+        .collect();
+    let use_keys = !key_indices.is_empty() && key_indices.len() == key_columns.len();
+
+    let chunk_size = if use_keys {
+        DELETE_CHUNK_SIZE
+    } else {
+        DELETE_CHUNK_SIZE_WITHOUT_KEY
+    };
+
+    rows.chunks(chunk_size)
+        .map(|chunk| {
+            let predicate = if use_keys {
+                DeletePredicate::Keys(
+                    chunk
+                        .iter()
+                        .map(|row| {
+                            key_indices
+                                .iter()
+                                .map(|&index| row.get(index).cloned().flatten().unwrap_or_default())
+                                .collect()
+                        })
+                        .collect(),
+                )
+            } else {
+                let conditions = chunk
+                    .iter()
+                    .map(|row| {
+                        let conditions = columns
+                            .iter()
+                            .zip(row.iter())
+                            .map(|(column, value)| {
+                                let column = dialect_identifier(db_type, column);
+                                match value {
+                                    None => format!("{} IS NULL", column),
+                                    Some(value) => {
+                                        format!("{} = {}", column, dialect_literal(db_type, value))
+                                    }
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                            .join(" AND ");
+                        format!("({})", conditions)
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" OR ");
+                DeletePredicate::Statement(format!(
+                    "DELETE FROM {} WHERE {}",
+                    qualified_table, conditions
+                ))
+            };
+
+            DeleteChunk {
+                predicate,
+                row_count: chunk.len(),
+            }
+        })
+        .collect()
+}
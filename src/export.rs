@@ -0,0 +1,131 @@
+//! Serialisation of result tabs to files for use outside the application.
+use crate::database::QueryResult;
+use std::path::PathBuf;
+
+/// File formats a result tab can be exported to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    /// Array of objects keyed by column name, with every value a string
+    Json,
+    /// Like `Json`, but numeric and boolean looking cells become JSON numbers and bools
+    TypedJson,
+}
+
+impl ExportFormat {
+    /// Format named `name`; `typed` (`--typed`) only applies to JSON
+    pub fn parse(name: &str, typed: bool) -> Option<Self> {
+        match (name.to_ascii_lowercase().as_str(), typed) {
+            ("csv", false) => Some(Self::Csv),
+            ("json", false) => Some(Self::Json),
+            ("json", true) => Some(Self::TypedJson),
+            _ => None,
+        }
+    }
+
+    pub fn render(self, result: &QueryResult) -> String {
+        match self {
+            Self::Csv => to_csv(result),
+            Self::Json => to_json(result, false),
+            Self::TypedJson => to_json(result, true),
+        }
+    }
+}
+
+/// Quotes a CSV field when it contains a separator, quote or line break (RFC 4180)
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// NULL fields are written empty
+fn csv_record(fields: &[Option<&str>]) -> String {
+    let mut record = fields
+        .iter()
+        .map(|field| csv_field(field.unwrap_or_default()))
+        .collect::<Vec<_>>()
+        .join(",");
+    record.push_str("\r\n");
+    record
+}
+
+/// Header row followed by every row, with CRLF line endings
+pub fn to_csv(result: &QueryResult) -> String {
+    let header: Vec<Option<&str>> = result.columns.iter().map(|c| Some(c.as_str())).collect();
+    std::iter::once(header)
+        .chain(
+            result
+                .rows
+                .iter()
+                .map(|row| row.iter().map(Option::as_deref).collect()),
+        )
+        .map(|fields| csv_record(&fields))
+        .collect()
+}
+
+/// JSON value of a cell; NULL becomes `null`
+fn json_value(cell: Option<&str>, typed: bool) -> serde_json::Value {
+    use serde_json::Value;
+
+    let Some(cell) = cell else {
+        return Value::Null;
+    };
+    if typed {
+        match cell {
+            "true" => return Value::Bool(true),
+            "false" => return Value::Bool(false),
+            _ => {}
+        }
+        // Only canonical integers, so values like `007` keep their leading zeros
+        if let Ok(int) = cell.parse::<i64>() {
+            if int.to_string() == cell {
+                return Value::from(int);
+            }
+        }
+        let looks_numeric = cell.chars().any(|c| c.is_ascii_digit())
+            && cell
+                .chars()
+                .all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E'));
+        let unsigned = cell.trim_start_matches(['-', '+']);
+        let zero_padded = unsigned.starts_with('0') && !unsigned.starts_with("0.");
+        if looks_numeric && !zero_padded {
+            if let Some(number) = cell
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+            {
+                return Value::Number(number);
+            }
+        }
+    }
+    Value::String(cell.to_string())
+}
+
+/// Array of objects keyed by column name, in column order
+pub fn to_json(result: &QueryResult, typed: bool) -> String {
+    let rows: Vec<serde_json::Value> = result
+        .rows
+        .iter()
+        .map(|row| {
+            let object = result
+                .columns
+                .iter()
+                .zip(row.iter())
+                .map(|(column, cell)| (column.clone(), json_value(cell.as_deref(), typed)))
+                .collect::<serde_json::Map<_, _>>();
+            serde_json::Value::Object(object)
+        })
+        .collect();
+    serde_json::to_string_pretty(&rows).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Resolves a user supplied path, expanding a leading `~/` to the home directory
+pub fn resolve_path(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
@@ -31,6 +31,8 @@ pub enum TreeAction {
     Expand,
     /// Collapse a tree item.
     Collapse,
+    /// Activate a tree item: expand connections, databases and schemas, open tables.
+    Activate,
 }
 
 /// Represents all possible actions in the application.
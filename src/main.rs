@@ -3,10 +3,18 @@ mod bootstrap;
 mod command;
 mod config;
 mod database;
+mod deletion;
+mod export;
 mod input;
 mod logging;
 mod navigation;
+mod query_history;
+mod query_log;
 mod runtime;
+mod saved_queries;
+mod search;
+mod secrets;
+mod session;
 mod theme;
 mod ui;
 
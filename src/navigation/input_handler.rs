@@ -12,14 +12,32 @@ pub struct NavigationInputHandler;
 impl NavigationInputHandler {
     /// Handle a key event using the new navigation system
     pub async fn handle_key(key: KeyCode, modifiers: KeyModifiers, app: &mut App) -> Result<()> {
+        // A running bulk deletion only listens for cancellation
+        if app.bulk_deletion.is_some() {
+            let action = app
+                .navigation_manager
+                .config()
+                .key_mapping
+                .get_action(key, modifiers);
+            if matches!(action, Some(NavigationAction::Cancel | NavigationAction::Quit)) {
+                app.cancel_bulk_deletion();
+            }
+            return Ok(());
+        }
+
         // Handle modal input using the modal manager
         if app.modal_manager.has_modals() {
+            // A modal in insert mode takes every key as text, including ':' and 'q'
+            let modal_editing = app.modal_manager.get_active_mode()
+                == Some(crate::navigation::types::VimMode::Insert);
+
             // Allow command mode to be opened even when modal is active
             if let Some(action) = app
                 .navigation_manager
                 .config()
                 .key_mapping
                 .get_action(key, modifiers)
+                .filter(|_| !modal_editing)
             {
                 match action {
                     NavigationAction::EnterCommandMode => {
@@ -41,8 +59,11 @@ impl NavigationInputHandler {
             // Delegate all other input to the modal
             if app.input_mode != crate::app::InputMode::Command {
                 // Check common modal keys first
-                let common_result =
-                    crate::ui::modal_manager::utils::handle_common_keys(key, modifiers, app);
+                let common_result = if modal_editing {
+                    None
+                } else {
+                    crate::ui::modal_manager::utils::handle_common_keys(key, modifiers, app)
+                };
                 if let Some(result) = common_result {
                     if matches!(result, crate::ui::modal_manager::ModalResult::Closed) {
                         app.modal_manager.close_active();
@@ -69,23 +90,57 @@ impl NavigationInputHandler {
                     }
                     crate::ui::modal_manager::ModalResult::Action(action) => {
                         // Handle modal actions
-                        if action.starts_with("apply_theme:") {
+                        if action == "confirm_deletions" {
+                            if let Err(e) = app.confirm_deletions().await {
+                                app.modal_manager.close_active();
+                                app.set_status_message(format!("Deletion failed: {}", e));
+                            }
+                        } else if action == "explain_analyze" {
+                            app.modal_manager.close_active();
+                            if let Err(e) = app.explain_current_query(true).await {
+                                app.set_status_message(format!("Explain failed: {}", e));
+                            }
+                        } else if let Some(values) = action.strip_prefix("bind_query_params:") {
+                            let values: Vec<String> =
+                                serde_json::from_str(values).unwrap_or_default();
+                            app.modal_manager.close_active();
+                            if let Err(e) = app.bind_query_params(values).await {
+                                app.set_status_message(format!("Query failed: {}", e));
+                            }
+                        } else if let Some(filter) = action.strip_prefix("quick_filter:") {
+                            let filter: Vec<String> =
+                                serde_json::from_str(filter).unwrap_or_default();
+                            app.modal_manager.close_active();
+                            if let [column, pattern] = filter.as_slice() {
+                                if let Err(e) = app.quick_filter(column, pattern).await {
+                                    app.set_status_message(format!("Filter failed: {}", e));
+                                }
+                            }
+                        } else if let Some(query) = action.strip_prefix("run_history_query:") {
+                            let query: Option<String> = serde_json::from_str(query).ok();
+                            app.modal_manager.close_active();
+                            if let Some(query) = query {
+                                app.query = query;
+                                if let Err(e) = app.run_query().await {
+                                    app.set_status_message(format!("Query failed: {}", e));
+                                }
+                            }
+                        } else if let Some(name) = action.strip_prefix("load_saved_query:") {
+                            let name = name.to_string();
+                            app.modal_manager.close_active();
+                            if let Err(e) = app.apply_saved_query(&name) {
+                                app.set_status_message(format!("Load failed: {}", e));
+                            }
+                        } else if action.starts_with("apply_theme:") {
                             let theme_name = action.strip_prefix("apply_theme:").unwrap_or("");
                             let _ = app.switch_theme(theme_name);
                             app.modal_manager.close_active();
-                        } else if action.starts_with("create_connection:") {
-                            // TODO: Parse and create connection
-                            let parts: Vec<&str> = action.split(':').collect();
-                            if parts.len() >= 6 {
-                                let name = parts[1];
-                                let host = parts[2];
-                                let port = parts[3];
-                                let _username = parts[4];
-                                let _password = parts[5];
-                                let _database = if parts.len() > 6 { parts[6] } else { "" };
-                                // TODO: Actually create the connection in app
-                                println!("Create connection: {name}@{host}:{port}/{_database}");
-                                app.modal_manager.close_active();
+                        } else if let Some(form) = action.strip_prefix("save_connection:") {
+                            let form: Option<crate::app::ConnectionForm> =
+                                serde_json::from_str(form).ok();
+                            app.modal_manager.close_active();
+                            if let Some(form) = form {
+                                app.submit_connection_form(form);
                             }
                         }
                     }
@@ -115,12 +170,54 @@ impl NavigationInputHandler {
                     return Ok(());
                 }
                 _ => {
-                    app.cell_text_input.handle_key(key, modifiers);
+                    let action = app
+                        .navigation_manager
+                        .config()
+                        .key_mapping
+                        .get_action(key, modifiers);
+                    // 'p' is typed as text; only Ctrl+V pastes while editing a cell
+                    if action == Some(NavigationAction::Paste)
+                        && modifiers.contains(KeyModifiers::CONTROL)
+                    {
+                        app.paste_from_clipboard();
+                    } else {
+                        app.cell_text_input.handle_key(key, modifiers);
+                    }
                     return Ok(());
                 }
             }
         }
 
+        // A connection tree filter being typed takes every key until Enter or Esc
+        if app.tree_filter_editing && app.active_pane == Pane::Connections {
+            match key {
+                KeyCode::Esc => app.clear_tree_filter(),
+                KeyCode::Enter => app.finish_tree_filter(),
+                KeyCode::Up => app.move_selection_up(),
+                KeyCode::Down => app.move_selection_down(),
+                KeyCode::Backspace => app.edit_tree_filter(None),
+                KeyCode::Char(c) => app.edit_tree_filter(Some(c)),
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        // Likewise a result search being typed, which is highlighted as it changes
+        if app.result_search_editing && app.active_pane == Pane::Results {
+            match key {
+                // Alt+r/c/w still toggle regex, case and whole-word matching
+                _ if modifiers.contains(KeyModifiers::ALT) => {
+                    Self::handle_navigation_key(key, modifiers, app);
+                }
+                KeyCode::Esc => app.cancel_result_search(),
+                KeyCode::Enter => app.finish_result_search(),
+                KeyCode::Backspace => app.edit_result_search(None),
+                KeyCode::Char(c) => app.edit_result_search(Some(c)),
+                _ => {}
+            }
+            return Ok(());
+        }
+
         // Handle pane-specific input based on input mode
         match app.active_pane {
             Pane::Connections => {
@@ -188,19 +285,7 @@ impl NavigationInputHandler {
                     }
 
                     // Process the command
-                    match crate::command::CommandProcessor::process_command(app) {
-                        Ok(true) => {}
-                        Ok(false) => {
-                            app.status_message = Some(format!("Unknown command: {}", command));
-                        }
-                        Err(e) => {
-                            let _ = crate::logging::error(&format!(
-                                "Error processing command: {}",
-                                e
-                            ));
-                            app.status_message = Some(format!("Error: {}", e));
-                        }
-                    }
+                    let _ = app.execute_command();
                 }
             }
             KeyCode::Backspace => {
@@ -217,11 +302,11 @@ impl NavigationInputHandler {
                 app.select_next_suggestion();
             }
             KeyCode::Tab => {
-                // Auto-complete with selected suggestion
-                if let Some(suggestion) = app.get_selected_suggestion() {
-                    app.command_input = suggestion.clone();
-                    app.update_command_suggestions();
-                }
+                // Complete and then cycle through suggestions
+                app.cycle_suggestions(false);
+            }
+            KeyCode::BackTab => {
+                app.cycle_suggestions(true);
             }
             KeyCode::Char(c) => {
                 // Add character to command input
@@ -320,6 +405,21 @@ impl NavigationInputHandler {
                 }
                 app.navigation_manager.handle_action(action)
             }
+            crate::navigation::types::NavigationAction::Paste => app.paste_from_clipboard(),
+            crate::navigation::types::NavigationAction::Confirm
+                if app.active_pane == Pane::Results
+                    && app.input_mode == crate::app::InputMode::Normal =>
+            {
+                app.show_cell_detail();
+                true
+            }
+            crate::navigation::types::NavigationAction::EnterVisualMode
+                if app.active_pane == Pane::Results
+                    && app.input_mode == crate::app::InputMode::Normal =>
+            {
+                app.toggle_result_selection();
+                true
+            }
             // Mode switching actions - sync with app input mode
             crate::navigation::types::NavigationAction::EnterInsertMode => {
                 if app.active_pane == Pane::Results
@@ -338,9 +438,16 @@ impl NavigationInputHandler {
                     .cursor_position();
                 true
             }
-            crate::navigation::types::NavigationAction::EnterNormalMode => {
+            // Esc is bound to Cancel; in a pane it returns to Normal mode
+            crate::navigation::types::NavigationAction::EnterNormalMode
+            | crate::navigation::types::NavigationAction::Cancel => {
+                // In the results Esc first ends a visual selection
+                if app.active_pane == Pane::Results && app.clear_result_selection() {
+                    return true;
+                }
                 app.input_mode = crate::app::InputMode::Normal;
-                app.navigation_manager.handle_action(action)
+                app.navigation_manager
+                    .handle_action(crate::navigation::types::NavigationAction::EnterNormalMode)
             }
             crate::navigation::types::NavigationAction::EnterCommandMode => {
                 app.input_mode = crate::app::InputMode::Command;
@@ -364,7 +471,7 @@ impl NavigationInputHandler {
                             .vim_editor_mut()
                             .set_cursor_position(app.cursor_position);
                     }
-                    Pane::Results => app.move_cursor_in_results(OldDirection::Left),
+                    Pane::Results => app.move_cursor_left(),
                     Pane::Connections => {
                         // In connections pane, left should collapse tree items
                         if let Err(e) = executor::block_on(
@@ -391,7 +498,7 @@ impl NavigationInputHandler {
                             .vim_editor_mut()
                             .set_cursor_position(app.cursor_position);
                     }
-                    Pane::Results => app.move_cursor_in_results(OldDirection::Right),
+                    Pane::Results => app.move_cursor_right(),
                     Pane::Connections => {
                         // In connections pane, right should expand tree items
                         if let Err(e) = executor::block_on(
@@ -408,7 +515,7 @@ impl NavigationInputHandler {
             crate::navigation::types::NavigationAction::MoveUp => {
                 app.last_key_was_y = false;
                 match app.active_pane {
-                    Pane::Results => app.move_cursor_in_results(OldDirection::Up),
+                    Pane::Results => executor::block_on(app.move_cursor_up()),
                     Pane::Connections => app.move_selection_up(),
                     Pane::QueryInput => {
                         app.handle_navigation(OldNavigationAction::Direction(OldDirection::Up));
@@ -425,7 +532,7 @@ impl NavigationInputHandler {
             crate::navigation::types::NavigationAction::MoveDown => {
                 app.last_key_was_y = false;
                 match app.active_pane {
-                    Pane::Results => app.move_cursor_in_results(OldDirection::Down),
+                    Pane::Results => executor::block_on(app.move_cursor_down()),
                     Pane::Connections => app.move_selection_down(),
                     Pane::QueryInput => {
                         app.handle_navigation(OldNavigationAction::Direction(OldDirection::Down));
@@ -442,13 +549,23 @@ impl NavigationInputHandler {
             // Yank actions use mapped keys (no hardcoded chars). In Results:
             // - single YankLine: yank cell; - double YankLine (yy): yank row
             crate::navigation::types::NavigationAction::YankLine => {
+                if app.active_pane == Pane::Results && app.has_result_selection() {
+                    let _ = app.copy_result_selection();
+                    let selection = app.clipboard.clone();
+                    app.navigation_manager
+                        .box_manager_mut()
+                        .vim_editor_mut()
+                        .set_yank_buffer(selection);
+                    app.last_key_was_y = false;
+                    return true;
+                }
                 if app.active_pane == Pane::Results {
                     if app.last_key_was_y {
                         // yy: yank entire row
                         if let Some(selected_tab_index) = app.selected_result_tab_index {
                             if let Some((_, result, _)) = app.result_tabs.get(selected_tab_index) {
                                 if let Some(row) = result.rows.get(app.cursor_position.1) {
-                                    let row_content = row.join("\t");
+                                    let row_content = app.row_copy_text(row);
                                     // system clipboard via app API
                                     let _ = app.copy_row();
                                     // also set vim yank buffer
@@ -468,7 +585,7 @@ impl NavigationInputHandler {
                             if let Some((_, result, _)) = app.result_tabs.get(selected_tab_index) {
                                 if let Some(row) = result.rows.get(app.cursor_position.1) {
                                     if let Some(cell) = row.get(app.cursor_position.0) {
-                                        cell_content = cell.clone();
+                                        cell_content = app.cell_copy_text(cell.as_deref());
                                     }
                                 }
                             }
@@ -484,8 +601,13 @@ impl NavigationInputHandler {
                         return true;
                     }
                 }
-                // Non-results: delegate to vim editor default behavior
                 app.last_key_was_y = false;
+                if app.active_pane == Pane::Connections {
+                    let include_password = app.config.database.dsn_include_password;
+                    app.copy_connection_dsn(include_password);
+                    return true;
+                }
+                // Non-results: delegate to vim editor default behavior
                 app.navigation_manager.handle_action(action)
             }
             // Pane navigation actions - delegate to navigation manager
@@ -522,12 +644,310 @@ impl NavigationInputHandler {
                 app.quit();
                 true
             }
+            crate::navigation::types::NavigationAction::ShowHelp => {
+                app.show_help();
+                true
+            }
+            crate::navigation::types::NavigationAction::Search
+                if app.active_pane == Pane::Connections =>
+            {
+                app.start_tree_filter();
+                true
+            }
+            crate::navigation::types::NavigationAction::Search
+                if app.active_pane == Pane::Results =>
+            {
+                app.start_result_search();
+                true
+            }
             crate::navigation::types::NavigationAction::Search => {
                 if !app.modal_manager.has_modals() {
                     app.focus_where_input();
                 }
                 true
             }
+            crate::navigation::types::NavigationAction::GrowQueryPane => {
+                app.adjust_query_split(5);
+                true
+            }
+            crate::navigation::types::NavigationAction::ShrinkQueryPane => {
+                app.adjust_query_split(-5);
+                true
+            }
+            crate::navigation::types::NavigationAction::GrowSidebar => {
+                app.adjust_sidebar_width(5);
+                true
+            }
+            crate::navigation::types::NavigationAction::ShrinkSidebar => {
+                app.adjust_sidebar_width(-5);
+                true
+            }
+            crate::navigation::types::NavigationAction::NextSearchMatch
+            | crate::navigation::types::NavigationAction::PreviousSearchMatch => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                app.search_next_match(
+                    action == crate::navigation::types::NavigationAction::NextSearchMatch,
+                )
+            }
+            crate::navigation::types::NavigationAction::ToggleSearchRegex => {
+                app.toggle_search_regex();
+                true
+            }
+            crate::navigation::types::NavigationAction::ToggleSearchCaseSensitive => {
+                app.toggle_search_case_sensitive();
+                true
+            }
+            crate::navigation::types::NavigationAction::ToggleSearchWholeWord => {
+                app.toggle_search_whole_word();
+                true
+            }
+            crate::navigation::types::NavigationAction::NextMarkedRow
+            | crate::navigation::types::NavigationAction::PreviousMarkedRow => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                app.jump_to_marked_row(
+                    action == crate::navigation::types::NavigationAction::NextMarkedRow,
+                );
+                true
+            }
+            crate::navigation::types::NavigationAction::CopyConnectionDsnWithPassword => {
+                if app.active_pane != Pane::Connections {
+                    return false;
+                }
+                app.copy_connection_dsn(true);
+                true
+            }
+            crate::navigation::types::NavigationAction::ShowConnectionError => {
+                if app.active_pane != Pane::Connections {
+                    return false;
+                }
+                app.show_connection_error();
+                true
+            }
+            crate::navigation::types::NavigationAction::RefreshTree => {
+                if app.active_pane != Pane::Connections {
+                    return false;
+                }
+                if let Err(e) = executor::block_on(app.refresh_tree(false)) {
+                    app.set_status_message(format!("Refresh failed: {}", e));
+                }
+                true
+            }
+            crate::navigation::types::NavigationAction::ReconnectConnection => {
+                if app.active_pane != Pane::Connections {
+                    return false;
+                }
+                let Some(index) = app.selected_tree_connection() else {
+                    app.set_status_message("No connection selected".to_string());
+                    return true;
+                };
+                if let Err(e) = executor::block_on(app.reconnect_connection(index)) {
+                    app.set_status_message(format!("Reconnect failed: {}", e));
+                }
+                true
+            }
+            crate::navigation::types::NavigationAction::OpenTableInBackground => {
+                if app.active_pane != Pane::Connections {
+                    return false;
+                }
+                if let Err(e) = executor::block_on(app.open_table_in_background()) {
+                    app.set_status_message(format!("Open failed: {}", e));
+                }
+                true
+            }
+            crate::navigation::types::NavigationAction::ClearDeletionMarks => {
+                app.clear_deletion_marks();
+                true
+            }
+            crate::navigation::types::NavigationAction::YankColumn => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                app.last_key_was_y = false;
+                let _ = app.copy_column();
+                let column_content = app.clipboard.clone();
+                app.navigation_manager
+                    .box_manager_mut()
+                    .vim_editor_mut()
+                    .set_yank_buffer(column_content);
+                true
+            }
+            crate::navigation::types::NavigationAction::YankInsertStatement => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                let _ = app.copy_insert_statements();
+                let statements = app.clipboard.clone();
+                app.navigation_manager
+                    .box_manager_mut()
+                    .vim_editor_mut()
+                    .set_yank_buffer(statements);
+                true
+            }
+            crate::navigation::types::NavigationAction::FilterByCell
+            | crate::navigation::types::NavigationAction::FilterOutCell
+            | crate::navigation::types::NavigationAction::AndFilterByCell => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                let exclude = action == crate::navigation::types::NavigationAction::FilterOutCell;
+                let append = action == crate::navigation::types::NavigationAction::AndFilterByCell;
+                if let Err(e) = executor::block_on(app.filter_by_cell(exclude, append)) {
+                    app.set_status_message(format!("Filter failed: {}", e));
+                }
+                true
+            }
+            crate::navigation::types::NavigationAction::DeleteMarkedRows => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                app.delete_selected_rows();
+                true
+            }
+            crate::navigation::types::NavigationAction::Undo => {
+                if app.active_pane != Pane::Results {
+                    return app.navigation_manager.handle_action(action);
+                }
+                app.undo_deletion();
+                true
+            }
+            crate::navigation::types::NavigationAction::RunQuery => {
+                if app.active_pane != Pane::QueryInput {
+                    return false;
+                }
+                if let Err(e) = executor::block_on(app.run_query()) {
+                    app.set_status_message(format!("Query failed: {}", e));
+                }
+                true
+            }
+            crate::navigation::types::NavigationAction::ReloadResults => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                app.request_reload_current_tab();
+                true
+            }
+            crate::navigation::types::NavigationAction::AddSortColumn => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                if let Err(e) = executor::block_on(app.add_sort_column()) {
+                    app.set_status_message(format!("Sort failed: {}", e));
+                }
+                true
+            }
+            crate::navigation::types::NavigationAction::PageDown => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                executor::block_on(app.page_down());
+                true
+            }
+            crate::navigation::types::NavigationAction::PageUp => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                executor::block_on(app.page_up());
+                true
+            }
+            crate::navigation::types::NavigationAction::NextResultTab => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                app.select_next_tab();
+                true
+            }
+            crate::navigation::types::NavigationAction::PreviousResultTab => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                app.select_previous_tab();
+                true
+            }
+            crate::navigation::types::NavigationAction::CloseResultTab => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                app.close_current_tab();
+                true
+            }
+            crate::navigation::types::NavigationAction::ToggleRecordView => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                app.toggle_record_view();
+                true
+            }
+            crate::navigation::types::NavigationAction::QuickFilter => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                if let Err(e) = app.open_quick_filter() {
+                    app.set_status_message(e.to_string());
+                }
+                true
+            }
+            crate::navigation::types::NavigationAction::LoadMoreRows => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                if let Err(e) = executor::block_on(app.load_more_rows()) {
+                    app.set_status_message(format!("Loading more rows failed: {}", e));
+                }
+                true
+            }
+            crate::navigation::types::NavigationAction::HideColumn => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                app.hide_cursor_column();
+                true
+            }
+            crate::navigation::types::NavigationAction::ShowHiddenColumns => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                app.reveal_column("all");
+                true
+            }
+            crate::navigation::types::NavigationAction::ToggleCellWrap => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                app.toggle_cell_wrap();
+                true
+            }
+            crate::navigation::types::NavigationAction::DuplicateResultTab => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                app.duplicate_current_tab();
+                true
+            }
+            crate::navigation::types::NavigationAction::PinResultTab => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                app.pin_current_tab();
+                true
+            }
+            crate::navigation::types::NavigationAction::ToggleSplitResults => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                app.toggle_split_results();
+                true
+            }
+            crate::navigation::types::NavigationAction::FocusOtherSplit => {
+                if app.active_pane != Pane::Results {
+                    return false;
+                }
+                app.focus_other_split();
+                true
+            }
             // Other actions - delegate to navigation manager
             _ => {
                 app.last_key_was_y = false;
@@ -585,6 +1005,11 @@ impl NavigationInputHandler {
     ) -> Result<()> {
         match app.input_mode {
             crate::app::InputMode::Normal => {
+                // Esc drops a filter that is no longer being typed
+                if key == KeyCode::Esc && app.tree_filter.is_some() {
+                    app.clear_tree_filter();
+                    return Ok(());
+                }
                 // In normal mode, try the new navigation system first
                 if Self::handle_navigation_key(key, modifiers, app) {
                     return Ok(());
@@ -645,6 +1070,11 @@ impl NavigationInputHandler {
                         let _ = crate::logging::error(&format!("Error going to last page: {}", e));
                     }
                 }
+                Action::Confirm => {
+                    if let Err(e) = app.handle_tree_action(TreeAction::Activate).await {
+                        crate::logging::error(&format!("Error activating tree item: {}", e));
+                    }
+                }
                 Action::TreeAction(tree_action) => {
                     if let Err(e) = app.handle_tree_action(tree_action).await {
                         let _ = crate::logging::error(&format!("Error in tree action: {}", e));
@@ -688,9 +1118,20 @@ impl NavigationInputHandler {
                                 .unwrap_or_default()
                                 .private_key_path
                                 .unwrap_or_default(),
+                            ssh_passphrase: connection
+                                .ssh_tunnel
+                                .clone()
+                                .unwrap_or_default()
+                                .passphrase
+                                .unwrap_or_default(),
+                            ssh_use_agent: connection
+                                .ssh_tunnel
+                                .as_ref()
+                                .is_some_and(|ssh| ssh.use_agent),
                             ssh_tunnel_name: connection.ssh_tunnel_name.clone(),
+                            read_only: connection.read_only,
                         };
-                        app.show_connection_modal();
+                        app.show_connection_form();
                         app.active_block = crate::app::ActiveBlock::ConnectionModal;
                         app.input_mode = crate::app::InputMode::Normal;
                         // Sync navigation manager's vim mode
@@ -729,6 +1170,31 @@ impl NavigationInputHandler {
         modifiers: KeyModifiers,
         app: &mut App,
     ) -> Result<()> {
+        // Arrow keys in Normal mode cycle the `:sql` history; j/k still switch fields
+        if modifiers.is_empty()
+            && app.query_input_pane.current_vim_mode() == crate::navigation::types::VimMode::Normal
+        {
+            match key {
+                KeyCode::Up => {
+                    app.query_history_up();
+                    return Ok(());
+                }
+                KeyCode::Down => {
+                    app.query_history_down();
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+
+        // Tab/Shift+Tab while typing complete column and table names
+        if matches!(key, KeyCode::Tab | KeyCode::BackTab)
+            && app.query_input_pane.current_vim_mode() == crate::navigation::types::VimMode::Insert
+        {
+            app.complete_query_word(key == KeyCode::BackTab).await;
+            return Ok(());
+        }
+
         // Use the new QueryInputPane for input handling
         let nav_action = app
             .navigation_manager
@@ -743,6 +1209,12 @@ impl NavigationInputHandler {
             return Ok(());
         }
 
+        // 'p' already pasted the yank buffer above, so this is Ctrl+V
+        if nav_action == Some(NavigationAction::Paste) {
+            app.paste_from_clipboard();
+            return Ok(());
+        }
+
         // Check if we need to handle Enter key for query execution
         if key == KeyCode::Enter {
             let current_mode = app.query_input_pane.current_vim_mode();
@@ -754,7 +1226,7 @@ impl NavigationInputHandler {
                     state.where_clause = where_content;
                     state.order_by_clause = order_by_content;
                 }
-                if let Err(e) = app.refresh_results().await {
+                if let Err(e) = app.execute_query_input().await {
                     let _ = crate::logging::error(&format!("Error refreshing results: {}", e));
                 }
                 app.input_mode = crate::app::InputMode::Normal;
@@ -842,7 +1314,7 @@ impl NavigationInputHandler {
                     state.where_clause = where_content;
                     state.order_by_clause = order_by_content;
                 }
-                if let Err(e) = app.refresh_results().await {
+                if let Err(e) = app.execute_query_input().await {
                     let _ = crate::logging::error(&format!("Error refreshing results: {}", e));
                 }
                 app.input_mode = crate::app::InputMode::Normal;
@@ -913,6 +1385,33 @@ impl NavigationInputHandler {
                     let _ = app.copy_cell();
                     return Ok(());
                 }
+                Action::Sort => {
+                    if let Err(e) = app.sort_results().await {
+                        app.set_status_message(format!("Sort failed: {}", e));
+                    }
+                    return Ok(());
+                }
+                Action::Delete => {
+                    app.toggle_row_deletion_mark();
+                    return Ok(());
+                }
+                Action::Navigation(OldNavigationAction::NextTab) => {
+                    app.select_next_tab();
+                    return Ok(());
+                }
+                Action::Navigation(OldNavigationAction::PreviousTab) => {
+                    app.select_previous_tab();
+                    return Ok(());
+                }
+                Action::Navigation(OldNavigationAction::Direction(direction)) => {
+                    match direction {
+                        OldDirection::Up => app.move_cursor_up().await,
+                        OldDirection::Down => app.move_cursor_down().await,
+                        OldDirection::Left => app.move_cursor_left(),
+                        OldDirection::Right => app.move_cursor_right(),
+                    }
+                    return Ok(());
+                }
                 _ => {}
             }
         }
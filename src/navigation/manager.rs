@@ -83,6 +83,83 @@ impl NavigationManager {
             NavigationAction::NextBox => self.next_box(),
             NavigationAction::PreviousBox => self.previous_box(),
 
+            // Layout resizing is handled by the input handler, which owns the app config
+            NavigationAction::GrowQueryPane
+            | NavigationAction::ShrinkQueryPane
+            | NavigationAction::GrowSidebar
+            | NavigationAction::ShrinkSidebar => false,
+
+            // Result search lives on the app and is toggled by the input handler
+            NavigationAction::NextSearchMatch
+            | NavigationAction::PreviousSearchMatch
+            | NavigationAction::ToggleSearchRegex
+            | NavigationAction::ToggleSearchCaseSensitive
+            | NavigationAction::ToggleSearchWholeWord => false,
+
+            // Deletion marks are stored per result tab on the app
+            NavigationAction::NextMarkedRow
+            | NavigationAction::PreviousMarkedRow
+            | NavigationAction::ClearDeletionMarks
+            | NavigationAction::DeleteMarkedRows => false,
+
+            // Ad-hoc statements run over the app's connections into new tabs
+            NavigationAction::RunQuery => false,
+
+            // Reloading and sorting re-run the tab's query through the app
+            NavigationAction::ReloadResults | NavigationAction::AddSortColumn => false,
+
+            // Column values are read from the app's result tabs
+            NavigationAction::YankColumn => false,
+
+            // INSERTs are built from the app's result tabs and the connection's type
+            NavigationAction::YankInsertStatement => false,
+
+            // Filtering by a cell rewrites the tab's WHERE clause and reloads it
+            NavigationAction::FilterByCell
+            | NavigationAction::FilterOutCell
+            | NavigationAction::AndFilterByCell
+            | NavigationAction::QuickFilter => false,
+
+            // Paging may fetch the next or previous page through the app
+            NavigationAction::PageDown | NavigationAction::PageUp => false,
+            NavigationAction::NextResultTab
+            | NavigationAction::PreviousResultTab
+            | NavigationAction::CloseResultTab
+            | NavigationAction::DuplicateResultTab => false,
+
+            // Pinned tabs and the split results pane are kept on the app
+            NavigationAction::PinResultTab
+            | NavigationAction::ToggleSplitResults
+            | NavigationAction::FocusOtherSplit => false,
+
+            // The record view is a flag of the selected tab's query state
+            NavigationAction::ToggleRecordView => false,
+
+            // So is whether a tab wraps its cells
+            NavigationAction::ToggleCellWrap => false,
+
+            // More rows are fetched over the app's connections
+            NavigationAction::LoadMoreRows => false,
+
+            // Hidden columns are kept on the app's query states
+            NavigationAction::HideColumn => false,
+            NavigationAction::ShowHiddenColumns => false,
+
+            // DSNs are built from the app's connection configs
+            NavigationAction::CopyConnectionDsnWithPassword => false,
+
+            // Failure reasons are kept by the app
+            NavigationAction::ShowConnectionError => false,
+
+            // The tree is reloaded over the app's connections
+            NavigationAction::RefreshTree => false,
+
+            // Connections are torn down and made again by the app's connection manager
+            NavigationAction::ReconnectConnection => false,
+
+            // Tables are opened into the app's result tabs
+            NavigationAction::OpenTableInBackground => false,
+
             // Movement
             NavigationAction::MoveLeft => self.handle_directional_move(Direction::Left),
             NavigationAction::MoveRight => self.handle_directional_move(Direction::Right),
@@ -186,6 +263,10 @@ impl NavigationManager {
                 // This would be handled by the main application
                 false
             }
+            NavigationAction::ShowHelp => {
+                // The help overlay is a modal opened by the app
+                false
+            }
             NavigationAction::Copy => {
                 // This would be handled by the main application
                 false
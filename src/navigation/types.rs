@@ -99,6 +99,20 @@ impl KeyMapping {
         }
     }
 
+    /// Adds one of the default mappings. A key stands for one action in every context, so
+    /// binding it a second time is a mistake rather than an override.
+    fn add_default(&mut self, key_combo: KeyCombination, action: NavigationAction) {
+        let previous = self.mappings.get(&key_combo).copied();
+        debug_assert!(
+            previous.is_none_or(|previous| previous == action),
+            "{} is bound to both {:?} and {:?}",
+            key_combo,
+            previous,
+            action
+        );
+        self.add_mapping(key_combo, action);
+    }
+
     /// Add a key mapping
     pub fn add_mapping(&mut self, key_combo: KeyCombination, action: NavigationAction) {
         self.mappings.insert(key_combo, action);
@@ -140,176 +154,377 @@ impl Default for KeyMapping {
     fn default() -> Self {
         let mut mapping = Self::new();
 
-        // Basic actions
-        mapping.add_mapping(
+        // Basic actions. Esc is Cancel everywhere: it closes modals and, in a pane, returns
+        // to Normal mode. ':' opens the command line and 'q' quits outside of text input.
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Char('q')),
             NavigationAction::Quit,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Esc),
             NavigationAction::Cancel,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Char('/')),
             NavigationAction::Search,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Enter),
             NavigationAction::Confirm,
         );
+        // '?' (with or without Shift, see '!' below) and F1 list the bindings
+        for modifiers in [KeyModifiers::empty(), KeyModifiers::SHIFT] {
+            mapping.add_default(
+                KeyCombination::new(KeyCode::Char('?'), modifiers),
+                NavigationAction::ShowHelp,
+            );
+        }
+        mapping.add_default(
+            KeyCombination::simple(KeyCode::F(1)),
+            NavigationAction::ShowHelp,
+        );
 
         // Pane navigation - LazyVim style with Ctrl+number
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::with_shift(KeyCode::Char('T')),
             NavigationAction::FocusConnections,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::with_shift(KeyCode::Char('F')),
             NavigationAction::FocusQueryInput,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::with_shift(KeyCode::Char('R')),
             NavigationAction::FocusResults,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::with_shift(KeyCode::Char('S')),
             NavigationAction::FocusSchemaExplorer,
         );
-        mapping.add_mapping(
-            KeyCombination::simple(KeyCode::Char(':')),
-            NavigationAction::FocusCommandLine,
-        );
 
         // Directional pane navigation - LazyVim style with Ctrl+h/j/k/l
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::with_shift(KeyCode::Char('H')),
             NavigationAction::FocusPaneLeft,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::with_shift(KeyCode::Char('L')),
             NavigationAction::FocusPaneRight,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::with_shift(KeyCode::Char('K')),
             NavigationAction::FocusPaneUp,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::with_shift(KeyCode::Char('J')),
             NavigationAction::FocusPaneDown,
         );
 
+        // Split layout divider
+        mapping.add_default(
+            KeyCombination::with_ctrl(KeyCode::Right),
+            NavigationAction::GrowQueryPane,
+        );
+        mapping.add_default(
+            KeyCombination::with_ctrl(KeyCode::Left),
+            NavigationAction::ShrinkQueryPane,
+        );
+
+        // Sidebar width
+        mapping.add_default(
+            KeyCombination::with_alt(KeyCode::Right),
+            NavigationAction::GrowSidebar,
+        );
+        mapping.add_default(
+            KeyCombination::with_alt(KeyCode::Left),
+            NavigationAction::ShrinkSidebar,
+        );
+
+        // Result search toggles
+        mapping.add_default(
+            KeyCombination::with_alt(KeyCode::Char('r')),
+            NavigationAction::ToggleSearchRegex,
+        );
+        mapping.add_default(
+            KeyCombination::with_alt(KeyCode::Char('c')),
+            NavigationAction::ToggleSearchCaseSensitive,
+        );
+        mapping.add_default(
+            KeyCombination::with_alt(KeyCode::Char('w')),
+            NavigationAction::ToggleSearchWholeWord,
+        );
+
+        // Deletion marks
+        mapping.add_default(
+            KeyCombination::simple(KeyCode::Char(']')),
+            NavigationAction::NextMarkedRow,
+        );
+        mapping.add_default(
+            KeyCombination::simple(KeyCode::Char('[')),
+            NavigationAction::PreviousMarkedRow,
+        );
+        mapping.add_default(
+            KeyCombination::with_alt(KeyCode::Char('d')),
+            NavigationAction::ClearDeletionMarks,
+        );
+        mapping.add_default(
+            KeyCombination::with_shift(KeyCode::Char('D')),
+            NavigationAction::DeleteMarkedRows,
+        );
+        mapping.add_default(
+            KeyCombination::simple(KeyCode::Char('u')),
+            NavigationAction::Undo,
+        );
+
+        // Results
+        mapping.add_default(
+            KeyCombination::simple(KeyCode::Char('n')),
+            NavigationAction::NextSearchMatch,
+        );
+        mapping.add_default(
+            KeyCombination::with_shift(KeyCode::Char('N')),
+            NavigationAction::PreviousSearchMatch,
+        );
+        mapping.add_default(
+            KeyCombination::simple(KeyCode::F(5)),
+            NavigationAction::ReloadResults,
+        );
+        mapping.add_default(
+            KeyCombination::simple(KeyCode::F(9)),
+            NavigationAction::RunQuery,
+        );
+        mapping.add_default(
+            KeyCombination::with_alt(KeyCode::Char('s')),
+            NavigationAction::AddSortColumn,
+        );
+        mapping.add_default(
+            KeyCombination::with_shift(KeyCode::Char('C')),
+            NavigationAction::YankColumn,
+        );
+        mapping.add_default(
+            KeyCombination::with_alt(KeyCode::Char('i')),
+            NavigationAction::YankInsertStatement,
+        );
+        // Terminals differ on whether shifted symbols carry Shift, so '!' and '&' take both
+        mapping.add_default(
+            KeyCombination::simple(KeyCode::Char('=')),
+            NavigationAction::FilterByCell,
+        );
+        for modifiers in [KeyModifiers::empty(), KeyModifiers::SHIFT] {
+            mapping.add_default(
+                KeyCombination::new(KeyCode::Char('!'), modifiers),
+                NavigationAction::FilterOutCell,
+            );
+            mapping.add_default(
+                KeyCombination::new(KeyCode::Char('&'), modifiers),
+                NavigationAction::AndFilterByCell,
+            );
+        }
+        mapping.add_default(
+            KeyCombination::with_alt(KeyCode::Char('f')),
+            NavigationAction::QuickFilter,
+        );
+        mapping.add_default(
+            KeyCombination::simple(KeyCode::PageDown),
+            NavigationAction::PageDown,
+        );
+        mapping.add_default(
+            KeyCombination::simple(KeyCode::PageUp),
+            NavigationAction::PageUp,
+        );
+        mapping.add_default(
+            KeyCombination::simple(KeyCode::Tab),
+            NavigationAction::NextResultTab,
+        );
+        mapping.add_default(
+            KeyCombination::simple(KeyCode::BackTab),
+            NavigationAction::PreviousResultTab,
+        );
+        mapping.add_default(
+            KeyCombination::with_shift(KeyCode::BackTab),
+            NavigationAction::PreviousResultTab,
+        );
+        mapping.add_default(
+            KeyCombination::with_ctrl(KeyCode::Char('w')),
+            NavigationAction::CloseResultTab,
+        );
+        mapping.add_default(
+            KeyCombination::with_alt(KeyCode::Char('t')),
+            NavigationAction::DuplicateResultTab,
+        );
+        mapping.add_default(
+            KeyCombination::with_alt(KeyCode::Char('p')),
+            NavigationAction::PinResultTab,
+        );
+        mapping.add_default(
+            KeyCombination::with_alt(KeyCode::Char('v')),
+            NavigationAction::ToggleSplitResults,
+        );
+        mapping.add_default(
+            KeyCombination::with_alt(KeyCode::Char('x')),
+            NavigationAction::ToggleRecordView,
+        );
+        mapping.add_default(
+            KeyCombination::with_alt(KeyCode::Char('z')),
+            NavigationAction::ToggleCellWrap,
+        );
+        mapping.add_default(
+            KeyCombination::with_alt(KeyCode::Char('m')),
+            NavigationAction::LoadMoreRows,
+        );
+        mapping.add_default(
+            KeyCombination::with_alt(KeyCode::Char('o')),
+            NavigationAction::FocusOtherSplit,
+        );
+        mapping.add_default(
+            KeyCombination::with_alt(KeyCode::Char('h')),
+            NavigationAction::HideColumn,
+        );
+        for modifiers in [KeyModifiers::ALT, KeyModifiers::ALT | KeyModifiers::SHIFT] {
+            mapping.add_default(
+                KeyCombination::new(KeyCode::Char('H'), modifiers),
+                NavigationAction::ShowHiddenColumns,
+            );
+        }
+
+        // Connections
+        mapping.add_default(
+            KeyCombination::with_alt(KeyCode::Char('y')),
+            NavigationAction::CopyConnectionDsnWithPassword,
+        );
+        mapping.add_default(
+            KeyCombination::with_ctrl(KeyCode::Char('r')),
+            NavigationAction::RefreshTree,
+        );
+        // Alt+Shift+r may arrive with or without Shift, like the shifted symbols
+        for modifiers in [KeyModifiers::ALT, KeyModifiers::ALT | KeyModifiers::SHIFT] {
+            mapping.add_default(
+                KeyCombination::new(KeyCode::Char('R'), modifiers),
+                NavigationAction::ReconnectConnection,
+            );
+        }
+        mapping.add_default(
+            KeyCombination::with_alt(KeyCode::Char('e')),
+            NavigationAction::ShowConnectionError,
+        );
+        // Few terminals tell Ctrl+Enter from Enter, so Alt+Enter does the same
+        mapping.add_default(
+            KeyCombination::with_ctrl(KeyCode::Enter),
+            NavigationAction::OpenTableInBackground,
+        );
+        mapping.add_default(
+            KeyCombination::with_alt(KeyCode::Enter),
+            NavigationAction::OpenTableInBackground,
+        );
+
         // Movement
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Char('h')),
             NavigationAction::MoveLeft,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Char('j')),
             NavigationAction::MoveDown,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Char('k')),
             NavigationAction::MoveUp,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Char('l')),
             NavigationAction::MoveRight,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Left),
             NavigationAction::MoveLeft,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Right),
             NavigationAction::MoveRight,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Up),
             NavigationAction::MoveUp,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Down),
             NavigationAction::MoveDown,
         );
 
         // Vim-style editing
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Char('i')),
             NavigationAction::EnterInsertMode,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Char('a')),
             NavigationAction::Append,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Char('v')),
             NavigationAction::EnterVisualMode,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Char(':')),
             NavigationAction::EnterCommandMode,
         );
-        mapping.add_mapping(
-            KeyCombination::simple(KeyCode::Esc),
-            NavigationAction::EnterNormalMode,
-        );
 
         // Edit mode switching
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Char('e')),
             NavigationAction::EnterEditMode,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::with_ctrl(KeyCode::Char('e')),
             NavigationAction::ToggleViewEditMode,
         );
 
         // Text editing
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Backspace),
             NavigationAction::DeleteCharBefore,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Delete),
             NavigationAction::DeleteChar,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Char('x')),
             NavigationAction::DeleteChar,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Char('r')),
             NavigationAction::ReplaceChar,
         );
 
         // Yank operations - these will be handled by the vim editor based on context
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Char('y')),
             NavigationAction::YankLine,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Char('Y')),
             NavigationAction::YankLine,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Char('p')),
             NavigationAction::Paste,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::simple(KeyCode::Char('P')),
             NavigationAction::Paste,
         );
 
         // Copy/paste
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::with_ctrl(KeyCode::Char('c')),
             NavigationAction::Copy,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::with_ctrl(KeyCode::Char('v')),
             NavigationAction::Paste,
         );
-        mapping.add_mapping(
+        mapping.add_default(
             KeyCombination::with_ctrl(KeyCode::Char('x')),
             NavigationAction::Cut,
         );
@@ -407,7 +622,7 @@ pub enum Direction {
 }
 
 /// Represents a navigation action
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum NavigationAction {
     // Pane navigation
     FocusConnections,
@@ -433,6 +648,12 @@ pub enum NavigationAction {
     NextBox,
     PreviousBox,
 
+    // Layout
+    GrowQueryPane,
+    ShrinkQueryPane,
+    GrowSidebar,
+    ShrinkSidebar,
+
     // Movement
     MoveLeft,
     MoveRight,
@@ -469,6 +690,51 @@ pub enum NavigationAction {
     YankToLineStart,
     YankSelection,
 
+    // Result search toggles
+    NextSearchMatch,
+    PreviousSearchMatch,
+    ToggleSearchRegex,
+    ToggleSearchCaseSensitive,
+    ToggleSearchWholeWord,
+
+    // Deletion marks
+    NextMarkedRow,
+    PreviousMarkedRow,
+    ClearDeletionMarks,
+    DeleteMarkedRows,
+
+    // Results
+    RunQuery,
+    ReloadResults,
+    AddSortColumn,
+    YankColumn,
+    YankInsertStatement,
+    FilterByCell,
+    FilterOutCell,
+    AndFilterByCell,
+    QuickFilter,
+    PageDown,
+    PageUp,
+    NextResultTab,
+    PreviousResultTab,
+    CloseResultTab,
+    DuplicateResultTab,
+    PinResultTab,
+    ToggleSplitResults,
+    FocusOtherSplit,
+    ToggleRecordView,
+    ToggleCellWrap,
+    LoadMoreRows,
+    HideColumn,
+    ShowHiddenColumns,
+
+    // Connections
+    CopyConnectionDsnWithPassword,
+    ShowConnectionError,
+    RefreshTree,
+    ReconnectConnection,
+    OpenTableInBackground,
+
     // Special actions
     Quit,
     Confirm,
@@ -477,8 +743,79 @@ pub enum NavigationAction {
     Copy,
     Paste,
     Cut,
+    ShowHelp,
+}
+
+impl NavigationAction {
+    /// Heading the action is listed under in the help overlay, one of `HELP_GROUPS`
+    pub fn help_group(self) -> &'static str {
+        use NavigationAction::*;
+        match self {
+            FocusConnections | FocusQueryInput | FocusResults | FocusSchemaExplorer
+            | FocusCommandLine | NextPane | PreviousPane | FocusPaneLeft | FocusPaneRight
+            | FocusPaneUp | FocusPaneDown | FocusTextInput | FocusDataTable | FocusTreeView
+            | FocusListView | FocusModal | NextBox | PreviousBox => "Panes",
+            GrowQueryPane | ShrinkQueryPane | GrowSidebar | ShrinkSidebar => "Layout",
+            MoveLeft | MoveRight | MoveUp | MoveDown | MoveToStart | MoveToEnd | MoveToNextWord
+            | MoveToPreviousWord | PageDown | PageUp => "Movement",
+            EnterInsertMode | Append | EnterVisualMode | EnterCommandMode | EnterNormalMode
+            | EnterEditMode | ExitEditMode | ToggleViewEditMode | InsertChar | DeleteChar
+            | DeleteCharBefore | DeleteLine | ReplaceChar | Undo | Redo => "Editing",
+            YankLine | YankWord | YankToLineEnd | YankToLineStart | YankSelection | YankColumn
+            | YankInsertStatement | Copy | Paste | Cut => "Clipboard",
+            Search
+            | NextSearchMatch
+            | PreviousSearchMatch
+            | ToggleSearchRegex
+            | ToggleSearchCaseSensitive
+            | ToggleSearchWholeWord => "Search",
+            NextMarkedRow | PreviousMarkedRow | ClearDeletionMarks | DeleteMarkedRows => "Deletion",
+            RunQuery | ReloadResults | AddSortColumn | FilterByCell | FilterOutCell
+            | AndFilterByCell | QuickFilter | NextResultTab | PreviousResultTab
+            | CloseResultTab | DuplicateResultTab | PinResultTab | ToggleSplitResults
+            | FocusOtherSplit | ToggleRecordView | ToggleCellWrap | LoadMoreRows | HideColumn
+            | ShowHiddenColumns => "Results",
+            CopyConnectionDsnWithPassword
+            | ShowConnectionError
+            | RefreshTree
+            | ReconnectConnection
+            | OpenTableInBackground => "Connections",
+            Quit | Confirm | Cancel | ShowHelp => "General",
+        }
+    }
+
+    /// Readable name of the action, e.g. "Move to next word" for `MoveToNextWord`
+    pub fn label(self) -> String {
+        let name = format!("{:?}", self);
+        let mut label = String::with_capacity(name.len() + 4);
+        for (index, c) in name.chars().enumerate() {
+            if index == 0 {
+                label.push(c);
+            } else if c.is_ascii_uppercase() {
+                label.push(' ');
+                label.push(c.to_ascii_lowercase());
+            } else {
+                label.push(c);
+            }
+        }
+        label
+    }
 }
 
+/// Headings of the help overlay, in the order they are listed
+pub const HELP_GROUPS: [&str; 10] = [
+    "General",
+    "Panes",
+    "Layout",
+    "Movement",
+    "Editing",
+    "Clipboard",
+    "Search",
+    "Results",
+    "Deletion",
+    "Connections",
+];
+
 /// Configuration for navigation hotkeys
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NavigationConfig {
@@ -574,3 +911,33 @@ impl Default for NavigationState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_keys_stand_for_one_action() {
+        // `add_default` asserts while the table is built that no key gets a second action;
+        // keys aren't scoped by pane, so that covers every pane
+        let mapping = KeyMapping::default();
+
+        assert_eq!(
+            mapping.get_action(KeyCode::Char('q'), KeyModifiers::NONE),
+            Some(NavigationAction::Quit)
+        );
+        assert_eq!(
+            mapping.get_keys_for_action(NavigationAction::FocusQueryInput),
+            [KeyCombination::with_shift(KeyCode::Char('F'))]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "is bound to both")]
+    fn binding_a_default_key_twice_panics() {
+        let mut mapping = KeyMapping::new();
+        let key = KeyCombination::simple(KeyCode::Char('q'));
+        mapping.add_default(key, NavigationAction::Quit);
+        mapping.add_default(key, NavigationAction::FocusQueryInput);
+    }
+}
@@ -3,10 +3,60 @@ use lazy_static::lazy_static;
 use std::sync::Mutex;
 
 lazy_static! {
-    static ref GLOBAL_YANK_BUFFER: Mutex<String> = Mutex::new(String::new());
+    static ref GLOBAL_YANK_BUFFER: Mutex<YankBuffer> = Mutex::new(YankBuffer::default());
+}
+
+/// Text last yanked, shared by every editor
+#[derive(Debug, Default)]
+struct YankBuffer {
+    text: String,
+    /// Whether whole lines were yanked (`yy`/`Y`), which paste as lines of their own
+    linewise: bool,
+}
+
+/// Stores `text` as the last yank
+fn set_global_yank(text: String, linewise: bool) {
+    if let Ok(mut global) = GLOBAL_YANK_BUFFER.lock() {
+        *global = YankBuffer { text, linewise };
+    }
 }
 use crossterm::event::{KeyCode, KeyModifiers};
 
+/// Byte offset of the character at column `col` of `line`, or the line's length past its end.
+/// Cursor columns count characters, so multi-byte text is never split.
+fn byte_index(line: &str, col: usize) -> usize {
+    line.char_indices()
+        .nth(col)
+        .map_or(line.len(), |(index, _)| index)
+}
+
+/// Number of characters in `line`, the column just past its end
+fn char_len(line: &str) -> usize {
+    line.chars().count()
+}
+
+/// What word motions see at a position: runs of word characters or of punctuation are words,
+/// whitespace and line breaks separate them, and an empty line is a word of its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Blank,
+    Word,
+    Punctuation,
+    EmptyLine,
+}
+
+impl CharClass {
+    fn of(c: char) -> Self {
+        if c.is_whitespace() {
+            CharClass::Blank
+        } else if c.is_alphanumeric() || c == '_' {
+            CharClass::Word
+        } else {
+            CharClass::Punctuation
+        }
+    }
+}
+
 /// Vim-style text editor for handling text input with vim keybindings
 #[derive(Debug, Clone)]
 pub struct VimEditor {
@@ -14,7 +64,7 @@ pub struct VimEditor {
     pub mode: VimMode,
     /// Text content
     content: String,
-    /// Cursor position (row, column)
+    /// Cursor position (row, column), with the column counted in characters
     cursor_position: (usize, usize),
     /// Whether we're in replace mode
     replace_mode: bool,
@@ -37,9 +87,7 @@ impl VimEditor {
     }
 
     pub fn set_yank_buffer(&mut self, text: String) {
-        if let Ok(mut global) = GLOBAL_YANK_BUFFER.lock() {
-            *global = text;
-        }
+        set_global_yank(text, false);
     }
 
     pub fn with_content(content: String) -> Self {
@@ -130,6 +178,10 @@ impl VimEditor {
                 self.move_to_previous_word();
                 true
             }
+            KeyCode::Char('e') => {
+                self.move_to_word_end();
+                true
+            }
             KeyCode::Char('0') => {
                 self.move_to_line_start();
                 true
@@ -178,6 +230,14 @@ impl VimEditor {
                 self.last_key = None;
                 true
             }
+            KeyCode::Char('p') => {
+                self.paste(false);
+                true
+            }
+            KeyCode::Char('P') => {
+                self.paste(true);
+                true
+            }
             KeyCode::Esc => {
                 self.mode = VimMode::Normal;
                 self.replace_mode = false;
@@ -301,54 +361,130 @@ impl VimEditor {
                 }
             }
             Direction::Right => {
-                let line_len = lines.get(row).map(|l| l.len()).unwrap_or(0);
+                let line_len = lines.get(row).map(|l| char_len(l)).unwrap_or(0);
                 if col < line_len {
                     self.cursor_position = (row, col + 1);
                 }
             }
             Direction::Up => {
                 if row > 0 {
-                    let new_col = col.min(lines[row - 1].len());
+                    let new_col = col.min(char_len(lines[row - 1]));
                     self.cursor_position = (row - 1, new_col);
                 }
             }
             Direction::Down => {
                 if row + 1 < lines.len() {
-                    let new_col = col.min(lines[row + 1].len());
+                    let new_col = col.min(char_len(lines[row + 1]));
                     self.cursor_position = (row + 1, new_col);
                 }
             }
         }
     }
 
-    pub fn move_to_next_word(&mut self) {
-        // Simplified word movement - move to next space or end of line
-        let (row, col) = self.cursor_position;
+    /// Every position word motions can stop at, in order, with the class of its character.
+    /// Each line but the last ends in a blank standing for the line break.
+    fn word_positions(&self) -> Vec<((usize, usize), CharClass)> {
         let lines: Vec<&str> = self.content.lines().collect();
+        let mut positions = Vec::new();
+        for (row, line) in lines.iter().enumerate() {
+            if line.is_empty() {
+                positions.push(((row, 0), CharClass::EmptyLine));
+                continue;
+            }
+            positions.extend(
+                line.chars()
+                    .enumerate()
+                    .map(|(col, c)| ((row, col), CharClass::of(c))),
+            );
+            if row + 1 < lines.len() {
+                positions.push(((row, char_len(line)), CharClass::Blank));
+            }
+        }
+        positions
+    }
 
-        if let Some(line) = lines.get(row) {
-            let remaining = &line[col..];
-            if let Some(pos) = remaining.find(' ') {
-                self.cursor_position = (row, col + pos + 1);
-            } else {
-                self.cursor_position = (row, line.len());
+    /// Index of the cursor in `positions`, or `positions.len()` when it is past the end
+    fn cursor_index(&self, positions: &[((usize, usize), CharClass)]) -> usize {
+        positions
+            .iter()
+            .position(|&(position, _)| position >= self.cursor_position)
+            .unwrap_or(positions.len())
+    }
+
+    /// `w`: moves to the start of the next word, crossing lines and stopping at empty ones
+    pub fn move_to_next_word(&mut self) {
+        let positions = self.word_positions();
+        let mut index = self.cursor_index(&positions);
+        let Some(&(_, class)) = positions.get(index) else {
+            return;
+        };
+
+        match class {
+            CharClass::Word | CharClass::Punctuation => {
+                while positions.get(index).is_some_and(|&(_, next)| next == class) {
+                    index += 1;
+                }
             }
+            CharClass::EmptyLine => index += 1,
+            CharClass::Blank => {}
+        }
+        while positions
+            .get(index)
+            .is_some_and(|&(_, next)| next == CharClass::Blank)
+        {
+            index += 1;
         }
+
+        // Past the last word the cursor stays on the last character, like vim
+        let index = index.min(positions.len() - 1);
+        self.cursor_position = positions[index].0;
     }
 
+    /// `b`: moves to the start of the current or previous word
     pub fn move_to_previous_word(&mut self) {
-        // Simplified word movement - move to previous space or start of line
-        let (row, col) = self.cursor_position;
-        let lines: Vec<&str> = self.content.lines().collect();
+        let positions = self.word_positions();
+        let mut index = self.cursor_index(&positions);
+        if index == 0 {
+            return;
+        }
 
-        if let Some(line) = lines.get(row) {
-            let before = &line[..col];
-            if let Some(pos) = before.rfind(' ') {
-                self.cursor_position = (row, pos + 1);
-            } else {
-                self.cursor_position = (row, 0);
+        index -= 1;
+        while index > 0 && positions[index].1 == CharClass::Blank {
+            index -= 1;
+        }
+        let class = positions[index].1;
+        if matches!(class, CharClass::Word | CharClass::Punctuation) {
+            while index > 0 && positions[index - 1].1 == class {
+                index -= 1;
             }
         }
+        self.cursor_position = positions[index].0;
+    }
+
+    /// `e`: moves to the end of the current or next word, skipping blanks and empty lines
+    pub fn move_to_word_end(&mut self) {
+        let positions = self.word_positions();
+        let mut index = self.cursor_index(&positions) + 1;
+
+        while positions
+            .get(index)
+            .is_some_and(|&(_, class)| matches!(class, CharClass::Blank | CharClass::EmptyLine))
+        {
+            index += 1;
+        }
+        let Some(&(_, class)) = positions.get(index) else {
+            if let Some(&(last, _)) = positions.last() {
+                self.cursor_position = last;
+            }
+            return;
+        };
+        while positions
+            .get(index + 1)
+            .is_some_and(|&(_, next)| next == class)
+        {
+            index += 1;
+        }
+        self.cursor_position = positions[index].0;
     }
 
     pub fn move_to_line_start(&mut self) {
@@ -360,7 +496,7 @@ impl VimEditor {
         let lines: Vec<&str> = self.content.lines().collect();
 
         if let Some(line) = lines.get(row) {
-            self.cursor_position = (row, line.len());
+            self.cursor_position = (row, char_len(line));
         }
     }
 
@@ -373,20 +509,69 @@ impl VimEditor {
         }
 
         if let Some(line) = lines.get_mut(row) {
-            line.insert(col, c);
+            let col = col.min(char_len(line));
+            line.insert(byte_index(line, col), c);
             self.cursor_position = (row, col + 1);
         }
 
         self.content = lines.join("\n");
     }
 
+    /// Inserts `text` at the cursor and moves the cursor past it
+    pub fn insert_str_at_cursor(&mut self, text: &str) {
+        let (row, col) = self.cursor_position;
+        let mut lines: Vec<String> = self.content.lines().map(|s| s.to_string()).collect();
+
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+
+        if let Some(line) = lines.get_mut(row) {
+            let col = col.min(char_len(line));
+            line.insert_str(byte_index(line, col), text);
+            self.cursor_position = (row, col + char_len(text));
+        }
+
+        self.content = lines.join("\n");
+    }
+
+    /// Text of the cursor line before the cursor
+    pub fn text_before_cursor(&self) -> String {
+        let (row, col) = self.cursor_position;
+        self.content
+            .lines()
+            .nth(row)
+            .map(|line| line.chars().take(col).collect())
+            .unwrap_or_default()
+    }
+
+    /// Replaces the text of the cursor line from column `start` up to the cursor with `text`,
+    /// leaving the cursor after it
+    pub fn replace_before_cursor(&mut self, start: usize, text: &str) {
+        let (row, col) = self.cursor_position;
+        let mut lines: Vec<String> = self.content.lines().map(|s| s.to_string()).collect();
+
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+
+        if let Some(line) = lines.get_mut(row) {
+            let col = col.min(char_len(line));
+            let start = start.min(col);
+            line.replace_range(byte_index(line, start)..byte_index(line, col), text);
+            self.cursor_position = (row, start + char_len(text));
+        }
+
+        self.content = lines.join("\n");
+    }
+
     fn replace_char_at_cursor(&mut self, c: char) {
         let (row, col) = self.cursor_position;
         let mut lines: Vec<String> = self.content.lines().map(|s| s.to_string()).collect();
 
         if let Some(line) = lines.get_mut(row) {
-            if col < line.len() {
-                line.replace_range(col..col + 1, &c.to_string());
+            if let Some((start, replaced)) = line.char_indices().nth(col) {
+                line.replace_range(start..start + replaced.len_utf8(), &c.to_string());
                 self.cursor_position = (row, col + 1);
             }
         }
@@ -399,8 +584,8 @@ impl VimEditor {
         let mut lines: Vec<String> = self.content.lines().map(|s| s.to_string()).collect();
 
         if let Some(line) = lines.get_mut(row) {
-            if col < line.len() {
-                line.remove(col);
+            if col < char_len(line) {
+                line.remove(byte_index(line, col));
             }
         }
 
@@ -412,8 +597,9 @@ impl VimEditor {
         let mut lines: Vec<String> = self.content.lines().map(|s| s.to_string()).collect();
 
         if let Some(line) = lines.get_mut(row) {
+            let col = col.min(char_len(line));
             if col > 0 {
-                line.remove(col - 1);
+                line.remove(byte_index(line, col - 1));
                 self.cursor_position = (row, col - 1);
             }
         }
@@ -441,9 +627,7 @@ impl VimEditor {
         let mut lines: Vec<String> = self.content.lines().map(|s| s.to_string()).collect();
 
         if let Some(line) = lines.get_mut(row) {
-            let before = line[..col].to_string();
-            let after = line[col..].to_string();
-            *line = before;
+            let after = line.split_off(byte_index(line, col));
             lines.insert(row + 1, after);
         }
 
@@ -469,9 +653,7 @@ impl VimEditor {
         };
 
         if let Some(line) = lines.get(row) {
-            if let Ok(mut global) = GLOBAL_YANK_BUFFER.lock() {
-                *global = (*line).to_string();
-            }
+            set_global_yank((*line).to_string(), true);
             Some(format!("Yanked line: {}", line))
         } else {
             None
@@ -483,9 +665,7 @@ impl VimEditor {
             let end = self.cursor_position;
             let selected_text = self.get_text_between(start, end);
             let status_msg = format!("Yanked selection: {}", selected_text);
-            if let Ok(mut global) = GLOBAL_YANK_BUFFER.lock() {
-                *global = selected_text.clone();
-            }
+            set_global_yank(selected_text, false);
             Some(status_msg)
         } else {
             None
@@ -499,9 +679,7 @@ impl VimEditor {
         if let Some(line) = lines.get(row) {
             let word = self.get_word_at_position(line, col);
             let status_msg = format!("Yanked word: {}", word);
-            if let Ok(mut global) = GLOBAL_YANK_BUFFER.lock() {
-                *global = word.clone();
-            }
+            set_global_yank(word, false);
             Some(status_msg)
         } else {
             None
@@ -513,10 +691,8 @@ impl VimEditor {
         let lines: Vec<&str> = self.content.lines().collect();
 
         if let Some(line) = lines.get(row) {
-            let remaining = &line[col..];
-            if let Ok(mut global) = GLOBAL_YANK_BUFFER.lock() {
-                *global = remaining.to_string();
-            }
+            let remaining = &line[byte_index(line, col)..];
+            set_global_yank(remaining.to_string(), false);
             Some(format!("Yanked to line end: {}", remaining))
         } else {
             None
@@ -528,41 +704,87 @@ impl VimEditor {
         let lines: Vec<&str> = self.content.lines().collect();
 
         if let Some(line) = lines.get(row) {
-            let before = &line[..col];
-            if let Ok(mut global) = GLOBAL_YANK_BUFFER.lock() {
-                *global = before.to_string();
-            }
+            let before = &line[..byte_index(line, col)];
+            set_global_yank(before.to_string(), false);
             Some(format!("Yanked to line start: {}", before))
         } else {
             None
         }
     }
 
-    pub fn paste(&mut self) {
-        let mut source = String::new();
-        if let Ok(global) = GLOBAL_YANK_BUFFER.lock() {
-            source = global.clone();
-        }
-        if source.is_empty() {
+    /// `p`/`P`: pastes the last yank after or `before` the cursor. Lines yanked whole go on
+    /// new lines below or above, with the cursor on the first; other text goes into the line,
+    /// with the cursor on its last character (or its first, when it spans lines).
+    pub fn paste(&mut self, before: bool) {
+        let Some(yank) = Self::last_yank() else {
             return;
-        }
+        };
 
         let (row, col) = self.cursor_position;
         let mut lines: Vec<String> = self.content.lines().map(|s| s.to_string()).collect();
-
         if lines.is_empty() {
             lines.push(String::new());
         }
+        let row = row.min(lines.len() - 1);
 
-        if let Some(line) = lines.get_mut(row) {
-            let insert_pos = col.min(line.len());
-            line.insert_str(insert_pos, &source);
-            self.cursor_position = (row, insert_pos + source.len());
+        if yank.linewise {
+            let at = if before { row } else { row + 1 };
+            for (offset, line) in yank.text.split('\n').enumerate() {
+                lines.insert(at + offset, line.to_string());
+            }
+            self.cursor_position = (at, 0);
+        } else {
+            let line_len = char_len(&lines[row]);
+            // `p` pastes after the character under the cursor
+            let col = if before || line_len == 0 {
+                col.min(line_len)
+            } else {
+                (col + 1).min(line_len)
+            };
+            let split = byte_index(&lines[row], col);
+            let after = lines[row].split_off(split);
+            let mut pasted = yank.text.split('\n');
+            lines[row].push_str(pasted.next().unwrap_or_default());
+            let mut last_row = row;
+            for line in pasted {
+                last_row += 1;
+                lines.insert(last_row, line.to_string());
+            }
+            if last_row == row {
+                self.cursor_position = (row, col + char_len(&yank.text).saturating_sub(1));
+            } else {
+                self.cursor_position = (row, col);
+            }
+            lines[last_row].push_str(&after);
         }
 
         self.content = lines.join("\n");
     }
 
+    /// Pastes the last yank into the line at the cursor and moves the cursor past it, for
+    /// single-line inputs: line breaks become spaces and lines yanked whole go inline too
+    pub fn paste_inline(&mut self, before: bool) {
+        let Some(yank) = Self::last_yank() else {
+            return;
+        };
+        if !before {
+            self.move_cursor(Direction::Right);
+        }
+        self.insert_str_at_cursor(&yank.text.replace('\n', " "));
+    }
+
+    /// The last yank, or `None` when nothing has been yanked
+    fn last_yank() -> Option<YankBuffer> {
+        let global = GLOBAL_YANK_BUFFER.lock().ok()?;
+        if global.text.is_empty() && !global.linewise {
+            return None;
+        }
+        Some(YankBuffer {
+            text: global.text.clone(),
+            linewise: global.linewise,
+        })
+    }
+
     fn get_text_between(&self, start: (usize, usize), end: (usize, usize)) -> String {
         let lines: Vec<&str> = self.content.lines().collect();
         let (start_row, start_col) = start;
@@ -570,8 +792,8 @@ impl VimEditor {
 
         if start_row == end_row {
             if let Some(line) = lines.get(start_row) {
-                let start_pos = start_col.min(line.len());
-                let end_pos = end_col.min(line.len());
+                let start_pos = byte_index(line, start_col);
+                let end_pos = byte_index(line, end_col);
                 if start_pos < end_pos {
                     return line[start_pos..end_pos].to_string();
                 }
@@ -581,9 +803,9 @@ impl VimEditor {
             for row in start_row..=end_row.min(lines.len().saturating_sub(1)) {
                 if let Some(line) = lines.get(row) {
                     if row == start_row {
-                        result.push_str(&line[start_col.min(line.len())..]);
+                        result.push_str(&line[byte_index(line, start_col)..]);
                     } else if row == end_row {
-                        result.push_str(&line[..end_col.min(line.len())]);
+                        result.push_str(&line[..byte_index(line, end_col)]);
                     } else {
                         result.push_str(line);
                     }
@@ -598,7 +820,7 @@ impl VimEditor {
     }
 
     fn get_word_at_position(&self, line: &str, col: usize) -> String {
-        if col >= line.len() {
+        if col >= char_len(line) {
             return String::new();
         }
 
@@ -621,3 +843,96 @@ impl VimEditor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(editor: &mut VimEditor, keys: &str) {
+        for c in keys.chars() {
+            editor.handle_key(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+    }
+
+    #[test]
+    fn edits_multibyte_text_by_character() {
+        let mut editor = VimEditor::with_content("café\n日本語".to_string());
+
+        press(&mut editor, "$");
+        assert_eq!(editor.cursor_position(), (0, 4));
+        press(&mut editor, "is");
+        editor.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(editor.content(), "cafés\n日本語");
+
+        press(&mut editor, "hhx");
+        assert_eq!(editor.content(), "cafs\n日本語");
+
+        press(&mut editor, "j0lx");
+        assert_eq!(editor.content(), "cafs\n日語");
+        press(&mut editor, "i本");
+        assert_eq!(editor.content(), "cafs\n日本語");
+        assert_eq!(editor.cursor_position(), (1, 2));
+        assert_eq!(editor.text_before_cursor(), "日本");
+    }
+
+    /// Cursor positions after each of `count` presses of `key`
+    fn motions(editor: &mut VimEditor, key: char, count: usize) -> Vec<(usize, usize)> {
+        (0..count)
+            .map(|_| {
+                press(editor, &key.to_string());
+                editor.cursor_position()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn word_motions_split_punctuation_and_cross_lines() {
+        let mut editor = VimEditor::with_content("foo.bar baz".to_string());
+        assert_eq!(
+            motions(&mut editor, 'w', 4),
+            [(0, 3), (0, 4), (0, 8), (0, 10)]
+        );
+        assert_eq!(
+            motions(&mut editor, 'b', 4),
+            [(0, 8), (0, 4), (0, 3), (0, 0)]
+        );
+        assert_eq!(
+            motions(&mut editor, 'e', 5),
+            [(0, 2), (0, 3), (0, 6), (0, 10), (0, 10)]
+        );
+
+        // Blanks around words are skipped and an empty line is a stop of its own
+        let mut editor = VimEditor::with_content("  foo  \n\n  bar".to_string());
+        assert_eq!(
+            motions(&mut editor, 'w', 4),
+            [(0, 2), (1, 0), (2, 2), (2, 4)]
+        );
+        assert_eq!(
+            motions(&mut editor, 'b', 4),
+            [(2, 2), (1, 0), (0, 2), (0, 0)]
+        );
+        assert_eq!(motions(&mut editor, 'e', 3), [(0, 4), (2, 4), (2, 4)]);
+    }
+
+    // The only test touching the yank buffer, which every editor shares
+    #[test]
+    fn paste_puts_text_inline_and_lines_on_their_own() {
+        let mut editor = VimEditor::with_content("abc".to_string());
+        editor.set_yank_buffer("XY".to_string());
+        editor.set_cursor_position((0, 1));
+        press(&mut editor, "p");
+        assert_eq!(editor.content(), "abXYc");
+        assert_eq!(editor.cursor_position(), (0, 3));
+        press(&mut editor, "0P");
+        assert_eq!(editor.content(), "XYabXYc");
+        assert_eq!(editor.cursor_position(), (0, 1));
+
+        let mut editor = VimEditor::with_content("one\ntwo".to_string());
+        press(&mut editor, "yyjp");
+        assert_eq!(editor.content(), "one\ntwo\none");
+        assert_eq!(editor.cursor_position(), (2, 0));
+        press(&mut editor, "kP");
+        assert_eq!(editor.content(), "one\none\ntwo\none");
+        assert_eq!(editor.cursor_position(), (1, 0));
+    }
+}
@@ -0,0 +1,144 @@
+//! Ad-hoc statements run with `:sql`, kept across sessions in `history.log`.
+use anyhow::{Context, Result};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+/// Entries kept; the oldest are dropped first
+pub const MAX_ENTRIES: usize = 500;
+
+#[derive(Debug, Default)]
+pub struct QueryHistory {
+    /// Oldest first
+    entries: VecDeque<String>,
+    /// Entry shown while cycling with Up/Down; `None` when not cycling
+    cursor: Option<usize>,
+}
+
+pub fn path() -> PathBuf {
+    crate::config::Config::get_config_dir().join("history.log")
+}
+
+/// One entry per line, so line breaks and backslashes inside a query are escaped
+fn escape(query: &str) -> String {
+    query.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(line: &str) -> String {
+    let mut query = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.peek() {
+                Some('n') => {
+                    query.push('\n');
+                    chars.next();
+                    continue;
+                }
+                Some('\\') => {
+                    query.push('\\');
+                    chars.next();
+                    continue;
+                }
+                _ => {}
+            }
+        }
+        query.push(c);
+    }
+    query
+}
+
+impl QueryHistory {
+    /// Reads `history.log`; a missing file is an empty history
+    pub fn load() -> Result<Self> {
+        let path = path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut history = Self::default();
+        for line in contents.lines().filter(|line| !line.is_empty()) {
+            history.push(unescape(line));
+        }
+        Ok(history)
+    }
+
+    /// Like `load`, but a history that can't be read is logged and replaced by an empty one
+    pub fn load_or_default() -> Self {
+        Self::load().unwrap_or_else(|err| {
+            crate::logging::handle_non_critical_error(&err);
+            Self::default()
+        })
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).context("Failed to create config directory")?;
+        }
+        let contents: String = self
+            .entries
+            .iter()
+            .map(|query| format!("{}\n", escape(query)))
+            .collect();
+        std::fs::write(&path, contents)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    fn push(&mut self, query: String) {
+        // Running the same statement again doesn't add a new entry
+        if self.entries.back() == Some(&query) {
+            return;
+        }
+        if self.entries.len() == MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(query);
+    }
+
+    /// Appends `query` and writes the history to disk; cycling starts over from the newest
+    pub fn record(&mut self, query: &str) -> Result<()> {
+        self.cursor = None;
+        self.push(query.to_string());
+        self.save()
+    }
+
+    /// Steps to the next older entry, staying on the oldest
+    pub fn previous(&mut self) -> Option<&str> {
+        let index = match self.cursor {
+            Some(index) => index.saturating_sub(1),
+            None => self.entries.len().checked_sub(1)?,
+        };
+        self.cursor = Some(index);
+        self.entries.get(index).map(String::as_str)
+    }
+
+    /// Steps to the next newer entry; past the newest, cycling stops and `None` is returned
+    pub fn next(&mut self) -> Option<&str> {
+        let index = self.cursor? + 1;
+        if index >= self.entries.len() {
+            self.cursor = None;
+            return None;
+        }
+        self.cursor = Some(index);
+        self.entries.get(index).map(String::as_str)
+    }
+
+    /// Position of the entry being shown, counted from the newest (1 is the newest)
+    pub fn position(&self) -> Option<usize> {
+        self.cursor.map(|index| self.entries.len() - index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entries newest first
+    pub fn entries(&self) -> Vec<String> {
+        self.entries.iter().rev().cloned().collect()
+    }
+}
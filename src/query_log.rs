@@ -0,0 +1,63 @@
+//! Per-connection audit trail of the queries executed against each database.
+use chrono::{DateTime, Local};
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+/// Entries kept per connection; the oldest are dropped first
+pub const MAX_ENTRIES_PER_CONNECTION: usize = 500;
+
+#[derive(Debug, Clone)]
+pub struct QueryLogEntry {
+    pub query: String,
+    pub executed_at: DateTime<Local>,
+    pub elapsed: Duration,
+    pub row_count: Option<usize>,
+    /// Error message when the query failed
+    pub error: Option<String>,
+}
+
+impl QueryLogEntry {
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Elapsed time as `12 ms` or `1.25 s`
+    pub fn elapsed_label(&self) -> String {
+        let millis = self.elapsed.as_millis();
+        if millis < 1000 {
+            format!("{} ms", millis)
+        } else {
+            format!("{:.2} s", self.elapsed.as_secs_f64())
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct QueryLog {
+    entries: HashMap<String, VecDeque<QueryLogEntry>>,
+}
+
+impl QueryLog {
+    pub fn record(&mut self, connection: &str, entry: QueryLogEntry) {
+        let entries = self.entries.entry(connection.to_string()).or_default();
+        if entries.len() == MAX_ENTRIES_PER_CONNECTION {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// Entries for `connection`, newest first
+    pub fn entries(&self, connection: &str) -> Vec<QueryLogEntry> {
+        self.entries
+            .get(connection)
+            .map(|entries| entries.iter().rev().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Clears the log for `connection`, returning how many entries were removed
+    pub fn clear(&mut self, connection: &str) -> usize {
+        self.entries
+            .remove(connection)
+            .map_or(0, |entries| entries.len())
+    }
+}
@@ -1,4 +1,7 @@
 use std::io;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
 
 use crossterm::cursor::SetCursorStyle;
 use crossterm::event::{self, Event, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
@@ -6,6 +9,7 @@ use crossterm::execute;
 use futures::executor;
 use ratatui::backend::Backend;
 use ratatui::layout::Rect;
+use ratatui::style::Color;
 use ratatui::Terminal;
 
 use crate::app::{App, InputMode};
@@ -15,14 +19,78 @@ use crate::navigation::NavigationInputHandler;
 use crate::ui;
 use crate::ui::types::Pane;
 
+/// Frames of the spinner shown while an event is still being handled
+const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+/// How long handling an event runs before the spinner appears
+const SPINNER_DELAY: Duration = Duration::from_millis(200);
+const SPINNER_INTERVAL: Duration = Duration::from_millis(100);
+/// How long a tick waits for input before redrawing anyway, so background results and
+/// expiring status messages show up without a key press
+const TICK_RATE: Duration = Duration::from_millis(100);
+
+/// Spinner drawn over the start of the status bar once handling an event has taken
+/// `SPINNER_DELAY`, e.g. while a query runs. Queries are awaited inline (some through
+/// `block_on`) with the app borrowed, so it runs on its own thread and writes to the terminal
+/// directly.
+struct BusySpinner {
+    stop: mpsc::Sender<()>,
+    thread: thread::JoinHandle<bool>,
+}
+
+impl BusySpinner {
+    fn start(fg: Color, bg: Color) -> Self {
+        use ratatui::crossterm::{cursor, style};
+
+        let (stop, stopped) = mpsc::channel();
+        let thread = thread::spawn(move || {
+            let started = Instant::now();
+            let mut wait = SPINNER_DELAY;
+            let mut frame = 0;
+            while stopped.recv_timeout(wait) == Err(mpsc::RecvTimeoutError::Timeout) {
+                let text = format!(
+                    " {} Running... {}s ",
+                    SPINNER_FRAMES[frame % SPINNER_FRAMES.len()],
+                    started.elapsed().as_secs()
+                );
+                let _ = ratatui::crossterm::execute!(
+                    io::stdout(),
+                    cursor::SavePosition,
+                    cursor::MoveTo(0, 0),
+                    style::SetColors(style::Colors::new(fg.into(), bg.into())),
+                    style::Print(text),
+                    style::ResetColor,
+                    cursor::RestorePosition
+                );
+                frame += 1;
+                wait = SPINNER_INTERVAL;
+            }
+            frame > 0
+        });
+        Self { stop, thread }
+    }
+
+    /// Stops the spinner; true when it was drawn, so the screen needs a full redraw
+    fn stop(self) -> bool {
+        let _ = self.stop.send(());
+        self.thread.join().unwrap_or(false)
+    }
+}
+
 pub struct Runner<'a, B: Backend> {
     terminal: &'a mut Terminal<B>,
     app: App,
+    dragging_split_divider: bool,
+    dragging_sidebar_divider: bool,
 }
 
 impl<'a, B: Backend> Runner<'a, B> {
     pub fn new(terminal: &'a mut Terminal<B>, app: App) -> Self {
-        Self { terminal, app }
+        Self {
+            terminal,
+            app,
+            dragging_split_divider: false,
+            dragging_sidebar_divider: false,
+        }
     }
 
     pub async fn run(mut self) -> Result<(), io::Error> {
@@ -30,6 +98,10 @@ impl<'a, B: Backend> Runner<'a, B> {
             self.tick().await?;
 
             if self.app.should_quit {
+                self.app.rollback_open_transactions().await;
+                if let Err(err) = self.app.save_session() {
+                    logging::handle_non_critical_error(&err);
+                }
                 return Ok(());
             }
         }
@@ -39,21 +111,75 @@ impl<'a, B: Backend> Runner<'a, B> {
         if let Err(err) = self.app.check_background_prefetching() {
             let _ = logging::error(&format!("Error checking background prefetching: {}", err));
         }
+        self.app.check_connection_retries();
+        self.app.check_background_row_counts();
+        self.app.check_auto_refresh();
+        self.app.check_theme_reload();
 
         // Clear expired status messages
         self.app.clear_expired_status_message();
 
+        self.layout_context()?
+            .scroll_results_to_cursor(&mut self.app);
         self.terminal
             .draw(|frame| ui::render(frame, &mut self.app))?;
 
         self.refresh_cursor_style();
 
-        match event::read()? {
+        // The reload indicator has been drawn, so the fetch can run now
+        if self.app.reload_requested {
+            let spinner = self.start_spinner();
+            if let Err(err) = self.app.reload_current_tab().await {
+                self.app.set_status_message(format!("Reload failed: {}", err));
+                logging::error(&format!("Error reloading result tab: {}", err));
+            }
+            if spinner.stop() {
+                self.terminal.clear()?;
+            }
+            return Ok(());
+        }
+
+        // Run deletion batches between redraws, only waiting for input once it is done
+        let mut timeout = TICK_RATE;
+        if self.app.bulk_deletion.is_some() {
+            if let Err(err) = self.app.run_bulk_deletion_step().await {
+                self.app.set_status_message(format!("Deletion failed: {}", err));
+                logging::error(&format!("Error running bulk deletion: {}", err));
+            }
+            timeout = Duration::ZERO;
+        }
+        if !event::poll(timeout)? {
+            return Ok(());
+        }
+
+        let event = event::read()?;
+        let spinner = self.start_spinner();
+        let handled = self.handle_event(event).await;
+        // The spinner wrote over the screen behind ratatui's back
+        if spinner.stop() {
+            self.terminal.clear()?;
+        }
+        handled
+    }
+
+    fn start_spinner(&self) -> BusySpinner {
+        let theme = &self.app.config.theme;
+        BusySpinner::start(theme.base_color(), theme.accent_color())
+    }
+
+    async fn handle_event(&mut self, event: Event) -> Result<(), io::Error> {
+        match event {
             Event::Key(key) => self.handle_key(key).await,
             Event::Mouse(event) => match event.kind {
                 MouseEventKind::ScrollUp => self.handle_scroll_up().await,
                 MouseEventKind::ScrollDown => self.handle_scroll_down().await,
                 MouseEventKind::Down(MouseButton::Left) => self.handle_mouse_click(event).await,
+                MouseEventKind::Down(MouseButton::Middle) => self.handle_middle_click(event),
+                MouseEventKind::Drag(MouseButton::Left) => self.handle_mouse_drag(event),
+                MouseEventKind::Up(MouseButton::Left) => {
+                    self.handle_mouse_release();
+                    Ok(())
+                }
                 _ => Ok(()),
             },
             _ => Ok(()),
@@ -69,6 +195,12 @@ impl<'a, B: Backend> Runner<'a, B> {
     }
 
     async fn handle_key(&mut self, key: KeyEvent) -> Result<(), io::Error> {
+        let layout = self.layout_context()?;
+        // Modals aren't drawn while the terminal is too small, so they get no keys either
+        if layout.is_too_small() && self.app.modal_manager.has_modals() {
+            return Ok(());
+        }
+        self.app.results_visible_rows = layout.results_visible_rows(&self.app);
         NavigationInputHandler::handle_key(key.code, key.modifiers, &mut self.app)
             .await
             .map_err(|err| {
@@ -78,8 +210,7 @@ impl<'a, B: Backend> Runner<'a, B> {
     }
 
     async fn handle_mouse(&mut self, event: MouseEvent) -> Result<(), io::Error> {
-        let size = self.terminal.size()?;
-        let layout = ui::layout::LayoutContext::new(Rect::new(0, 0, size.width, size.height));
+        let layout = self.layout_context()?;
         match layout.locate(event.column, event.row, &self.app) {
             ui::layout::Hit::Connections(index) => {
                 self.app.select_connection(index);
@@ -94,20 +225,34 @@ impl<'a, B: Backend> Runner<'a, B> {
                 self.app.focus_results(column, row);
                 Ok(())
             }
+            ui::layout::Hit::PinnedResults(column, row) => {
+                self.app.focus_other_split();
+                self.app.focus_results(column, row);
+                Ok(())
+            }
             ui::layout::Hit::ResultTabs(tab) => {
                 self.app.select_tab(tab);
                 self.app.focus_results(0, 0);
                 Ok(())
             }
             ui::layout::Hit::Pagination(control) => self.handle_pagination(control).await,
-            ui::layout::Hit::None => Ok(()),
+            ui::layout::Hit::SplitDivider
+            | ui::layout::Hit::SidebarDivider
+            | ui::layout::Hit::None => Ok(()),
         }
     }
 
     async fn handle_mouse_click(&mut self, event: MouseEvent) -> Result<(), io::Error> {
-        let size = self.terminal.size()?;
-        let layout = ui::layout::LayoutContext::new(Rect::new(0, 0, size.width, size.height));
+        let layout = self.layout_context()?;
         match layout.locate(event.column, event.row, &self.app) {
+            ui::layout::Hit::SplitDivider => {
+                self.dragging_split_divider = true;
+                Ok(())
+            }
+            ui::layout::Hit::SidebarDivider => {
+                self.dragging_sidebar_divider = true;
+                Ok(())
+            }
             ui::layout::Hit::Connections(index) => {
                 self.app.select_connection(index);
                 self.app.focus_connections();
@@ -121,6 +266,11 @@ impl<'a, B: Backend> Runner<'a, B> {
                 self.app.focus_results(column, row);
                 Ok(())
             }
+            ui::layout::Hit::PinnedResults(column, row) => {
+                self.app.focus_other_split();
+                self.app.focus_results(column, row);
+                Ok(())
+            }
             ui::layout::Hit::ResultTabs(tab) => {
                 self.app.select_tab(tab);
                 self.app.focus_results(0, 0);
@@ -131,6 +281,45 @@ impl<'a, B: Backend> Runner<'a, B> {
         }
     }
 
+    /// Middle-clicking a result tab closes it
+    fn handle_middle_click(&mut self, event: MouseEvent) -> Result<(), io::Error> {
+        let layout = self.layout_context()?;
+        if let ui::layout::Hit::ResultTabs(tab) = layout.locate(event.column, event.row, &self.app)
+        {
+            self.app.close_tab(tab);
+        }
+        Ok(())
+    }
+
+    fn handle_mouse_drag(&mut self, event: MouseEvent) -> Result<(), io::Error> {
+        if self.dragging_split_divider {
+            let layout = self.layout_context()?;
+            self.app
+                .set_query_split_percent(layout.split_percent_at(event.column));
+        } else if self.dragging_sidebar_divider {
+            let layout = self.layout_context()?;
+            self.app
+                .set_sidebar_percent(layout.sidebar_percent_at(event.column));
+        }
+        Ok(())
+    }
+
+    fn handle_mouse_release(&mut self) {
+        if self.dragging_split_divider || self.dragging_sidebar_divider {
+            self.dragging_split_divider = false;
+            self.dragging_sidebar_divider = false;
+            self.app.save_layout();
+        }
+    }
+
+    fn layout_context(&self) -> Result<ui::layout::LayoutContext, io::Error> {
+        let size = self.terminal.size()?;
+        Ok(ui::layout::LayoutContext::with_app(
+            Rect::new(0, 0, size.width, size.height),
+            &self.app,
+        ))
+    }
+
     async fn expand_selected_connection(&mut self) -> Result<(), io::Error> {
         executor::block_on(self.app.handle_tree_action(input::TreeAction::Expand)).map_err(|err| {
             let _ = logging::error(&format!("Error expanding tree item: {}", err));
@@ -144,44 +333,45 @@ impl<'a, B: Backend> Runner<'a, B> {
     ) -> Result<(), io::Error> {
         use ui::layout::PaginationControl::*;
 
-        match control {
-            First => self.app.first_page().await.map(|_| ()),
-            Previous => self.app.previous_page().await.map(|_| ()),
-            Next => self.app.next_page().await.map(|_| ()),
-            Last => self.app.last_page().await.map(|_| ()),
-        }
-        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+        let paged = match control {
+            First => self.app.first_page().await,
+            Previous => self.app.previous_page().await,
+            Next => self.app.next_page().await,
+            Last => self.app.last_page().await,
+        };
+        self.report_paging_error(paged);
+        Ok(())
     }
 
     async fn handle_scroll_up(&mut self) -> Result<(), io::Error> {
         match self.app.active_pane {
-            Pane::Connections => {
-                self.app.select_previous_connection();
-                Ok(())
+            Pane::Connections => self.app.select_previous_connection(),
+            Pane::Results => {
+                let paged = self.app.previous_page().await;
+                self.report_paging_error(paged);
             }
-            Pane::Results => self
-                .app
-                .previous_page()
-                .await
-                .map(|_| ())
-                .map_err(|err| io::Error::new(io::ErrorKind::Other, err)),
-            _ => Ok(()),
+            _ => {}
         }
+        Ok(())
     }
 
     async fn handle_scroll_down(&mut self) -> Result<(), io::Error> {
         match self.app.active_pane {
-            Pane::Connections => {
-                self.app.select_next_connection();
-                Ok(())
+            Pane::Connections => self.app.select_next_connection(),
+            Pane::Results => {
+                let paged = self.app.next_page().await;
+                self.report_paging_error(paged);
             }
-            Pane::Results => self
-                .app
-                .next_page()
-                .await
-                .map(|_| ())
-                .map_err(|err| io::Error::new(io::ErrorKind::Other, err)),
-            _ => Ok(()),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// A failed page fetch is shown in the status bar rather than ending the event loop
+    fn report_paging_error(&mut self, paged: anyhow::Result<()>) {
+        if let Err(err) = paged {
+            self.app.set_status_message(format!("Failed to load page: {}", err));
+            logging::error(&format!("Error changing page: {}", err));
         }
     }
 }
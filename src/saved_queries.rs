@@ -0,0 +1,92 @@
+//! Named queries persisted to `queries.toml` in the config directory for reuse across sessions.
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SavedQuery {
+    pub name: String,
+    /// Statement last run with `:sql`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub query: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub where_clause: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub order_by: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub table: Option<String>,
+}
+
+impl SavedQuery {
+    /// `connection: schema.table` the query was saved from, if any
+    pub fn source_label(&self) -> Option<String> {
+        let connection = self.connection.as_deref()?;
+        Some(match (self.schema.as_deref(), self.table.as_deref()) {
+            (Some(schema), Some(table)) if !schema.is_empty() => {
+                format!("{}: {}.{}", connection, schema, table)
+            }
+            (_, Some(table)) => format!("{}: {}", connection, table),
+            _ => connection.to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct SavedQueriesFile {
+    #[serde(default)]
+    queries: Vec<SavedQuery>,
+}
+
+pub fn path() -> PathBuf {
+    crate::config::Config::get_config_dir().join("queries.toml")
+}
+
+/// Saved queries in the order they were first saved; a missing file has none
+pub fn load() -> Result<Vec<SavedQuery>> {
+    let path = path();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let file: SavedQueriesFile = toml::from_str(&contents)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok(file.queries)
+}
+
+/// Adds `query` to the file, creating it if needed. A query with the same name is replaced
+/// when `overwrite` is set and is an error otherwise. Returns whether one was replaced.
+pub fn save(query: SavedQuery, overwrite: bool) -> Result<bool> {
+    let mut queries = load()?;
+    let replaced = match queries.iter_mut().find(|saved| saved.name == query.name) {
+        Some(_) if !overwrite => {
+            return Err(anyhow::anyhow!(
+                "A query named '{}' already exists; use 'savequery! {}' to overwrite it",
+                query.name,
+                query.name
+            ));
+        }
+        Some(saved) => {
+            *saved = query;
+            true
+        }
+        None => {
+            queries.push(query);
+            false
+        }
+    };
+
+    let path = path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).context("Failed to create config directory")?;
+    }
+    let toml_string = toml::to_string_pretty(&SavedQueriesFile { queries })
+        .context("Failed to serialize saved queries")?;
+    std::fs::write(&path, toml_string)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(replaced)
+}
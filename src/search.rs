@@ -0,0 +1,139 @@
+//! Client-side search over the rows loaded in the current result tab.
+use regex::{Regex, RegexBuilder};
+
+/// Search state for the results pane.
+///
+/// Plain and regex searches are both compiled to a `Regex` (plain needles are escaped),
+/// so matching and highlighting share a single code path.
+#[derive(Debug, Clone, Default)]
+pub struct ResultSearch {
+    pub query: String,
+    pub regex_mode: bool,
+    pub case_sensitive: bool,
+    pub whole_word: bool,
+    /// Compile error for the current query, shown inline instead of silently matching nothing
+    pub error: Option<String>,
+    compiled: Option<Regex>,
+}
+
+impl ResultSearch {
+    pub fn set_query(&mut self, query: &str) {
+        self.query = query.to_string();
+        self.recompile();
+    }
+
+    pub fn toggle_regex(&mut self) -> bool {
+        self.regex_mode = !self.regex_mode;
+        self.recompile();
+        self.regex_mode
+    }
+
+    pub fn toggle_case_sensitive(&mut self) -> bool {
+        self.case_sensitive = !self.case_sensitive;
+        self.recompile();
+        self.case_sensitive
+    }
+
+    pub fn toggle_whole_word(&mut self) -> bool {
+        self.whole_word = !self.whole_word;
+        self.recompile();
+        self.whole_word
+    }
+
+    /// Whether a valid, non-empty search is in effect
+    pub fn is_active(&self) -> bool {
+        self.compiled.is_some()
+    }
+
+    /// Byte ranges of every match in `text`
+    pub fn match_ranges(&self, text: &str) -> Vec<(usize, usize)> {
+        match &self.compiled {
+            Some(re) => re
+                .find_iter(text)
+                .filter(|m| !m.is_empty())
+                .map(|m| (m.start(), m.end()))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        self.compiled.as_ref().is_some_and(|re| re.is_match(text))
+    }
+
+    /// Number of cells in `rows` containing at least one match; NULL cells never match
+    pub fn count_matching_cells(&self, rows: &[Vec<Option<String>>]) -> usize {
+        rows.iter()
+            .flat_map(|row| row.iter().flatten())
+            .filter(|cell| self.is_match(cell))
+            .count()
+    }
+
+    /// `(column, row)` of every matching cell among `columns`, in reading order (row by row)
+    pub fn matching_cells(
+        &self,
+        rows: &[Vec<Option<String>>],
+        columns: &[usize],
+    ) -> Vec<(usize, usize)> {
+        let mut cells = Vec::new();
+        for (row_index, row) in rows.iter().enumerate() {
+            for &column in columns {
+                let cell = row.get(column).and_then(Option::as_deref);
+                if cell.is_some_and(|cell| self.is_match(cell)) {
+                    cells.push((column, row_index));
+                }
+            }
+        }
+        cells
+    }
+
+    /// Short description of the query and active toggles, e.g. `/foo/ [regex, word]`
+    pub fn describe(&self) -> String {
+        let mut flags = Vec::new();
+        if self.regex_mode {
+            flags.push("regex");
+        }
+        if self.case_sensitive {
+            flags.push("case");
+        }
+        if self.whole_word {
+            flags.push("word");
+        }
+
+        if flags.is_empty() {
+            format!("/{}/", self.query)
+        } else {
+            format!("/{}/ [{}]", self.query, flags.join(", "))
+        }
+    }
+
+    fn recompile(&mut self) {
+        self.compiled = None;
+        self.error = None;
+        if self.query.is_empty() {
+            return;
+        }
+
+        let mut pattern = if self.regex_mode {
+            self.query.clone()
+        } else {
+            regex::escape(&self.query)
+        };
+        if self.whole_word {
+            pattern = format!(r"\b(?:{})\b", pattern);
+        }
+
+        match RegexBuilder::new(&pattern)
+            .case_insensitive(!self.case_sensitive)
+            .build()
+        {
+            Ok(re) => self.compiled = Some(re),
+            Err(e) => {
+                // Syntax errors span several lines; the last one carries the reason
+                let message = e.to_string();
+                let reason = message.lines().last().unwrap_or(&message).trim();
+                self.error = Some(reason.to_string());
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Connection and SSH passwords kept in the OS keyring, with `config.toml` holding only a
+//! `keyring:<entry>` reference to each.
+use crate::database::{ConnectionConfig, SSHConfig, KEYRING_REFERENCE_PREFIX};
+use anyhow::{Context, Result};
+
+/// Keyring service the entries are stored under
+const SERVICE: &str = "lazylode";
+
+/// Keyring entry prefix of the secrets of connection `name`
+fn connection_owner(name: &str) -> String {
+    format!("connection/{}", name)
+}
+
+/// Keyring entry prefix of the secrets of the SSH tunnel profile `name`
+pub fn tunnel_owner(name: &str) -> String {
+    format!("ssh-tunnel/{}", name)
+}
+
+/// Whether `connection` has a password, SSH password or passphrase written out in plain text
+pub fn has_plaintext_connection(connection: &ConnectionConfig) -> bool {
+    is_plaintext(&connection.password)
+        || connection
+            .ssh_tunnel
+            .as_ref()
+            .is_some_and(has_plaintext_ssh)
+}
+
+/// Whether `ssh` has a password or passphrase written out in plain text
+pub fn has_plaintext_ssh(ssh: &SSHConfig) -> bool {
+    is_plaintext(&ssh.password) || is_plaintext(&ssh.passphrase)
+}
+
+/// Moves the plaintext secrets of `connection` into the keyring, leaving references behind
+pub fn stash_connection(connection: &mut ConnectionConfig) -> Result<()> {
+    let owner = connection_owner(&connection.name);
+    stash(&mut connection.password, &format!("{}/password", owner))?;
+    if let Some(ssh) = connection.ssh_tunnel.as_mut() {
+        stash_ssh(ssh, &owner)?;
+    }
+    Ok(())
+}
+
+/// Moves the plaintext SSH password and passphrase into the keyring under `owner`
+pub fn stash_ssh(ssh: &mut SSHConfig, owner: &str) -> Result<()> {
+    stash(&mut ssh.password, &format!("{}/ssh-password", owner))?;
+    stash(&mut ssh.passphrase, &format!("{}/ssh-passphrase", owner))
+}
+
+/// Replaces the keyring references of `connection` with the secrets they name
+pub fn resolve_connection(connection: &mut ConnectionConfig) -> Result<()> {
+    resolve(&mut connection.password)?;
+    if let Some(ssh) = connection.ssh_tunnel.as_mut() {
+        resolve_ssh(ssh)?;
+    }
+    Ok(())
+}
+
+/// Replaces the keyring references of `ssh` with the secrets they name
+pub fn resolve_ssh(ssh: &mut SSHConfig) -> Result<()> {
+    resolve(&mut ssh.password)?;
+    resolve(&mut ssh.passphrase)
+}
+
+/// Removes the keyring entries of connection `name`, e.g. once it is deleted or renamed
+pub fn forget_connection(name: &str) -> Result<()> {
+    let owner = connection_owner(name);
+    for secret in ["password", "ssh-password", "ssh-passphrase"] {
+        let entry = format!("{}/{}", owner, secret);
+        match keyring::Entry::new(SERVICE, &entry).and_then(|e| e.delete_credential()) {
+            Ok(()) | Err(keyring::Error::NoEntry) => {}
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("Failed to remove '{}' from the OS keyring", entry))
+            }
+        }
+    }
+    Ok(())
+}
+
+fn is_plaintext(secret: &Option<String>) -> bool {
+    secret
+        .as_deref()
+        .is_some_and(|s| !s.is_empty() && !s.starts_with(KEYRING_REFERENCE_PREFIX))
+}
+
+/// Stores a plaintext `secret` as keyring `entry` and replaces it with a reference
+fn stash(secret: &mut Option<String>, entry: &str) -> Result<()> {
+    if !is_plaintext(secret) {
+        return Ok(());
+    }
+    let value = secret.as_deref().unwrap_or_default();
+    let stored = keyring::Entry::new(SERVICE, entry).and_then(|e| {
+        // Saving rewrites every connection; only touch entries whose secret changed
+        if e.get_password().ok().as_deref() != Some(value) {
+            e.set_password(value)?;
+        }
+        Ok(())
+    });
+    stored.with_context(|| {
+        format!(
+            "Failed to store '{}' in the OS keyring (set `use_keyring = false` under [database] \
+             to keep passwords in config.toml)",
+            entry
+        )
+    })?;
+    *secret = Some(format!("{}{}", KEYRING_REFERENCE_PREFIX, entry));
+    Ok(())
+}
+
+/// Replaces a keyring reference in `secret` with the secret it names
+fn resolve(secret: &mut Option<String>) -> Result<()> {
+    let Some(entry) = secret
+        .as_deref()
+        .and_then(|s| s.strip_prefix(KEYRING_REFERENCE_PREFIX))
+    else {
+        return Ok(());
+    };
+    let value = keyring::Entry::new(SERVICE, entry)
+        .and_then(|e| e.get_password())
+        .with_context(|| format!("Failed to read '{}' from the OS keyring", entry))?;
+    *secret = Some(value);
+    Ok(())
+}
@@ -0,0 +1,66 @@
+//! Open table tabs saved to `session.json` on quit and reopened on the next start. Only the
+//! tabs' query state is kept; their rows are fetched again once a tab is shown.
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::database::core::NullsOrder;
+
+/// A table tab as saved in the session
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SessionTab {
+    pub name: String,
+    /// Connection, schema and table the tab reads from
+    pub table_info: (String, String, String),
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub where_clause: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub where_params: Vec<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub order_by_clause: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sort_columns: Vec<(String, bool)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nulls_order: Option<NullsOrder>,
+    pub page_size: u32,
+    pub current_page: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_query: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub revealed_columns: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Session {
+    #[serde(default)]
+    pub tabs: Vec<SessionTab>,
+    /// Index into `tabs` of the tab that was selected
+    #[serde(default)]
+    pub selected_tab: Option<usize>,
+    #[serde(default)]
+    pub last_table_info: Option<(String, String, String)>,
+}
+
+pub fn path() -> PathBuf {
+    crate::config::Config::get_config_dir().join("session.json")
+}
+
+/// Reads `session.json`; a missing file is an empty session
+pub fn load() -> Result<Session> {
+    let path = path();
+    if !path.exists() {
+        return Ok(Session::default());
+    }
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&contents).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+pub fn save(session: &Session) -> Result<()> {
+    let path = path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).context("Failed to create config directory")?;
+    }
+    let json = serde_json::to_string_pretty(session).context("Failed to serialize session")?;
+    std::fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))
+}
@@ -1,5 +1,30 @@
+use anyhow::{Context, Result};
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// Palette keys of a theme file, each set to `[r, g, b]` or a `"#rrggbb"` hex string
+const COLOR_KEYS: [&str; 19] = [
+    "base",
+    "surface0",
+    "surface1",
+    "surface2",
+    "text",
+    "subtext0",
+    "subtext1",
+    "blue",
+    "lavender",
+    "sapphire",
+    "mauve",
+    "red",
+    "peach",
+    "yellow",
+    "green",
+    "header_bg",
+    "header_fg",
+    "row_even_bg",
+    "row_odd_bg",
+];
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Theme {
@@ -63,8 +88,40 @@ impl Theme {
         }
     }
 
+    /// Parses theme file `content`; colors it leaves out or can't be read fall back to the
+    /// defaults, with a warning naming `theme_name`
+    pub fn from_toml(theme_name: &str, content: &str) -> Result<Self> {
+        let mut table: toml::Table =
+            toml::from_str(content).context("Failed to parse theme file")?;
+        for key in COLOR_KEYS {
+            match table.get(key).map(parse_color) {
+                Some(Some(rgb)) => {
+                    let rgb = rgb
+                        .iter()
+                        .map(|&c| toml::Value::Integer(c.into()))
+                        .collect();
+                    table.insert(key.to_string(), toml::Value::Array(rgb));
+                }
+                Some(None) => {
+                    crate::logging::warn(&format!(
+                        "Theme {}: '{}' is not [r, g, b] or \"#rrggbb\"; using the default",
+                        theme_name, key
+                    ));
+                    table.remove(key);
+                }
+                None => crate::logging::warn(&format!(
+                    "Theme {}: no color for '{}'; using the default",
+                    theme_name, key
+                )),
+            }
+        }
+        toml::Value::Table(table)
+            .try_into()
+            .context("Failed to parse theme file")
+    }
+
     fn color(&self, rgb: Option<[u8; 3]>, default: Color) -> Color {
-        rgb.map_or(default, |[r, g, b]| Color::Rgb(r, g, b))
+        rgb.map_or(default, |[r, g, b]| self::rgb(r, g, b))
     }
 
     pub fn bg_color(&self, rgb: Option<[u8; 3]>) -> Color {
@@ -123,7 +180,151 @@ impl Theme {
         self.color(self.row_odd_bg, self.surface1_color())
     }
 
+    /// Sort arrows in result headers, set apart from the accent-colored column names
+    pub fn sort_indicator_color(&self) -> Color {
+        self.color(self.peach, Color::Yellow)
+    }
+
     pub fn cursor_color(&self) -> Color {
         self.color(self.mauve, Color::Cyan)
     }
 }
+
+/// Reads a theme color given as `[r, g, b]` or a `"#rrggbb"` hex string
+fn parse_color(value: &toml::Value) -> Option<[u8; 3]> {
+    match value {
+        toml::Value::String(hex) => {
+            let hex = hex.strip_prefix('#').unwrap_or(hex);
+            if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return None;
+            }
+            let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+            Some([channel(0)?, channel(2)?, channel(4)?])
+        }
+        toml::Value::Array(channels) if channels.len() == 3 => {
+            let channel = |i: usize| channels[i].as_integer().and_then(|c| u8::try_from(c).ok());
+            Some([channel(0)?, channel(1)?, channel(2)?])
+        }
+        _ => None,
+    }
+}
+
+/// Colors the terminal can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSupport {
+    /// 24-bit RGB
+    TrueColor,
+    /// The xterm 256-color palette
+    Ansi256,
+    /// The 16 named ANSI colors
+    Ansi16,
+}
+
+impl ColorSupport {
+    /// Support of the running terminal, detected once from `COLORTERM` and `TERM`
+    pub fn current() -> Self {
+        static SUPPORT: OnceLock<ColorSupport> = OnceLock::new();
+        *SUPPORT.get_or_init(Self::detect)
+    }
+
+    fn detect() -> Self {
+        let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+        if colorterm.eq_ignore_ascii_case("truecolor") || colorterm.eq_ignore_ascii_case("24bit") {
+            return Self::TrueColor;
+        }
+        // Windows terminals render RGB but don't advertise it
+        if cfg!(windows) {
+            return Self::TrueColor;
+        }
+        if std::env::var("TERM").is_ok_and(|term| term.contains("256color")) {
+            Self::Ansi256
+        } else {
+            Self::Ansi16
+        }
+    }
+
+    /// `color`, with RGB replaced by its nearest color the terminal supports
+    pub fn adapt(self, color: Color) -> Color {
+        match (self, color) {
+            (Self::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_ansi256([r, g, b])),
+            (Self::Ansi16, Color::Rgb(r, g, b)) => nearest_ansi16([r, g, b]),
+            _ => color,
+        }
+    }
+}
+
+/// An RGB color, downgraded when the terminal lacks truecolor support
+pub fn rgb(r: u8, g: u8, b: u8) -> Color {
+    ColorSupport::current().adapt(Color::Rgb(r, g, b))
+}
+
+/// A color from hue (degrees), saturation and lightness (0-1), as `rgb` would give it
+pub fn hsl(hue: f64, saturation: f64, lightness: f64) -> Color {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    let channel = |value: f64| ((value + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    rgb(channel(r), channel(g), channel(b))
+}
+
+fn distance([r1, g1, b1]: [u8; 3], [r2, g2, b2]: [u8; 3]) -> u32 {
+    let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2) as u32;
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+/// Index of the closest color in the 6x6x6 cube or grayscale ramp of the 256-color palette
+fn nearest_ansi256(color: [u8; 3]) -> u8 {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let level = |value: u8| {
+        (0..LEVELS.len())
+            .min_by_key(|&i| LEVELS[i].abs_diff(value))
+            .unwrap_or(0)
+    };
+    let [r, g, b] = color.map(level);
+    let cube = [LEVELS[r], LEVELS[g], LEVELS[b]];
+    let cube_index = (16 + 36 * r + 6 * g + b) as u8;
+
+    let average = color.iter().map(|&c| u32::from(c)).sum::<u32>() / 3;
+    let gray_step = (average.saturating_sub(3) / 10).min(23) as u8;
+    let gray = 8 + 10 * gray_step;
+    if distance(color, [gray; 3]) < distance(color, cube) {
+        232 + gray_step
+    } else {
+        cube_index
+    }
+}
+
+/// The named ANSI color closest to `color`, using xterm's default values
+fn nearest_ansi16(color: [u8; 3]) -> Color {
+    const PALETTE: [(Color, [u8; 3]); 16] = [
+        (Color::Black, [0, 0, 0]),
+        (Color::Red, [205, 0, 0]),
+        (Color::Green, [0, 205, 0]),
+        (Color::Yellow, [205, 205, 0]),
+        (Color::Blue, [0, 0, 238]),
+        (Color::Magenta, [205, 0, 205]),
+        (Color::Cyan, [0, 205, 205]),
+        (Color::Gray, [229, 229, 229]),
+        (Color::DarkGray, [127, 127, 127]),
+        (Color::LightRed, [255, 0, 0]),
+        (Color::LightGreen, [0, 255, 0]),
+        (Color::LightYellow, [255, 255, 0]),
+        (Color::LightBlue, [92, 92, 255]),
+        (Color::LightMagenta, [255, 0, 255]),
+        (Color::LightCyan, [0, 255, 255]),
+        (Color::White, [255, 255, 255]),
+    ];
+    PALETTE
+        .iter()
+        .min_by_key(|(_, rgb)| distance(color, *rgb))
+        .map_or(Color::Reset, |(named, _)| *named)
+}
@@ -39,7 +39,7 @@ impl TextInput {
                     return false;
                 }
                 KeyCode::Char('p') | KeyCode::Char('P') => {
-                    self.vim_editor.paste();
+                    self.vim_editor.paste_inline(key == KeyCode::Char('P'));
                     return true;
                 }
                 _ => {}
@@ -52,6 +52,18 @@ impl TextInput {
         self.vim_editor.insert_char_at_cursor(c);
     }
 
+    pub fn insert_str_at_cursor(&mut self, text: &str) {
+        self.vim_editor.insert_str_at_cursor(text);
+    }
+
+    pub fn text_before_cursor(&self) -> String {
+        self.vim_editor.text_before_cursor()
+    }
+
+    pub fn replace_before_cursor(&mut self, start: usize, text: &str) {
+        self.vim_editor.replace_before_cursor(start, text);
+    }
+
     pub fn set_content(&mut self, content: String) {
         self.vim_editor.set_content(content);
     }
@@ -62,7 +74,11 @@ impl TextInput {
 
     pub fn display_text_with_cursor(&self) -> String {
         let content = self.vim_editor.content();
-        let cursor = self.cursor_position().min(content.len());
+        // The cursor counts characters; find the byte it sits at
+        let cursor = content
+            .char_indices()
+            .nth(self.cursor_position())
+            .map_or(content.len(), |(index, _)| index);
 
         if self.mode() == VimMode::Insert {
             if content.is_empty() {
@@ -2,6 +2,91 @@ use ratatui::layout::{Constraint, Layout, Position, Rect};
 use std::rc::Rc;
 
 use crate::app::App;
+use crate::config::{LayoutConfig, MainPanelLayout};
+use crate::ui::panes::results::{ResultsPane, COLUMN_SPACING};
+
+/// Height of a single query field (WHERE / ORDER BY) including borders.
+pub const QUERY_FIELD_HEIGHT: u16 = 3;
+
+/// Smallest terminal width the full layout is drawn at; narrower ones get a notice instead.
+pub const MIN_TERMINAL_WIDTH: u16 = 40;
+
+/// Smallest terminal height the full layout is drawn at; shorter ones get a notice instead.
+pub const MIN_TERMINAL_HEIGHT: u16 = 12;
+
+/// Rows taken by the results table header.
+const RESULTS_HEADER_HEIGHT: u16 = 1;
+
+/// Result rows that fit in a results table whose inner (borderless) height is `table_height`.
+pub fn visible_result_rows(table_height: u16) -> usize {
+    usize::from(table_height.saturating_sub(RESULTS_HEADER_HEIGHT))
+}
+
+/// Whether a terminal of `area` is below `MIN_TERMINAL_WIDTH` x `MIN_TERMINAL_HEIGHT`, where
+/// the fixed-height parts of the layout would no longer fit.
+pub fn is_too_small(area: Rect) -> bool {
+    area.width < MIN_TERMINAL_WIDTH || area.height < MIN_TERMINAL_HEIGHT
+}
+
+/// First result row drawn when `visible_rows` fit, keeping the cursor row centred where
+/// possible. Rendering and hit-testing share this so clicks land on the row shown.
+pub fn first_visible_result_row(
+    total_rows: usize,
+    visible_rows: usize,
+    cursor_row: usize,
+) -> usize {
+    if visible_rows == 0 {
+        return 0;
+    }
+    let cursor_row = cursor_row.min(total_rows.saturating_sub(1));
+    cursor_row
+        .saturating_sub(visible_rows / 2)
+        .min(total_rows.saturating_sub(visible_rows))
+}
+
+/// Splits the area below the title bar into the connections sidebar and the main panel.
+pub fn split_main_content(area: Rect, layout: &LayoutConfig) -> Rc<[Rect]> {
+    let percent = LayoutConfig::clamp_sidebar_percent(layout.sidebar_percent);
+    Layout::default()
+        .direction(ratatui::layout::Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(percent),
+            Constraint::Percentage(100 - percent),
+        ])
+        .split(area)
+}
+
+/// Splits the main panel into the query area and the results area.
+pub fn split_main_panel(area: Rect, layout: &LayoutConfig) -> Rc<[Rect]> {
+    match layout.main_panel {
+        MainPanelLayout::Stacked => Layout::default()
+            .direction(ratatui::layout::Direction::Vertical)
+            .constraints([
+                Constraint::Length(QUERY_FIELD_HEIGHT * 2),
+                Constraint::Min(1),
+            ])
+            .split(area),
+        MainPanelLayout::Split => {
+            let percent = LayoutConfig::clamp_split_percent(layout.query_split_percent);
+            Layout::default()
+                .direction(ratatui::layout::Direction::Horizontal)
+                .constraints([
+                    Constraint::Percentage(percent),
+                    Constraint::Percentage(100 - percent),
+                ])
+                .split(area)
+        }
+    }
+}
+
+/// Splits the results table area into the halves drawing the selected and the pinned tab
+/// side by side.
+pub fn split_results_table(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
+        .direction(ratatui::layout::Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area)
+}
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum QueryField {
@@ -22,8 +107,12 @@ pub enum Hit {
     Connections(usize),
     QueryInput(QueryField, usize),
     Results(usize, usize),
+    /// Cell of the pinned tab in the other half of a split results pane
+    PinnedResults(usize, usize),
     ResultTabs(usize),
     Pagination(PaginationControl),
+    SplitDivider,
+    SidebarDivider,
     None,
 }
 
@@ -33,10 +122,11 @@ pub struct LayoutContext {
     main_chunks: Rc<[Rect]>,
     sidebar_chunks: Rc<[Rect]>,
     main_panel_chunks: Rc<[Rect]>,
+    main_panel_layout: MainPanelLayout,
 }
 
 impl LayoutContext {
-    pub fn new(root: Rect) -> Self {
+    fn with_layout(root: Rect, layout: &LayoutConfig) -> Self {
         let vertical_chunks = Layout::default()
             .direction(ratatui::layout::Direction::Vertical)
             .constraints([
@@ -47,20 +137,14 @@ impl LayoutContext {
             .split(root);
 
         let main_area = vertical_chunks[1];
-        let main_chunks = Layout::default()
-            .direction(ratatui::layout::Direction::Horizontal)
-            .constraints([Constraint::Percentage(20), Constraint::Percentage(80)])
-            .split(main_area);
+        let main_chunks = split_main_content(main_area, layout);
 
         let sidebar_chunks = Layout::default()
             .direction(ratatui::layout::Direction::Vertical)
             .constraints([Constraint::Length(3), Constraint::Min(1)])
             .split(main_chunks[0]);
 
-        let main_panel_chunks = Layout::default()
-            .direction(ratatui::layout::Direction::Vertical)
-            .constraints([Constraint::Length(6), Constraint::Min(1)])
-            .split(main_chunks[1]);
+        let main_panel_chunks = split_main_panel(main_chunks[1], layout);
 
         Self {
             root,
@@ -68,17 +152,62 @@ impl LayoutContext {
             main_chunks,
             sidebar_chunks,
             main_panel_chunks,
+            main_panel_layout: layout.main_panel,
+        }
+    }
+
+    pub fn with_app(root: Rect, app: &App) -> Self {
+        Self::with_layout(root, &app.config.layout)
+    }
+
+    /// Whether only the "too small" notice is drawn in place of the panes and modals
+    pub fn is_too_small(&self) -> bool {
+        is_too_small(self.root)
+    }
+
+    /// Converts a column into a query split percentage of the main panel.
+    pub fn split_percent_at(&self, column: u16) -> u16 {
+        let panel = self.main_chunks[1];
+        if panel.width == 0 {
+            return LayoutConfig::default().query_split_percent;
+        }
+        let offset = column.saturating_sub(panel.x) as u32;
+        let percent = (offset * 100 / panel.width as u32) as u16;
+        LayoutConfig::clamp_split_percent(percent)
+    }
+
+    /// Converts a column into a sidebar width percentage of the terminal.
+    pub fn sidebar_percent_at(&self, column: u16) -> u16 {
+        let area = self.vertical_chunks[1];
+        if area.width == 0 {
+            return LayoutConfig::default().sidebar_percent;
         }
+        let offset = column.saturating_sub(area.x) as u32 + 1;
+        let percent = (offset * 100 / area.width as u32) as u16;
+        LayoutConfig::clamp_sidebar_percent(percent)
     }
 
-    pub fn with_app(root: Rect, _app: &App) -> Self {
-        Self::new(root)
+    fn is_on_sidebar_divider(&self, position: Position) -> bool {
+        let sidebar = self.main_chunks[0];
+        let on_column = position.x + 1 == sidebar.right() || position.x == self.main_chunks[1].x;
+        on_column && position.y >= sidebar.y && position.y < sidebar.bottom()
+    }
+
+    fn is_on_split_divider(&self, position: Position) -> bool {
+        if self.main_panel_layout != MainPanelLayout::Split {
+            return false;
+        }
+        let query = self.query_area();
+        let results = self.main_panel_chunks[1];
+        let on_column = position.x + 1 == query.right() || position.x == results.x;
+        on_column && position.y >= query.y && position.y < query.bottom()
     }
 
     pub fn locate(&self, column: u16, row: u16, app: &App) -> Hit {
         let position = Position::new(column, row);
 
-        if app.modal_manager.active_blocks_interaction() {
+        // Only the "too small" notice is drawn, so there is nothing to click
+        if self.is_too_small() || app.modal_manager.active_blocks_interaction() {
             return Hit::None;
         }
 
@@ -90,6 +219,14 @@ impl LayoutContext {
             return hit;
         }
 
+        if self.is_on_sidebar_divider(position) {
+            return Hit::SidebarDivider;
+        }
+
+        if self.is_on_split_divider(position) {
+            return Hit::SplitDivider;
+        }
+
         if self.query_area().contains(position) {
             return self.hit_query(position, app);
         }
@@ -98,6 +235,13 @@ impl LayoutContext {
             return self.hit_results(position, app);
         }
 
+        if let Some(area) = self
+            .pinned_results_area(app)
+            .filter(|area| area.contains(position))
+        {
+            return self.hit_pinned_results(position, area, app);
+        }
+
         if self
             .tabs_area(app)
             .map_or(false, |area| area.contains(position))
@@ -134,8 +278,7 @@ impl LayoutContext {
         let area = self.query_area();
         let relative_y = position.y.saturating_sub(area.y);
         let relative_x = position.x.saturating_sub(area.x) as usize;
-        let where_height = area.height / 2;
-        let field = if relative_y < where_height {
+        let field = if relative_y < QUERY_FIELD_HEIGHT {
             QueryField::Where
         } else {
             QueryField::OrderBy
@@ -155,7 +298,8 @@ impl LayoutContext {
         Hit::QueryInput(field, relative_x.min(max_len))
     }
 
-    fn results_area(&self, app: &App) -> Rect {
+    /// Area of the results table, both halves of it when the pane is split
+    fn results_table_area(&self, app: &App) -> Rect {
         let chunks = self.result_panel_chunks(app);
         if app.result_tabs.is_empty() {
             chunks[0]
@@ -164,68 +308,224 @@ impl LayoutContext {
         }
     }
 
+    /// Area drawing the selected tab: the whole results table, or its half of a split
+    fn results_area(&self, app: &App) -> Rect {
+        let area = self.results_table_area(app);
+        if app.split_active() {
+            split_results_table(area)[usize::from(app.split_focus_right)]
+        } else {
+            area
+        }
+    }
+
+    /// Area drawing the pinned tab when the results pane is split
+    fn pinned_results_area(&self, app: &App) -> Option<Rect> {
+        app.split_active().then(|| {
+            split_results_table(self.results_table_area(app))[usize::from(!app.split_focus_right)]
+        })
+    }
+
     fn hit_results(&self, position: Position, app: &App) -> Hit {
-        let area = self.results_area(app);
-        let relative_x = position.x.saturating_sub(area.x);
+        let Some(tab_index) = app.selected_result_tab_index else {
+            return Hit::Results(0, 0);
+        };
+        let (column, row) = self.hit_result_cell(
+            position,
+            self.results_area(app),
+            tab_index,
+            app.cursor_position,
+            app,
+        );
+        Hit::Results(column, row)
+    }
+
+    fn hit_pinned_results(&self, position: Position, area: Rect, app: &App) -> Hit {
+        let Some(pinned) = app.pinned_tab else {
+            return Hit::None;
+        };
+        let (column, row) = self.hit_result_cell(position, area, pinned.index, pinned.cursor, app);
+        Hit::PinnedResults(column, row)
+    }
+
+    /// Column and row under `position` in the table of tab `tab_index` drawn in `area`
+    /// around `cursor`
+    fn hit_result_cell(
+        &self,
+        position: Position,
+        area: Rect,
+        tab_index: usize,
+        cursor: (usize, usize),
+        app: &App,
+    ) -> (usize, usize) {
         let table_inner = ratatui::widgets::Block::default()
             .borders(ratatui::widgets::Borders::ALL)
             .inner(area);
 
-        let column = self.hit_result_column(relative_x, table_inner.width, app);
-        let row = self.hit_result_row(position, table_inner, app);
+        // A record view shows the cursor's row only, one column per line
+        if app
+            .result_tabs
+            .get(tab_index)
+            .is_some_and(|(_, _, state)| state.record_view)
+        {
+            let column = self.hit_record_column(position, table_inner, tab_index, cursor, app);
+            return (column, cursor.1);
+        }
+
+        let column = self.hit_result_column(position, table_inner, tab_index, cursor, app);
+        let row = self.hit_result_row(position, table_inner, tab_index, cursor, app);
 
-        Hit::Results(column, row)
+        (column, row)
     }
 
-    fn hit_result_column(&self, relative_x: u16, width: u16, app: &App) -> usize {
-        let Some(tab_index) = app.selected_result_tab_index else {
-            return 0;
-        };
-        let Some((_, result, _)) = app.result_tabs.get(tab_index) else {
+    /// Column under `position`, measured with the same widths the results pane draws
+    fn hit_result_column(
+        &self,
+        position: Position,
+        table_inner: Rect,
+        tab_index: usize,
+        cursor: (usize, usize),
+        app: &App,
+    ) -> usize {
+        let Some((drawn_columns, line_num_width, widths)) =
+            self.drawn_result_columns(table_inner, tab_index, cursor, app)
+        else {
             return 0;
         };
 
-        let line_num_width = result.rows.len().to_string().len().max(3) as u16 + 1;
-        if relative_x <= line_num_width {
-            return 0;
+        // The row numbers don't scroll, so a click on them picks the first column drawn
+        let relative_x = position.x.saturating_sub(table_inner.x);
+        if relative_x < line_num_width {
+            return drawn_columns[0];
         }
 
-        let inner_width = width.saturating_sub(2);
-        let data_cols = result.columns.len() as u16;
-        if data_cols == 0 {
-            return 0;
-        }
-
-        let spacing: u16 = 1;
-        let total_spacing = spacing.saturating_mul(data_cols.saturating_sub(1));
-        let remaining_width = inner_width
-            .saturating_sub(line_num_width)
-            .saturating_sub(total_spacing);
-        let base = remaining_width / data_cols;
-        let remainder = remaining_width % data_cols;
-
-        let mut accum: u16 = line_num_width;
-        for (index, column_width) in (0..data_cols)
-            .map(|i| base + if i < remainder { 1 } else { 0 })
-            .enumerate()
-        {
-            accum = accum.saturating_add(column_width);
+        let mut accum = line_num_width;
+        for (index, column_width) in widths.into_iter().enumerate() {
+            accum = accum.saturating_add(COLUMN_SPACING).saturating_add(column_width);
             if relative_x < accum {
-                return index;
+                return drawn_columns[index];
             }
-            accum = accum.saturating_add(spacing);
         }
 
-        (data_cols - 1) as usize
+        drawn_columns[drawn_columns.len() - 1]
+    }
+
+    /// Columns drawn in the table of tab `tab_index` around `cursor`, with the widths of the
+    /// row numbers and of each of them
+    fn drawn_result_columns(
+        &self,
+        table_inner: Rect,
+        tab_index: usize,
+        cursor: (usize, usize),
+        app: &App,
+    ) -> Option<(Vec<usize>, u16, Vec<u16>)> {
+        let (_, result, state) = app.result_tabs.get(tab_index)?;
+        let visible_columns = app.tab_visible_column_indices(tab_index);
+        if visible_columns.is_empty() {
+            return None;
+        }
+
+        let visible_rows = visible_result_rows(table_inner.height);
+        let start_row = first_visible_result_row(result.rows.len(), visible_rows, cursor.1);
+        let rows = start_row..start_row + visible_rows;
+        let (_, drawn_columns) = ResultsPane::scrolled_columns(
+            result,
+            state,
+            &visible_columns,
+            rows.clone(),
+            table_inner.width,
+            cursor.0,
+            &app.config.display,
+        );
+        let (line_num_width, widths) = ResultsPane::column_widths(
+            result,
+            state,
+            &drawn_columns,
+            rows,
+            table_inner.width,
+            &app.config.display,
+        );
+        Some((drawn_columns, line_num_width, widths))
     }
 
-    fn hit_result_row(&self, position: Position, table_inner: Rect, app: &App) -> usize {
-        const HEADER_HEIGHT: u16 = 1;
+    /// Column whose line of the record view is under `position`
+    fn hit_record_column(
+        &self,
+        position: Position,
+        table_inner: Rect,
+        tab_index: usize,
+        cursor: (usize, usize),
+        app: &App,
+    ) -> usize {
+        let Some((_, result, query_state)) = app.result_tabs.get(tab_index) else {
+            return cursor.0;
+        };
+        let visible_columns = app.tab_visible_column_indices(tab_index);
+        let (_, lines) = ResultsPane::record_lines(
+            result,
+            query_state,
+            &visible_columns,
+            cursor.1,
+            table_inner.width,
+            &app.config.display,
+        );
+        let first_line =
+            ResultsPane::first_visible_record_line(&lines, table_inner.height, cursor.0);
+        let line = first_line + usize::from(position.y.saturating_sub(table_inner.y));
+        lines
+            .get(line)
+            .or(lines.last())
+            .map_or(cursor.0, |line| line.column)
+    }
 
+    /// Stores the results pane's horizontal scroll for the cursor's column, so the offset
+    /// only moves when the cursor leaves the columns drawn
+    pub fn scroll_results_to_cursor(&self, app: &mut App) {
         let Some(tab_index) = app.selected_result_tab_index else {
-            return 0;
+            return;
+        };
+        let visible_columns = app.visible_column_indices();
+        let Some((_, result, state)) = app.result_tabs.get(tab_index) else {
+            return;
         };
-        let Some((_, result, _)) = app.result_tabs.get(tab_index) else {
+
+        let table_inner = ratatui::widgets::Block::default()
+            .borders(ratatui::widgets::Borders::ALL)
+            .inner(self.results_area(app));
+        let visible_rows = visible_result_rows(table_inner.height);
+        let start_row =
+            first_visible_result_row(result.rows.len(), visible_rows, app.cursor_position.1);
+        let (offset, _) = ResultsPane::scrolled_columns(
+            result,
+            state,
+            &visible_columns,
+            start_row..start_row + visible_rows,
+            table_inner.width,
+            app.cursor_position.0,
+            &app.config.display,
+        );
+
+        if let Some((_, _, state)) = app.result_tabs.get_mut(tab_index) {
+            state.col_scroll_offset = offset;
+        }
+    }
+
+    /// Result rows that fit in the results table at the current terminal size
+    pub fn results_visible_rows(&self, app: &App) -> usize {
+        let table_inner = ratatui::widgets::Block::default()
+            .borders(ratatui::widgets::Borders::ALL)
+            .inner(self.results_area(app));
+        visible_result_rows(table_inner.height)
+    }
+
+    fn hit_result_row(
+        &self,
+        position: Position,
+        table_inner: Rect,
+        tab_index: usize,
+        cursor: (usize, usize),
+        app: &App,
+    ) -> usize {
+        let Some((_, result, state)) = app.result_tabs.get(tab_index) else {
             return 0;
         };
 
@@ -235,21 +535,34 @@ impl LayoutContext {
         }
 
         let data_y = position.y.saturating_sub(table_inner.y);
-        let row_in_view = usize::from(data_y.saturating_sub(HEADER_HEIGHT));
-        let visible_capacity = usize::from(table_inner.height.saturating_sub(HEADER_HEIGHT));
-
-        let cursor_row = app.cursor_position.1.min(total_rows.saturating_sub(1));
-        let start_row = if visible_capacity == 0 {
-            0
+        let row_in_view = usize::from(data_y.saturating_sub(RESULTS_HEADER_HEIGHT));
+        let visible_capacity = visible_result_rows(table_inner.height);
+        let cursor_height = self
+            .drawn_result_columns(table_inner, tab_index, cursor, app)
+            .map_or(1, |(drawn_columns, _, widths)| {
+                ResultsPane::wrapped_row_height(
+                    result,
+                    state,
+                    cursor.1,
+                    &drawn_columns,
+                    &widths,
+                    visible_capacity,
+                    &app.config.display,
+                )
+            });
+        let rows_drawn = visible_capacity.saturating_sub(cursor_height - 1);
+        let start_row = first_visible_result_row(total_rows, rows_drawn, cursor.1);
+
+        // Lines of a wrapped cursor row all pick it; rows below sit that much lower
+        let cursor_in_view = cursor.1.saturating_sub(start_row);
+        let row = if row_in_view < cursor_in_view {
+            start_row + row_in_view
+        } else if row_in_view < cursor_in_view + cursor_height {
+            cursor.1
         } else {
-            cursor_row
-                .saturating_sub(visible_capacity / 2)
-                .min(total_rows.saturating_sub(visible_capacity))
+            start_row + row_in_view - (cursor_height - 1)
         };
-
-        start_row
-            .saturating_add(row_in_view)
-            .min(total_rows.saturating_sub(1))
+        row.min(total_rows.saturating_sub(1))
     }
 
     fn tabs_area(&self, app: &App) -> Option<Rect> {
@@ -27,6 +27,11 @@ pub fn render(frame: &mut Frame, app: &App) {
         frame.area(),
     );
 
+    if layout::is_too_small(frame.area()) {
+        render_too_small(frame, app);
+        return;
+    }
+
     // Define vertical chunks for status bar and main content (no command bar)
     let chunks = Layout::default()
         .direction(LayoutDirection::Vertical)
@@ -42,6 +47,36 @@ pub fn render(frame: &mut Frame, app: &App) {
     app.modal_manager.render_all(frame, app);
 }
 
+/// Stands in for the whole UI while the terminal is below the minimum size, saying how big
+/// it needs to be
+fn render_too_small(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let message = vec![
+        Line::from("Terminal too small"),
+        Line::from(format!(
+            "{}x{}, needs {}x{}",
+            area.width,
+            area.height,
+            layout::MIN_TERMINAL_WIDTH,
+            layout::MIN_TERMINAL_HEIGHT
+        )),
+    ];
+    // Centred vertically where there is room for both lines
+    let top = area.height.saturating_sub(message.len() as u16) / 2;
+    let text_area = Rect {
+        y: area.y + top,
+        height: area.height - top,
+        ..area
+    };
+    frame.render_widget(
+        Paragraph::new(message)
+            .alignment(ratatui::layout::Alignment::Center)
+            .wrap(ratatui::widgets::Wrap { trim: true })
+            .style(Style::default().fg(app.config.theme.text_color())),
+        text_area,
+    );
+}
+
 /// Renders the status bar at the top of the UI.
 fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
     // Determine mode indicator - check modal first, then navigation system
@@ -67,11 +102,34 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
         app.navigation_manager.get_navigation_info()
     };
 
+    let marked = app
+        .current_query_state()
+        .map_or(0, |state| state.rows_marked_for_deletion.len());
+    let marked_info = if marked > 0 {
+        format!(" | {} marked", marked)
+    } else {
+        String::new()
+    };
+
+    let refresh_info = app
+        .current_query_state()
+        .and_then(|state| state.auto_refresh)
+        .map_or(String::new(), |interval| format!(" | ⟳ every {}s", interval.as_secs()));
+
+    let transaction_info = if app.open_transactions.is_empty() {
+        String::new()
+    } else {
+        format!(" | ● TX open: {}", app.open_transaction_names())
+    };
+
     // Create status text, including current mode, navigation info, and status message
     let status = Line::from(format!(
-        "{} | {} | {}",
+        "{} | {}{}{}{} | {}",
         mode,
         nav_info,
+        marked_info,
+        refresh_info,
+        transaction_info,
         app.status_message.as_deref().unwrap_or("")
     ));
 
@@ -88,13 +146,7 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
 /// Renders the main content area, split into sidebar and main panel.
 fn render_main_content(frame: &mut Frame, app: &App, area: Rect) {
     // Split main area horizontally into sidebar (connections) and main panel (query, results)
-    let horizontal_chunks = Layout::default()
-        .direction(LayoutDirection::Horizontal)
-        .constraints([
-            Constraint::Percentage(20), // Sidebar takes 20% width
-            Constraint::Percentage(80), // Main panel takes 80% width
-        ])
-        .split(area);
+    let horizontal_chunks = layout::split_main_content(area, &app.config.layout);
 
     app.sidebar_pane.render(frame, app, horizontal_chunks[0]); // Render the sidebar (connections tree)
     render_main_panel(frame, app, horizontal_chunks[1]); // Render the main panel (query input, results)
@@ -106,16 +158,21 @@ fn render_main_panel(frame: &mut Frame, app: &App, area: Rect) {
         area,
     );
 
-    let chunks = Layout::default()
-        .direction(LayoutDirection::Vertical)
-        .constraints([Constraint::Length(6), Constraint::Min(1)])
-        .split(area);
+    let chunks = layout::split_main_panel(area, &app.config.layout);
 
     app.results_pane.render(frame, app, chunks[1]);
 
     app.query_input_pane.render(frame, app, chunks[0]);
 }
 
+/// Splits a copied tab's name (see `App::duplicate_current_tab`) into the name of the tab it
+/// copies and its ` (n)` suffix
+pub fn split_duplicate_suffix(name: &str) -> Option<(&str, &str)> {
+    let open = name.rfind(" (")?;
+    let number = name[open + 2..].strip_suffix(')')?;
+    (!number.is_empty() && number.chars().all(|c| c.is_ascii_digit())).then(|| name.split_at(open))
+}
+
 /// Intelligently shortens tab names to fit within the available width.
 /// Analyzes all open tabs to determine what distinguishing information to preserve.
 pub fn shorten_tab_name_intelligent(
@@ -126,6 +183,12 @@ pub fn shorten_tab_name_intelligent(
     if full_name.len() <= max_width {
         return full_name.to_string();
     }
+    // A copy is shortened like the tab it copies, keeping its suffix
+    if let Some((base, suffix)) = split_duplicate_suffix(full_name) {
+        let shortened =
+            shorten_tab_name_intelligent(base, all_tabs, max_width.saturating_sub(suffix.len()));
+        return format!("{}{}", shortened, suffix);
+    }
 
     // Parse the full name: "connection:database:schema.table" or "connection:schema.table"
     let parts: Vec<&str> = full_name.split(':').collect();
@@ -424,21 +487,25 @@ pub fn get_tab_color(tab_name: &str, _index: usize) -> Color {
         hash = hash.wrapping_mul(31).wrapping_add(byte as u32);
     }
 
-    // Use the hash to select from a palette of distinguishable colors
-    let colors = [
-        Color::Red,
-        Color::Green,
-        Color::Blue,
-        Color::Yellow,
-        Color::Magenta,
-        Color::Cyan,
-        Color::LightRed,
-        Color::LightGreen,
-        Color::LightBlue,
-        Color::LightYellow,
-        Color::LightMagenta,
-        Color::LightCyan,
-    ];
+    // Use the hash to pick a hue, so many tabs rarely share a color
+    crate::theme::hsl(f64::from(hash % 360), 0.65, 0.65)
+}
 
-    colors[hash as usize % colors.len()]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn tiny_terminal_shows_only_the_too_small_notice() {
+        let app = crate::app::tests::mock_app();
+        let mut terminal = Terminal::new(TestBackend::new(10, 3)).unwrap();
+        terminal.draw(|frame| render(frame, &app)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let rows: Vec<String> = (0..3)
+            .map(|y| (0..10).map(|x| buffer[(x, y)].symbol()).collect())
+            .collect();
+        assert_eq!(rows, [" Terminal ", " too small", "   10x3,  "]);
+    }
 }
@@ -129,24 +129,14 @@ pub fn render_deletion_modal(frame: &mut Frame, app: &crate::app::App) {
 
     // Get and render preview data
     if let Some(preview_data) = app.get_deletion_preview() {
-        if let Some((_, result, _)) = app
-            .selected_result_tab_index
-            .and_then(|idx| app.result_tabs.get(idx))
-        {
-            let header = Row::new(
-                result
-                    .columns
-                    .iter()
-                    .map(|c| c.as_str())
-                    .collect::<Vec<_>>(),
-            )
-            .style(
+        if let Some((columns, marked_rows)) = preview_data.split_first() {
+            let header = Row::new(columns.iter().map(|c| c.as_str()).collect::<Vec<_>>()).style(
                 Style::default()
                     .fg(app.config.theme.accent_color())
                     .add_modifier(Modifier::BOLD),
             );
 
-            let rows: Vec<Row> = preview_data
+            let rows: Vec<Row> = marked_rows
                 .iter()
                 .map(|row| {
                     Row::new(row.iter().map(|cell| cell.as_str()).collect::<Vec<_>>())
@@ -155,8 +145,8 @@ pub fn render_deletion_modal(frame: &mut Frame, app: &crate::app::App) {
                 .collect();
 
             let widths = vec![
-                Constraint::Percentage(100 / result.columns.len() as u16);
-                result.columns.len()
+                Constraint::Percentage(100 / columns.len().max(1) as u16);
+                columns.len()
             ];
 
             let table = Table::new(rows, widths)
@@ -196,6 +196,15 @@ impl Default for ModalManager {
 /// Common modal input handling utilities
 pub mod utils {
     use super::*;
+    use ratatui::widgets::{Paragraph, Wrap};
+
+    /// Lines `text` takes up once wrapped (untrimmed, as the scrollable modals draw it) to
+    /// `width` columns
+    pub fn wrapped_line_count(text: &str, width: u16) -> usize {
+        Paragraph::new(text)
+            .wrap(Wrap { trim: false })
+            .line_count(width)
+    }
 
     /// Handle common modal keys using key mappings
     pub fn handle_common_keys(
@@ -0,0 +1,161 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use std::cell::Cell;
+
+use crate::app::App;
+use crate::ui::modal_manager::utils::wrapped_line_count;
+use crate::ui::modal_manager::{Modal, ModalResult};
+
+/// Lines moved by PageUp/PageDown and Ctrl+u/Ctrl+d
+const PAGE_LINES: u16 = 10;
+
+/// Read-only view of the full value of a result cell
+#[derive(Debug)]
+pub struct CellDetailModal {
+    column: String,
+    /// `None` for NULL
+    value: Option<String>,
+    scroll: u16,
+    /// Width and height the value was last drawn in
+    viewport: Cell<(u16, u16)>,
+}
+
+impl CellDetailModal {
+    /// JSON objects and arrays are pretty-printed; anything else is shown as is
+    pub fn new(column: &str, value: Option<&str>) -> Self {
+        let value = value.map(|value| {
+            let trimmed = value.trim_start();
+            let pretty = if trimmed.starts_with('{') || trimmed.starts_with('[') {
+                serde_json::from_str::<serde_json::Value>(value)
+                    .ok()
+                    .and_then(|json| serde_json::to_string_pretty(&json).ok())
+            } else {
+                None
+            };
+            pretty.unwrap_or_else(|| value.to_string())
+        });
+
+        Self {
+            column: column.to_string(),
+            value,
+            scroll: 0,
+            viewport: Cell::new((0, 0)),
+        }
+    }
+
+    /// Highest useful scroll offset, which brings the last wrapped line to the bottom
+    fn max_scroll(&self) -> u16 {
+        let (width, height) = self.viewport.get();
+        let lines = self
+            .value
+            .as_deref()
+            .map_or(1, |value| wrapped_line_count(value, width));
+        u16::try_from(lines.saturating_sub(usize::from(height))).unwrap_or(u16::MAX)
+    }
+
+    fn scroll_by(&mut self, lines: i32) {
+        let scroll = (i32::from(self.scroll) + lines).clamp(0, i32::from(self.max_scroll()));
+        self.scroll = scroll as u16;
+    }
+}
+
+impl Modal for CellDetailModal {
+    fn render(&self, frame: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title(self.column.as_str())
+            .borders(Borders::ALL)
+            .style(
+                Style::default()
+                    .fg(app.config.theme.text_color())
+                    .bg(app.config.theme.surface1_color()),
+            );
+
+        frame.render_widget(block.clone(), area);
+        let inner_area = block.inner(area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Min(1),    // Value
+                Constraint::Length(1), // Footer
+            ])
+            .split(inner_area);
+        self.viewport.set((chunks[0].width, chunks[0].height));
+
+        let value = match self.value.as_deref() {
+            Some(value) => Paragraph::new(value),
+            None => Paragraph::new("NULL").style(
+                Style::default()
+                    .fg(app.config.theme.subtext0_color())
+                    .add_modifier(Modifier::ITALIC),
+            ),
+        };
+        frame.render_widget(
+            value.wrap(Wrap { trim: false }).scroll((self.scroll, 0)),
+            chunks[0],
+        );
+        frame.render_widget(
+            Paragraph::new("j/k to scroll, Ctrl+d/Ctrl+u by page, g/G top/bottom, Esc to close"),
+            chunks[1],
+        );
+    }
+
+    fn handle_input(
+        &mut self,
+        key: KeyCode,
+        modifiers: KeyModifiers,
+        _nav_action: Option<crate::navigation::types::NavigationAction>,
+    ) -> ModalResult {
+        let page = i32::from(PAGE_LINES);
+        match key {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => return ModalResult::Closed,
+            KeyCode::Char('d') if modifiers.contains(KeyModifiers::CONTROL) => self.scroll_by(page),
+            KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.scroll_by(-page)
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.scroll_by(1),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll_by(-1),
+            KeyCode::PageDown => self.scroll_by(page),
+            KeyCode::PageUp => self.scroll_by(-page),
+            KeyCode::Home | KeyCode::Char('g') => self.scroll = 0,
+            KeyCode::End | KeyCode::Char('G') => self.scroll = self.max_scroll(),
+            _ => {}
+        }
+        ModalResult::Continue
+    }
+
+    fn get_title(&self) -> &str {
+        "Cell Detail"
+    }
+
+    fn get_size(&self) -> (u16, u16) {
+        (70, 70)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_scroll_counts_wrapped_lines() {
+        let value = "x".repeat(50);
+        let modal = CellDetailModal::new("note", Some(&value));
+        modal.viewport.set((10, 2));
+        // 5 lines of 10 columns, 2 of them visible
+        assert_eq!(modal.max_scroll(), 3);
+
+        let modal = CellDetailModal::new("note", Some("short\nvalue"));
+        modal.viewport.set((10, 2));
+        assert_eq!(modal.max_scroll(), 0);
+    }
+}
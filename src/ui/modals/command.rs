@@ -85,6 +85,12 @@ impl Modal for CommandModal {
         "Command"
     }
 
+    fn get_mode(&self) -> Option<crate::navigation::types::VimMode> {
+        // Every key is typed into the command line, so 'q' doesn't quit and Esc is left to
+        // command mode, which also leaves it
+        Some(crate::navigation::types::VimMode::Insert)
+    }
+
     fn get_size(&self) -> (u16, u16) {
         (60, 5)
     }
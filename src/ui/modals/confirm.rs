@@ -0,0 +1,87 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+use crate::ui::modal_manager::{Modal, ModalResult};
+
+/// Yes/no prompt that emits `action` when confirmed
+#[derive(Debug)]
+pub struct ConfirmModal {
+    title: String,
+    message: String,
+    action: String,
+}
+
+impl ConfirmModal {
+    pub fn new(title: &str, message: &str, action: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            message: message.to_string(),
+            action: action.to_string(),
+        }
+    }
+}
+
+impl Modal for ConfirmModal {
+    fn render(&self, frame: &mut Frame, area: Rect, app: &App) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title(self.title.as_str())
+            .borders(Borders::ALL)
+            .style(
+                Style::default()
+                    .fg(app.config.theme.text_color())
+                    .bg(app.config.theme.surface1_color()),
+            );
+
+        frame.render_widget(block.clone(), area);
+        let inner_area = block.inner(area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Min(1),    // Message
+                Constraint::Length(1), // Footer
+            ])
+            .split(inner_area);
+
+        frame.render_widget(
+            Paragraph::new(self.message.as_str()).wrap(Wrap { trim: false }),
+            chunks[0],
+        );
+        frame.render_widget(Paragraph::new("Enter or y to confirm, Esc or n to cancel"), chunks[1]);
+    }
+
+    fn handle_input(
+        &mut self,
+        key: KeyCode,
+        _modifiers: KeyModifiers,
+        nav_action: Option<crate::navigation::types::NavigationAction>,
+    ) -> ModalResult {
+        use crate::navigation::types::NavigationAction;
+        match (key, nav_action) {
+            (KeyCode::Char('y'), _) | (_, Some(NavigationAction::Confirm)) => {
+                ModalResult::Action(self.action.clone())
+            }
+            (KeyCode::Char('n'), _)
+            | (_, Some(NavigationAction::Cancel))
+            | (_, Some(NavigationAction::Quit)) => ModalResult::Closed,
+            _ => ModalResult::Continue,
+        }
+    }
+
+    fn get_title(&self) -> &str {
+        &self.title
+    }
+
+    fn get_size(&self) -> (u16, u16) {
+        (60, 30)
+    }
+}
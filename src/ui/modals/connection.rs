@@ -2,60 +2,125 @@ use crossterm::event::{KeyCode, KeyModifiers};
 use ratatui::layout::Rect;
 use ratatui::{
     layout::{Constraint, Direction, Layout},
-    style::Style,
+    style::{Color, Style},
     widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
 
-use crate::app::App;
+use crate::app::{App, ConnectionForm};
+use crate::database::DatabaseType;
 use crate::ui::components::{FieldNavigator, TextInput};
 use crate::ui::modal_manager::{Modal, ModalResult};
 
+/// Index of the port text field
+const PORT_FIELD: usize = 2;
+
+/// Indexes of the masked text fields
+const PASSWORD_FIELD: usize = 4;
+const PASSPHRASE_FIELD: usize = 6;
+
+/// Index of the database type selector, after the text fields
+const TYPE_FIELD: usize = 7;
+
+/// Index of the read-only toggle, after the type selector
+const READ_ONLY_FIELD: usize = 8;
+
+/// Index of the ssh-agent toggle, the last field
+const USE_AGENT_FIELD: usize = 9;
+
+/// Database types offered by the form, in the order Left/Right cycles through them
+const DATABASE_TYPES: [DatabaseType; 5] = [
+    DatabaseType::Postgres,
+    DatabaseType::MySQL,
+    DatabaseType::MongoDB,
+    DatabaseType::SQLite,
+    DatabaseType::Mock,
+];
+
 /// Modal for managing database connections
 #[derive(Debug)]
 pub struct ConnectionModal {
-    // Modal owns its state
-    name: String,
-    db_type: crate::database::DatabaseType,
-    host: String,
-    port: String,
-    username: String,
-    password: String,
-    database: String,
+    /// The connection being created or edited, along with the fields the modal doesn't show
+    /// (the SSH host and login, which connection is edited)
+    form: ConnectionForm,
     field_navigator: FieldNavigator,
     text_inputs: Vec<TextInput>,
+    /// Why the last key or save was rejected, shown below the fields
+    error: Option<String>,
 }
 
 impl ConnectionModal {
-    pub fn new() -> Self {
+    /// A modal filled in from `form`; an empty port starts at the type's default
+    pub fn new(form: &ConnectionForm) -> Self {
+        let mut form = form.clone();
+        if form.port.is_empty() {
+            form.port = match form.db_type.default_port() {
+                0 => String::new(),
+                port => port.to_string(),
+            };
+        }
+        let mut text_inputs = vec![TextInput::new(); 7];
+        let values = [
+            &form.name,
+            &form.host,
+            &form.port,
+            &form.username,
+            &form.password,
+            &form.database,
+            &form.ssh_passphrase,
+        ];
+        for (input, value) in text_inputs.iter_mut().zip(values) {
+            input.set_content(value.clone());
+        }
         Self {
-            name: String::new(),
-            db_type: crate::database::DatabaseType::Postgres,
-            host: String::new(),
-            port: "5432".to_string(),
-            username: String::new(),
-            password: String::new(),
-            database: String::new(),
-            field_navigator: FieldNavigator::new(6), // name, host, port, username, password, database
-            text_inputs: vec![TextInput::new(); 6],
+            form,
+            // name, host, port, username, password, database, SSH passphrase, type, read-only,
+            // SSH agent
+            field_navigator: FieldNavigator::new(USE_AGENT_FIELD + 1),
+            text_inputs,
+            error: None,
         }
     }
 
-    fn get_current_input(&self) -> &TextInput {
-        &self.text_inputs[self.field_navigator.current_field()]
+    fn get_current_input(&self) -> Option<&TextInput> {
+        self.text_inputs.get(self.field_navigator.current_field())
     }
 
-    fn get_current_input_mut(&mut self) -> &mut TextInput {
-        &mut self.text_inputs[self.field_navigator.current_field()]
+    /// Switches to the next (or previous) database type, moving the port along to the new
+    /// type's default unless the user typed a different one
+    fn cycle_db_type(&mut self, forward: bool) {
+        let count = DATABASE_TYPES.len();
+        let index = DATABASE_TYPES
+            .iter()
+            .position(|db_type| *db_type == self.form.db_type)
+            .unwrap_or(0);
+        let next = if forward {
+            (index + 1) % count
+        } else {
+            (index + count - 1) % count
+        };
+        let db_type = DATABASE_TYPES[next].clone();
+
+        let port_input = &mut self.text_inputs[PORT_FIELD];
+        let old_default = self.form.db_type.default_port().to_string();
+        if port_input.content().is_empty() || port_input.content() == old_default {
+            let port = match db_type.default_port() {
+                0 => String::new(),
+                port => port.to_string(),
+            };
+            port_input.set_content(port);
+        }
+        self.form.db_type = db_type;
     }
 
     fn sync_all_values(&mut self) {
-        self.name = self.text_inputs[0].content().to_string();
-        self.host = self.text_inputs[1].content().to_string();
-        self.port = self.text_inputs[2].content().to_string();
-        self.username = self.text_inputs[3].content().to_string();
-        self.password = self.text_inputs[4].content().to_string();
-        self.database = self.text_inputs[5].content().to_string();
+        self.form.name = self.text_inputs[0].content().to_string();
+        self.form.host = self.text_inputs[1].content().to_string();
+        self.form.port = self.text_inputs[PORT_FIELD].content().to_string();
+        self.form.username = self.text_inputs[3].content().to_string();
+        self.form.password = self.text_inputs[PASSWORD_FIELD].content().to_string();
+        self.form.database = self.text_inputs[5].content().to_string();
+        self.form.ssh_passphrase = self.text_inputs[PASSPHRASE_FIELD].content().to_string();
     }
 }
 
@@ -64,7 +129,7 @@ impl Modal for ConnectionModal {
         frame.render_widget(Clear, area);
 
         let block = Block::default()
-            .title("New Connection")
+            .title(self.get_title())
             .borders(Borders::ALL)
             .style(
                 Style::default()
@@ -85,31 +150,39 @@ impl Modal for ConnectionModal {
                 Constraint::Length(3),
                 Constraint::Length(3),
                 Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Min(1), // Error
             ])
             .margin(1)
             .split(inner_area);
 
         let ssh_tunnel_label = "SSH Tunnel:".to_string();
-        let ssh_tunnel_value = "None".to_string(); // TODO: support SSH tunnel selection
-
-        let password_display = "*".repeat(self.text_inputs[4].content().len());
+        let ssh_tunnel_value = self
+            .form
+            .ssh_tunnel_name
+            .clone()
+            .unwrap_or_else(|| "None".to_string());
 
         // Build fields with cursor display for current field
         let current_field = self.field_navigator.current_field();
-        let fields: Vec<(String, String)> = (0..6)
+        let fields: Vec<(String, String)> = (0..self.text_inputs.len())
             .map(|i| {
                 let label = match i {
                     0 => "Name:",
                     1 => "Host:",
                     2 => "Port:",
                     3 => "Username:",
-                    4 => "Password:",
+                    PASSWORD_FIELD => "Password:",
                     5 => "Database:",
+                    PASSPHRASE_FIELD => "SSH Key Passphrase:",
                     _ => "",
                 };
 
-                let value = if i == 4 {
-                    password_display.clone()
+                let value = if i == PASSWORD_FIELD || i == PASSPHRASE_FIELD {
+                    "*".repeat(self.text_inputs[i].content().len())
                 } else if i == current_field {
                     self.text_inputs[i].display_text_with_cursor()
                 } else {
@@ -120,10 +193,33 @@ impl Modal for ConnectionModal {
             })
             .collect();
 
-        let all_fields = vec![fields, vec![(ssh_tunnel_label, ssh_tunnel_value)]]
-            .into_iter()
-            .flatten()
-            .collect::<Vec<_>>();
+        let type_value = if current_field == TYPE_FIELD {
+            format!("< {:?} >", self.form.db_type)
+        } else {
+            format!("{:?}", self.form.db_type)
+        };
+        let type_field = ("Type:".to_string(), type_value);
+        let read_only_value = if self.form.read_only { "[x]" } else { "[ ]" };
+        let read_only_field = ("Read-only:".to_string(), read_only_value.to_string());
+        let use_agent_value = if self.form.ssh_use_agent {
+            "[x]"
+        } else {
+            "[ ]"
+        };
+        let use_agent_field = ("Use SSH Agent:".to_string(), use_agent_value.to_string());
+
+        let all_fields = vec![
+            fields,
+            vec![
+                type_field,
+                read_only_field,
+                use_agent_field,
+                (ssh_tunnel_label, ssh_tunnel_value),
+            ],
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
 
         for (i, (label, value)) in all_fields.iter().enumerate() {
             let style = if i == self.field_navigator.current_field() {
@@ -137,6 +233,13 @@ impl Modal for ConnectionModal {
                 chunks[i],
             );
         }
+
+        if let Some(error) = &self.error {
+            frame.render_widget(
+                Paragraph::new(error.as_str()).style(Style::default().fg(Color::Red)),
+                chunks[all_fields.len()],
+            );
+        }
     }
 
     fn handle_input(
@@ -146,12 +249,59 @@ impl Modal for ConnectionModal {
         nav_action: Option<crate::navigation::types::NavigationAction>,
     ) -> ModalResult {
         let current = self.field_navigator.current_field();
-        let current_input = &mut self.text_inputs[current];
+        self.error = None;
 
-        // Try handling all keys through VimEditor first
-        if current_input.handle_key(key, _modifiers) {
+        // Only digits can be typed into the port
+        let typing = self
+            .get_current_input()
+            .is_some_and(|input| input.mode() == crate::navigation::types::VimMode::Insert);
+        if current == PORT_FIELD && typing {
+            if let KeyCode::Char(c) = key {
+                if !c.is_ascii_digit() {
+                    self.error = Some("Port can only contain digits".to_string());
+                    return ModalResult::Continue;
+                }
+            }
+        }
+
+        if current == TYPE_FIELD {
+            match key {
+                KeyCode::Left | KeyCode::Char('h') => {
+                    self.cycle_db_type(false);
+                    return ModalResult::Continue;
+                }
+                KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') => {
+                    self.cycle_db_type(true);
+                    return ModalResult::Continue;
+                }
+                _ => {}
+            }
+        }
+        if current == READ_ONLY_FIELD
+            && matches!(
+                key,
+                KeyCode::Left | KeyCode::Right | KeyCode::Char('h' | 'l' | ' ')
+            )
+        {
+            self.form.read_only = !self.form.read_only;
             return ModalResult::Continue;
         }
+        if current == USE_AGENT_FIELD
+            && matches!(
+                key,
+                KeyCode::Left | KeyCode::Right | KeyCode::Char('h' | 'l' | ' ')
+            )
+        {
+            self.form.ssh_use_agent = !self.form.ssh_use_agent;
+            return ModalResult::Continue;
+        }
+
+        // Try handling all keys through VimEditor first
+        if let Some(current_input) = self.text_inputs.get_mut(current) {
+            if current_input.handle_key(key, _modifiers) {
+                return ModalResult::Continue;
+            }
+        }
 
         // Handle modal-specific actions
         if let Some(action) = nav_action {
@@ -162,22 +312,34 @@ impl Modal for ConnectionModal {
                 }
                 crate::navigation::types::NavigationAction::Confirm => {
                     self.sync_all_values();
-                    let action = format!(
-                        "create_connection:{}:{}:{}:{}:{}:{}",
-                        self.name,
-                        self.host,
-                        self.port,
-                        self.username,
-                        self.password,
-                        self.database
-                    );
-                    return ModalResult::Action(action);
+                    if self.form.name.trim().is_empty() {
+                        self.error = Some("Name is required".to_string());
+                        return ModalResult::Continue;
+                    }
+                    if let Err(e) = self.form.db_type.parse_port(&self.form.port) {
+                        self.error = Some(e);
+                        return ModalResult::Continue;
+                    }
+                    // Named tunnel profiles keep their own SSH settings in config.toml
+                    let sets_ssh = self.form.ssh_use_agent || !self.form.ssh_passphrase.is_empty();
+                    if sets_ssh && !self.form.ssh_enabled {
+                        self.error = Some(
+                            "The SSH passphrase and agent need the connection's own ssh_tunnel"
+                                .to_string(),
+                        );
+                        return ModalResult::Continue;
+                    }
+                    let form = serde_json::to_string(&self.form).unwrap_or_default();
+                    return ModalResult::Action(format!("save_connection:{}", form));
                 }
                 _ => {}
             }
 
             // Allow field navigation in normal mode
-            if current_input.mode() == crate::navigation::types::VimMode::Normal {
+            let in_normal_mode = self
+                .get_current_input()
+                .is_none_or(|input| input.mode() == crate::navigation::types::VimMode::Normal);
+            if in_normal_mode {
                 if self.field_navigator.handle_action(action) {
                     return ModalResult::Continue;
                 }
@@ -188,12 +350,18 @@ impl Modal for ConnectionModal {
     }
 
     fn get_title(&self) -> &str {
-        "New Connection"
+        if self.form.editing_index.is_some() {
+            "Edit Connection"
+        } else {
+            "New Connection"
+        }
     }
 
     fn get_mode(&self) -> Option<crate::navigation::types::VimMode> {
-        let current = self.field_navigator.current_field();
-        Some(self.text_inputs[current].mode())
+        Some(
+            self.get_current_input()
+                .map_or(crate::navigation::types::VimMode::Normal, TextInput::mode),
+        )
     }
 
     fn get_size(&self) -> (u16, u16) {
@@ -0,0 +1,155 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use std::cell::Cell;
+
+use crate::app::App;
+use crate::database::{ConnectionConfig, ConnectionFailure, FailedStage};
+use crate::ui::modal_manager::utils::wrapped_line_count;
+use crate::ui::modal_manager::{Modal, ModalResult};
+
+/// Lines moved by PageUp/PageDown and Ctrl+u/Ctrl+d
+const PAGE_LINES: u16 = 10;
+
+/// Full reason the last connect of a connection failed
+#[derive(Debug)]
+pub struct ConnectionErrorModal {
+    title: String,
+    /// Which part failed, with the host it was trying to reach
+    stage: String,
+    at: String,
+    message: String,
+    scroll: u16,
+    /// Width and height the message was last drawn in
+    viewport: Cell<(u16, u16)>,
+}
+
+impl ConnectionErrorModal {
+    pub fn new(config: &ConnectionConfig, failure: &ConnectionFailure) -> Self {
+        let stage = match (failure.stage, &config.ssh_tunnel) {
+            (FailedStage::SshTunnel, Some(ssh)) => {
+                format!("SSH tunnel to {}:{}", ssh.host, ssh.port)
+            }
+            (FailedStage::SshTunnel, None) => "SSH tunnel".to_string(),
+            (FailedStage::Database, Some(_)) => format!(
+                "Database at {}:{} (the SSH tunnel was up)",
+                config.host, config.port
+            ),
+            (FailedStage::Database, None) => {
+                format!("Database at {}:{}", config.host, config.port)
+            }
+        };
+
+        Self {
+            title: format!("{} failed", config.name),
+            stage,
+            at: failure.at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            message: failure.message.clone(),
+            scroll: 0,
+            viewport: Cell::new((0, 0)),
+        }
+    }
+
+    /// Highest useful scroll offset, which brings the last wrapped line to the bottom
+    fn max_scroll(&self) -> u16 {
+        let (width, height) = self.viewport.get();
+        let lines = wrapped_line_count(&self.message, width);
+        u16::try_from(lines.saturating_sub(usize::from(height))).unwrap_or(u16::MAX)
+    }
+
+    fn scroll_by(&mut self, lines: i32) {
+        let scroll = (i32::from(self.scroll) + lines).clamp(0, i32::from(self.max_scroll()));
+        self.scroll = scroll as u16;
+    }
+}
+
+impl Modal for ConnectionErrorModal {
+    fn render(&self, frame: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title(self.title.as_str())
+            .borders(Borders::ALL)
+            .style(
+                Style::default()
+                    .fg(app.config.theme.text_color())
+                    .bg(app.config.theme.surface1_color()),
+            );
+
+        frame.render_widget(block.clone(), area);
+        let inner_area = block.inner(area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(3), // Stage and time
+                Constraint::Min(1),    // Error
+                Constraint::Length(1), // Footer
+            ])
+            .split(inner_area);
+        self.viewport.set((chunks[1].width, chunks[1].height));
+
+        let label = Style::default().add_modifier(Modifier::BOLD);
+        let summary = vec![
+            Line::from(vec![
+                Span::styled("Failed at: ", label),
+                Span::styled(self.stage.as_str(), Style::default().fg(Color::Red)),
+            ]),
+            Line::from(vec![
+                Span::styled("When:      ", label),
+                Span::raw(self.at.as_str()),
+            ]),
+        ];
+        frame.render_widget(Paragraph::new(summary), chunks[0]);
+
+        frame.render_widget(
+            Paragraph::new(self.message.as_str())
+                .wrap(Wrap { trim: false })
+                .scroll((self.scroll, 0)),
+            chunks[1],
+        );
+        frame.render_widget(
+            Paragraph::new("j/k to scroll, Ctrl+d/Ctrl+u by page, g/G top/bottom, Esc to close"),
+            chunks[2],
+        );
+    }
+
+    fn handle_input(
+        &mut self,
+        key: KeyCode,
+        modifiers: KeyModifiers,
+        _nav_action: Option<crate::navigation::types::NavigationAction>,
+    ) -> ModalResult {
+        let page = i32::from(PAGE_LINES);
+        match key {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => return ModalResult::Closed,
+            KeyCode::Char('d') if modifiers.contains(KeyModifiers::CONTROL) => self.scroll_by(page),
+            KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.scroll_by(-page)
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.scroll_by(1),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll_by(-1),
+            KeyCode::PageDown => self.scroll_by(page),
+            KeyCode::PageUp => self.scroll_by(-page),
+            KeyCode::Home | KeyCode::Char('g') => self.scroll = 0,
+            KeyCode::End | KeyCode::Char('G') => self.scroll = self.max_scroll(),
+            _ => {}
+        }
+        ModalResult::Continue
+    }
+
+    fn get_title(&self) -> &str {
+        "Connection Error"
+    }
+
+    fn get_size(&self) -> (u16, u16) {
+        (70, 50)
+    }
+}
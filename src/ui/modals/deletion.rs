@@ -1,35 +1,117 @@
 use crossterm::event::{KeyCode, KeyModifiers};
-use ratatui::{layout::Rect, Frame};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Gauge, Paragraph, Row, Table},
+    Frame,
+};
 
 use crate::app::App;
 use crate::ui::modal_manager::{Modal, ModalResult};
 
-/// Modal for confirming row deletions
+/// Modal for confirming row deletions, which then shows their progress
 #[derive(Debug)]
 pub struct DeletionModal {
-    // TODO: Move deletion preview state here
+    /// Table the rows are deleted from, e.g. `public.orders`
+    target: String,
+    row_count: usize,
 }
 
 impl DeletionModal {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(target: &str, row_count: usize) -> Self {
+        Self {
+            target: target.to_string(),
+            row_count,
+        }
     }
 }
 
+/// Table of the rows about to be deleted, headed by the column names
+fn render_preview(frame: &mut Frame, area: Rect, app: &App) {
+    let Some(preview) = app.get_deletion_preview() else {
+        return;
+    };
+    let Some((columns, rows)) = preview.split_first() else {
+        return;
+    };
+
+    let header = Row::new(columns.iter().map(String::as_str)).style(
+        Style::default()
+            .fg(app.config.theme.accent_color())
+            .add_modifier(Modifier::BOLD),
+    );
+    let rows = rows.iter().map(|row| Row::new(row.iter().map(String::as_str)));
+    let widths = vec![Constraint::Fill(1); columns.len()];
+    frame.render_widget(Table::new(rows, widths).header(header).column_spacing(1), area);
+}
+
 impl Modal for DeletionModal {
     fn render(&self, frame: &mut Frame, area: Rect, app: &App) {
-        // TODO: Implement deletion modal rendering
-        use ratatui::style::Style;
-        use ratatui::widgets::{Block, Borders, Clear};
-
         frame.render_widget(Clear, area);
-        frame.render_widget(
-            Block::default()
-                .title("Deletion Modal (TODO)")
-                .borders(Borders::ALL)
-                .style(Style::default().fg(app.config.theme.text_color())),
-            area,
-        );
+
+        let block = Block::default()
+            .title("Confirm Deletion")
+            .borders(Borders::ALL)
+            .style(
+                Style::default()
+                    .fg(app.config.theme.text_color())
+                    .bg(app.config.theme.surface1_color()),
+            );
+
+        frame.render_widget(block.clone(), area);
+        let inner_area = block.inner(area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(2), // Summary
+                Constraint::Length(1), // Progress
+                Constraint::Min(0),    // Preview of the marked rows
+                Constraint::Length(1), // Footer
+            ])
+            .split(inner_area);
+
+        let footer = match &app.bulk_deletion {
+            Some(deletion) => {
+                let summary = format!(
+                    "Deleting {} rows from {} ({} deleted so far)",
+                    deletion.requested, deletion.target, deletion.deleted
+                );
+                frame.render_widget(Paragraph::new(summary), chunks[0]);
+
+                let gauge = Gauge::default()
+                    .gauge_style(
+                        Style::default()
+                            .fg(app.config.theme.accent_color())
+                            .bg(app.config.theme.surface0_color()),
+                    )
+                    .ratio(deletion.progress().clamp(0.0, 1.0))
+                    .label(format!("{}/{}", deletion.processed, deletion.requested));
+                frame.render_widget(gauge, chunks[1]);
+
+                if deletion.cancel_requested {
+                    "Cancelling, rolling back..."
+                } else {
+                    "Esc to cancel and roll back"
+                }
+            }
+            None => {
+                let summary = vec![
+                    Line::from(format!(
+                        "Delete {} marked rows from {}?",
+                        self.row_count, self.target
+                    )),
+                    Line::from("Rows are deleted in batches inside a single transaction."),
+                ];
+                frame.render_widget(Paragraph::new(summary), chunks[0]);
+                render_preview(frame, chunks[2], app);
+                "Enter to delete, Esc to cancel"
+            }
+        };
+
+        frame.render_widget(Paragraph::new(footer), chunks[3]);
     }
 
     fn handle_input(
@@ -41,6 +123,7 @@ impl Modal for DeletionModal {
         use crate::navigation::types::NavigationAction;
         match nav_action {
             Some(NavigationAction::Cancel) | Some(NavigationAction::Quit) => ModalResult::Closed,
+            Some(NavigationAction::Confirm) => ModalResult::Action("confirm_deletions".to_string()),
             _ => ModalResult::Continue,
         }
     }
@@ -50,6 +133,6 @@ impl Modal for DeletionModal {
     }
 
     fn get_size(&self) -> (u16, u16) {
-        (70, 60)
+        (70, 50)
     }
 }
@@ -0,0 +1,175 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+use crate::navigation::types::{KeyCombination, KeyMapping, NavigationAction, HELP_GROUPS};
+use crate::ui::modal_manager::{Modal, ModalResult};
+
+/// Lines moved by PageUp/PageDown and Ctrl+u/Ctrl+d
+const PAGE_LINES: u16 = 10;
+
+/// Width of the key column
+const KEYS_WIDTH: usize = 24;
+
+/// Key bindings of the active key mapping, grouped under the headings of `HELP_GROUPS`
+#[derive(Debug)]
+pub struct HelpModal {
+    /// Each heading with the keys and name of every bound action under it
+    sections: Vec<(&'static str, Vec<(String, String)>)>,
+    scroll: u16,
+}
+
+impl HelpModal {
+    pub fn new(mapping: &KeyMapping) -> Self {
+        let mut bindings = mapping.get_all_mappings();
+        bindings.sort_by_key(|(combo, action)| (*action, combo.to_string()));
+
+        let sections = HELP_GROUPS
+            .iter()
+            .map(|&group| {
+                let mut entries: Vec<(String, String)> = Vec::new();
+                let mut last_action = None;
+                for (combo, action) in &bindings {
+                    if action.help_group() != group || Self::is_shifted_duplicate(mapping, combo) {
+                        continue;
+                    }
+                    // Actions bound to several keys get one line listing all of them
+                    match entries.last_mut() {
+                        Some((keys, _)) if last_action == Some(*action) => {
+                            keys.push_str(", ");
+                            keys.push_str(&combo.to_string());
+                        }
+                        _ => entries.push((combo.to_string(), action.label())),
+                    }
+                    last_action = Some(*action);
+                }
+                (group, entries)
+            })
+            .filter(|(_, entries)| !entries.is_empty())
+            .collect();
+
+        Self {
+            sections,
+            scroll: 0,
+        }
+    }
+
+    /// Shifted symbols are bound with and without Shift since terminals differ on whether
+    /// they report it; only the plain key is listed
+    fn is_shifted_duplicate(mapping: &KeyMapping, combo: &KeyCombination) -> bool {
+        let KeyCode::Char(c) = combo.key else {
+            return false;
+        };
+        combo.modifiers == KeyModifiers::SHIFT
+            && !c.is_alphabetic()
+            && mapping.get_action(combo.key, KeyModifiers::empty())
+                == mapping.get_action(combo.key, combo.modifiers)
+    }
+
+    /// Heading, binding and blank separator lines
+    fn line_count(&self) -> usize {
+        self.sections
+            .iter()
+            .map(|(_, entries)| entries.len() + 2)
+            .sum()
+    }
+
+    fn max_scroll(&self) -> u16 {
+        u16::try_from(self.line_count().saturating_sub(1)).unwrap_or(u16::MAX)
+    }
+
+    fn scroll_by(&mut self, lines: i32) {
+        let scroll = (i32::from(self.scroll) + lines).clamp(0, i32::from(self.max_scroll()));
+        self.scroll = scroll as u16;
+    }
+}
+
+impl Modal for HelpModal {
+    fn render(&self, frame: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title("Key Bindings")
+            .borders(Borders::ALL)
+            .style(
+                Style::default()
+                    .fg(app.config.theme.text_color())
+                    .bg(app.config.theme.surface1_color()),
+            );
+
+        frame.render_widget(block.clone(), area);
+        let inner_area = block.inner(area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Min(1),    // Bindings
+                Constraint::Length(1), // Footer
+            ])
+            .split(inner_area);
+
+        let heading = Style::default()
+            .fg(app.config.theme.accent_color())
+            .add_modifier(Modifier::BOLD);
+        let keys = Style::default().fg(app.config.theme.sort_indicator_color());
+        let mut lines = Vec::with_capacity(self.line_count());
+        for (group, entries) in &self.sections {
+            lines.push(Line::from(Span::styled(*group, heading)));
+            for (combos, label) in entries {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {:<width$}", combos, width = KEYS_WIDTH), keys),
+                    Span::raw(label.as_str()),
+                ]));
+            }
+            lines.push(Line::default());
+        }
+
+        frame.render_widget(Paragraph::new(lines).scroll((self.scroll, 0)), chunks[0]);
+        frame.render_widget(
+            Paragraph::new("j/k to scroll, Ctrl+d/Ctrl+u by page, g/G top/bottom, Esc to close"),
+            chunks[1],
+        );
+    }
+
+    fn handle_input(
+        &mut self,
+        key: KeyCode,
+        modifiers: KeyModifiers,
+        nav_action: Option<NavigationAction>,
+    ) -> ModalResult {
+        if nav_action == Some(NavigationAction::ShowHelp) {
+            return ModalResult::Closed;
+        }
+        let page = i32::from(PAGE_LINES);
+        match key {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => return ModalResult::Closed,
+            KeyCode::Char('d') if modifiers.contains(KeyModifiers::CONTROL) => self.scroll_by(page),
+            KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.scroll_by(-page)
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.scroll_by(1),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll_by(-1),
+            KeyCode::PageDown => self.scroll_by(page),
+            KeyCode::PageUp => self.scroll_by(-page),
+            KeyCode::Home | KeyCode::Char('g') => self.scroll = 0,
+            KeyCode::End | KeyCode::Char('G') => self.scroll = self.max_scroll(),
+            _ => {}
+        }
+        ModalResult::Continue
+    }
+
+    fn get_title(&self) -> &str {
+        "Key Bindings"
+    }
+
+    fn get_size(&self) -> (u16, u16) {
+        (70, 80)
+    }
+}
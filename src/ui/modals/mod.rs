@@ -3,13 +3,31 @@
 //! This module contains concrete implementations of the Modal trait
 //! for different types of modals in the application.
 
+pub mod cell_detail;
 pub mod command;
+pub mod confirm;
 pub mod connection;
+pub mod connection_error;
 pub mod deletion;
+pub mod help;
+pub mod query_history;
+pub mod query_log;
+pub mod query_params;
+pub mod quick_filter;
+pub mod saved_queries;
 pub mod themes;
 
 // Re-export modal types for convenience
+pub use cell_detail::CellDetailModal;
 pub use command::CommandModal;
+pub use confirm::ConfirmModal;
 pub use connection::ConnectionModal;
+pub use connection_error::ConnectionErrorModal;
 pub use deletion::DeletionModal;
+pub use help::HelpModal;
+pub use query_history::QueryHistoryModal;
+pub use query_log::QueryLogModal;
+pub use query_params::QueryParamsModal;
+pub use quick_filter::QuickFilterModal;
+pub use saved_queries::SavedQueriesModal;
 pub use themes::ThemesModal;
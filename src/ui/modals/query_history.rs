@@ -0,0 +1,154 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::Style,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+use crate::navigation::types::VimMode;
+use crate::ui::modal_manager::{Modal, ModalResult};
+
+/// Modal listing the ad-hoc query history, filtered by what is typed
+#[derive(Debug)]
+pub struct QueryHistoryModal {
+    /// Snapshot of the history taken when the modal was opened, newest first
+    entries: Vec<String>,
+    filter: String,
+    selected_index: usize,
+}
+
+impl QueryHistoryModal {
+    pub fn new(entries: Vec<String>) -> Self {
+        Self {
+            entries,
+            filter: String::new(),
+            selected_index: 0,
+        }
+    }
+
+    /// Entries containing the filter, ignoring case
+    fn matching(&self) -> Vec<&String> {
+        let filter = self.filter.to_lowercase();
+        self.entries
+            .iter()
+            .filter(|query| query.to_lowercase().contains(&filter))
+            .collect()
+    }
+}
+
+impl Modal for QueryHistoryModal {
+    fn render(&self, frame: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title("Query History")
+            .borders(Borders::ALL)
+            .style(
+                Style::default()
+                    .fg(app.config.theme.text_color())
+                    .bg(app.config.theme.surface1_color()),
+            );
+
+        frame.render_widget(block.clone(), area);
+        let inner_area = block.inner(area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(2), // Filter
+                Constraint::Min(3),    // History entries
+                Constraint::Length(1), // Footer
+            ])
+            .split(inner_area);
+
+        frame.render_widget(
+            Paragraph::new(format!("Search: {}_", self.filter))
+                .style(Style::default().fg(app.config.theme.accent_color())),
+            chunks[0],
+        );
+
+        let matching = self.matching();
+        if matching.is_empty() {
+            let message = if self.entries.is_empty() {
+                "No queries run with :sql yet"
+            } else {
+                "No matching queries"
+            };
+            frame.render_widget(Paragraph::new(message), chunks[1]);
+        } else {
+            // Multi-line statements are shown on one line
+            let items: Vec<ListItem> = matching
+                .iter()
+                .map(|query| ListItem::new(query.replace('\n', " ")))
+                .collect();
+            let list = List::new(items).highlight_style(
+                Style::default()
+                    .fg(app.config.theme.base_color())
+                    .bg(app.config.theme.accent_color()),
+            );
+            let mut state = ListState::default().with_selected(Some(self.selected_index));
+            frame.render_stateful_widget(list, chunks[1], &mut state);
+        }
+
+        frame.render_widget(
+            Paragraph::new("Type to search, Up/Down to select, Enter to run, Esc to close"),
+            chunks[2],
+        );
+    }
+
+    fn handle_input(
+        &mut self,
+        key: KeyCode,
+        _modifiers: KeyModifiers,
+        _nav_action: Option<crate::navigation::types::NavigationAction>,
+    ) -> ModalResult {
+        // Every character goes to the search, so only keys that can't be typed act on the list
+        match key {
+            KeyCode::Esc => ModalResult::Closed,
+            KeyCode::Enter => match self.matching().get(self.selected_index) {
+                Some(query) => {
+                    let query = serde_json::to_string(query).unwrap_or_default();
+                    ModalResult::Action(format!("run_history_query:{}", query))
+                }
+                None => ModalResult::Continue,
+            },
+            KeyCode::Up => {
+                self.selected_index = self.selected_index.saturating_sub(1);
+                ModalResult::Continue
+            }
+            KeyCode::Down => {
+                if self.selected_index + 1 < self.matching().len() {
+                    self.selected_index += 1;
+                }
+                ModalResult::Continue
+            }
+            KeyCode::Backspace => {
+                self.filter.pop();
+                self.selected_index = 0;
+                ModalResult::Continue
+            }
+            KeyCode::Char(c) => {
+                self.filter.push(c);
+                self.selected_index = 0;
+                ModalResult::Continue
+            }
+            _ => ModalResult::Continue,
+        }
+    }
+
+    fn get_title(&self) -> &str {
+        "Query History"
+    }
+
+    fn get_mode(&self) -> Option<VimMode> {
+        // Keeps ':' and 'q' from being taken as commands while searching
+        Some(VimMode::Insert)
+    }
+
+    fn get_size(&self) -> (u16, u16) {
+        (80, 60)
+    }
+}
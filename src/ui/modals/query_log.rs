@@ -0,0 +1,166 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::app::App;
+use crate::query_log::QueryLogEntry;
+use crate::ui::modal_manager::{Modal, ModalResult};
+
+/// Modal listing the queries executed against a connection
+#[derive(Debug)]
+pub struct QueryLogModal {
+    title: String,
+    /// Snapshot of the log taken when the modal was opened, newest first
+    entries: Vec<QueryLogEntry>,
+    selected_index: usize,
+}
+
+impl QueryLogModal {
+    pub fn new(connection: &str, entries: Vec<QueryLogEntry>) -> Self {
+        Self {
+            title: format!("Query Log: {}", connection),
+            entries,
+            selected_index: 0,
+        }
+    }
+
+    fn move_up(&mut self) {
+        self.selected_index = self.selected_index.saturating_sub(1);
+    }
+
+    fn move_down(&mut self) {
+        if self.selected_index + 1 < self.entries.len() {
+            self.selected_index += 1;
+        }
+    }
+}
+
+impl Modal for QueryLogModal {
+    fn render(&self, frame: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title(self.title.as_str())
+            .borders(Borders::ALL)
+            .style(
+                Style::default()
+                    .fg(app.config.theme.text_color())
+                    .bg(app.config.theme.surface1_color()),
+            );
+
+        frame.render_widget(block.clone(), area);
+        let inner_area = block.inner(area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Min(3),    // Log entries
+                Constraint::Length(3), // Selected query and footer
+            ])
+            .split(inner_area);
+
+        if self.entries.is_empty() {
+            frame.render_widget(
+                Paragraph::new("No queries executed on this connection yet")
+                    .style(Style::default().fg(app.config.theme.text_color())),
+                chunks[0],
+            );
+        } else {
+            let header = Row::new(vec!["Time", "Elapsed", "Rows", "Status", "Query"]).style(
+                Style::default()
+                    .fg(app.config.theme.accent_color())
+                    .add_modifier(Modifier::BOLD),
+            );
+
+            let rows: Vec<Row> = self
+                .entries
+                .iter()
+                .map(|entry| {
+                    let (status, status_color) = if entry.is_success() {
+                        ("ok", Color::Green)
+                    } else {
+                        ("failed", Color::Red)
+                    };
+                    Row::new(vec![
+                        Cell::from(entry.executed_at.format("%H:%M:%S").to_string()),
+                        Cell::from(entry.elapsed_label()),
+                        Cell::from(entry.row_count.map_or("-".to_string(), |n| n.to_string())),
+                        Cell::from(status).style(Style::default().fg(status_color)),
+                        Cell::from(entry.query.as_str()),
+                    ])
+                })
+                .collect();
+
+            let table = Table::new(
+                rows,
+                [
+                    Constraint::Length(8),
+                    Constraint::Length(9),
+                    Constraint::Length(6),
+                    Constraint::Length(6),
+                    Constraint::Min(10),
+                ],
+            )
+            .header(header)
+            .column_spacing(1)
+            .row_highlight_style(
+                Style::default()
+                    .fg(app.config.theme.base_color())
+                    .bg(app.config.theme.accent_color()),
+            );
+
+            let mut state = TableState::default().with_selected(Some(self.selected_index));
+            frame.render_stateful_widget(table, chunks[0], &mut state);
+        }
+
+        // Show the error of a failed query, since the table truncates long lines
+        let detail = self
+            .entries
+            .get(self.selected_index)
+            .and_then(|entry| entry.error.as_deref())
+            .unwrap_or("");
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from(detail).style(Style::default().fg(Color::Red)),
+                Line::from("j/k to scroll, Esc to close, :querylog clear to reset"),
+            ])
+            .style(Style::default().fg(app.config.theme.text_color())),
+            chunks[1],
+        );
+    }
+
+    fn handle_input(
+        &mut self,
+        _key: KeyCode,
+        _modifiers: KeyModifiers,
+        nav_action: Option<crate::navigation::types::NavigationAction>,
+    ) -> ModalResult {
+        use crate::navigation::types::NavigationAction;
+        match nav_action {
+            Some(NavigationAction::Cancel) | Some(NavigationAction::Quit) => ModalResult::Closed,
+            Some(NavigationAction::MoveUp) => {
+                self.move_up();
+                ModalResult::Continue
+            }
+            Some(NavigationAction::MoveDown) => {
+                self.move_down();
+                ModalResult::Continue
+            }
+            _ => ModalResult::Continue,
+        }
+    }
+
+    fn get_title(&self) -> &str {
+        "Query Log"
+    }
+
+    fn get_size(&self) -> (u16, u16) {
+        (80, 70)
+    }
+}
@@ -0,0 +1,160 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+use crate::navigation::types::VimMode;
+use crate::ui::components::{FieldNavigator, TextInput};
+use crate::ui::modal_manager::{Modal, ModalResult};
+
+/// Modal asking for the values bound to the `$n` placeholders of a WHERE clause
+#[derive(Debug)]
+pub struct QueryParamsModal {
+    where_clause: String,
+    field_navigator: FieldNavigator,
+    text_inputs: Vec<TextInput>,
+}
+
+impl QueryParamsModal {
+    /// One field per placeholder, prefilled with the values used the last time
+    pub fn new(where_clause: &str, placeholder_count: usize, previous: &[String]) -> Self {
+        let text_inputs = (0..placeholder_count)
+            .map(|i| {
+                let mut input = TextInput::new();
+                input.set_content(previous.get(i).cloned().unwrap_or_default());
+                input.move_cursor_to_end();
+                input.set_mode(VimMode::Insert);
+                input
+            })
+            .collect();
+
+        Self {
+            where_clause: where_clause.to_string(),
+            field_navigator: FieldNavigator::new(placeholder_count),
+            text_inputs,
+        }
+    }
+
+    fn submit(&self) -> ModalResult {
+        let values: Vec<&str> = self.text_inputs.iter().map(TextInput::content).collect();
+        let values = serde_json::to_string(&values).unwrap_or_else(|_| "[]".to_string());
+        ModalResult::Action(format!("bind_query_params:{}", values))
+    }
+}
+
+impl Modal for QueryParamsModal {
+    fn render(&self, frame: &mut Frame, area: Rect, app: &App) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title("Query Parameters")
+            .borders(Borders::ALL)
+            .style(
+                Style::default()
+                    .fg(app.config.theme.text_color())
+                    .bg(app.config.theme.surface1_color()),
+            );
+
+        frame.render_widget(block.clone(), area);
+        let inner_area = block.inner(area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(2), // WHERE clause
+                Constraint::Min(1),    // Parameter fields
+                Constraint::Length(1), // Footer
+            ])
+            .split(inner_area);
+
+        frame.render_widget(
+            Paragraph::new(format!("WHERE {}", self.where_clause)).wrap(Wrap { trim: true }),
+            chunks[0],
+        );
+
+        let current_field = self.field_navigator.current_field();
+        let lines: Vec<Line> = self
+            .text_inputs
+            .iter()
+            .enumerate()
+            .map(|(i, input)| {
+                let (value, style) = if i == current_field {
+                    (
+                        input.display_text_with_cursor(),
+                        Style::default().fg(app.config.theme.accent_color()),
+                    )
+                } else {
+                    (
+                        input.content().to_string(),
+                        Style::default().fg(app.config.theme.text_color()),
+                    )
+                };
+                Line::styled(format!("${}: {}", i + 1, value), style)
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), chunks[1]);
+
+        frame.render_widget(
+            Paragraph::new("Tab/Up/Down to switch, Enter to run, Esc to cancel"),
+            chunks[2],
+        );
+    }
+
+    fn handle_input(
+        &mut self,
+        key: KeyCode,
+        modifiers: KeyModifiers,
+        nav_action: Option<crate::navigation::types::NavigationAction>,
+    ) -> ModalResult {
+        use crate::navigation::types::NavigationAction;
+
+        // Fields are single line, so Enter runs the query and Up/Down switch fields
+        match key {
+            KeyCode::Enter => return self.submit(),
+            KeyCode::Tab | KeyCode::Down => {
+                self.field_navigator.next_field();
+                return ModalResult::Continue;
+            }
+            KeyCode::BackTab | KeyCode::Up => {
+                self.field_navigator.previous_field();
+                return ModalResult::Continue;
+            }
+            _ => {}
+        }
+
+        let current = self.field_navigator.current_field();
+        if let Some(input) = self.text_inputs.get_mut(current) {
+            if input.handle_key(key, modifiers) {
+                return ModalResult::Continue;
+            }
+        }
+
+        match nav_action {
+            Some(NavigationAction::Cancel) | Some(NavigationAction::Quit) => ModalResult::Closed,
+            Some(action) => {
+                self.field_navigator.handle_action(action);
+                ModalResult::Continue
+            }
+            None => ModalResult::Continue,
+        }
+    }
+
+    fn get_title(&self) -> &str {
+        "Query Parameters"
+    }
+
+    fn get_mode(&self) -> Option<VimMode> {
+        let current = self.field_navigator.current_field();
+        self.text_inputs.get(current).map(TextInput::mode)
+    }
+
+    fn get_size(&self) -> (u16, u16) {
+        (60, 40)
+    }
+}
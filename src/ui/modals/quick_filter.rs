@@ -0,0 +1,140 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+use crate::navigation::types::VimMode;
+use crate::ui::components::TextInput;
+use crate::ui::modal_manager::{Modal, ModalResult};
+
+/// Modal asking for a column of the current tab and the text its values should contain
+#[derive(Debug)]
+pub struct QuickFilterModal {
+    columns: Vec<String>,
+    selected_index: usize,
+    pattern: TextInput,
+}
+
+impl QuickFilterModal {
+    /// Starts at the `selected` column with an empty pattern
+    pub fn new(columns: Vec<String>, selected: usize) -> Self {
+        let mut pattern = TextInput::new();
+        pattern.set_mode(VimMode::Insert);
+        Self {
+            selected_index: selected.min(columns.len().saturating_sub(1)),
+            columns,
+            pattern,
+        }
+    }
+
+    fn submit(&self) -> ModalResult {
+        let Some(column) = self.columns.get(self.selected_index) else {
+            return ModalResult::Closed;
+        };
+        let filter = serde_json::to_string(&[column.as_str(), self.pattern.content()])
+            .unwrap_or_else(|_| "[]".to_string());
+        ModalResult::Action(format!("quick_filter:{}", filter))
+    }
+}
+
+impl Modal for QuickFilterModal {
+    fn render(&self, frame: &mut Frame, area: Rect, app: &App) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title("Quick Filter")
+            .borders(Borders::ALL)
+            .style(
+                Style::default()
+                    .fg(app.config.theme.text_color())
+                    .bg(app.config.theme.surface1_color()),
+            );
+
+        frame.render_widget(block.clone(), area);
+        let inner_area = block.inner(area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(2), // Pattern
+                Constraint::Min(3),    // Columns
+                Constraint::Length(1), // Footer
+            ])
+            .split(inner_area);
+
+        frame.render_widget(
+            Paragraph::new(format!(
+                "Contains: {}",
+                self.pattern.display_text_with_cursor()
+            ))
+            .style(Style::default().fg(app.config.theme.accent_color())),
+            chunks[0],
+        );
+
+        let items: Vec<ListItem> = self
+            .columns
+            .iter()
+            .map(|column| ListItem::new(column.as_str()))
+            .collect();
+        let list = List::new(items)
+            .style(Style::default().fg(app.config.theme.text_color()))
+            .highlight_style(
+                Style::default()
+                    .fg(app.config.theme.base_color())
+                    .bg(app.config.theme.accent_color()),
+            );
+        // The list scrolls to keep the selected column in view
+        let mut state = ListState::default().with_selected(Some(self.selected_index));
+        frame.render_stateful_widget(list, chunks[1], &mut state);
+
+        frame.render_widget(
+            Paragraph::new("Up/Down picks the column, Enter filters (empty clears), Esc cancels"),
+            chunks[2],
+        );
+    }
+
+    fn handle_input(
+        &mut self,
+        key: KeyCode,
+        modifiers: KeyModifiers,
+        _nav_action: Option<crate::navigation::types::NavigationAction>,
+    ) -> ModalResult {
+        // Every character goes to the pattern, so only keys that can't be typed pick the column
+        match key {
+            KeyCode::Esc => ModalResult::Closed,
+            KeyCode::Enter => self.submit(),
+            KeyCode::Up => {
+                self.selected_index = self.selected_index.saturating_sub(1);
+                ModalResult::Continue
+            }
+            KeyCode::Down => {
+                if self.selected_index + 1 < self.columns.len() {
+                    self.selected_index += 1;
+                }
+                ModalResult::Continue
+            }
+            _ => {
+                self.pattern.handle_key(key, modifiers);
+                ModalResult::Continue
+            }
+        }
+    }
+
+    fn get_title(&self) -> &str {
+        "Quick Filter"
+    }
+
+    fn get_mode(&self) -> Option<VimMode> {
+        // Keeps ':' and 'q' from being taken as commands while typing the pattern
+        Some(VimMode::Insert)
+    }
+
+    fn get_size(&self) -> (u16, u16) {
+        (50, 60)
+    }
+}
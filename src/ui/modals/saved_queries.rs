@@ -0,0 +1,164 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::app::App;
+use crate::saved_queries::SavedQuery;
+use crate::ui::modal_manager::{Modal, ModalResult};
+
+/// Modal listing the saved queries to load one back into the query pane
+#[derive(Debug)]
+pub struct SavedQueriesModal {
+    queries: Vec<SavedQuery>,
+    selected_index: usize,
+}
+
+impl SavedQueriesModal {
+    pub fn new(queries: Vec<SavedQuery>) -> Self {
+        Self {
+            queries,
+            selected_index: 0,
+        }
+    }
+
+    fn move_up(&mut self) {
+        self.selected_index = self.selected_index.saturating_sub(1);
+    }
+
+    fn move_down(&mut self) {
+        if self.selected_index + 1 < self.queries.len() {
+            self.selected_index += 1;
+        }
+    }
+}
+
+/// WHERE / ORDER BY / SQL summary shown for a query
+fn query_summary(query: &SavedQuery) -> String {
+    let mut parts = Vec::new();
+    if !query.where_clause.is_empty() {
+        parts.push(format!("WHERE {}", query.where_clause));
+    }
+    if !query.order_by.is_empty() {
+        parts.push(format!("ORDER BY {}", query.order_by));
+    }
+    if !query.query.is_empty() {
+        parts.push(format!("SQL: {}", query.query));
+    }
+    parts.join("  ")
+}
+
+impl Modal for SavedQueriesModal {
+    fn render(&self, frame: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title("Saved Queries")
+            .borders(Borders::ALL)
+            .style(
+                Style::default()
+                    .fg(app.config.theme.text_color())
+                    .bg(app.config.theme.surface1_color()),
+            );
+
+        frame.render_widget(block.clone(), area);
+        let inner_area = block.inner(area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Min(3),    // Saved queries
+                Constraint::Length(1), // Footer
+            ])
+            .split(inner_area);
+
+        if self.queries.is_empty() {
+            frame.render_widget(
+                Paragraph::new("No saved queries yet; save one with :savequery <name>")
+                    .style(Style::default().fg(app.config.theme.text_color())),
+                chunks[0],
+            );
+        } else {
+            let header = Row::new(vec!["Name", "Source", "Query"]).style(
+                Style::default()
+                    .fg(app.config.theme.accent_color())
+                    .add_modifier(Modifier::BOLD),
+            );
+
+            let rows: Vec<Row> = self
+                .queries
+                .iter()
+                .map(|query| {
+                    Row::new(vec![
+                        Cell::from(query.name.as_str()),
+                        Cell::from(query.source_label().unwrap_or_default()),
+                        Cell::from(query_summary(query)),
+                    ])
+                })
+                .collect();
+
+            let table = Table::new(
+                rows,
+                [
+                    Constraint::Length(16),
+                    Constraint::Length(24),
+                    Constraint::Min(10),
+                ],
+            )
+            .header(header)
+            .column_spacing(1)
+            .row_highlight_style(
+                Style::default()
+                    .fg(app.config.theme.base_color())
+                    .bg(app.config.theme.accent_color()),
+            );
+
+            let mut state = TableState::default().with_selected(Some(self.selected_index));
+            frame.render_stateful_widget(table, chunks[0], &mut state);
+        }
+
+        frame.render_widget(
+            Paragraph::new(Line::from("j/k to select, Enter to load, Esc to close"))
+                .style(Style::default().fg(app.config.theme.text_color())),
+            chunks[1],
+        );
+    }
+
+    fn handle_input(
+        &mut self,
+        _key: KeyCode,
+        _modifiers: KeyModifiers,
+        nav_action: Option<crate::navigation::types::NavigationAction>,
+    ) -> ModalResult {
+        use crate::navigation::types::NavigationAction;
+        match nav_action {
+            Some(NavigationAction::Cancel) | Some(NavigationAction::Quit) => ModalResult::Closed,
+            Some(NavigationAction::MoveUp) => {
+                self.move_up();
+                ModalResult::Continue
+            }
+            Some(NavigationAction::MoveDown) => {
+                self.move_down();
+                ModalResult::Continue
+            }
+            Some(NavigationAction::Confirm) => match self.queries.get(self.selected_index) {
+                Some(query) => ModalResult::Action(format!("load_saved_query:{}", query.name)),
+                None => ModalResult::Closed,
+            },
+            _ => ModalResult::Continue,
+        }
+    }
+
+    fn get_title(&self) -> &str {
+        "Saved Queries"
+    }
+
+    fn get_size(&self) -> (u16, u16) {
+        (80, 60)
+    }
+}
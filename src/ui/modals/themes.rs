@@ -7,6 +7,7 @@ use ratatui::{
 };
 
 use crate::app::App;
+use crate::navigation::types::VimMode;
 use crate::ui::modal_manager::{Modal, ModalResult};
 
 /// Modal for selecting and switching themes
@@ -14,7 +15,9 @@ use crate::ui::modal_manager::{Modal, ModalResult};
 pub struct ThemesModal {
     /// List of available themes
     themes: Vec<String>,
-    /// Currently selected theme index
+    /// Narrows the list to themes containing it, ignoring case
+    filter: String,
+    /// Currently selected theme index among the matching ones
     selected_index: usize,
     /// Current theme name (for highlighting)
     current_theme: String,
@@ -27,14 +30,24 @@ impl ThemesModal {
 
         Self {
             themes,
+            filter: String::new(),
             selected_index: 0,
             current_theme,
         }
     }
 
+    /// Themes containing the filter, ignoring case
+    fn matching(&self) -> Vec<&String> {
+        let filter = self.filter.to_lowercase();
+        self.themes
+            .iter()
+            .filter(|theme| theme.to_lowercase().contains(&filter))
+            .collect()
+    }
+
     /// Get the currently selected theme
     pub fn selected_theme(&self) -> Option<&String> {
-        self.themes.get(self.selected_index)
+        self.matching().get(self.selected_index).copied()
     }
 
     /// Move selection up
@@ -42,13 +55,13 @@ impl ThemesModal {
         if self.selected_index > 0 {
             self.selected_index -= 1;
         } else {
-            self.selected_index = self.themes.len().saturating_sub(1);
+            self.selected_index = self.matching().len().saturating_sub(1);
         }
     }
 
     /// Move selection down
     fn move_down(&mut self) {
-        if self.selected_index + 1 < self.themes.len() {
+        if self.selected_index + 1 < self.matching().len() {
             self.selected_index += 1;
         } else {
             self.selected_index = 0;
@@ -91,34 +104,38 @@ impl Modal for ThemesModal {
             ])
             .split(inner_area);
 
-        // Render header
-        let header = "Select a theme to switch to:";
+        // Render the filter as the header
         frame.render_widget(
-            Paragraph::new(header).style(Style::default().fg(app.config.theme.text_color())),
+            Paragraph::new(format!("Search: {}_", self.filter))
+                .style(Style::default().fg(app.config.theme.accent_color())),
             chunks[0],
         );
 
         // Render themes
-        if self.themes.is_empty() {
-            let no_themes = "No themes available";
+        let matching = self.matching();
+        if matching.is_empty() {
+            let no_themes = if self.themes.is_empty() {
+                "No themes available"
+            } else {
+                "No matching themes"
+            };
             frame.render_widget(
                 Paragraph::new(no_themes).style(Style::default().fg(app.config.theme.text_color())),
                 chunks[1],
             );
         } else {
             // Create a list of theme items
-            let theme_items: Vec<_> = self
-                .themes
+            let theme_items: Vec<_> = matching
                 .iter()
                 .enumerate()
                 .map(|(i, theme)| {
-                    let is_current = theme == &self.current_theme;
+                    let is_current = **theme == self.current_theme;
                     let is_selected = i == self.selected_index;
 
                     let display_text = if is_current {
                         format!("{} (current)", theme)
                     } else {
-                        theme.clone()
+                        theme.to_string()
                     };
 
                     let style = if is_selected {
@@ -144,7 +161,7 @@ impl Modal for ThemesModal {
         }
 
         // Render footer with instructions
-        let footer = "Press Enter to apply, Esc to close";
+        let footer = "Type to search, Enter to apply, Esc to clear the search or close";
         frame.render_widget(
             Paragraph::new(footer).style(Style::default().fg(app.config.theme.text_color())),
             chunks[2],
@@ -153,30 +170,34 @@ impl Modal for ThemesModal {
 
     fn handle_input(
         &mut self,
-        _key: KeyCode,
+        key: KeyCode,
         _modifiers: KeyModifiers,
-        nav_action: Option<crate::navigation::types::NavigationAction>,
+        _nav_action: Option<crate::navigation::types::NavigationAction>,
     ) -> ModalResult {
-        use crate::navigation::types::NavigationAction;
-        match nav_action {
-            Some(NavigationAction::Cancel) | Some(NavigationAction::Quit) => {
-                return ModalResult::Closed;
-            }
-            Some(NavigationAction::MoveUp) => {
-                self.move_up();
-                return ModalResult::Continue;
-            }
-            Some(NavigationAction::MoveDown) => {
-                self.move_down();
-                return ModalResult::Continue;
+        // Every character goes to the search, so only keys that can't be typed act on the list
+        match key {
+            KeyCode::Esc if !self.filter.is_empty() => {
+                self.filter.clear();
+                self.selected_index = 0;
             }
-            Some(NavigationAction::Confirm) => {
+            KeyCode::Esc => return ModalResult::Closed,
+            KeyCode::Up => self.move_up(),
+            KeyCode::Down => self.move_down(),
+            KeyCode::Enter => {
                 if let Some(theme_name) = self.selected_theme() {
                     return ModalResult::Action(format!("apply_theme:{}", theme_name));
-                } else {
+                } else if self.themes.is_empty() {
                     return ModalResult::Closed;
                 }
             }
+            KeyCode::Backspace => {
+                self.filter.pop();
+                self.selected_index = 0;
+            }
+            KeyCode::Char(c) => {
+                self.filter.push(c);
+                self.selected_index = 0;
+            }
             _ => {}
         }
         ModalResult::Continue
@@ -186,6 +207,11 @@ impl Modal for ThemesModal {
         "Themes"
     }
 
+    fn get_mode(&self) -> Option<VimMode> {
+        // Keeps ':' and 'q' from being taken as commands while searching
+        Some(VimMode::Insert)
+    }
+
     fn is_blocking(&self) -> bool {
         true
     }
@@ -6,6 +6,7 @@ use crossterm::event::{KeyCode, KeyModifiers};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::Style,
+    text::Line,
     widgets::{Block, Borders, Paragraph as Widget},
     Frame,
 };
@@ -14,6 +15,23 @@ pub struct QueryInputPane {
     where_clause: TextInput,
     order_by_clause: TextInput,
     field_navigator: FieldNavigator,
+    /// Matches of the last Tab completion, cycled while the inserted one is kept
+    completion: Option<Completion>,
+}
+
+/// Names completing the word before the cursor of the current field
+struct Completion {
+    /// Column of the cursor line the completed word starts at
+    start: usize,
+    /// Each match as it is inserted
+    matches: Vec<String>,
+    /// Match currently inserted
+    index: usize,
+}
+
+/// Characters of an unquoted identifier
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
 }
 
 impl QueryInputPane {
@@ -22,6 +40,7 @@ impl QueryInputPane {
             where_clause: TextInput::new(),
             order_by_clause: TextInput::new(),
             field_navigator: FieldNavigator::new(2),
+            completion: None,
         }
     }
 
@@ -42,12 +61,19 @@ impl QueryInputPane {
 
         let is_active = app.active_pane == Pane::QueryInput;
         let current_field = self.field_navigator.current_field();
+        // MongoDB collections are filtered and sorted with JSON documents
+        let (where_title, order_by_title) =
+            if app.current_db_type() == Some(crate::database::DatabaseType::MongoDB) {
+                ("Filter (JSON)", "Sort (JSON, or field [DESC], ...)")
+            } else {
+                ("WHERE", "ORDER BY")
+            };
 
         // Render WHERE clause
         self.render_field(
             frame,
             chunks[0],
-            "WHERE",
+            where_title,
             &self.where_clause,
             0,
             current_field,
@@ -59,7 +85,7 @@ impl QueryInputPane {
         self.render_field(
             frame,
             chunks[1],
-            "ORDER BY",
+            order_by_title,
             &self.order_by_clause,
             1,
             current_field,
@@ -123,8 +149,13 @@ impl QueryInputPane {
             && text_input.mode() == crate::navigation::types::VimMode::Normal
         {
             let inner = Block::default().borders(Borders::ALL).inner(area);
-            let cursor_pos = text_input.cursor_position().min(text_input.content().len());
-            let cursor_x = inner.x + cursor_pos as u16;
+            // Wide characters before the cursor take more than one cell
+            let before_cursor: String = text_input
+                .content()
+                .chars()
+                .take(text_input.cursor_position())
+                .collect();
+            let cursor_x = inner.x + Line::from(before_cursor).width() as u16;
             let cursor_y = inner.y;
             frame.set_cursor_position(ratatui::layout::Position {
                 x: cursor_x,
@@ -184,6 +215,94 @@ impl QueryInputPane {
         self.order_by_clause.content().to_string()
     }
 
+    pub fn set_where_content(&mut self, content: &str) {
+        self.where_clause.set_content(content.to_string());
+    }
+
+    pub fn set_order_by_content(&mut self, content: &str) {
+        self.order_by_clause.set_content(content.to_string());
+    }
+
+    /// Inserts `text` at the cursor of the current field
+    pub fn insert_text(&mut self, text: &str) {
+        if self.field_navigator.current_field() == 0 {
+            self.where_clause.insert_str_at_cursor(text);
+        } else {
+            self.order_by_clause.insert_str_at_cursor(text);
+        }
+    }
+
+    /// Completes the word before the cursor of the current field with the `candidates` that
+    /// start with it, ignoring case, each inserted as `quote` renders it. Completing again
+    /// right after cycles through the matches, backwards with `reverse`. Returns whether
+    /// anything matched.
+    pub fn complete_word(
+        &mut self,
+        candidates: &[String],
+        quote: impl Fn(&str) -> String,
+        reverse: bool,
+    ) -> bool {
+        let current_input = if self.field_navigator.current_field() == 0 {
+            &mut self.where_clause
+        } else {
+            &mut self.order_by_clause
+        };
+        let before: Vec<char> = current_input.text_before_cursor().chars().collect();
+
+        // The last match still sits before the cursor, so move on to the next one
+        if let Some(completion) = self.completion.as_mut() {
+            let inserted: String = before.iter().skip(completion.start).collect();
+            if completion.matches.get(completion.index) == Some(&inserted) {
+                let count = completion.matches.len();
+                completion.index = if reverse {
+                    (completion.index + count - 1) % count
+                } else {
+                    (completion.index + 1) % count
+                };
+                current_input
+                    .replace_before_cursor(completion.start, &completion.matches[completion.index]);
+                return true;
+            }
+        }
+        self.completion = None;
+
+        let word_start = before.len()
+            - before
+                .iter()
+                .rev()
+                .take_while(|c| is_identifier_char(**c))
+                .count();
+        // A word opened with a quote is replaced along with it
+        let start = match word_start.checked_sub(1).map(|i| before[i]) {
+            Some('"' | '`') => word_start - 1,
+            _ => word_start,
+        };
+        let word: String = before[word_start..]
+            .iter()
+            .collect::<String>()
+            .to_lowercase();
+
+        let mut matches: Vec<String> = Vec::new();
+        for candidate in candidates {
+            let quoted = quote(candidate);
+            if candidate.to_lowercase().starts_with(&word) && !matches.contains(&quoted) {
+                matches.push(quoted);
+            }
+        }
+        if matches.is_empty() {
+            return false;
+        }
+
+        let index = if reverse { matches.len() - 1 } else { 0 };
+        current_input.replace_before_cursor(start, &matches[index]);
+        self.completion = Some(Completion {
+            start,
+            matches,
+            index,
+        });
+        true
+    }
+
     pub fn exit_insert_mode(&mut self) {
         self.where_clause
             .set_mode(crate::navigation::types::VimMode::Normal);
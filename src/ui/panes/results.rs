@@ -1,15 +1,44 @@
-use crate::app::App;
+use crate::app::{App, QueryState};
+use crate::config::DisplayConfig;
+use crate::database::core::{ColumnAlignment, ColumnKind, NullsOrder, QueryResult};
+use crate::ui::layout;
 use crate::ui::types::Pane;
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
-    text::{Line, Span},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
     widgets::{Block, Borders, Cell, Paragraph, Row, Table, Tabs},
     Frame,
 };
 
+use std::borrow::Cow;
+use std::ops::Range;
+
+/// Columns between adjacent table columns
+pub const COLUMN_SPACING: u16 = 1;
+
+/// Widest a column grows to fit its content; longer values are cut off
+const MAX_CONTENT_WIDTH: u16 = 40;
+
 pub struct ResultsPane;
 
+/// A line of the record view of a row
+pub struct RecordLine {
+    pub column: usize,
+    /// Whether the line starts the column's value, and so shows its name
+    pub first: bool,
+    pub text: String,
+}
+
+/// A result tab drawn in the results table area, which is split when a pinned tab is shown
+/// beside the selected one
+struct ResultView {
+    tab: Option<usize>,
+    cursor: (usize, usize),
+    /// Whether keys move this tab's cursor, i.e. it is the selected tab
+    focused: bool,
+}
+
 impl ResultsPane {
     pub fn new() -> Self {
         Self
@@ -38,7 +67,24 @@ impl ResultsPane {
             index += 1;
         }
 
-        self.render_results(frame, app, chunks[index]);
+        let selected = ResultView {
+            tab: app.selected_result_tab_index,
+            cursor: app.cursor_position,
+            focused: true,
+        };
+        if let Some(pinned) = app.pinned_tab.filter(|_| app.split_active()) {
+            let halves = layout::split_results_table(chunks[index]);
+            let focused_half = usize::from(app.split_focus_right);
+            self.render_results(frame, app, halves[focused_half], &selected);
+            let pinned = ResultView {
+                tab: Some(pinned.index),
+                cursor: pinned.cursor,
+                focused: false,
+            };
+            self.render_results(frame, app, halves[1 - focused_half], &pinned);
+        } else {
+            self.render_results(frame, app, chunks[index], &selected);
+        }
         index += 1;
         self.render_pagination(frame, app, chunks[index]);
     }
@@ -73,7 +119,12 @@ impl ResultsPane {
                     max_tab_width as usize,
                 );
                 let color = crate::ui::get_tab_color(name, index);
-                Line::from(Span::styled(shortened_name, Style::default().fg(color)))
+                let mut style = Style::default().fg(color);
+                // Underlined rather than marked, so tab widths stay as hit-testing measures them
+                if app.pinned_tab.is_some_and(|pinned| pinned.index == index) {
+                    style = style.add_modifier(Modifier::UNDERLINED);
+                }
+                Line::from(Span::styled(shortened_name, style))
             })
             .collect();
 
@@ -95,14 +146,128 @@ impl ResultsPane {
         frame.render_widget(tabs, area);
     }
 
-    fn render_results(&self, frame: &mut Frame, app: &App, area: Rect) {
-        let results_nav_info = if app.active_pane == Pane::Results {
+    fn render_results(&self, frame: &mut Frame, app: &App, area: Rect, view: &ResultView) {
+        let focused = view.focused && app.active_pane == Pane::Results;
+        let results_nav_info = if focused {
             format!(" [{}]", app.navigation_manager.get_navigation_info())
         } else {
             String::new()
         };
 
-        let results_title = format!("Results{}", results_nav_info);
+        let search_info = if !view.focused {
+            String::new()
+        } else if app.result_search_editing {
+            let error = app
+                .result_search
+                .error
+                .as_ref()
+                .map(|error| format!(" [Invalid regex: {}]", error))
+                .unwrap_or_default();
+            format!(" [Find: {}_]{}", app.result_search.query, error)
+        } else if let Some(error) = &app.result_search.error {
+            format!(" [Invalid regex: {}]", error)
+        } else if app.result_search.is_active() {
+            format!(" [Search {}]", app.result_search.describe())
+        } else {
+            String::new()
+        };
+
+        let current_result = view.tab.and_then(|tab_index| {
+            app.result_tabs
+                .get(tab_index)
+                .map(|(_, result, state)| (result, state))
+        });
+        let visible_columns = view
+            .tab
+            .map(|tab_index| app.tab_visible_column_indices(tab_index))
+            .unwrap_or_default();
+        let selection_info = current_result
+            .and_then(|(_, state)| state.selection_anchor)
+            .map(|anchor| {
+                format!(
+                    " [Visual {}x{}]",
+                    anchor.1.abs_diff(view.cursor.1) + 1,
+                    visible_columns
+                        .iter()
+                        .filter(|&&column| {
+                            (anchor.0.min(view.cursor.0)..=anchor.0.max(view.cursor.0))
+                                .contains(&column)
+                        })
+                        .count()
+                )
+            })
+            .unwrap_or_default();
+        let hidden_info = current_result
+            .map(|(result, _)| result.columns.len().saturating_sub(visible_columns.len()))
+            .filter(|&hidden| hidden > 0)
+            .map(|hidden| format!(" [{} columns hidden, :showcol]", hidden))
+            .unwrap_or_default();
+
+        let capped_info = current_result
+            .filter(|(_, state)| state.capped_query.is_some())
+            .map(|(result, state)| match state.total_records {
+                Some(total) => {
+                    format!(" [{} of {} rows, Alt+m for more]", result.rows.len(), total)
+                }
+                None => format!(" [first {} rows, Alt+m for more]", result.rows.len()),
+            })
+            .unwrap_or_default();
+
+        let table_inner = Block::default().borders(Borders::ALL).inner(area);
+        let visible_capacity = layout::visible_result_rows(table_inner.height);
+        let start_row = current_result.map_or(0, |(result, _)| {
+            layout::first_visible_result_row(result.rows.len(), visible_capacity, view.cursor.1)
+        });
+        let (col_offset, drawn_columns) = current_result
+            .map(|(result, query_state)| {
+                Self::scrolled_columns(
+                    result,
+                    query_state,
+                    &visible_columns,
+                    start_row..start_row + visible_capacity,
+                    table_inner.width,
+                    view.cursor.0,
+                    &app.config.display,
+                )
+            })
+            .unwrap_or_default();
+        let record_view = current_result.is_some_and(|(_, state)| state.record_view);
+        let scroll_info = if let Some((result, _)) = current_result.filter(|_| record_view) {
+            format!(
+                " [record {} of {}]",
+                (view.cursor.1 + 1).min(result.rows.len()),
+                result.rows.len()
+            )
+        } else if drawn_columns.len() < visible_columns.len() {
+            format!(
+                " [columns {}-{} of {}]",
+                col_offset + 1,
+                col_offset + drawn_columns.len(),
+                visible_columns.len()
+            )
+        } else {
+            String::new()
+        };
+
+        // Each half of a split is titled with its tab, which the tab bar only colours
+        let label = match view
+            .tab
+            .and_then(|tab_index| app.result_tabs.get(tab_index))
+        {
+            Some((name, _, _)) if app.split_active() && view.focused => name.clone(),
+            Some((name, _, _)) if app.split_active() => format!("{} (pinned)", name),
+            _ => "Results".to_string(),
+        };
+        let results_title = format!(
+            "{}{}{}{}{}{}{}",
+            label,
+            results_nav_info,
+            search_info,
+            selection_info,
+            hidden_info,
+            capped_info,
+            scroll_info
+        );
         let mut block = Block::default()
             .title(results_title)
             .borders(Borders::ALL)
@@ -111,18 +276,15 @@ impl ResultsPane {
                     .fg(app.config.theme.header_fg_color())
                     .bg(app.config.theme.header_bg_color()),
             );
-        if app.active_pane == Pane::Results {
+        if focused {
             block = block.border_style(Style::default().fg(app.config.theme.accent_color()));
         }
-        let current_result = app.selected_result_tab_index.and_then(|tab_index| {
-            app.result_tabs
-                .get(tab_index)
-                .map(|(_, result, state)| (result, state))
-        });
-        if let Some((result, query_state)) = current_result {
-            let header = result
-                .columns
+        if let Some(current) = current_result.filter(|_| record_view) {
+            self.render_record(frame, app, area, block, view, current);
+        } else if let Some((result, query_state)) = current_result {
+            let header = drawn_columns
                 .iter()
+                .filter_map(|&index| result.columns.get(index))
                 .map(|c| c.as_str())
                 .collect::<Vec<_>>();
             if header.is_empty() {
@@ -134,36 +296,33 @@ impl ResultsPane {
                 return;
             }
 
-            let max_lines = result.rows.len();
-            let line_num_width = max_lines.to_string().len().max(3) as u16;
-
-            let spacing: u16 = 1;
-            let table_inner = block.inner(area);
-            let first_col_w = line_num_width + 1;
-            let mut widths: Vec<Constraint> = Vec::with_capacity(1 + header.len());
-            widths.push(Constraint::Length(first_col_w));
-            let data_cols = header.len() as u16;
-            if data_cols > 0 {
-                let total_spacing = spacing.saturating_mul(data_cols.saturating_sub(1));
-                let remaining_w = table_inner
-                    .width
-                    .saturating_sub(first_col_w)
-                    .saturating_sub(total_spacing);
-                let base = if data_cols > 0 {
-                    remaining_w / data_cols
-                } else {
-                    0
-                };
-                let rem = if data_cols > 0 {
-                    remaining_w % data_cols
-                } else {
-                    0
-                };
-                for i in 0..data_cols {
-                    let w = base + if i < rem { 1 } else { 0 };
-                    widths.push(Constraint::Length(w));
-                }
-            }
+            let line_num_width = result.rows.len().to_string().len().max(3) as u16;
+
+            let (first_col_w, column_widths) = Self::column_widths(
+                result,
+                query_state,
+                &drawn_columns,
+                start_row..start_row + visible_capacity,
+                table_inner.width,
+                &app.config.display,
+            );
+            // A wrapped cursor row takes the place of the rows below it
+            let cursor_height = Self::wrapped_row_height(
+                result,
+                query_state,
+                view.cursor.1,
+                &drawn_columns,
+                &column_widths,
+                visible_capacity,
+                &app.config.display,
+            );
+            let rows_drawn = visible_capacity.saturating_sub(cursor_height - 1);
+            let first_row =
+                layout::first_visible_result_row(result.rows.len(), rows_drawn, view.cursor.1);
+            let widths: Vec<Constraint> = std::iter::once(first_col_w)
+                .chain(column_widths.iter().copied())
+                .map(Constraint::Length)
+                .collect();
 
             let mut header_cells = vec![Cell::from("#").style(
                 Style::default()
@@ -171,8 +330,16 @@ impl ResultsPane {
                     .add_modifier(Modifier::BOLD),
             )];
 
-            header_cells.extend(header.iter().map(|&h| {
-                Cell::from(h).style(
+            header_cells.extend(drawn_columns.iter().zip(&header).map(|(&col_idx, &h)| {
+                let mut label = vec![Span::raw(h.to_string())];
+                if let Some(indicator) = Self::sort_indicator(h, query_state) {
+                    label.push(Span::styled(
+                        format!(" {}", indicator),
+                        Style::default().fg(app.config.theme.sort_indicator_color()),
+                    ));
+                }
+                let alignment = Self::column_alignment(result, query_state, col_idx);
+                Cell::from(Line::from(label).alignment(alignment)).style(
                     Style::default()
                         .fg(app.config.theme.accent_color())
                         .add_modifier(Modifier::BOLD),
@@ -181,36 +348,21 @@ impl ResultsPane {
 
             let header_row = Row::new(header_cells);
 
-            let header_rows: u16 = 1;
-            let visible_capacity = table_inner.height.saturating_sub(header_rows).max(0) as usize;
-
-            let total_rows = result.rows.len();
-            let max_start = total_rows.saturating_sub(visible_capacity);
-            let cursor_row = app.cursor_position.1.min(total_rows.saturating_sub(1));
-            let start_row = if visible_capacity == 0 {
-                0
-            } else {
-                cursor_row
-                    .saturating_sub(visible_capacity / 2)
-                    .min(max_start)
-            };
-
             let rows: Vec<Row> = result
                 .rows
                 .iter()
                 .enumerate()
-                .skip(start_row)
-                .take(visible_capacity)
+                .skip(first_row)
+                .take(rows_drawn)
                 .map(|(row_idx, row)| {
                     let is_marked = query_state.rows_marked_for_deletion.contains(&row_idx);
-                    let is_selected =
-                        app.active_pane == Pane::Results && row_idx == app.cursor_position.1;
+                    let is_selected = focused && row_idx == view.cursor.1;
 
                     let base_bg = if is_marked {
-                        Color::Rgb(139, 0, 0)
+                        crate::theme::rgb(139, 0, 0)
                     } else if is_selected {
                         app.config.theme.accent_color()
-                    } else if (row_idx + start_row) % 2 == 0 {
+                    } else if (row_idx + first_row).is_multiple_of(2) {
                         app.config.theme.row_even_bg_color()
                     } else {
                         app.config.theme.row_odd_bg_color()
@@ -227,50 +379,140 @@ impl ResultsPane {
                             .bg(base_bg),
                     )];
 
-                    row_cells.extend(row.iter().enumerate().map(|(col_idx, cell)| {
-                        let is_selected = app.active_pane == Pane::Results
-                            && row_idx == app.cursor_position.1
-                            && col_idx == app.cursor_position.0;
+                    let cells = drawn_columns.iter().zip(&column_widths).filter_map(
+                        |(&col_idx, &width)| row.get(col_idx).map(|cell| (col_idx, width, cell)),
+                    );
+                    row_cells.extend(cells.map(|(col_idx, width, cell)| {
+                        let is_selected =
+                            focused && row_idx == view.cursor.1 && col_idx == view.cursor.0;
                         let is_marked = query_state.rows_marked_for_deletion.contains(&row_idx);
+                        let in_selection =
+                            query_state.in_selection(view.cursor, (col_idx, row_idx));
 
-                        let is_editing = app.editing_cell_position
-                            .map_or(false, |(edit_col, edit_row)| {
-                                edit_row == row_idx && edit_col == col_idx
-                            });
+                        let is_editing = view.focused
+                            && app
+                                .editing_cell_position
+                                .map_or(false, |(edit_col, edit_row)| {
+                                    edit_row == row_idx && edit_col == col_idx
+                                });
 
-                        let base_bg = if is_marked {
-                            Color::Rgb(139, 0, 0)
+                        let base_bg = if is_marked && is_selected {
+                            crate::theme::rgb(205, 92, 92)
+                        } else if is_marked {
+                            crate::theme::rgb(139, 0, 0)
                         } else if is_editing {
                             app.config.theme.accent_color()
                         } else if is_selected {
                             app.config.theme.accent_color()
-                        } else if (row_idx + start_row) % 2 == 0 {
+                        } else if in_selection {
+                            app.config.theme.surface2_color()
+                        } else if (row_idx + first_row).is_multiple_of(2) {
                             app.config.theme.row_even_bg_color()
                         } else {
                             app.config.theme.row_odd_bg_color()
                         };
 
-                        let style = Style::default()
+                        let mut style = Style::default()
                             .fg(app.config.theme.text_color())
                             .bg(base_bg);
+                        if is_marked {
+                            style = style.add_modifier(Modifier::CROSSED_OUT);
+                        }
 
-                        let display_value = if is_editing {
-                            app.cell_text_input.display_text_with_cursor()
-                        } else {
-                            cell.as_str().to_string()
+                        if is_editing {
+                            return Cell::from(app.cell_text_input.display_text_with_cursor())
+                                .style(style);
+                        }
+
+                        let alignment = Self::column_alignment(result, query_state, col_idx);
+                        // NULL and empty strings would both be blank, so each gets a marker
+                        let cell = match cell {
+                            None => {
+                                let style = style
+                                    .fg(app.config.theme.subtext0_color())
+                                    .add_modifier(Modifier::ITALIC);
+                                return Cell::from(
+                                    Line::from(Self::cell_text(
+                                        result,
+                                        query_state,
+                                        col_idx,
+                                        None,
+                                        &app.config.display,
+                                    ))
+                                    .alignment(alignment),
+                                )
+                                .style(style);
+                            }
+                            Some(cell) if cell.is_empty() => {
+                                let style = style.fg(app.config.theme.subtext0_color());
+                                return Cell::from(
+                                    Line::from(Self::cell_text(
+                                        result,
+                                        query_state,
+                                        col_idx,
+                                        Some(cell),
+                                        &app.config.display,
+                                    ))
+                                    .alignment(alignment),
+                                )
+                                .style(style);
+                            }
+                            Some(cell) => cell,
                         };
+                        let text = Self::cell_text(
+                            result,
+                            query_state,
+                            col_idx,
+                            Some(cell),
+                            &app.config.display,
+                        );
+
+                        if row_idx == view.cursor.1 && cursor_height > 1 {
+                            let lines: Vec<Line> = Self::wrap_value(&text, usize::from(width))
+                                .into_iter()
+                                .map(Line::from)
+                                .collect();
+                            return Cell::from(Text::from(lines).alignment(alignment)).style(style);
+                        }
 
-                        Cell::from(display_value).style(style)
+                        // Match ranges index the raw value, so a boolean glyph or formatted
+                        // number isn't highlighted
+                        let ranges = if text == cell.as_str() {
+                            app.result_search.match_ranges(cell)
+                        } else {
+                            Vec::new()
+                        };
+                        if ranges.is_empty() {
+                            Cell::from(Line::from(text).alignment(alignment)).style(style)
+                        } else {
+                            // The cursor's cell already has the accent background
+                            let highlight = if is_selected {
+                                Style::default().fg(app.config.theme.base_color())
+                            } else {
+                                Style::default().fg(app.config.theme.accent_color())
+                            }
+                            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+                            Cell::from(
+                                Self::highlight_matches(cell, &ranges, highlight)
+                                    .alignment(alignment),
+                            )
+                            .style(style)
+                        }
                     }));
 
-                    Row::new(row_cells)
+                    let height = if row_idx == view.cursor.1 {
+                        cursor_height
+                    } else {
+                        1
+                    };
+                    Row::new(row_cells).height(height as u16)
                 })
                 .collect();
 
             let table = Table::new(rows, widths)
                 .header(header_row)
                 .block(block)
-                .column_spacing(spacing)
+                .column_spacing(COLUMN_SPACING)
                 .style(Style::default().bg(app.config.theme.surface0_color()));
 
             frame.render_widget(table, area);
@@ -294,6 +536,482 @@ impl ResultsPane {
         }
     }
 
+    /// Draws the cursor's row of a tab in record view: a line per column with its name and
+    /// value, values wrapping onto the lines below
+    fn render_record(
+        &self,
+        frame: &mut Frame,
+        app: &App,
+        area: Rect,
+        block: Block,
+        view: &ResultView,
+        (result, query_state): (&QueryResult, &QueryState),
+    ) {
+        let focused = view.focused && app.active_pane == Pane::Results;
+        let visible_columns = view
+            .tab
+            .map(|tab_index| app.tab_visible_column_indices(tab_index))
+            .unwrap_or_default();
+        let inner = block.inner(area);
+        let background = Style::default().bg(app.config.theme.surface0_color());
+        if result.rows.is_empty() || visible_columns.is_empty() {
+            frame.render_widget(
+                Paragraph::new("No results to display.")
+                    .block(block)
+                    .style(background.fg(app.config.theme.text_color())),
+                area,
+            );
+            return;
+        }
+
+        let row = view.cursor.1.min(result.rows.len() - 1);
+        let (name_width, lines) = Self::record_lines(
+            result,
+            query_state,
+            &visible_columns,
+            row,
+            inner.width,
+            &app.config.display,
+        );
+        let first_line = Self::first_visible_record_line(&lines, inner.height, view.cursor.0);
+        let is_marked = query_state.rows_marked_for_deletion.contains(&row);
+        let editing = app
+            .editing_cell_position
+            .filter(|&(_, edit_row)| view.focused && edit_row == row)
+            .map(|(edit_col, _)| edit_col);
+
+        let name_style = Style::default()
+            .fg(app.config.theme.accent_color())
+            .add_modifier(Modifier::BOLD);
+        let drawn: Vec<Line> = lines
+            .iter()
+            .skip(first_line)
+            .take(usize::from(inner.height))
+            .map(|line| {
+                let name = if line.first {
+                    result.columns.get(line.column).map_or("", String::as_str)
+                } else {
+                    ""
+                };
+                let name = Span::styled(
+                    format!("{:<width$} ", name, width = usize::from(name_width)),
+                    name_style,
+                );
+
+                let is_selected = focused && line.column == view.cursor.0;
+                let mut style = Style::default().fg(app.config.theme.text_color());
+                if is_marked && is_selected {
+                    style = style.bg(crate::theme::rgb(205, 92, 92));
+                } else if is_marked {
+                    style = style.bg(crate::theme::rgb(139, 0, 0));
+                } else if is_selected {
+                    style = style.bg(app.config.theme.accent_color());
+                }
+                if is_marked {
+                    style = style.add_modifier(Modifier::CROSSED_OUT);
+                }
+                // NULL and empty strings get the same markers as in the grid
+                match result.rows[row].get(line.column) {
+                    Some(None) => {
+                        style = style
+                            .fg(app.config.theme.subtext0_color())
+                            .add_modifier(Modifier::ITALIC);
+                    }
+                    Some(Some(cell)) if cell.is_empty() => {
+                        style = style.fg(app.config.theme.subtext0_color());
+                    }
+                    _ => {}
+                }
+
+                let value = if editing == Some(line.column) {
+                    if !line.first {
+                        return Line::from(name);
+                    }
+                    app.cell_text_input.display_text_with_cursor()
+                } else {
+                    line.text.clone()
+                };
+                Line::from(vec![name, Span::styled(value, style)])
+            })
+            .collect();
+
+        frame.render_widget(Paragraph::new(drawn).block(block).style(background), area);
+    }
+
+    /// Lines of the record view of `row` in a pane `width` wide, with the width of the column
+    /// names. Each visible column starts a line with its name; its value wraps onto further
+    /// lines in the width left beside the names. Rendering and hit-testing share this.
+    pub fn record_lines(
+        result: &QueryResult,
+        query_state: &QueryState,
+        visible_columns: &[usize],
+        row: usize,
+        width: u16,
+        display: &DisplayConfig,
+    ) -> (u16, Vec<RecordLine>) {
+        let name_width = visible_columns
+            .iter()
+            .filter_map(|&index| result.columns.get(index))
+            .map(|name| Text::from(name.as_str()).width().min(usize::from(u16::MAX)) as u16)
+            .max()
+            .unwrap_or(0)
+            .min(MAX_CONTENT_WIDTH)
+            .min(width / 2);
+        let value_width = usize::from(width.saturating_sub(name_width + 1).max(1));
+
+        let Some(cells) = result.rows.get(row) else {
+            return (name_width, Vec::new());
+        };
+        let lines = visible_columns
+            .iter()
+            .flat_map(|&column| {
+                let cell = cells.get(column).and_then(|cell| cell.as_deref());
+                let text = Self::cell_text(result, query_state, column, cell, display);
+                Self::wrap_value(&text, value_width)
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(index, text)| RecordLine {
+                        column,
+                        first: index == 0,
+                        text,
+                    })
+            })
+            .collect();
+        (name_width, lines)
+    }
+
+    /// First of `lines` drawn in a record view `height` lines tall, keeping the first line of
+    /// the cursor's column centred where possible
+    pub fn first_visible_record_line(
+        lines: &[RecordLine],
+        height: u16,
+        cursor_column: usize,
+    ) -> usize {
+        let cursor_line = lines
+            .iter()
+            .position(|line| line.column >= cursor_column)
+            .unwrap_or(0);
+        layout::first_visible_result_row(lines.len(), usize::from(height), cursor_line)
+    }
+
+    /// Lines taken by `row` of a tab drawn with `drawn_columns` at `widths`: one, or when the
+    /// tab wraps its cells, as many as its longest value wraps to, at most `visible_rows`
+    pub fn wrapped_row_height(
+        result: &QueryResult,
+        query_state: &QueryState,
+        row: usize,
+        drawn_columns: &[usize],
+        widths: &[u16],
+        visible_rows: usize,
+        display: &DisplayConfig,
+    ) -> usize {
+        let Some(cells) = result.rows.get(row).filter(|_| query_state.wrap_cells) else {
+            return 1;
+        };
+        drawn_columns
+            .iter()
+            .zip(widths)
+            .filter_map(|(&index, &width)| {
+                let cell = cells.get(index)?.as_deref();
+                let text = Self::cell_text(result, query_state, index, cell, display);
+                Some(Self::wrap_value(&text, usize::from(width)).len())
+            })
+            .max()
+            .unwrap_or(1)
+            .clamp(1, visible_rows.max(1))
+    }
+
+    /// Breaks `text` at its line breaks and wherever it grows past `width` columns
+    fn wrap_value(text: &str, width: usize) -> Vec<String> {
+        let mut lines = Vec::new();
+        for part in text.split('\n') {
+            let mut line = String::new();
+            let mut line_width = 0;
+            for ch in part.trim_end_matches('\r').chars() {
+                let ch_width = Span::raw(ch.to_string()).width();
+                if line_width + ch_width > width && !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                    line_width = 0;
+                }
+                line.push(ch);
+                line_width += ch_width;
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    /// Splits `text` into spans, emphasising the matched byte ranges
+    fn highlight_matches<'a>(
+        text: &'a str,
+        ranges: &[(usize, usize)],
+        highlight: Style,
+    ) -> Line<'a> {
+        let mut spans = Vec::with_capacity(ranges.len() * 2 + 1);
+        let mut last = 0;
+        for &(start, end) in ranges {
+            if start > last {
+                spans.push(Span::raw(&text[last..start]));
+            }
+            spans.push(Span::styled(&text[start..end], highlight));
+            last = end;
+        }
+        if last < text.len() {
+            spans.push(Span::raw(&text[last..]));
+        }
+        Line::from(spans)
+    }
+
+    /// Text drawn for a cell of column `index`: NULL and empty strings get markers so they
+    /// aren't blank, booleans the configured glyphs, dates and timestamps the configured
+    /// formats and numbers the column's `format`
+    fn cell_text<'a>(
+        result: &QueryResult,
+        query_state: &QueryState,
+        index: usize,
+        cell: Option<&'a str>,
+        display: &'a DisplayConfig,
+    ) -> Cow<'a, str> {
+        match cell {
+            None => Cow::Borrowed(&display.null_display),
+            Some("") => Cow::Borrowed("\u{2205}"),
+            Some(cell) if result.column_kind(index) == ColumnKind::Boolean => {
+                Cow::Borrowed(match cell.to_ascii_lowercase().as_str() {
+                    "true" | "t" | "1" => &display.true_display,
+                    "false" | "f" | "0" => &display.false_display,
+                    _ => cell,
+                })
+            }
+            Some(cell) if result.column_kind(index) == ColumnKind::Temporal => {
+                let type_name = result.column_types.get(index).map_or("", String::as_str);
+                display
+                    .temporal_text(type_name, cell)
+                    .map_or(Cow::Borrowed(cell), Cow::Owned)
+            }
+            Some(cell) => result
+                .columns
+                .get(index)
+                .and_then(|column| query_state.column_formats.get(column))
+                .filter(|format| format.formats_numbers())
+                .and_then(|format| format.format_number(cell))
+                .map_or(Cow::Borrowed(cell), Cow::Owned),
+        }
+    }
+
+    /// Numbers and dates line up on the right so their digits do; text stays on the left and
+    /// booleans in the middle, unless the column's `format` says otherwise
+    fn column_alignment(result: &QueryResult, query_state: &QueryState, index: usize) -> Alignment {
+        let chosen = result
+            .columns
+            .get(index)
+            .and_then(|column| query_state.column_formats.get(column))
+            .and_then(|format| format.alignment);
+        match chosen {
+            Some(ColumnAlignment::Left) => return Alignment::Left,
+            Some(ColumnAlignment::Center) => return Alignment::Center,
+            Some(ColumnAlignment::Right) => return Alignment::Right,
+            None => {}
+        }
+        match result.column_kind(index) {
+            ColumnKind::Numeric | ColumnKind::Temporal => Alignment::Right,
+            ColumnKind::Boolean => Alignment::Center,
+            ColumnKind::Text => Alignment::Left,
+        }
+    }
+
+    /// Width of the row number column, which stays in place when the table scrolls right
+    fn gutter_width(result: &QueryResult) -> u16 {
+        result.rows.len().to_string().len().max(3) as u16 + 1
+    }
+
+    /// Each column's header width and the width it asks for to fit the cells of `rows`, both
+    /// capped at `MAX_CONTENT_WIDTH`
+    fn requested_widths(
+        result: &QueryResult,
+        query_state: &QueryState,
+        visible_columns: &[usize],
+        rows: Range<usize>,
+        display: &DisplayConfig,
+    ) -> (Vec<u16>, Vec<u16>) {
+        let text_width = |text: &str| Text::from(text).width().min(usize::from(u16::MAX)) as u16;
+        visible_columns
+            .iter()
+            .map(|&index| {
+                let header = result
+                    .columns
+                    .get(index)
+                    .map_or(0, |column| text_width(&Self::header_label(column, query_state)))
+                    .clamp(1, MAX_CONTENT_WIDTH);
+                let content = result
+                    .rows
+                    .get(rows.start.min(result.rows.len())..rows.end.min(result.rows.len()))
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|row| row.get(index))
+                    .map(|cell| {
+                        let text =
+                            Self::cell_text(result, query_state, index, cell.as_deref(), display);
+                        text_width(&text)
+                    })
+                    .max()
+                    .unwrap_or(0)
+                    .min(MAX_CONTENT_WIDTH);
+                (header, header.max(content))
+            })
+            .unzip()
+    }
+
+    /// The run of `visible_columns` drawn in a table `table_width` wide, with its position in
+    /// `visible_columns`. The run starts at the tab's `col_scroll_offset`, moved just enough
+    /// to keep `cursor_column` in view, and takes as many columns as fit at the width they ask
+    /// for. Rendering, hit-testing and the stored offset all go through here.
+    pub fn scrolled_columns(
+        result: &QueryResult,
+        query_state: &QueryState,
+        visible_columns: &[usize],
+        rows: Range<usize>,
+        table_width: u16,
+        cursor_column: usize,
+        display: &DisplayConfig,
+    ) -> (usize, Vec<usize>) {
+        let count = visible_columns.len();
+        if count == 0 {
+            return (0, Vec::new());
+        }
+        let available = u32::from(table_width.saturating_sub(Self::gutter_width(result)));
+        let (_, wanted) =
+            Self::requested_widths(result, query_state, visible_columns, rows, display);
+        // The spacing is drawn before every data column, including the first
+        let fits = |from: usize, to: usize| {
+            let needed: u32 = wanted[from..=to]
+                .iter()
+                .map(|&width| u32::from(width) + u32::from(COLUMN_SPACING))
+                .sum();
+            needed <= available
+        };
+
+        let cursor = visible_columns
+            .iter()
+            .position(|&index| index >= cursor_column)
+            .unwrap_or(count - 1);
+        let mut offset = query_state.col_scroll_offset.min(cursor);
+        while offset < cursor && !fits(offset, cursor) {
+            offset += 1;
+        }
+        // Scrolled right with room to spare, e.g. after the pane grew: bring columns back
+        while offset > 0 && fits(offset - 1, count - 1) {
+            offset -= 1;
+        }
+
+        let mut end = offset + 1;
+        while end < count && fits(offset, end) {
+            end += 1;
+        }
+        (offset, visible_columns[offset..end].to_vec())
+    }
+
+    /// Widths of the row number column and of each of `visible_columns` in a results table
+    /// `table_width` wide, fitted to the header and the cells of `rows`.
+    ///
+    /// Each column asks for its widest header or cell, up to `MAX_CONTENT_WIDTH`, and never
+    /// gets less than its header (if the headers fit at all). Width left over after every
+    /// minimum is shared in proportion to what each column asked for. When the headers alone
+    /// don't fit, the width is split evenly. Mouse hit-testing uses the same widths.
+    pub fn column_widths(
+        result: &QueryResult,
+        query_state: &QueryState,
+        visible_columns: &[usize],
+        rows: Range<usize>,
+        table_width: u16,
+        display: &DisplayConfig,
+    ) -> (u16, Vec<u16>) {
+        let line_num_width = Self::gutter_width(result);
+        let count = visible_columns.len() as u16;
+        if count == 0 {
+            return (line_num_width, Vec::new());
+        }
+        // The spacing is drawn before every data column, including the first
+        let available = table_width
+            .saturating_sub(line_num_width)
+            .saturating_sub(COLUMN_SPACING.saturating_mul(count));
+
+        let (minimums, wanted) =
+            Self::requested_widths(result, query_state, visible_columns, rows, display);
+
+        let minimum: u16 = minimums.iter().sum();
+        if minimum > available {
+            let base = available / count;
+            let remainder = available % count;
+            let widths = (0..count)
+                .map(|i| base + u16::from(i < remainder))
+                .collect();
+            return (line_num_width, widths);
+        }
+
+        // Share what is left after the minimums: first up to what each column asked for, then
+        // any remainder so the table still fills the pane
+        let mut widths = minimums.clone();
+        let growth: Vec<u16> = wanted.iter().zip(&minimums).map(|(w, m)| w - m).collect();
+        let spare = available - minimum;
+        let asked: u16 = growth.iter().sum();
+        if asked >= spare {
+            Self::share(&mut widths, &growth, spare);
+        } else {
+            for (width, grow) in widths.iter_mut().zip(&growth) {
+                *width += grow;
+            }
+            Self::share(&mut widths, &wanted, spare - asked);
+        }
+        (line_num_width, widths)
+    }
+
+    /// Adds `amount` to `widths` in proportion to `weights`, handing out what rounding
+    /// leaves from the first column on
+    fn share(widths: &mut [u16], weights: &[u16], amount: u16) {
+        let total: u32 = weights.iter().map(|&w| u32::from(w)).sum();
+        if total == 0 || widths.is_empty() {
+            return;
+        }
+        let mut given = 0;
+        for (width, &weight) in widths.iter_mut().zip(weights) {
+            let share = (u32::from(amount) * u32::from(weight) / total) as u16;
+            *width += share;
+            given += share;
+        }
+        let len = widths.len();
+        for i in 0..usize::from(amount - given) {
+            widths[i % len] += 1;
+        }
+    }
+
+    /// Column header text with the sort direction and NULL placement indicator
+    fn header_label(column: &str, query_state: &QueryState) -> String {
+        match Self::sort_indicator(column, query_state) {
+            Some(indicator) => format!("{} {}", column, indicator),
+            None => column.to_string(),
+        }
+    }
+
+    /// Arrow drawn after a sorted column's name, numbered by priority in a multi-column sort
+    /// and followed by the NULL placement; `None` for unsorted columns
+    fn sort_indicator(column: &str, query_state: &QueryState) -> Option<String> {
+        let sort_columns = &query_state.sort_columns;
+        let priority = sort_columns.iter().position(|(name, _)| name == column)?;
+        let arrow = if sort_columns[priority].1 { "▲" } else { "▼" };
+        // Number the columns of a multi-column sort by priority
+        let arrow = if sort_columns.len() > 1 {
+            format!("{}{}", arrow, priority + 1)
+        } else {
+            arrow.to_string()
+        };
+        let nulls = match query_state.nulls_order {
+            Some(NullsOrder::First) => " ∅↑",
+            Some(NullsOrder::Last) => " ∅↓",
+            None => "",
+        };
+        Some(format!("{}{}", arrow, nulls))
+    }
+
     fn render_pagination(&self, frame: &mut Frame, app: &App, area: Rect) {
         let mut block = Block::default()
             .title("Pagination")
@@ -309,12 +1027,14 @@ impl ResultsPane {
         }
 
         let pagination_info = if let Some(state) = app.current_query_state() {
+            // Totals are unknown until the rows have been counted
+            let unknown = || "?".to_string();
             format!(
                 "Page: {}/{} | Size: {} | Total: {} | {}:First {}:Last {}:Prev {}:Next ",
                 state.current_page,
-                state.total_pages.unwrap_or(1),
+                state.total_pages.map_or_else(unknown, |pages| pages.to_string()),
                 state.page_size,
-                state.total_records.unwrap_or(0),
+                state.total_records.map_or_else(unknown, |total| total.to_string()),
                 app.config.keymap.first_page_key,
                 app.config.keymap.last_page_key,
                 app.config.keymap.prev_page_key,
@@ -1,5 +1,5 @@
-use crate::app::App;
-use crate::database::ConnectionStatus;
+use crate::app::{App, ConnectionRetry, ConnectionTreeItem, SchemaTreeItem, TreeItem};
+use crate::database::{ConnectionStatus, RelationKind};
 use crate::logging;
 use crate::ui::types::Pane;
 use ratatui::{
@@ -34,7 +34,13 @@ impl SidebarPane {
             String::new()
         };
 
-        let header = Line::from(format!("Connections (press 'a' to add){}", nav_info));
+        let header = match &app.tree_filter {
+            Some(filter) => {
+                let cursor = if app.tree_filter_editing { "_" } else { "" };
+                Line::from(format!("Filter: {}{}{}", filter, cursor, nav_info))
+            }
+            None => Line::from(format!("Connections (press 'a' to add){}", nav_info)),
+        };
         let header_block = Block::default().borders(Borders::ALL).style(
             Style::default()
                 .fg(app.config.theme.text_color())
@@ -43,125 +49,69 @@ impl SidebarPane {
 
         frame.render_widget(Paragraph::new(header).block(header_block), chunks[0]);
 
+        let visible = app.visible_tree_items();
         let mut tree_items = Vec::new();
-        let mut current_visual_index = 0;
-
-        for (conn_idx, connection) in app.connection_tree.iter().enumerate() {
-            logging::debug(&format!(
-                "Rendering connection {}: {} (expanded: {}, status: {:?}, databases: {})",
-                conn_idx,
-                connection.connection_config.name,
-                connection.is_expanded,
-                connection.status,
-                connection.databases.len()
-            ));
-
-            let expanded_symbol = if connection.is_expanded { "▼" } else { "▶" };
-            let status_symbol = match connection.status {
-                ConnectionStatus::Connected => "●",
-                ConnectionStatus::Connecting => "◌",
-                ConnectionStatus::Failed => "✗",
-                ConnectionStatus::NotConnected => "○",
-            };
-
-            let conn_style = if app.highlight_selected_item(current_visual_index) {
+        for (visual_index, &item) in visible.iter().enumerate() {
+            let style = if app.highlight_selected_item(visual_index) {
                 Style::default()
                     .fg(app.config.theme.accent_color())
                     .add_modifier(Modifier::BOLD)
             } else {
                 Style::default().fg(app.config.theme.text_color())
             };
+            // While filtering, ancestors of matches are shown expanded
+            let shows_children = visible
+                .get(visual_index + 1)
+                .is_some_and(|next| Self::depth(*next) > Self::depth(item));
+            let expanded_symbol = |expanded: bool| {
+                if expanded || shows_children {
+                    "▼"
+                } else {
+                    "▶"
+                }
+            };
 
-            tree_items.push(ListItem::new(Line::from(vec![
-                Span::raw(format!("{} ", expanded_symbol)),
-                Span::styled(
-                    status_symbol,
-                    match connection.status {
-                        ConnectionStatus::Connected => Style::default().fg(Color::Green),
-                        ConnectionStatus::Connecting => Style::default().fg(Color::Yellow),
-                        ConnectionStatus::Failed => Style::default().fg(Color::Red),
-                        ConnectionStatus::NotConnected => Style::default().fg(Color::Gray),
-                    },
-                ),
-                Span::raw(" "),
-                Span::styled(&connection.connection_config.name, conn_style),
-            ])));
-
-            current_visual_index += 1;
-
-            if connection.is_expanded {
-                logging::debug(&format!(
-                    "Connection {} is expanded, showing {} databases",
-                    connection.connection_config.name,
-                    connection.databases.len()
-                ));
-
-                for (_db_idx, database) in connection.databases.iter().enumerate() {
-                    let db_expanded = if database.is_expanded { "▼" } else { "▶" };
-
-                    let db_style = if app.highlight_selected_item(current_visual_index) {
-                        Style::default()
-                            .fg(app.config.theme.accent_color())
-                            .add_modifier(Modifier::BOLD)
-                    } else {
-                        Style::default().fg(app.config.theme.text_color())
-                    };
-
-                    tree_items.push(ListItem::new(Line::from(vec![
+            let line = match item {
+                TreeItem::Connection(conn_idx) => {
+                    let connection = &app.connection_tree[conn_idx];
+                    let symbol = expanded_symbol(connection.is_expanded);
+                    let retry = app
+                        .connection_retries
+                        .get(&connection.connection_config.name);
+                    Self::connection_line(connection, retry, symbol, style)
+                }
+                TreeItem::Database(conn_idx, db_idx) => {
+                    let database = &app.connection_tree[conn_idx].databases[db_idx];
+                    Line::from(vec![
                         Span::raw("  "),
-                        Span::raw(db_expanded),
+                        Span::raw(expanded_symbol(database.is_expanded)),
                         Span::raw(" 🗄 "),
-                        Span::styled(&database.name, db_style),
-                    ])));
-
-                    current_visual_index += 1;
-
-                    if database.is_expanded {
-                        for (_schema_idx, schema) in database.schemas.iter().enumerate() {
-                            let schema_expanded = if schema.is_expanded { "▼" } else { "▶" };
-
-                            let schema_style = if app.highlight_selected_item(current_visual_index)
-                            {
-                                Style::default()
-                                    .fg(app.config.theme.accent_color())
-                                    .add_modifier(Modifier::BOLD)
-                            } else {
-                                Style::default().fg(app.config.theme.text_color())
-                            };
-
-                            tree_items.push(ListItem::new(Line::from(vec![
-                                Span::raw("    "),
-                                Span::raw(schema_expanded),
-                                Span::raw(" 📁 "),
-                                Span::styled(&schema.name, schema_style),
-                            ])));
-
-                            current_visual_index += 1;
-
-                            if schema.is_expanded {
-                                for table in &schema.tables {
-                                    let table_style =
-                                        if app.highlight_selected_item(current_visual_index) {
-                                            Style::default()
-                                                .fg(app.config.theme.accent_color())
-                                                .add_modifier(Modifier::BOLD)
-                                        } else {
-                                            Style::default().fg(app.config.theme.text_color())
-                                        };
-
-                                    tree_items.push(ListItem::new(Line::from(vec![
-                                        Span::raw("      "),
-                                        Span::raw("📋 "),
-                                        Span::styled(table, table_style),
-                                    ])));
-
-                                    current_visual_index += 1;
-                                }
-                            }
-                        }
-                    }
+                        Span::styled(&database.name, style),
+                    ])
                 }
-            }
+                TreeItem::Schema(conn_idx, db_idx, schema_idx) => {
+                    let schema =
+                        &app.connection_tree[conn_idx].databases[db_idx].schemas[schema_idx];
+                    Line::from(vec![
+                        Span::raw("    "),
+                        Span::raw(expanded_symbol(schema.is_expanded)),
+                        Span::raw(" 📁 "),
+                        Span::styled(&schema.name, style),
+                    ])
+                }
+                TreeItem::Table(conn_idx, db_idx, schema_idx, table_idx) => {
+                    let connection = &app.connection_tree[conn_idx];
+                    let schema = &connection.databases[db_idx].schemas[schema_idx];
+                    // Schema-scoped connections list their tables directly under the connection
+                    let indent = if connection.scoped_schema().is_some() {
+                        "  "
+                    } else {
+                        "      "
+                    };
+                    Self::relation_line(indent, schema, &schema.tables[table_idx], style)
+                }
+            };
+            tree_items.push(ListItem::new(line));
         }
 
         let mut tree_block = Block::default()
@@ -180,4 +130,96 @@ impl SidebarPane {
             chunks[1],
         );
     }
+
+    /// Nesting level of an item in the tree, from 0 for connections
+    fn depth(item: TreeItem) -> usize {
+        match item {
+            TreeItem::Connection(..) => 0,
+            TreeItem::Database(..) => 1,
+            TreeItem::Schema(..) => 2,
+            TreeItem::Table(..) => 3,
+        }
+    }
+
+    /// Tree line for a connection, with its status, retry progress and read-only/scope tags
+    fn connection_line<'a>(
+        connection: &'a ConnectionTreeItem,
+        retry: Option<&ConnectionRetry>,
+        expanded_symbol: &'a str,
+        style: Style,
+    ) -> Line<'a> {
+        let status_symbol = match connection.status {
+            ConnectionStatus::Connected => "●",
+            ConnectionStatus::Connecting => "◌",
+            ConnectionStatus::Failed => "✗",
+            ConnectionStatus::NotConnected => "○",
+        };
+
+        let mut spans = vec![
+            Span::raw(format!("{} ", expanded_symbol)),
+            Span::styled(
+                status_symbol,
+                match connection.status {
+                    ConnectionStatus::Connected => Style::default().fg(Color::Green),
+                    ConnectionStatus::Connecting => Style::default().fg(Color::Yellow),
+                    ConnectionStatus::Failed => Style::default().fg(Color::Red),
+                    ConnectionStatus::NotConnected => Style::default().fg(Color::Gray),
+                },
+            ),
+            Span::raw(" "),
+            Span::styled(&connection.connection_config.name, style),
+        ];
+        if let Some(retry) = retry {
+            let progress = match retry.next_at {
+                Some(next_at) => format!(
+                    " [retry {}/{} in {}s]",
+                    retry.attempt + 1,
+                    retry.max_attempts,
+                    next_at
+                        .saturating_duration_since(std::time::Instant::now())
+                        .as_secs_f32()
+                        .ceil()
+                ),
+                None => format!(" [retry {}/{}]", retry.attempt, retry.max_attempts),
+            };
+            spans.push(Span::styled(progress, Style::default().fg(Color::Yellow)));
+        }
+        if connection.connection_config.read_only {
+            spans.push(Span::styled(" [ro]", Style::default().fg(Color::Yellow)));
+        }
+        if let Some(scope) = &connection.connection_config.scope_schema {
+            spans.push(Span::styled(
+                format!(" [{}]", scope),
+                Style::default().fg(Color::Gray),
+            ));
+        }
+        Line::from(spans)
+    }
+
+    /// Tree line for a table, with an icon and tag telling views and partitions apart
+    fn relation_line<'a>(
+        indent: &'a str,
+        schema: &SchemaTreeItem,
+        table: &'a str,
+        style: Style,
+    ) -> Line<'a> {
+        let kind = schema
+            .relation_kinds
+            .get(table)
+            .copied()
+            .unwrap_or(RelationKind::Table);
+        let icon = match kind {
+            RelationKind::Table | RelationKind::Partition => "📋 ",
+            RelationKind::View => "👁 ",
+            RelationKind::MaterializedView => "💾 ",
+            RelationKind::ForeignTable => "🔗 ",
+            RelationKind::PartitionedTable => "🗂 ",
+        };
+
+        let mut spans = vec![Span::raw(indent), Span::raw(icon), Span::styled(table, style)];
+        if let Some(tag) = kind.tag() {
+            spans.push(Span::styled(format!(" [{}]", tag), Style::default().fg(Color::Gray)));
+        }
+        Line::from(spans)
+    }
 }